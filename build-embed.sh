@@ -0,0 +1,7 @@
+#!/usr/bin/env bash
+
+set -exo pipefail
+
+# Builds the simulator-only bundle for embedding rotation previews into `raphael-embed/pkg`.
+# Requires wasm-pack: cargo install --locked wasm-pack
+wasm-pack build raphael-embed --release --target web $@
@@ -0,0 +1,7 @@
+#!/usr/bin/env bash
+
+set -exo pipefail
+
+# Builds the solver library for web tools into `raphael-wasm/pkg`, ready to be published to npm.
+# Requires wasm-pack: cargo install --locked wasm-pack
+wasm-pack build raphael-wasm --release --target web $@
@@ -26,3 +26,6 @@ trunk build index.html --release --dist distrib
 
 mv distrib/webworker.js distrib/webworker${RANDOM_SUFFIX}.js
 mv distrib/webworker_bg.wasm distrib/webworker${RANDOM_SUFFIX}_bg.wasm
+
+# Latest game data for native builds of older releases
+cargo run --release --package raphael-cli -- export-data --data-version "$(date -u +%Y-%m-%d)" > distrib/game-data.json
@@ -0,0 +1,96 @@
+//! Loads a debug bundle exported via the "🐞 Debug bundle" button, replays its macro and solves
+//! its settings again.
+//!
+//! ```sh
+//! cargo run --release --example load_debug_bundle -- raphael-debug-bundle.json
+//! ```
+
+use raphael_sim::{Action, Condition, Settings, SimulationState};
+use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
+
+fn main() {
+    env_logger::builder()
+        .format_timestamp(None)
+        .format_target(false)
+        .init();
+
+    let Some(path) = std::env::args_os().nth(1) else {
+        eprintln!("Usage: load_debug_bundle <path to bundle>");
+        std::process::exit(2);
+    };
+    let bundle = std::fs::read(&path)
+        .map_err(|error| error.to_string())
+        .and_then(|bytes| raphael_xiv::DebugBundle::from_bytes(&bytes))
+        .unwrap_or_else(|error| {
+            eprintln!("Failed to load {}: {}", path.display(), error);
+            std::process::exit(1);
+        });
+
+    println!("Raphael {} on {}", bundle.app_version, bundle.platform);
+    println!("{:?}", bundle.settings);
+    println!("{:?}", bundle.solver_config);
+    if let Some(error) = &bundle.solver_error {
+        println!("Solve failed: {error:?}");
+    }
+    println!("Recent logs:");
+    for line in &bundle.logs {
+        println!("  {line}");
+    }
+
+    let settings = &bundle.settings;
+    let initial_state = bundle.solver_config.initial_effects.initial_state(settings);
+    print_state(
+        "Macro",
+        settings,
+        replay(settings, initial_state, &bundle.actions),
+    );
+
+    let solver_settings = SolverSettings {
+        simulator_settings: *settings,
+        ..bundle.solve_request.solver_settings()
+    };
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    match solver.solve_from(initial_state) {
+        Ok(actions) => {
+            println!("Solution: {actions:?}");
+            print_state(
+                "Solution",
+                settings,
+                replay(settings, initial_state, &actions),
+            );
+        }
+        Err(error) => println!("Solve failed: {error:?}"),
+    }
+}
+
+/// State after the actions, stopping at the first action that fails.
+fn replay(settings: &Settings, mut state: SimulationState, actions: &[Action]) -> SimulationState {
+    for (index, action) in actions.iter().enumerate() {
+        match state.use_action(*action, Condition::Normal, settings) {
+            Ok(next_state) => state = next_state,
+            Err(error) => {
+                println!("Step {} ({:?}) fails: {}", index + 1, action, error);
+                break;
+            }
+        }
+    }
+    state
+}
+
+fn print_state(label: &str, settings: &Settings, state: SimulationState) {
+    println!(
+        "{}: Progress {}/{}, Quality {}/{}, Durability {}, CP {}",
+        label,
+        state.progress,
+        settings.max_progress,
+        state.quality,
+        settings.max_quality,
+        state.durability,
+        state.cp
+    );
+}
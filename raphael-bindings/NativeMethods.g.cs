@@ -76,6 +76,9 @@ namespace Raphael
         QuickInnovation,
         ImmaculateMend,
         TrainedPerfection,
+        RapidSynthesis,
+        HastyTouch,
+        DaringTouch,
     }
 
 
@@ -37,6 +37,9 @@ enum class Action : uint8_t {
   QuickInnovation,
   ImmaculateMend,
   TrainedPerfection,
+  RapidSynthesis,
+  HastyTouch,
+  DaringTouch,
 };
 
 struct SolveArgs {
@@ -1,4 +1,4 @@
-use raphael_sim::{ActionMask, Settings};
+use raphael_sim::{ActionMask, RulesOverride, Settings, SuccessModel};
 use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
 
 #[repr(C)]
@@ -55,6 +55,9 @@ pub enum Action {
     QuickInnovation,
     ImmaculateMend,
     TrainedPerfection,
+    RapidSynthesis,
+    HastyTouch,
+    DaringTouch,
 }
 
 // This should produce an error if raphael_sim::Action is changed
@@ -92,6 +95,9 @@ impl From<raphael_sim::Action> for Action {
             raphael_sim::Action::QuickInnovation => Self::QuickInnovation,
             raphael_sim::Action::ImmaculateMend => Self::ImmaculateMend,
             raphael_sim::Action::TrainedPerfection => Self::TrainedPerfection,
+            raphael_sim::Action::RapidSynthesis => Self::RapidSynthesis,
+            raphael_sim::Action::HastyTouch => Self::HastyTouch,
+            raphael_sim::Action::DaringTouch => Self::DaringTouch,
         }
     }
 }
@@ -108,11 +114,15 @@ impl From<SolveArgs> for SolverSettings {
             job_level: value.job_level,
             allowed_actions: ActionMask::from_bits(value.action_mask),
             adversarial: value.adversarial,
+            success_model: SuccessModel::AssumeFail,
+            rules_override: RulesOverride::NONE,
         };
         Self {
             simulator_settings,
             backload_progress: value.backload_progress,
             allow_unsound_branch_pruning: value.unsound_branch_pruning,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
         }
     }
 }
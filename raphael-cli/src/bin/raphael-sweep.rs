@@ -0,0 +1,393 @@
+//! Runs bound checks or full solves for every combination of a matrix of settings and writes
+//! the results as CSV, e.g. to find the stat breakpoints of a recipe.
+//!
+//! Example matrix:
+//! ```toml
+//! mode = "bounds" # or "solve"
+//! item_id = 44104
+//! craftsmanship = { start = 4800, end = 5200, step = 50 }
+//! control = [4900, 5000]
+//! cp = 600
+//! manipulation = true
+//! adversarial = [false, true]
+//! ```
+//! Stats are the final stats, including food and potion bonuses.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use clap::Parser;
+use raphael_data::{CrafterStats, RECIPES};
+use raphael_solver::{AtomicFlag, SolveRequest, SolverException, ThreadPoolSettings, craft_bounds};
+use rayon::prelude::*;
+use serde::Deserialize;
+
+#[derive(Parser, Debug)]
+#[command(
+    version,
+    about = "Runs the Raphael-XIV crafting solver on a matrix of settings and writes the results as CSV."
+)]
+struct Args {
+    /// TOML file with the settings matrix
+    matrix: PathBuf,
+
+    /// Write the CSV to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Number of solver threads, 0 uses one thread per logical core
+    #[arg(long, default_value_t = 0)]
+    threads: usize,
+
+    /// Run the solver threads below normal priority
+    #[arg(long, default_value_t = false)]
+    background_priority: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Mode {
+    /// Quality upper bound and steps lower bound, without searching for a rotation
+    #[default]
+    Bounds,
+    /// Full solve
+    Solve,
+}
+
+/// A single value or a list of values.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum Values<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T: Copy> Values<T> {
+    fn values(&self) -> Vec<T> {
+        match self {
+            Self::One(value) => vec![*value],
+            Self::Many(values) => values.clone(),
+        }
+    }
+}
+
+/// A single value, a list of values, or an inclusive range of values.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum StatValues {
+    One(u16),
+    Many(Vec<u16>),
+    Range { start: u16, end: u16, step: u16 },
+}
+
+impl StatValues {
+    fn values(&self) -> Vec<u16> {
+        match self {
+            Self::One(value) => vec![*value],
+            Self::Many(values) => values.clone(),
+            Self::Range { start, end, step } => {
+                (*start..=*end).step_by(usize::from(*step).max(1)).collect()
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct Matrix {
+    #[serde(default)]
+    mode: Mode,
+    item_id: Values<u32>,
+    craftsmanship: StatValues,
+    control: StatValues,
+    cp: StatValues,
+    #[serde(default = "default_level")]
+    level: Values<u8>,
+    #[serde(default = "enabled")]
+    manipulation: Values<bool>,
+    #[serde(default = "disabled")]
+    heart_and_soul: Values<bool>,
+    #[serde(default = "disabled")]
+    quick_innovation: Values<bool>,
+    #[serde(default = "disabled")]
+    adversarial: Values<bool>,
+    #[serde(default = "disabled")]
+    backload_progress: Values<bool>,
+    #[serde(default = "zero")]
+    initial_quality: StatValues,
+    /// Defaults to the max Quality of the recipe.
+    target_quality: Option<StatValues>,
+}
+
+fn default_level() -> Values<u8> {
+    Values::One(100)
+}
+
+fn enabled() -> Values<bool> {
+    Values::One(true)
+}
+
+fn disabled() -> Values<bool> {
+    Values::One(false)
+}
+
+fn zero() -> StatValues {
+    StatValues::One(0)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Configuration {
+    item_id: u32,
+    crafter_stats: CrafterStats,
+    adversarial: bool,
+    backload_progress: bool,
+    initial_quality: u16,
+    target_quality: Option<u16>,
+}
+
+/// Combines each configuration with each value.
+fn expand<T: Copy>(
+    configurations: Vec<Configuration>,
+    values: &[T],
+    set: impl Fn(&mut Configuration, T),
+) -> Vec<Configuration> {
+    let set = &set;
+    configurations
+        .into_iter()
+        .flat_map(|configuration| {
+            values.iter().map(move |value| {
+                let mut configuration = configuration;
+                set(&mut configuration, *value);
+                configuration
+            })
+        })
+        .collect()
+}
+
+impl Matrix {
+    fn configurations(&self) -> Vec<Configuration> {
+        let target_qualities: Vec<Option<u16>> = match &self.target_quality {
+            Some(target_quality) => target_quality.values().into_iter().map(Some).collect(),
+            None => vec![None],
+        };
+        let mut configurations = vec![Configuration::default()];
+        configurations = expand(configurations, &self.item_id.values(), |c, value| {
+            c.item_id = value;
+        });
+        configurations = expand(configurations, &self.level.values(), |c, value| {
+            c.crafter_stats.level = value;
+        });
+        configurations = expand(configurations, &self.craftsmanship.values(), |c, value| {
+            c.crafter_stats.craftsmanship = value;
+        });
+        configurations = expand(configurations, &self.control.values(), |c, value| {
+            c.crafter_stats.control = value;
+        });
+        configurations = expand(configurations, &self.cp.values(), |c, value| {
+            c.crafter_stats.cp = value;
+        });
+        configurations = expand(configurations, &self.manipulation.values(), |c, value| {
+            c.crafter_stats.manipulation = value;
+        });
+        configurations = expand(configurations, &self.heart_and_soul.values(), |c, value| {
+            c.crafter_stats.heart_and_soul = value;
+        });
+        configurations = expand(
+            configurations,
+            &self.quick_innovation.values(),
+            |c, value| {
+                c.crafter_stats.quick_innovation = value;
+            },
+        );
+        configurations = expand(configurations, &self.adversarial.values(), |c, value| {
+            c.adversarial = value;
+        });
+        configurations = expand(
+            configurations,
+            &self.backload_progress.values(),
+            |c, value| {
+                c.backload_progress = value;
+            },
+        );
+        configurations = expand(
+            configurations,
+            &self.initial_quality.values(),
+            |c, value| {
+                c.initial_quality = value;
+            },
+        );
+        expand(configurations, &target_qualities, |c, value| {
+            c.target_quality = value;
+        })
+    }
+}
+
+const CONFIGURATION_COLUMNS: &str = "item_id,craftsmanship,control,cp,level,manipulation,heart_and_soul,quick_innovation,adversarial,backload_progress,initial_quality,target_quality";
+
+impl Configuration {
+    fn csv_fields(&self) -> String {
+        let stats = &self.crafter_stats;
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.item_id,
+            stats.craftsmanship,
+            stats.control,
+            stats.cp,
+            stats.level,
+            stats.manipulation,
+            stats.heart_and_soul,
+            stats.quick_innovation,
+            self.adversarial,
+            self.backload_progress,
+            self.initial_quality,
+            self.target_quality
+                .map_or_else(String::new, |quality| quality.to_string()),
+        )
+    }
+
+    fn solve_request(&self) -> Result<SolveRequest, String> {
+        let recipe = RECIPES
+            .iter()
+            .find(|recipe| recipe.item_id == self.item_id)
+            .ok_or_else(|| {
+                format!(
+                    "Unable to find Recipe for an item with item ID: {}",
+                    self.item_id
+                )
+            })?;
+        let mut request = SolveRequest::new(*recipe, self.crafter_stats)
+            .adversarial(self.adversarial)
+            .backload_progress(self.backload_progress)
+            .initial_quality(self.initial_quality);
+        if let Some(target_quality) = self.target_quality {
+            request = request.target_quality(target_quality);
+        }
+        Ok(request)
+    }
+}
+
+fn result_columns(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Bounds => "quality_upper_bound,steps_lower_bound,error",
+        Mode::Solve => "quality,steps,duration,actions,error",
+    }
+}
+
+fn error_fields(mode: Mode, error: &str) -> String {
+    match mode {
+        Mode::Bounds => format!(",,{error}"),
+        Mode::Solve => format!(",,,,{error}"),
+    }
+}
+
+/// Quotes a field that may contain commas or quotes.
+fn csv_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+fn solver_error(error: SolverException) -> String {
+    match error {
+        SolverException::NoSolution => "no_solution".to_owned(),
+        SolverException::Interrupted => "interrupted".to_owned(),
+        SolverException::MemoryLimitReached => "memory_limit_reached".to_owned(),
+        SolverException::InternalError(message) => csv_string(&message),
+    }
+}
+
+fn run(mode: Mode, configuration: &Configuration) -> String {
+    let request = match configuration.solve_request() {
+        Ok(request) => request,
+        Err(error) => return error_fields(mode, &csv_string(&error)),
+    };
+    let initial_quality = configuration.initial_quality;
+    match mode {
+        Mode::Bounds => match craft_bounds(request.solver_settings(), AtomicFlag::new()) {
+            Ok(bounds) => format!(
+                "{},{},",
+                initial_quality.saturating_add(bounds.quality_upper_bound),
+                bounds
+                    .steps_lower_bound
+                    .map_or_else(String::new, |steps| steps.to_string()),
+            ),
+            Err(error) => error_fields(mode, &solver_error(error)),
+        },
+        Mode::Solve => match request.solve(AtomicFlag::new()) {
+            Ok(actions) => {
+                let settings = request.simulator_settings();
+                let quality = match raphael_sim::SimulationState::from_macro(&settings, &actions) {
+                    Ok(state) => initial_quality.saturating_add(state.quality),
+                    Err(error) => return error_fields(mode, &csv_string(error)),
+                };
+                let duration: u16 = actions
+                    .iter()
+                    .map(|action| u16::from(action.time_cost()))
+                    .sum();
+                let actions: Vec<String> =
+                    actions.iter().map(|action| format!("{action:?}")).collect();
+                format!(
+                    "{},{},{},{},",
+                    quality,
+                    actions.len(),
+                    duration,
+                    actions.join(" ")
+                )
+            }
+            Err(error) => error_fields(mode, &solver_error(error)),
+        },
+    }
+}
+
+fn main() {
+    env_logger::builder()
+        .format_timestamp(None)
+        .format_target(false)
+        .init();
+
+    let args = Args::parse();
+
+    let matrix = std::fs::read_to_string(&args.matrix).expect("Failed to read the matrix file");
+    let matrix: Matrix = toml::from_str(&matrix).expect("Failed to parse the matrix file");
+    let configurations = matrix.configurations();
+    log::info!("Running {} configurations", configurations.len());
+
+    let thread_pool = ThreadPoolSettings {
+        num_threads: args.threads,
+        background_priority: args.background_priority,
+    }
+    .build()
+    .expect("Failed to build thread pool");
+
+    let finished = AtomicUsize::new(0);
+    let rows: Vec<String> = thread_pool.install(|| {
+        configurations
+            .par_iter()
+            .map(|configuration| {
+                let row = format!(
+                    "{},{}",
+                    configuration.csv_fields(),
+                    run(matrix.mode, configuration)
+                );
+                let finished = finished.fetch_add(1, Ordering::Relaxed) + 1;
+                log::info!("Finished {finished}/{}", configurations.len());
+                row
+            })
+            .collect()
+    });
+
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => {
+            Box::new(std::fs::File::create(path).expect("Failed to create the output file"))
+        }
+        None => Box::new(std::io::stdout().lock()),
+    };
+    writeln!(
+        output,
+        "{CONFIGURATION_COLUMNS},{}",
+        result_columns(matrix.mode)
+    )
+    .expect("Failed to write the output");
+    for row in rows {
+        writeln!(output, "{row}").expect("Failed to write the output");
+    }
+}
@@ -0,0 +1,73 @@
+//! On-disk cache of solved macros, so that scripts that solve the same request repeatedly
+//! don't have to wait for the solver every time.
+
+use std::path::PathBuf;
+
+use raphael_sim::{Action, SimulationState};
+use raphael_solver::SolveRequest;
+use sha2::{Digest, Sha256};
+
+/// Stores the macro of each solve request as JSON in a file that is named after a hash of the
+/// request. Results of different CLI versions are stored separately because the solver may
+/// find a different macro for the same request.
+pub struct SolveCache {
+    dir: PathBuf,
+}
+
+impl SolveCache {
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn default_dir() -> PathBuf {
+        std::env::temp_dir().join("raphael-cli-cache")
+    }
+
+    fn path(&self, request: &SolveRequest) -> PathBuf {
+        let request = serde_json::to_string(request).expect("Failed to serialize the request");
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        hasher.update(request);
+        let hash: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        self.dir.join(format!("{hash}.json"))
+    }
+
+    /// Returns the cached macro of `request`. Entries that can't be read or whose macro doesn't
+    /// finish the craft are ignored.
+    pub fn get(&self, request: &SolveRequest) -> Option<Vec<Action>> {
+        let path = self.path(request);
+        let contents = std::fs::read_to_string(&path).ok()?;
+        let actions: Vec<Action> = match serde_json::from_str(&contents) {
+            Ok(actions) => actions,
+            Err(error) => {
+                log::warn!("Ignoring invalid cache entry {}: {error}", path.display());
+                return None;
+            }
+        };
+        let settings = request.simulator_settings();
+        match SimulationState::from_macro(&settings, &actions) {
+            Ok(state) if state.progress >= settings.max_progress => {
+                log::info!("Using cached result {}", path.display());
+                Some(actions)
+            }
+            _ => {
+                log::warn!("Ignoring invalid cache entry {}", path.display());
+                None
+            }
+        }
+    }
+
+    pub fn insert(&self, request: &SolveRequest, actions: &[Action]) -> std::io::Result<()> {
+        let path = self.path(request);
+        std::fs::create_dir_all(&self.dir)?;
+        // write to a temporary file first so that concurrent invocations never read a
+        // partially written entry
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&temp_path, serde_json::to_string(actions)?)?;
+        std::fs::rename(&temp_path, &path)
+    }
+}
@@ -0,0 +1,149 @@
+use clap::{Args, ValueEnum};
+use raphael_data::{CrafterStats, RECIPES, condition_pool};
+use raphael_sim::{Action, ActionMask, Condition, SimulationState};
+use raphael_solver::{AtomicFlag, ExpertAdvisor, SolveRequest};
+
+#[derive(Args, Debug)]
+pub struct AdviseArgs {
+    /// Item ID
+    #[arg(short, long)]
+    pub item_id: u32,
+
+    /// Complete stats
+    #[arg(short, long, num_args = 3, value_names = ["CRAFTSMANSHIP", "CONTROL", "CP"], required = true)]
+    pub stats: Vec<u16>,
+
+    /// Crafter level
+    #[arg(short, long, default_value_t = 100)]
+    pub level: u8,
+
+    /// Enable Manipulation
+    #[arg(short, long, default_value_t = false)]
+    pub manipulation: bool,
+
+    /// Enable Heart and Soul
+    #[arg(long, default_value_t = false)]
+    pub heart_and_soul: bool,
+
+    /// Enable Quick Innovation
+    #[arg(long, default_value_t = false)]
+    pub quick_innovation: bool,
+
+    /// Actions used so far, in the format '<ACTION>[:<CONDITION>]', e.g. 'Manipulation:pliant'.
+    /// The condition defaults to Normal
+    #[arg(long, num_args = 1.., value_name = "ACTION", value_parser = parse_step)]
+    pub history: Vec<(Action, Condition)>,
+
+    /// Current condition
+    #[arg(short = 'n', long, value_enum, ignore_case = true, default_value_t = ConditionArg::Normal)]
+    pub condition: ConditionArg,
+
+    /// Number of suggestions to print
+    #[arg(long, default_value_t = 5)]
+    pub count: usize,
+}
+
+#[derive(Copy, Clone, ValueEnum, Debug)]
+pub enum ConditionArg {
+    Normal,
+    Good,
+    Excellent,
+    Poor,
+    Centered,
+    Sturdy,
+    Pliant,
+    Malleable,
+    Primed,
+    GoodOmen,
+}
+
+impl From<ConditionArg> for Condition {
+    fn from(condition: ConditionArg) -> Self {
+        match condition {
+            ConditionArg::Normal => Self::Normal,
+            ConditionArg::Good => Self::Good,
+            ConditionArg::Excellent => Self::Excellent,
+            ConditionArg::Poor => Self::Poor,
+            ConditionArg::Centered => Self::Centered,
+            ConditionArg::Sturdy => Self::Sturdy,
+            ConditionArg::Pliant => Self::Pliant,
+            ConditionArg::Malleable => Self::Malleable,
+            ConditionArg::Primed => Self::Primed,
+            ConditionArg::GoodOmen => Self::GoodOmen,
+        }
+    }
+}
+
+fn parse_step(s: &str) -> Result<(Action, Condition), String> {
+    let (action, condition) = s.split_once(':').unwrap_or((s, "normal"));
+    let action = ActionMask::all()
+        .actions_iter()
+        .find(|candidate| format!("{candidate:?}").eq_ignore_ascii_case(action))
+        .ok_or_else(|| format!("Unknown action: {action}"))?;
+    let condition = ConditionArg::from_str(condition, true)
+        .map_err(|_| format!("Unknown condition: {condition}"))?;
+    Ok((action, condition.into()))
+}
+
+pub fn execute(args: &AdviseArgs) {
+    let recipe = RECIPES
+        .iter()
+        .find(|r| r.item_id == args.item_id)
+        .unwrap_or_else(|| {
+            panic!(
+                "Unable to find Recipe for an item with item ID: {}",
+                args.item_id
+            )
+        });
+    let crafter_stats = CrafterStats {
+        craftsmanship: args.stats[0],
+        control: args.stats[1],
+        cp: args.stats[2],
+        level: args.level,
+        manipulation: args.manipulation,
+        heart_and_soul: args.heart_and_soul,
+        quick_innovation: args.quick_innovation,
+    };
+    let request = SolveRequest::new(*recipe, crafter_stats);
+    let settings = request.simulator_settings();
+
+    let mut state = SimulationState::new(&settings);
+    for (step, (action, condition)) in args.history.iter().enumerate() {
+        state = state
+            .use_action(*action, *condition, &settings)
+            .unwrap_or_else(|error| panic!("Step {} ({action:?}) failed: {error}", step + 1));
+    }
+    if state.is_final(&settings) {
+        println!("The craft is already finished");
+        return;
+    }
+
+    let mut advisor = ExpertAdvisor::new(
+        request.solver_settings(),
+        condition_pool(*recipe),
+        AtomicFlag::new(),
+    );
+    let advice = advisor
+        .advise(state, args.condition.into())
+        .expect("Failed to advise");
+    if advice.is_empty() {
+        println!("The craft can't be finished anymore");
+        return;
+    }
+    for advice in advice.iter().take(args.count) {
+        let actions: Vec<String> = advice
+            .actions
+            .iter()
+            .map(|action| format!("{action:?}"))
+            .collect();
+        println!(
+            "{}: expected quality upper bound {:.0}, steps lower bound {}, CP left {}",
+            actions.join(" > "),
+            advice.expected_quality_upper_bound,
+            advice
+                .steps_lower_bound
+                .map_or_else(|| "-".to_owned(), |steps| steps.to_string()),
+            advice.cp
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use clap::Args;
+use raphael_data::GameDataset;
+
+#[derive(Args, Debug)]
+pub struct ExportDataArgs {
+    /// Name of this version of the data, e.g. the game patch or the date of the export
+    #[arg(long)]
+    pub data_version: String,
+}
+
+pub fn execute(args: &ExportDataArgs) {
+    let dataset = GameDataset::from_bundled(args.data_version.clone());
+    println!("{}", serde_json::to_string(&dataset).unwrap());
+}
@@ -1,2 +1,5 @@
+pub mod advise;
+pub mod export_data;
 pub mod search;
+pub mod self_test;
 pub mod solve;
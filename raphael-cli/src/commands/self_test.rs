@@ -0,0 +1,153 @@
+use std::time::Instant;
+
+use raphael_sim::{Action, ActionMask, RulesOverride, Settings, SuccessModel};
+use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings, test_utils::get_score_quad};
+
+/// Craft whose optimal macro is known from the solver's test suite.
+struct Scenario {
+    name: &'static str,
+    settings: Settings,
+    backload_progress: bool,
+    /// Quality (capped at the max Quality), steps, duration and Quality above the max Quality
+    /// of the optimal macro.
+    expected_score: (u16, u8, u8, u16),
+}
+
+const BASE_SETTINGS: Settings = Settings {
+    max_cp: 0,
+    max_durability: 0,
+    max_progress: 0,
+    max_quality: 0,
+    base_progress: 0,
+    base_quality: 0,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+const RINASCITA_3700_3280: Settings = Settings {
+    max_cp: 680,
+    max_durability: 70,
+    max_progress: 5060,
+    max_quality: 12628,
+    base_progress: 229,
+    base_quality: 224,
+    ..BASE_SETTINGS
+};
+
+const SCENARIOS: [Scenario; 5] = [
+    Scenario {
+        name: "Rinascita (3700 Craftsmanship, 3280 Control, 680 CP)",
+        settings: RINASCITA_3700_3280,
+        backload_progress: false,
+        expected_score: (10623, 26, 70, 0),
+    },
+    Scenario {
+        name: "Rinascita, Progress backloaded",
+        settings: RINASCITA_3700_3280,
+        backload_progress: true,
+        expected_score: (10492, 25, 66, 0),
+    },
+    Scenario {
+        name: "Pactmaker (3240 Craftsmanship, 3130 Control, 600 CP), Heart and Soul",
+        settings: Settings {
+            max_cp: 600,
+            max_durability: 70,
+            max_progress: 4300,
+            max_quality: 12800,
+            base_progress: 200,
+            base_quality: 215,
+            allowed_actions: ActionMask::all()
+                .remove(Action::TrainedEye)
+                .remove(Action::QuickInnovation),
+            ..BASE_SETTINGS
+        },
+        backload_progress: false,
+        expected_score: (9608, 24, 65, 0),
+    },
+    Scenario {
+        name: "Rarefied Tacos de Carne Asada (4785 Craftsmanship, 4758 Control, 646 CP)",
+        settings: Settings {
+            max_cp: 646,
+            max_durability: 80,
+            max_progress: 6600,
+            max_quality: 12000,
+            base_progress: 256,
+            base_quality: 265,
+            job_level: 100,
+            ..BASE_SETTINGS
+        },
+        backload_progress: false,
+        expected_score: (12000, 21, 56, 123),
+    },
+    Scenario {
+        name: "Rarefied Stuffed Peppers (4785 Craftsmanship, 4758 Control, 646 CP), adversarial",
+        settings: Settings {
+            max_cp: 646,
+            max_durability: 80,
+            max_progress: 6300,
+            max_quality: 11400,
+            base_progress: 289,
+            base_quality: 360,
+            job_level: 100,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        backload_progress: false,
+        expected_score: (11400, 16, 45, 282),
+    },
+];
+
+/// Solves the built-in scenarios and exits with status 1 if any result differs from the known
+/// optimal macro.
+pub fn execute() {
+    let mut failures = 0;
+    for scenario in &SCENARIOS {
+        let start = Instant::now();
+        let result = MacroSolver::new(
+            SolverSettings {
+                simulator_settings: scenario.settings,
+                backload_progress: scenario.backload_progress,
+                allow_unsound_branch_pruning: false,
+                forbid_quality_during_muscle_memory: false,
+                first_quality_step: 0,
+            },
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            AtomicFlag::new(),
+        )
+        .solve();
+        let elapsed = start.elapsed().as_secs_f32();
+        match result {
+            Ok(actions) => {
+                let score = get_score_quad(&scenario.settings, &actions);
+                match score == scenario.expected_score {
+                    true => println!("{} ... ok ({elapsed:.2}s)", scenario.name),
+                    false => {
+                        failures += 1;
+                        println!(
+                            "{} ... FAILED: expected (quality, steps, duration, overflow) {:?}, got {:?}",
+                            scenario.name, scenario.expected_score, score
+                        );
+                    }
+                }
+            }
+            Err(error) => {
+                failures += 1;
+                println!("{} ... FAILED: {error:?}", scenario.name);
+            }
+        }
+    }
+    match failures {
+        0 => println!("All {} scenarios passed", SCENARIOS.len()),
+        _ => {
+            println!("{failures} of {} scenarios failed", SCENARIOS.len());
+            std::process::exit(1);
+        }
+    }
+}
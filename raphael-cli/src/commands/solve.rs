@@ -1,28 +1,35 @@
-use clap::Args;
-use raphael_data::{CrafterStats, MEALS, POTIONS, RECIPES, get_game_settings};
-use raphael_sim::SimulationState;
-use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use raphael_data::{CrafterStats, MEALS, POTIONS, RECIPES};
+use raphael_sim::{Action, RulesOverride, SimulationState, SuccessModel, lint::Linter};
+use raphael_solver::{
+    AtomicFlag, ProcAdvice, SolveRequest, SolverException, ThreadPoolSettings,
+    best_byregot_placement, condition_cheat_sheet,
+};
+
+use crate::cache::SolveCache;
 
 #[derive(Args, Debug)]
 pub struct SolveArgs {
     /// Item ID
-    #[arg(short, long)]
-    pub item_id: u32,
+    #[arg(short, long, required_unless_present = "watch")]
+    pub item_id: Option<u32>,
 
     /// Craftsmanship rating
-    #[arg(short, long, requires_all(["control", "cp"]), required_unless_present = "stats")]
+    #[arg(short, long, requires_all(["control", "cp"]), required_unless_present_any(["stats", "watch"]))]
     pub craftsmanship: Option<u16>,
 
     /// Control rating
-    #[arg(short = 'o', long, requires_all(["craftsmanship", "cp"]), required_unless_present = "stats")]
+    #[arg(short = 'o', long, requires_all(["craftsmanship", "cp"]), required_unless_present_any(["stats", "watch"]))]
     pub control: Option<u16>,
 
     /// Crafting points
-    #[arg(short = 'p', long, requires_all(["craftsmanship", "control"]), required_unless_present = "stats")]
+    #[arg(short = 'p', long, requires_all(["craftsmanship", "control"]), required_unless_present_any(["stats", "watch"]))]
     pub cp: Option<u16>,
 
     /// Complete stats, conflicts with setting one or more of the stats separately
-    #[arg(short, long, num_args = 3, value_names = ["CRAFTSMANSHIP", "CONTROL", "CP"], required_unless_present_all(["craftsmanship", "control", "cp"]), conflicts_with_all(["craftsmanship", "control", "cp"]))]
+    #[arg(short, long, num_args = 3, value_names = ["CRAFTSMANSHIP", "CONTROL", "CP"], required_unless_present_all(["craftsmanship", "control", "cp"]), required_unless_present = "watch", conflicts_with_all(["craftsmanship", "control", "cp"]))]
     pub stats: Vec<u16>,
 
     /// Crafter level
@@ -73,13 +80,73 @@ pub struct SolveArgs {
     #[arg(long, default_value_t = false)]
     pub backload_progress: bool,
 
+    /// Don't use Quality actions while Muscle Memory is active
+    #[arg(long, default_value_t = false)]
+    pub progress_opener: bool,
+
+    /// Don't use Quality actions before this step, counted from 0 at the start of the craft
+    #[arg(long, default_value_t = 0)]
+    pub first_quality_step: u8,
+
+    /// Solve without Byregot's Blessing and keep its CP and durability unspent, and print the step where using it gives the most Quality.
+    /// For using Byregot's Blessing by hand on a Good or Excellent condition while the rest runs as a macro
+    #[arg(long, default_value_t = false)]
+    pub manual_byregot: bool,
+
+    /// Print what to do by hand when a step of the macro gets a Good or Excellent condition
+    #[arg(long, default_value_t = false)]
+    pub cheat_sheet: bool,
+
     /// Enable unsound branch pruning
     #[arg(long, default_value_t = false)]
     pub unsound: bool,
 
+    /// Among the macros that reach the target quality, prefer the one that spends the least CP
+    #[arg(long, default_value_t = false)]
+    pub minimize_cp: bool,
+
+    /// Prefer macros that repeat actions back to back and start with a common opener, which are easier to execute by hand.
+    /// The weight is how many tenths of a second of macro duration one fewer block of actions is worth, 0 disables the bias
+    #[arg(long, value_name = "WEIGHT", default_value_t = 0)]
+    pub human_friendly_bias: u8,
+
+    /// How actions with a success rate below 100% (Rapid Synthesis, Hasty Touch, Daring Touch) are treated
+    #[arg(long, value_enum, default_value_t = SuccessModelArg::AssumeFail)]
+    pub success_model: SuccessModelArg,
+
+    /// JSON file with changes to action potencies and costs, to simulate balance changes before they are released.
+    /// Contains a list of entries like `{"action": "BasicTouch", "quality_efficiency": 120, "cp_cost": 20}`
+    #[arg(long, value_name = "FILE", value_parser = parse_rules_override)]
+    pub rules_override: Option<RulesOverride>,
+
+    /// Number of solver threads, 0 uses one thread per logical core
+    #[arg(long, default_value_t = 0)]
+    pub threads: usize,
+
+    /// Run the solver threads below normal priority
+    #[arg(long, default_value_t = false)]
+    pub background_priority: bool,
+
+    /// Solve even if the result is already cached. The new result replaces the cached one
+    #[arg(long, default_value_t = false)]
+    pub no_cache: bool,
+
+    /// Directory of the result cache, defaults to a directory in the system's temporary directory
+    #[arg(long, env = "RAPHAEL_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Write the solve request to a JSON file, e.g. as a starting point for `--watch`
+    #[arg(long, value_name = "FILE", conflicts_with = "watch")]
+    pub write_request: Option<PathBuf>,
+
+    /// Solve the request in a JSON file and solve it again whenever the file changes, printing how the macro changed.
+    /// The recipe, crafter and solver arguments are ignored, the request file contains all of them
+    #[arg(long, value_name = "FILE")]
+    pub watch: Option<PathBuf>,
+
     /// Output the provided list of variables. The output is deliminated by the output-field-separator
     ///
-    /// <IDENTIFIER> can be any of the following: `item_id`, `recipe`, `food`, `potion`, `craftsmanship`, `control`, `cp`, `crafter_stats`, `settings`, `initial_quality`, `target_quality`, `recipe_max_quality`, `actions`, `final_state`, `state_quality`, `final_quality`, `steps`, `duration`.
+    /// <IDENTIFIER> can be any of the following: `item_id`, `recipe`, `food`, `potion`, `craftsmanship`, `control`, `cp`, `crafter_stats`, `settings`, `initial_quality`, `target_quality`, `recipe_max_quality`, `actions`, `final_state`, `state_quality`, `final_quality`, `steps`, `duration`, `lint`.
     /// While the output is mainly intended for generating CSVs, some output can contain `,` inside brackets that are not deliminating columns. For this reason they are wrapped in double quotes and the argument `output-field-separator` can be used to override the delimiter to something that is easier to parse and process
     #[arg(long, num_args = 1.., value_name = "IDENTIFIER")]
     pub output_variables: Vec<String>,
@@ -89,6 +156,26 @@ pub struct SolveArgs {
     output_field_separator: String,
 }
 
+#[derive(Copy, Clone, ValueEnum, Debug)]
+pub enum SuccessModelArg {
+    /// Never use the actions
+    AssumeFail,
+    /// Assume that the actions always succeed
+    AssumeSuccess,
+    /// Use the average Progress and Quality increase of the actions
+    ExpectedValue,
+}
+
+impl From<SuccessModelArg> for SuccessModel {
+    fn from(success_model: SuccessModelArg) -> Self {
+        match success_model {
+            SuccessModelArg::AssumeFail => Self::AssumeFail,
+            SuccessModelArg::AssumeSuccess => Self::AssumeSuccess,
+            SuccessModelArg::ExpectedValue => Self::ExpectedValue,
+        }
+    }
+}
+
 fn parse_consumable(s: &str) -> Result<ConsumableArg, String> {
     const PARSE_ERROR_STRING: &'static str =
         "Consumable is not parsable. Consumables must have the format '<ITEM_ID>[,HQ]'";
@@ -112,6 +199,11 @@ fn parse_consumable(s: &str) -> Result<ConsumableArg, String> {
     }
 }
 
+fn parse_rules_override(path: &str) -> Result<RulesOverride, String> {
+    let contents = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    serde_json::from_str(&contents).map_err(|error| error.to_string())
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum ConsumableArg {
     /// NQ Consumable
@@ -144,12 +236,18 @@ fn map_and_clamp_hq_ingredients(recipe: &raphael_data::Recipe, hq_ingredients: [
 }
 
 pub fn execute(args: &SolveArgs) {
+    if let Some(path) = &args.watch {
+        crate::watch::watch(path, |request| solve(args, request));
+        return;
+    }
+
+    let item_id = args.item_id.unwrap();
     let recipe = RECIPES
         .iter()
-        .find(|r| r.item_id == args.item_id)
+        .find(|r| r.item_id == item_id)
         .expect(&format!(
             "Unable to find Recipe for an item with item ID: {}",
-            args.item_id
+            item_id
         ));
     let food = match args.food {
         Some(food_arg) => {
@@ -225,13 +323,25 @@ pub fn execute(args: &SolveArgs) {
         quick_innovation: args.quick_innovation,
     };
 
-    let mut settings = get_game_settings(*recipe, crafter_stats, food, potion, args.adversarial);
-    let target_quality = match args.target_quality {
-        Some(target) => target.clamp(0, settings.max_quality),
-        None => settings.max_quality,
-    };
+    let mut request = SolveRequest::new(*recipe, crafter_stats)
+        .food(food)
+        .potion(potion)
+        .adversarial(args.adversarial)
+        .backload_progress(args.backload_progress)
+        .forbid_quality_during_muscle_memory(args.progress_opener)
+        .first_quality_step(args.first_quality_step)
+        .exclude_byregot(args.manual_byregot)
+        .allow_unsound_branch_pruning(args.unsound)
+        .minimize_cp(args.minimize_cp)
+        .human_friendly_bias(args.human_friendly_bias)
+        .success_model(args.success_model.into())
+        .rules_override(args.rules_override.unwrap_or_default());
+    let recipe_max_quality = request.game_settings().max_quality;
+    if let Some(target_quality) = args.target_quality {
+        request = request.target_quality(target_quality);
+    }
     let initial_quality = match args.initial_quality {
-        Some(initial) => initial.clamp(0, settings.max_quality),
+        Some(initial) => initial.clamp(0, recipe_max_quality),
         None => match args.hq_ingredients.clone() {
             Some(mut hq_ingredients) => {
                 hq_ingredients.resize(6, 0);
@@ -247,28 +357,26 @@ pub fn execute(args: &SolveArgs) {
             None => 0,
         },
     };
-    let recipe_max_quality = settings.max_quality;
-    settings.max_quality = target_quality.saturating_sub(initial_quality);
-
-    let solver_settings = SolverSettings {
-        simulator_settings: settings,
-        backload_progress: args.backload_progress,
-        allow_unsound_branch_pruning: args.unsound,
-    };
+    request = request.initial_quality(initial_quality);
+    let target_quality = request.get_target_quality();
+    let settings = request.simulator_settings();
+
+    if let Some(path) = &args.write_request {
+        let request =
+            serde_json::to_string_pretty(&request).expect("Failed to serialize the request");
+        if let Err(error) = std::fs::write(path, request) {
+            log::warn!("Failed to write the request to {}: {error}", path.display());
+        }
+    }
 
-    let mut solver = MacroSolver::new(
-        solver_settings,
-        Box::new(|_| {}),
-        Box::new(|_| {}),
-        AtomicFlag::new(),
-    );
-    let actions = solver.solve().expect("Failed to solve");
+    let actions = solve(args, &request).expect("Failed to solve");
 
     let final_state = SimulationState::from_macro(&settings, &actions).unwrap();
     let state_quality = final_state.quality;
     let final_quality = state_quality.saturating_add(initial_quality);
     let steps = actions.len();
     let duration: u8 = actions.iter().map(|action| action.time_cost()).sum();
+    let lint_findings = Linter::default().lint(&settings, &actions);
 
     if args.output_variables.is_empty() {
         println!("Item ID: {}", recipe.item_id);
@@ -280,9 +388,59 @@ pub fn execute(args: &SolveArgs) {
         println!("Steps: {}", steps);
         println!("Duration: {} seconds", duration);
         println!("\nActions:");
-        for action in actions {
+        for action in &actions {
             println!("{:?}", action);
         }
+        if args.manual_byregot {
+            let game_settings = request.game_settings();
+            let initial_state = SimulationState::new(&game_settings);
+            match best_byregot_placement(&game_settings, initial_state, &actions) {
+                Some(placement) => println!(
+                    "\nByregot's Blessing: before step {} ({:?}), Quality {}",
+                    placement.index + 1,
+                    actions[placement.index],
+                    placement.quality.saturating_add(initial_quality)
+                ),
+                None => println!("\nByregot's Blessing: no step leaves enough CP and durability"),
+            }
+        }
+        if args.cheat_sheet {
+            let game_settings = request.game_settings();
+            let initial_state = SimulationState::new(&game_settings);
+            println!("\nCheat sheet:");
+            for suggestion in condition_cheat_sheet(&game_settings, initial_state, &actions) {
+                let advice = match suggestion.advice {
+                    ProcAdvice::Continue => continue,
+                    ProcAdvice::Replace(action) => {
+                        format!("use {:?} instead of {:?}", action, actions[suggestion.step])
+                    }
+                    ProcAdvice::Insert(action) => {
+                        format!("use {:?} before {:?}", action, actions[suggestion.step])
+                    }
+                };
+                println!(
+                    "{:?} at step {}: {}, Quality {} (+{})",
+                    suggestion.condition,
+                    suggestion.step + 1,
+                    advice,
+                    suggestion.quality.saturating_add(initial_quality),
+                    suggestion.quality - suggestion.continue_quality
+                );
+            }
+        }
+        if !lint_findings.is_empty() {
+            println!("\nLint:");
+            for finding in &lint_findings {
+                println!(
+                    "{:?} at step {} ({:?}): {} [{}]",
+                    finding.severity,
+                    finding.step + 1,
+                    finding.action,
+                    finding.message,
+                    finding.rule
+                );
+            }
+        }
     } else {
         let mut output_string = "".to_owned();
 
@@ -290,7 +448,7 @@ pub fn execute(args: &SolveArgs) {
         //let segments: Vec<&str> = args.output_variables;
         for identifier in &args.output_variables {
             let map_to_debug_str = |actions: Vec<raphael_sim::Action>| match &*(*identifier) {
-                "item_id" => format!("{:?}", args.item_id),
+                "item_id" => format!("{:?}", item_id),
                 "recipe" => format!("\"{:?}\"", recipe),
                 "food" => format!("\"{:?}\"", food),
                 "potion" => format!("\"{:?}\"", potion),
@@ -308,6 +466,7 @@ pub fn execute(args: &SolveArgs) {
                 "final_quality" => format!("{:?}", final_quality),
                 "steps" => format!("{:?}", steps),
                 "duration" => format!("{:?}", duration),
+                "lint" => format!("\"{:?}\"", lint_findings),
                 _ => "Undefined".to_owned(),
             };
 
@@ -320,3 +479,31 @@ pub fn execute(args: &SolveArgs) {
         );
     }
 }
+
+/// Solves `request`, or returns the cached macro of an earlier solve of the same request.
+fn solve(args: &SolveArgs, request: &SolveRequest) -> Result<Vec<Action>, SolverException> {
+    let cache = SolveCache::new(
+        args.cache_dir
+            .clone()
+            .unwrap_or_else(SolveCache::default_dir),
+    );
+    let cached_actions = match args.no_cache {
+        true => None,
+        false => cache.get(request),
+    };
+    if let Some(actions) = cached_actions {
+        return Ok(actions);
+    }
+    let thread_pool = ThreadPoolSettings {
+        num_threads: args.threads,
+        background_priority: args.background_priority,
+    }
+    .build()
+    .expect("Failed to build thread pool");
+
+    let actions = thread_pool.install(|| request.solve(AtomicFlag::new()))?;
+    if let Err(error) = cache.insert(request, &actions) {
+        log::warn!("Failed to cache the result: {error}");
+    }
+    Ok(actions)
+}
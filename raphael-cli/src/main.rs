@@ -1,24 +1,37 @@
 use clap::{Parser, Subcommand};
 
+mod cache;
 mod commands;
+mod watch;
 
 #[derive(Parser, Debug)]
 #[command(
     version,
-    about = "A command-line interface for the Raphael-XIV crafting solver."
+    about = "A command-line interface for the Raphael-XIV crafting solver.",
+    args_conflicts_with_subcommands = true,
+    arg_required_else_help = true
 )]
 
 struct Cli {
+    /// Solve built-in crafts with known optimal macros and check that the results match, to confirm that this build produces correct results
+    #[arg(long)]
+    self_test: bool,
+
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Search for recipes by name
     Search(commands::search::SearchArgs),
     /// Solve a crafting rotation
     Solve(commands::solve::SolveArgs),
+    /// Suggest the next action of an expert craft, given the actions so far and the current condition
+    Advise(commands::advise::AdviseArgs),
+    /// Print the bundled game data as JSON, which the app can download to update its data
+    ExportData(commands::export_data::ExportDataArgs),
 }
 
 fn main() {
@@ -30,7 +43,11 @@ fn main() {
     let cli = Cli::parse();
 
     match &cli.command {
-        Commands::Search(args) => commands::search::execute(args),
-        Commands::Solve(args) => commands::solve::execute(args),
+        Some(Commands::Search(args)) => commands::search::execute(args),
+        Some(Commands::Solve(args)) => commands::solve::execute(args),
+        Some(Commands::Advise(args)) => commands::advise::execute(args),
+        Some(Commands::ExportData(args)) => commands::export_data::execute(args),
+        // `--self-test` is the only way to run without a command
+        None => commands::self_test::execute(),
     }
 }
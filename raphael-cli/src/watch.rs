@@ -0,0 +1,109 @@
+//! Solves a request file again whenever it changes, so that theorycrafters can iterate on
+//! custom recipe parameters in an editor.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use raphael_sim::{Action, Condition};
+use raphael_solver::{SolveRequest, SolverException};
+
+/// How often the modification time of the request file is checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Solves the request in the JSON file at `path` every time the file is modified and prints
+/// how the macro changed compared to the previous solve. Runs until the process is killed.
+pub fn watch(path: &Path, solve: impl Fn(&SolveRequest) -> Result<Vec<Action>, SolverException>) {
+    let mut last_modified: Option<SystemTime> = None;
+    let mut previous_actions: Option<Vec<Action>> = None;
+    loop {
+        let modified = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if modified.is_some() && modified != last_modified {
+            last_modified = modified;
+            match read_request(path) {
+                Ok(request) => match solve(&request) {
+                    Ok(actions) => {
+                        print_result(&request, &actions);
+                        if let Some(previous_actions) = &previous_actions {
+                            print_diff(previous_actions, &actions);
+                        }
+                        previous_actions = Some(actions);
+                    }
+                    Err(error) => eprintln!("Failed to solve: {error:?}"),
+                },
+                // e.g. the editor hasn't finished writing the file
+                Err(error) => eprintln!("Failed to read {}: {error}", path.display()),
+            }
+            println!("\nWatching {} for changes ...", path.display());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn read_request(path: &Path) -> Result<SolveRequest, String> {
+    let contents = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    serde_json::from_str(&contents).map_err(|error| error.to_string())
+}
+
+fn print_result(request: &SolveRequest, actions: &[Action]) {
+    let settings = request.simulator_settings();
+    let target_quality = request.get_target_quality();
+    let initial_quality = target_quality - settings.max_quality;
+    let mut state = request.initial_state();
+    for action in actions {
+        match state.use_action(*action, Condition::Normal, &settings) {
+            Ok(new_state) => state = new_state,
+            Err(error) => {
+                eprintln!("{action:?} fails: {error}");
+                break;
+            }
+        }
+    }
+    let duration: u8 = actions.iter().map(|action| action.time_cost()).sum();
+    println!();
+    println!(
+        "Quality: {}/{}",
+        initial_quality.saturating_add(state.quality),
+        target_quality
+    );
+    println!("Progress: {}/{}", state.progress, settings.max_progress);
+    println!("Steps: {}", actions.len());
+    println!("Duration: {} seconds", duration);
+}
+
+/// Prints the actions that were removed from the previous macro with `-` and the actions that
+/// were added to the new macro with `+`, based on the longest common subsequence.
+fn print_diff(previous_actions: &[Action], actions: &[Action]) {
+    if previous_actions == actions {
+        println!("\nThe macro didn't change.");
+        return;
+    }
+    // common_len[i][j] is the length of the longest common subsequence of the suffixes
+    let mut common_len = vec![vec![0usize; actions.len() + 1]; previous_actions.len() + 1];
+    for i in (0..previous_actions.len()).rev() {
+        for j in (0..actions.len()).rev() {
+            common_len[i][j] = match previous_actions[i] == actions[j] {
+                true => common_len[i + 1][j + 1] + 1,
+                false => std::cmp::max(common_len[i + 1][j], common_len[i][j + 1]),
+            };
+        }
+    }
+    println!("\nChanges:");
+    let (mut i, mut j) = (0, 0);
+    while i < previous_actions.len() || j < actions.len() {
+        if i < previous_actions.len() && j < actions.len() && previous_actions[i] == actions[j] {
+            println!("  {:?}", actions[j]);
+            i += 1;
+            j += 1;
+        } else if j < actions.len()
+            && (i == previous_actions.len() || common_len[i][j + 1] >= common_len[i + 1][j])
+        {
+            println!("+ {:?}", actions[j]);
+            j += 1;
+        } else {
+            println!("- {:?}", previous_actions[i]);
+            i += 1;
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::records::{
+    CollectablesShopItemRecord, CollectablesShopRefineRecord, CollectablesShopRewardScripRecord,
+};
+use crate::utils::read_csv_data;
+
+const SHOP_ITEM_PATH: &str = "data/CollectablesShopItem.csv";
+const REFINE_PATH: &str = "data/CollectablesShopRefine.csv";
+const REWARD_SCRIP_PATH: &str = "data/CollectablesShopRewardScrip.csv";
+
+pub fn import_collectable_rewards(
+    relevant_items: &HashSet<u32>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut rewards = phf_codegen::Map::new();
+    let refines: HashMap<u32, CollectablesShopRefineRecord> =
+        read_csv_data::<CollectablesShopRefineRecord>(REFINE_PATH)
+            .map(|record| (record.id, record))
+            .collect();
+    let reward_scrips: HashMap<u32, CollectablesShopRewardScripRecord> =
+        read_csv_data::<CollectablesShopRewardScripRecord>(REWARD_SCRIP_PATH)
+            .map(|record| (record.id, record))
+            .collect();
+    let mut imported_items = HashSet::new();
+    for shop_item in read_csv_data::<CollectablesShopItemRecord>(SHOP_ITEM_PATH) {
+        if !relevant_items.contains(&shop_item.item_id) || !imported_items.insert(shop_item.item_id)
+        {
+            continue;
+        }
+        let (Some(refine), Some(reward)) = (
+            refines.get(&shop_item.refine_id),
+            reward_scrips.get(&shop_item.reward_scrip_id),
+        ) else {
+            continue;
+        };
+        rewards.entry(shop_item.item_id, &format!(
+            "CollectableRewards {{ currency: {}, tiers: [CollectableReward {{ min_collectability: {}, scrips: {}, exp_ratio: {} }}, CollectableReward {{ min_collectability: {}, scrips: {}, exp_ratio: {} }}, CollectableReward {{ min_collectability: {}, scrips: {}, exp_ratio: {} }}] }}",
+            reward.currency,
+            refine.low_collectability,
+            reward.low_reward,
+            reward.exp_ratio_low,
+            refine.mid_collectability,
+            reward.mid_reward,
+            reward.exp_ratio_mid,
+            refine.high_collectability,
+            reward.high_reward,
+            reward.exp_ratio_high,
+        ));
+    }
+    let out_path = Path::new(&std::env::var("OUT_DIR")?).join("collectable_rewards.rs");
+    let mut writer = BufWriter::new(File::create(out_path).unwrap());
+    writeln!(writer, "{}", rewards.build())?;
+    Ok(())
+}
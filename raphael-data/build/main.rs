@@ -2,6 +2,7 @@ mod records;
 use records::*;
 use utils::read_csv_data;
 
+mod collectables;
 mod consumables;
 mod items;
 mod utils;
@@ -26,6 +27,7 @@ fn import_game_data() -> Result<(), Box<dyn std::error::Error>> {
     import_recipe_records(&mut relevant_items, &rlvls)?;
 
     consumables::import_consumable_records(&mut relevant_items)?;
+    collectables::import_collectable_rewards(&relevant_items)?;
     items::import_item_records(relevant_items)?;
     Ok(())
 }
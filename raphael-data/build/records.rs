@@ -154,3 +154,45 @@ pub struct RecipeLevelRecord {
     #[serde(rename = "ConditionsFlag")]
     pub conditions_flag: u32,
 }
+
+#[derive(Deserialize)]
+pub struct CollectablesShopItemRecord {
+    #[serde(rename = "Item")]
+    pub item_id: u32,
+    #[serde(rename = "CollectablesShopRefine")]
+    pub refine_id: u32,
+    #[serde(rename = "CollectablesShopRewardScrip")]
+    pub reward_scrip_id: u32,
+}
+
+#[derive(Deserialize)]
+pub struct CollectablesShopRefineRecord {
+    #[serde(rename = "#")]
+    pub id: u32,
+    #[serde(rename = "LowCollectability")]
+    pub low_collectability: u16,
+    #[serde(rename = "MidCollectability")]
+    pub mid_collectability: u16,
+    #[serde(rename = "HighCollectability")]
+    pub high_collectability: u16,
+}
+
+#[derive(Deserialize)]
+pub struct CollectablesShopRewardScripRecord {
+    #[serde(rename = "#")]
+    pub id: u32,
+    #[serde(rename = "Currency")]
+    pub currency: u16,
+    #[serde(rename = "LowReward")]
+    pub low_reward: u16,
+    #[serde(rename = "MidReward")]
+    pub mid_reward: u16,
+    #[serde(rename = "HighReward")]
+    pub high_reward: u16,
+    #[serde(rename = "ExpRatioLow")]
+    pub exp_ratio_low: u16,
+    #[serde(rename = "ExpRatioMid")]
+    pub exp_ratio_mid: u16,
+    #[serde(rename = "ExpRatioHigh")]
+    pub exp_ratio_high: u16,
+}
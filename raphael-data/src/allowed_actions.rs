@@ -0,0 +1,65 @@
+use raphael_sim::{Action, ActionMask};
+
+use crate::{CrafterStats, Recipe};
+
+/// Reason why an action can't be used to craft a recipe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionExclusion {
+    /// The action is learned from a job quest that the crafter hasn't completed.
+    NotLearned,
+    /// The action can only be used by specialists.
+    NotSpecialist,
+    /// The action can't be used on expert recipes.
+    ExpertRecipe,
+    /// The action requires the crafter to be at least 10 levels above the recipe level.
+    RecipeLevelTooHigh,
+}
+
+impl ActionExclusion {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::NotLearned => "Not learned",
+            Self::NotSpecialist => "Specialist only",
+            Self::ExpertRecipe => "Not allowed on expert recipes",
+            Self::RecipeLevelTooHigh => "Requires being 10 levels above the recipe",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExcludedAction {
+    pub action: Action,
+    pub reason: ActionExclusion,
+}
+
+/// Actions that can't be used to craft `recipe`, independent of the level requirement of
+/// each action, which the simulator checks on its own.
+pub fn excluded_actions_for(recipe: Recipe, crafter_stats: CrafterStats) -> Vec<ExcludedAction> {
+    let mut excluded_actions = Vec::new();
+    let mut exclude = |action, reason| excluded_actions.push(ExcludedAction { action, reason });
+    if !crafter_stats.manipulation {
+        exclude(Action::Manipulation, ActionExclusion::NotLearned);
+    }
+    if recipe.is_expert {
+        exclude(Action::TrainedEye, ActionExclusion::ExpertRecipe);
+    } else if crafter_stats.level < recipe.level + 10 {
+        exclude(Action::TrainedEye, ActionExclusion::RecipeLevelTooHigh);
+    }
+    if !crafter_stats.heart_and_soul {
+        exclude(Action::HeartAndSoul, ActionExclusion::NotSpecialist);
+    }
+    if !crafter_stats.quick_innovation {
+        exclude(Action::QuickInnovation, ActionExclusion::NotSpecialist);
+    }
+    excluded_actions
+}
+
+/// Actions that can be used to craft `recipe`. Used for the simulator settings of all
+/// front-ends, so that the GUI and the solvers agree on which actions are available.
+pub fn allowed_actions_for(recipe: Recipe, crafter_stats: CrafterStats) -> ActionMask {
+    excluded_actions_for(recipe, crafter_stats)
+        .into_iter()
+        .fold(ActionMask::all(), |allowed_actions, excluded| {
+            allowed_actions.remove(excluded.action)
+        })
+}
@@ -0,0 +1,39 @@
+/// Rewards for turning in a collectable whose collectability is at least `min_collectability`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectableReward {
+    pub min_collectability: u16,
+    pub scrips: u16,
+    /// Experience in percent of the experience needed for the next level.
+    pub exp_ratio: u16,
+}
+
+/// Rewards of the three collectability tiers of a collectable item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectableRewards {
+    /// Row ID of the scrip currency in the game data.
+    pub currency: u16,
+    pub tiers: [CollectableReward; 3],
+}
+
+impl CollectableRewards {
+    /// Highest reached tier (1 to 3) and its rewards for an item crafted with `quality`.
+    /// The collectability of an item is a tenth of its Quality.
+    pub fn reward(&self, quality: u16) -> Option<(usize, CollectableReward)> {
+        let collectability = quality / 10;
+        self.tiers
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, tier)| {
+                tier.min_collectability != 0 && collectability >= tier.min_collectability
+            })
+            .map(|(index, tier)| (index + 1, *tier))
+    }
+}
+
+pub static COLLECTABLE_REWARDS: phf::Map<u32, CollectableRewards> =
+    include!(concat!(env!("OUT_DIR"), "/collectable_rewards.rs"));
+
+pub fn get_collectable_rewards(item_id: u32) -> Option<&'static CollectableRewards> {
+    COLLECTABLE_REWARDS.get(&item_id)
+}
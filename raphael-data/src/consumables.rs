@@ -3,6 +3,10 @@ use std::fmt::Write;
 pub const MEALS: &[Consumable] = include!(concat!(env!("OUT_DIR"), "/meals.rs"));
 pub const POTIONS: &[Consumable] = include!(concat!(env!("OUT_DIR"), "/potions.rs"));
 
+/// Item ID of the first custom consumable. Custom consumables use the IDs from here on, which are
+/// far above the IDs of the game's items.
+pub const CUSTOM_CONSUMABLE_ITEM_ID: u32 = 0x8000_0000;
+
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Consumable {
@@ -18,6 +22,10 @@ pub struct Consumable {
 }
 
 impl Consumable {
+    pub fn is_custom(self) -> bool {
+        self.item_id >= CUSTOM_CONSUMABLE_ITEM_ID
+    }
+
     pub fn effect_string(self, craftsmanship: u16, control: u16, cp: u16) -> String {
         let mut effect: String = String::new();
         if self.craft_rel != 0 {
@@ -48,6 +56,89 @@ impl Consumable {
         effect.pop();
         effect
     }
+
+    /// Whether the percentage bonus exceeds the flat cap for every stat that the consumable
+    /// raises, i.e. the consumable gives its maximum bonus.
+    pub fn is_capped(self, craftsmanship: u16, control: u16, cp: u16) -> bool {
+        [
+            (craftsmanship, self.craft_rel, self.craft_max),
+            (control, self.control_rel, self.control_max),
+            (cp, self.cp_rel, self.cp_max),
+        ]
+        .into_iter()
+        .filter(|(_, rel, _)| *rel != 0)
+        .all(|(base, rel, max)| base as u32 * rel as u32 / 100 >= max as u32)
+    }
+
+    /// The lowest item level consumable of `consumables` that gives at least the same bonus for
+    /// every stat, preferring NQ over HQ. `None` if the consumable is the cheapest one.
+    pub fn cheapest_equivalent(
+        self,
+        consumables: &[Self],
+        craftsmanship: u16,
+        control: u16,
+        cp: u16,
+    ) -> Option<Self> {
+        let bonuses = |consumable: Self| {
+            let consumables = [Some(consumable)];
+            (
+                craftsmanship_bonus(craftsmanship, &consumables),
+                control_bonus(control, &consumables),
+                cp_bonus(cp, &consumables),
+            )
+        };
+        let (min_craft, min_control, min_cp) = bonuses(self);
+        consumables
+            .iter()
+            .copied()
+            .filter(|other| (other.item_level, other.hq) < (self.item_level, self.hq))
+            .filter(|other| {
+                let (other_craft, other_control, other_cp) = bonuses(*other);
+                other_craft >= min_craft && other_control >= min_control && other_cp >= min_cp
+            })
+            .min_by_key(|other| (other.item_level, other.hq))
+    }
+}
+
+/// Food or potion that isn't in the bundled data, e.g. an item of a new patch, defined by the
+/// user. Its bonuses are calculated like those of any other [`Consumable`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CustomConsumable {
+    pub name: String,
+    pub consumable: Consumable,
+}
+
+impl CustomConsumable {
+    /// A consumable without any bonus, with the next free custom item ID after `consumables`.
+    pub fn new(name: String, hq: bool, consumables: &[Self]) -> Self {
+        let item_id = consumables
+            .iter()
+            .map(|custom| custom.consumable.item_id + 1)
+            .max()
+            .unwrap_or(CUSTOM_CONSUMABLE_ITEM_ID);
+        Self {
+            name,
+            consumable: Consumable {
+                item_id,
+                item_level: 0,
+                hq,
+                craft_rel: 0,
+                craft_max: 0,
+                control_rel: 0,
+                control_max: 0,
+                cp_rel: 0,
+                cp_max: 0,
+            },
+        }
+    }
+
+    pub fn display_name(&self) -> String {
+        match self.consumable.hq {
+            true => format!("{} {}", self.name, crate::HQ_ICON_CHAR),
+            false => self.name.clone(),
+        }
+    }
 }
 
 pub fn craftsmanship_bonus(base: u16, consumables: &[Option<Consumable>]) -> u16 {
@@ -0,0 +1,229 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use crate::{
+    Consumable, ITEM_NAMES_DE, ITEM_NAMES_EN, ITEM_NAMES_FR, ITEM_NAMES_JP, ITEMS, Item, Locale,
+    MEALS, POTIONS, RECIPES, RLVLS, Recipe, RecipeLevel,
+};
+
+/// Version of the [`GameDataset`] format. Datasets of other versions are rejected.
+pub const GAME_DATASET_FORMAT: u32 = 1;
+
+/// Source of the recipe, item and consumable tables.
+///
+/// The tables are bundled with the crate at build time. A newer [`GameDataset`] can be installed
+/// at runtime with [`install_game_dataset`], e.g. to use the recipes of a patch that came out after
+/// the release of the app. Use [`game_data`] to read the tables that are currently in use.
+pub trait DataProvider: Send + Sync {
+    fn recipes(&self) -> &[Recipe];
+    /// Recipe levels, indexed by [`Recipe::recipe_level`].
+    fn recipe_levels(&self) -> &[RecipeLevel];
+    fn item(&self, item_id: u32) -> Option<Item>;
+    fn item_name(&self, item_id: u32, locale: Locale) -> Option<&str>;
+    fn meals(&self) -> &[Consumable];
+    fn potions(&self) -> &[Consumable];
+}
+
+/// The tables that are bundled with the crate.
+pub struct BundledData;
+
+impl DataProvider for BundledData {
+    fn recipes(&self) -> &[Recipe] {
+        RECIPES
+    }
+
+    fn recipe_levels(&self) -> &[RecipeLevel] {
+        &RLVLS
+    }
+
+    fn item(&self, item_id: u32) -> Option<Item> {
+        ITEMS.get(&item_id).copied()
+    }
+
+    fn item_name(&self, item_id: u32, locale: Locale) -> Option<&str> {
+        let item_names = match locale {
+            Locale::EN => &ITEM_NAMES_EN,
+            Locale::DE => &ITEM_NAMES_DE,
+            Locale::FR => &ITEM_NAMES_FR,
+            Locale::JP => &ITEM_NAMES_JP,
+        };
+        item_names.get(&item_id).copied()
+    }
+
+    fn meals(&self) -> &[Consumable] {
+        MEALS
+    }
+
+    fn potions(&self) -> &[Consumable] {
+        POTIONS
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatasetItem {
+    pub item_id: u32,
+    pub item: Item,
+    /// Names in the order EN, DE, FR, JP.
+    pub names: [String; 4],
+}
+
+/// Versioned set of game data that replaces the bundled tables when installed.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameDataset {
+    /// Must be [`GAME_DATASET_FORMAT`].
+    pub format: u32,
+    /// Name of this version of the data, e.g. the game patch or the date it was exported.
+    pub data_version: String,
+    pub recipes: Vec<Recipe>,
+    pub recipe_levels: Vec<RecipeLevel>,
+    pub items: Vec<DatasetItem>,
+    pub meals: Vec<Consumable>,
+    pub potions: Vec<Consumable>,
+}
+
+impl GameDataset {
+    /// Dataset with the bundled tables, e.g. to publish them for older releases of the app.
+    pub fn from_bundled(data_version: String) -> Self {
+        Self {
+            format: GAME_DATASET_FORMAT,
+            data_version,
+            recipes: RECIPES.to_vec(),
+            recipe_levels: RLVLS.to_vec(),
+            items: ITEMS
+                .entries()
+                .map(|(item_id, item)| DatasetItem {
+                    item_id: *item_id,
+                    item: *item,
+                    names: [Locale::EN, Locale::DE, Locale::FR, Locale::JP].map(|locale| {
+                        BundledData
+                            .item_name(*item_id, locale)
+                            .unwrap_or_default()
+                            .to_string()
+                    }),
+                })
+                .collect(),
+            meals: MEALS.to_vec(),
+            potions: POTIONS.to_vec(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.format != GAME_DATASET_FORMAT {
+            return Err(format!(
+                "Unsupported game data format {} (expected {GAME_DATASET_FORMAT})",
+                self.format
+            ));
+        }
+        if let Some(recipe) = self
+            .recipes
+            .iter()
+            .find(|recipe| usize::from(recipe.recipe_level) >= self.recipe_levels.len())
+        {
+            return Err(format!(
+                "Recipe for item {} has an unknown recipe level {}",
+                recipe.item_id, recipe.recipe_level
+            ));
+        }
+        let item_ids: HashSet<u32> = self.items.iter().map(|item| item.item_id).collect();
+        let is_known_item =
+            |item_id: u32| item_ids.contains(&item_id) || BundledData.item(item_id).is_some();
+        if let Some(ingredient) = self
+            .recipes
+            .iter()
+            .flat_map(|recipe| recipe.ingredients)
+            .find(|ingredient| ingredient.item_id != 0 && !is_known_item(ingredient.item_id))
+        {
+            return Err(format!("Unknown ingredient item {}", ingredient.item_id));
+        }
+        Ok(())
+    }
+}
+
+/// An installed [`GameDataset`]. Items that aren't in the dataset fall back to the bundled ones.
+struct InstalledDataset {
+    dataset: GameDataset,
+    item_indices: HashMap<u32, usize>,
+}
+
+impl DataProvider for InstalledDataset {
+    fn recipes(&self) -> &[Recipe] {
+        &self.dataset.recipes
+    }
+
+    fn recipe_levels(&self) -> &[RecipeLevel] {
+        &self.dataset.recipe_levels
+    }
+
+    fn item(&self, item_id: u32) -> Option<Item> {
+        self.item_indices.get(&item_id).map_or_else(
+            || BundledData.item(item_id),
+            |index| Some(self.dataset.items[*index].item),
+        )
+    }
+
+    fn item_name(&self, item_id: u32, locale: Locale) -> Option<&str> {
+        let Some(index) = self.item_indices.get(&item_id) else {
+            return BundledData.item_name(item_id, locale);
+        };
+        let names = &self.dataset.items[*index].names;
+        let name = match locale {
+            Locale::EN => &names[0],
+            Locale::DE => &names[1],
+            Locale::FR => &names[2],
+            Locale::JP => &names[3],
+        };
+        // new items are sometimes not translated yet
+        match name.is_empty() {
+            true => Some(names[0].as_str()),
+            false => Some(name.as_str()),
+        }
+    }
+
+    fn meals(&self) -> &[Consumable] {
+        &self.dataset.meals
+    }
+
+    fn potions(&self) -> &[Consumable] {
+        &self.dataset.potions
+    }
+}
+
+static INSTALLED_DATASET: RwLock<Option<&'static InstalledDataset>> = RwLock::new(None);
+
+/// The game data that is currently in use.
+pub fn game_data() -> &'static dyn DataProvider {
+    match *INSTALLED_DATASET.read().unwrap() {
+        Some(dataset) => dataset,
+        None => &BundledData,
+    }
+}
+
+/// Replaces the bundled tables with `dataset`.
+///
+/// Indices into the tables of [`game_data`], e.g. search results, are invalidated by this.
+/// Previously installed datasets are leaked, as references to them may still be alive.
+pub fn install_game_dataset(dataset: GameDataset) -> Result<(), String> {
+    dataset.validate()?;
+    let item_indices = dataset
+        .items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (item.item_id, index))
+        .collect();
+    let installed = Box::leak(Box::new(InstalledDataset {
+        dataset,
+        item_indices,
+    }));
+    *INSTALLED_DATASET.write().unwrap() = Some(installed);
+    Ok(())
+}
+
+/// Data version of the installed dataset, `None` if the bundled tables are in use.
+pub fn installed_data_version() -> Option<String> {
+    INSTALLED_DATASET
+        .read()
+        .unwrap()
+        .map(|installed| installed.dataset.data_version.clone())
+}
@@ -0,0 +1,127 @@
+use raphael_sim::{ConditionPool, RulesOverride, Settings, SuccessModel};
+
+use crate::{
+    Consumable, CrafterStats, Recipe, RecipeLevel, allowed_actions_for, control_bonus, cp_bonus,
+    craftsmanship_bonus, game_data,
+};
+
+/// Replaces intermediate values of the game settings computation, e.g. to try out stat changes
+/// or formula changes without touching the game data. `None` keeps the value derived from the
+/// recipe and crafter stats.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GameSettingsOverrides {
+    /// Craftsmanship including consumables.
+    pub craftsmanship: Option<u16>,
+    /// Control including consumables.
+    pub control: Option<u16>,
+    /// CP including consumables.
+    pub cp: Option<u16>,
+    pub recipe_level: Option<RecipeLevel>,
+    pub level_penalty: Option<bool>,
+}
+
+/// Intermediate values of the game settings computation, see [`get_game_settings_breakdown`].
+#[derive(Debug, Clone, Copy)]
+pub struct GameSettingsBreakdown {
+    /// Crafter stats without consumables.
+    pub base_stats: CrafterStats,
+    /// Craftsmanship, Control and CP including consumables.
+    pub effective_stats: CrafterStats,
+    pub recipe_level: RecipeLevel,
+    /// Whether the Progress and Quality modifiers of the recipe level apply, which is the case
+    /// if the crafter level isn't above the recipe level.
+    pub level_penalty: bool,
+    /// Progress per 100% efficiency, before rounding down.
+    pub base_progress: f32,
+    /// Quality per 100% efficiency, before rounding down.
+    pub base_quality: f32,
+    pub settings: Settings,
+}
+
+pub fn get_game_settings(
+    recipe: Recipe,
+    crafter_stats: CrafterStats,
+    food: Option<Consumable>,
+    potion: Option<Consumable>,
+    adversarial: bool,
+) -> Settings {
+    get_game_settings_breakdown(
+        recipe,
+        crafter_stats,
+        food,
+        potion,
+        adversarial,
+        GameSettingsOverrides::default(),
+    )
+    .settings
+}
+
+/// Same as [`get_game_settings`], but also returns the intermediate values and allows
+/// overriding them.
+pub fn get_game_settings_breakdown(
+    recipe: Recipe,
+    crafter_stats: CrafterStats,
+    food: Option<Consumable>,
+    potion: Option<Consumable>,
+    adversarial: bool,
+    overrides: GameSettingsOverrides,
+) -> GameSettingsBreakdown {
+    let rlvl = overrides
+        .recipe_level
+        .unwrap_or(game_data().recipe_levels()[recipe.recipe_level as usize]);
+
+    let effective_stats = CrafterStats {
+        craftsmanship: overrides.craftsmanship.unwrap_or_else(|| {
+            crafter_stats.craftsmanship
+                + craftsmanship_bonus(crafter_stats.craftsmanship, &[food, potion])
+        }),
+        control: overrides.control.unwrap_or_else(|| {
+            crafter_stats.control + control_bonus(crafter_stats.control, &[food, potion])
+        }),
+        cp: overrides
+            .cp
+            .unwrap_or_else(|| crafter_stats.cp + cp_bonus(crafter_stats.cp, &[food, potion])),
+        ..crafter_stats
+    };
+
+    let level_penalty = overrides
+        .level_penalty
+        .unwrap_or(crafter_stats.level <= recipe.level);
+    let mut base_progress =
+        effective_stats.craftsmanship as f32 * 10.0 / rlvl.progress_div as f32 + 2.0;
+    let mut base_quality = effective_stats.control as f32 * 10.0 / rlvl.quality_div as f32 + 35.0;
+    if level_penalty {
+        base_progress = base_progress * rlvl.progress_mod as f32 / 100.0;
+        base_quality = base_quality * rlvl.quality_mod as f32 / 100.0;
+    }
+
+    GameSettingsBreakdown {
+        base_stats: crafter_stats,
+        effective_stats,
+        recipe_level: rlvl,
+        level_penalty,
+        base_progress,
+        base_quality,
+        settings: Settings {
+            max_cp: effective_stats.cp as _,
+            max_durability: recipe.durability as _,
+            max_progress: recipe.progress,
+            max_quality: recipe.quality,
+            base_progress: base_progress as u16,
+            base_quality: base_quality as u16,
+            job_level: crafter_stats.level,
+            allowed_actions: allowed_actions_for(recipe, crafter_stats),
+            adversarial,
+            success_model: SuccessModel::AssumeFail,
+            rules_override: RulesOverride::NONE,
+        },
+    }
+}
+
+/// Conditions that can occur while crafting `recipe`, from the `ConditionsFlag` of its recipe
+/// level.
+pub fn condition_pool(recipe: Recipe) -> ConditionPool {
+    ConditionPool::from_flags(
+        game_data().recipe_levels()[recipe.recipe_level as usize].conditions_flag,
+    )
+}
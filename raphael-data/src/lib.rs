@@ -10,12 +10,23 @@ pub use locales::*;
 mod search;
 pub use search::*;
 
-use raphael_sim::{Action, ActionMask, Settings};
+mod game_settings;
+pub use game_settings::*;
+
+mod allowed_actions;
+pub use allowed_actions::*;
+
+mod collectables;
+pub use collectables::*;
+
+mod data_provider;
+pub use data_provider::*;
 
 pub const HQ_ICON_CHAR: char = '\u{e03c}';
 pub const CL_ICON_CHAR: char = '\u{e03d}';
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Item {
     pub item_level: u16,
     pub can_be_hq: bool,
@@ -30,6 +41,7 @@ pub struct Ingredient {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RecipeLevel {
     pub progress_div: u16,
     pub quality_div: u16,
@@ -58,61 +70,13 @@ pub const RECIPES: &[Recipe] = include!(concat!(env!("OUT_DIR"), "/recipes.rs"))
 
 pub static ITEMS: phf::OrderedMap<u32, Item> = include!(concat!(env!("OUT_DIR"), "/items.rs"));
 
-pub fn get_game_settings(
-    recipe: Recipe,
-    crafter_stats: CrafterStats,
-    food: Option<Consumable>,
-    potion: Option<Consumable>,
-    adversarial: bool,
-) -> Settings {
-    let rlvl = &RLVLS[recipe.recipe_level as usize];
-
-    let craftsmanship = crafter_stats.craftsmanship
-        + craftsmanship_bonus(crafter_stats.craftsmanship, &[food, potion]);
-    let control = crafter_stats.control + control_bonus(crafter_stats.control, &[food, potion]);
-    let cp = crafter_stats.cp + cp_bonus(crafter_stats.cp, &[food, potion]);
-
-    let mut base_progress = craftsmanship as f32 * 10.0 / rlvl.progress_div as f32 + 2.0;
-    let mut base_quality = control as f32 * 10.0 / rlvl.quality_div as f32 + 35.0;
-    if crafter_stats.level <= recipe.level {
-        base_progress = base_progress * rlvl.progress_mod as f32 / 100.0;
-        base_quality = base_quality * rlvl.quality_mod as f32 / 100.0;
-    }
-
-    let mut allowed_actions = ActionMask::all();
-    if !crafter_stats.manipulation {
-        allowed_actions = allowed_actions.remove(Action::Manipulation);
-    }
-    if recipe.is_expert || crafter_stats.level < recipe.level + 10 {
-        allowed_actions = allowed_actions.remove(Action::TrainedEye);
-    }
-    if !crafter_stats.heart_and_soul {
-        allowed_actions = allowed_actions.remove(Action::HeartAndSoul);
-    }
-    if !crafter_stats.quick_innovation {
-        allowed_actions = allowed_actions.remove(Action::QuickInnovation);
-    }
-
-    Settings {
-        max_cp: cp as _,
-        max_durability: recipe.durability as _,
-        max_progress: recipe.progress,
-        max_quality: recipe.quality,
-        base_progress: base_progress as u16,
-        base_quality: base_quality as u16,
-        job_level: crafter_stats.level,
-        allowed_actions,
-        adversarial,
-    }
-}
-
 pub fn get_initial_quality(recipe: Recipe, hq_ingredients: [u8; 6]) -> u16 {
     let ingredients: Vec<(Item, u32)> = recipe
         .ingredients
         .iter()
         .filter_map(|ingredient| match ingredient.item_id {
             0 => None,
-            id => Some((*ITEMS.get(&id).unwrap(), ingredient.amount)),
+            id => Some((game_data().item(id).unwrap(), ingredient.amount)),
         })
         .collect();
 
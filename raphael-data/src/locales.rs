@@ -1,5 +1,5 @@
-use crate::ITEMS;
-use raphael_sim::Action;
+use crate::game_data;
+use raphael_sim::{Action, ActionMask};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -44,25 +44,10 @@ pub static ITEM_NAMES_JP: phf::Map<u32, &'static str> =
     include!(concat!(env!("OUT_DIR"), "/item_names_jp.rs"));
 
 pub fn get_item_name(item_id: u32, hq: bool, locale: Locale) -> String {
-    let item_name = match locale {
-        Locale::EN => ITEM_NAMES_EN
-            .get(&item_id)
-            .copied()
-            .unwrap_or("Unknown item"),
-        Locale::DE => ITEM_NAMES_DE
-            .get(&item_id)
-            .copied()
-            .unwrap_or("Unknown item"),
-        Locale::FR => ITEM_NAMES_FR
-            .get(&item_id)
-            .copied()
-            .unwrap_or("Unknown item"),
-        Locale::JP => ITEM_NAMES_JP
-            .get(&item_id)
-            .copied()
-            .unwrap_or("Unknown item"),
-    };
-    let item_entry = ITEMS.get(&item_id);
+    let item_name = game_data()
+        .item_name(item_id, locale)
+        .unwrap_or("Unknown item");
+    let item_entry = game_data().item(item_id);
     let always_collectable = item_entry.is_some_and(|item| item.always_collectable);
     if !always_collectable {
         match hq {
@@ -83,6 +68,27 @@ pub const fn action_name(action: Action, locale: Locale) -> &'static str {
     }
 }
 
+/// Action whose name in `locale` is `name`, the inverse of [`action_name`].
+/// Ignores case, surrounding whitespace and typographic apostrophes.
+pub fn action_from_name(name: &str, locale: Locale) -> Option<Action> {
+    let name = normalize_action_name(name);
+    ActionMask::all()
+        .actions_iter()
+        .find(|action| normalize_action_name(action_name(*action, locale)) == name)
+}
+
+/// Like [`action_from_name`], but looks the name up in all locales, e.g. for macros that were
+/// copied from a game client in an unknown language. Also returns the locale of the name.
+pub fn action_from_name_any_locale(name: &str) -> Option<(Action, Locale)> {
+    [Locale::EN, Locale::DE, Locale::FR, Locale::JP]
+        .into_iter()
+        .find_map(|locale| action_from_name(name, locale).map(|action| (action, locale)))
+}
+
+fn normalize_action_name(name: &str) -> String {
+    name.trim().replace('\u{2019}', "'").to_lowercase()
+}
+
 const fn action_name_en(action: Action) -> &'static str {
     match action {
         Action::BasicSynthesis => "Basic Synthesis",
@@ -116,6 +122,9 @@ const fn action_name_en(action: Action) -> &'static str {
         Action::TrainedPerfection => "Trained Perfection",
         Action::TrainedEye => "Trained Eye",
         Action::QuickInnovation => "Quick Innovation",
+        Action::RapidSynthesis => "Rapid Synthesis",
+        Action::HastyTouch => "Hasty Touch",
+        Action::DaringTouch => "Daring Touch",
     }
 }
 
@@ -152,6 +161,9 @@ const fn action_name_de(action: Action) -> &'static str {
         Action::TrainedPerfection => "Meisters Beitrag",
         Action::TrainedEye => "Flinke Hand",
         Action::QuickInnovation => "Spontane Innovation",
+        Action::RapidSynthesis => "Schnelle Bearbeitung",
+        Action::HastyTouch => "Hastige Veredelung",
+        Action::DaringTouch => "Waghalsige Veredelung",
     }
 }
 
@@ -188,6 +200,9 @@ const fn action_name_fr(action: Action) -> &'static str {
         Action::TrainedPerfection => "Main suprême",
         Action::TrainedEye => "Main preste",
         Action::QuickInnovation => "Innovation instantanée",
+        Action::RapidSynthesis => "Travail hâtif",
+        Action::HastyTouch => "Ouvrage hâtif",
+        Action::DaringTouch => "Ouvrage audacieux",
     }
 }
 
@@ -224,5 +239,89 @@ const fn action_name_jp(action: Action) -> &'static str {
         Action::TrainedPerfection => "匠の絶技",
         Action::TrainedEye => "匠の早業",
         Action::QuickInnovation => "クイックイノベーション",
+        Action::RapidSynthesis => "突貫作業",
+        Action::HastyTouch => "ヘイスティタッチ",
+        Action::DaringTouch => "デアリングタッチ",
+    }
+}
+
+/// Short description of the action's effect, based on the in-game tooltip.
+/// Only English descriptions are available, so all locales use the English text.
+pub const fn action_description(action: Action, _locale: Locale) -> &'static str {
+    match action {
+        Action::BasicSynthesis => "Increases progress.",
+        Action::BasicTouch => "Increases quality.",
+        Action::MasterMend => "Restores 30 durability.",
+        Action::Observe => "Do nothing for one step.",
+        Action::TricksOfTheTrade => {
+            "Restores 20 CP. Available only when condition is Good or Excellent, or with Heart and Soul active."
+        }
+        Action::WasteNot => "Reduces loss of durability by 50% for the next 4 steps.",
+        Action::Veneration => {
+            "Increases efficiency of Synthesis actions by 50% for the next 4 steps."
+        }
+        Action::StandardTouch => "Increases quality. Combo: CP cost is reduced after Basic Touch.",
+        Action::GreatStrides => {
+            "Increases efficiency of the next Touch action by 100% for the next 3 steps."
+        }
+        Action::Innovation => "Increases efficiency of Touch actions by 50% for the next 4 steps.",
+        Action::WasteNot2 => "Reduces loss of durability by 50% for the next 8 steps.",
+        Action::ByregotsBlessing => {
+            "Increases quality. Efficiency increases by 20% for each stack of Inner Quiet, which is removed afterwards. Requires at least one stack of Inner Quiet."
+        }
+        Action::PreciseTouch => {
+            "Increases quality and grants an additional stack of Inner Quiet. Available only when condition is Good or Excellent, or with Heart and Soul active."
+        }
+        Action::MuscleMemory => {
+            "Increases progress. Increases efficiency of the next Synthesis action by 100% within the next 5 steps. Available only on the first step."
+        }
+        Action::CarefulSynthesis => "Increases progress.",
+        Action::Manipulation => "Restores 5 durability after each of the next 8 steps.",
+        Action::PrudentTouch => {
+            "Increases quality at half the durability cost. Cannot be used while Waste Not is active."
+        }
+        Action::AdvancedTouch => {
+            "Increases quality. Combo: CP cost is reduced after Standard Touch or Observe."
+        }
+        Action::Reflect => {
+            "Increases quality and grants an additional stack of Inner Quiet. Available only on the first step."
+        }
+        Action::PreparatoryTouch => {
+            "Increases quality and grants an additional stack of Inner Quiet."
+        }
+        Action::Groundwork => {
+            "Increases progress. Efficiency is halved when durability is lower than the durability cost."
+        }
+        Action::DelicateSynthesis => "Increases both progress and quality.",
+        Action::IntensiveSynthesis => {
+            "Increases progress. Available only when condition is Good or Excellent, or with Heart and Soul active."
+        }
+        Action::HeartAndSoul => {
+            "Allows the next action that requires a Good or Excellent condition to be used regardless of condition. Usable once per craft."
+        }
+        Action::PrudentSynthesis => {
+            "Increases progress at half the durability cost. Cannot be used while Waste Not is active."
+        }
+        Action::TrainedFinesse => {
+            "Increases quality without consuming durability. Available only with 10 stacks of Inner Quiet."
+        }
+        Action::RefinedTouch => {
+            "Increases quality and grants an additional stack of Inner Quiet. Can only be used after Basic Touch."
+        }
+        Action::ImmaculateMend => "Fully restores durability.",
+        Action::TrainedPerfection => {
+            "The next action that consumes durability does not consume any. Usable once per craft."
+        }
+        Action::TrainedEye => {
+            "Increases quality to the maximum. Available only on the first step and for recipes at least 10 levels below the job level."
+        }
+        Action::QuickInnovation => {
+            "Grants Innovation for 1 step without consuming CP. Cannot be used while Innovation is active. Usable once per craft."
+        }
+        Action::RapidSynthesis => "Increases progress. 50% success rate.",
+        Action::HastyTouch => "Increases quality. 60% success rate.",
+        Action::DaringTouch => {
+            "Increases quality. 60% success rate. Can only be used after a successful Hasty Touch."
+        }
     }
 }
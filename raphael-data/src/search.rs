@@ -1,5 +1,5 @@
 use crate::{
-    CL_ICON_CHAR, Consumable, HQ_ICON_CHAR, Locale, MEALS, POTIONS, RECIPES, get_item_name,
+    CL_ICON_CHAR, Consumable, CustomConsumable, HQ_ICON_CHAR, Locale, game_data, get_item_name,
 };
 
 fn contains_noncontiguous(string: &str, pattern: &str) -> bool {
@@ -25,7 +25,8 @@ fn preprocess_pattern(pattern: &str) -> String {
 
 pub fn find_recipes(search_string: &str, locale: Locale) -> Vec<usize> {
     let pattern = preprocess_pattern(search_string);
-    RECIPES
+    game_data()
+        .recipes()
         .iter()
         .enumerate()
         .filter_map(|(index, recipe)| {
@@ -54,9 +55,26 @@ fn find_consumables(search_string: &str, locale: Locale, consumables: &[Consumab
 }
 
 pub fn find_meals(search_string: &str, locale: Locale) -> Vec<usize> {
-    find_consumables(search_string, locale, MEALS)
+    find_consumables(search_string, locale, game_data().meals())
 }
 
 pub fn find_potions(search_string: &str, locale: Locale) -> Vec<usize> {
-    find_consumables(search_string, locale, POTIONS)
+    find_consumables(search_string, locale, game_data().potions())
+}
+
+pub fn find_custom_consumables(
+    search_string: &str,
+    consumables: &[CustomConsumable],
+) -> Vec<usize> {
+    let pattern = preprocess_pattern(search_string);
+    consumables
+        .iter()
+        .enumerate()
+        .filter_map(|(index, consumable)| {
+            match contains_noncontiguous(&consumable.name.to_lowercase(), &pattern) {
+                true => Some(index),
+                false => None,
+            }
+        })
+        .collect()
 }
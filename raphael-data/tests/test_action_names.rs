@@ -0,0 +1,46 @@
+use raphael_data::*;
+use raphael_sim::{Action, ActionMask};
+
+#[test]
+fn test_action_from_name_round_trip() {
+    for locale in [Locale::EN, Locale::DE, Locale::FR, Locale::JP] {
+        for action in ActionMask::all().actions_iter() {
+            let name = action_name(action, locale);
+            assert_eq!(action_from_name(name, locale), Some(action), "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_action_from_name_normalization() {
+    assert_eq!(
+        action_from_name(" master’s mend ", Locale::EN),
+        Some(Action::MasterMend)
+    );
+    assert_eq!(
+        action_from_name("GROSSE SCHRITTE", Locale::DE),
+        None // "ß" is only lowercased, not folded to "ss"
+    );
+    assert_eq!(
+        action_from_name("GROßE SCHRITTE", Locale::DE),
+        Some(Action::GreatStrides)
+    );
+    assert_eq!(action_from_name("Basic Touch", Locale::DE), None);
+}
+
+#[test]
+fn test_action_from_name_any_locale() {
+    assert_eq!(
+        action_from_name_any_locale("Basic Touch"),
+        Some((Action::BasicTouch, Locale::EN))
+    );
+    assert_eq!(
+        action_from_name_any_locale("Ouvrage de base"),
+        Some((Action::BasicTouch, Locale::FR))
+    );
+    assert_eq!(
+        action_from_name_any_locale("下地加工"),
+        Some((Action::PreparatoryTouch, Locale::JP))
+    );
+    assert_eq!(action_from_name_any_locale("Final Appraisal"), None);
+}
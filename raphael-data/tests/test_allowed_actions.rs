@@ -0,0 +1,87 @@
+use raphael_data::*;
+use raphael_sim::{Action, ActionMask};
+
+fn find_recipe(item_name: &'static str) -> Recipe {
+    *RECIPES
+        .iter()
+        .find(|recipe| get_item_name(recipe.item_id, false, Locale::EN) == item_name)
+        .unwrap()
+}
+
+const CRAFTER_STATS: CrafterStats = CrafterStats {
+    craftsmanship: 4956,
+    control: 4963,
+    cp: 627,
+    level: 100,
+    manipulation: true,
+    heart_and_soul: true,
+    quick_innovation: true,
+};
+
+#[test]
+fn test_all_actions_allowed() {
+    let mut recipe = find_recipe("Roast Chicken");
+    recipe.level = 90;
+    assert!(excluded_actions_for(recipe, CRAFTER_STATS).is_empty());
+    assert_eq!(
+        allowed_actions_for(recipe, CRAFTER_STATS),
+        ActionMask::all()
+    );
+}
+
+#[test]
+fn test_trained_eye() {
+    let mut recipe = find_recipe("Roast Chicken");
+    assert_eq!(
+        excluded_actions_for(recipe, CRAFTER_STATS),
+        [ExcludedAction {
+            action: Action::TrainedEye,
+            reason: ActionExclusion::RecipeLevelTooHigh,
+        }]
+    );
+    recipe.level = 90;
+    recipe.is_expert = true;
+    assert_eq!(
+        excluded_actions_for(recipe, CRAFTER_STATS),
+        [ExcludedAction {
+            action: Action::TrainedEye,
+            reason: ActionExclusion::ExpertRecipe,
+        }]
+    );
+}
+
+#[test]
+fn test_crafter_actions() {
+    let mut recipe = find_recipe("Roast Chicken");
+    recipe.level = 90;
+    let crafter_stats = CrafterStats {
+        manipulation: false,
+        heart_and_soul: false,
+        quick_innovation: false,
+        ..CRAFTER_STATS
+    };
+    let reasons: Vec<_> = excluded_actions_for(recipe, crafter_stats)
+        .into_iter()
+        .map(|excluded| (excluded.action, excluded.reason))
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            (Action::Manipulation, ActionExclusion::NotLearned),
+            (Action::HeartAndSoul, ActionExclusion::NotSpecialist),
+            (Action::QuickInnovation, ActionExclusion::NotSpecialist),
+        ]
+    );
+    assert_eq!(
+        allowed_actions_for(recipe, crafter_stats),
+        ActionMask::all()
+            .remove(Action::Manipulation)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation)
+    );
+    // the settings of all front-ends use the same actions
+    assert_eq!(
+        get_game_settings(recipe, crafter_stats, None, None, false).allowed_actions,
+        allowed_actions_for(recipe, crafter_stats)
+    );
+}
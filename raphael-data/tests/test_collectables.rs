@@ -0,0 +1,50 @@
+use raphael_data::{CollectableReward, CollectableRewards};
+
+const REWARDS: CollectableRewards = CollectableRewards {
+    currency: 6,
+    tiers: [
+        CollectableReward {
+            min_collectability: 500,
+            scrips: 54,
+            exp_ratio: 10,
+        },
+        CollectableReward {
+            min_collectability: 650,
+            scrips: 90,
+            exp_ratio: 12,
+        },
+        CollectableReward {
+            min_collectability: 800,
+            scrips: 144,
+            exp_ratio: 15,
+        },
+    ],
+};
+
+#[test]
+fn test_collectable_reward_tiers() {
+    assert_eq!(REWARDS.reward(4999), None);
+    assert_eq!(REWARDS.reward(5000), Some((1, REWARDS.tiers[0])));
+    assert_eq!(REWARDS.reward(6499), Some((1, REWARDS.tiers[0])));
+    assert_eq!(REWARDS.reward(6500), Some((2, REWARDS.tiers[1])));
+    assert_eq!(REWARDS.reward(8000), Some((3, REWARDS.tiers[2])));
+    assert_eq!(REWARDS.reward(u16::MAX), Some((3, REWARDS.tiers[2])));
+}
+
+#[test]
+fn test_collectable_without_high_tier() {
+    // some collectables only have the low and mid tier
+    let rewards = CollectableRewards {
+        tiers: [
+            REWARDS.tiers[0],
+            REWARDS.tiers[1],
+            CollectableReward {
+                min_collectability: 0,
+                scrips: 0,
+                exp_ratio: 0,
+            },
+        ],
+        ..REWARDS
+    };
+    assert_eq!(rewards.reward(9000), Some((2, REWARDS.tiers[1])));
+}
@@ -0,0 +1,23 @@
+use raphael_data::*;
+use raphael_sim::ConditionPool;
+
+#[test]
+fn test_next_condition_probabilities() {
+    let pools = RECIPES
+        .iter()
+        .map(|recipe| condition_pool(*recipe))
+        .chain([ConditionPool::NORMAL]);
+    for pool in pools {
+        for condition in pool.conditions() {
+            let probabilities = pool.next_condition_probabilities(condition);
+            let total: f32 = probabilities
+                .iter()
+                .map(|(_, probability)| probability)
+                .sum();
+            assert!((total - 1.0).abs() < 1e-4, "{pool:?} {condition:?}");
+            for (next_condition, _) in probabilities {
+                assert!(pool.contains(next_condition), "{pool:?} {condition:?}");
+            }
+        }
+    }
+}
@@ -22,3 +22,65 @@ fn test_rroneek_steak() {
     assert_eq!((consumable.control_rel, consumable.control_max), (5, 97));
     assert_eq!((consumable.cp_rel, consumable.cp_max), (26, 92));
 }
+
+fn control_meal(item_level: u32, hq: bool, control_rel: u16, control_max: u16) -> Consumable {
+    Consumable {
+        item_id: 0,
+        item_level,
+        hq,
+        craft_rel: 0,
+        craft_max: 0,
+        control_rel,
+        control_max,
+        cp_rel: 0,
+        cp_max: 0,
+    }
+}
+
+#[test]
+fn test_capped_consumable_equivalent() {
+    let cheap = control_meal(600, false, 4, 77);
+    let expensive = control_meal(700, true, 5, 97);
+    let consumables = [expensive, cheap];
+    // both meals are capped, but the cheaper meal has a lower cap
+    assert!(expensive.is_capped(0, 4000, 0));
+    assert!(
+        expensive
+            .cheapest_equivalent(&consumables, 0, 4000, 0)
+            .is_none()
+    );
+    // the cheaper meal isn't capped, but gives the capped bonus of the expensive meal
+    assert!(!cheap.is_capped(0, 1000, 0));
+    let expensive = control_meal(700, true, 5, 40);
+    let consumables = [expensive, cheap];
+    assert!(expensive.is_capped(0, 1000, 0));
+    let equivalent = expensive
+        .cheapest_equivalent(&consumables, 0, 1000, 0)
+        .unwrap();
+    assert_eq!(equivalent.item_level, 600);
+    assert!(
+        cheap
+            .cheapest_equivalent(&consumables, 0, 1000, 0)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_custom_consumable() {
+    let mut custom = CustomConsumable::new("Patch Steak".to_string(), true, &[]);
+    assert_eq!(custom.consumable.item_id, CUSTOM_CONSUMABLE_ITEM_ID);
+    assert!(custom.consumable.is_custom());
+    custom.consumable.control_rel = 5;
+    custom.consumable.control_max = 100;
+    custom.consumable.cp_rel = 26;
+    custom.consumable.cp_max = 95;
+    assert_eq!(
+        custom.consumable.effect_string(4021, 4023, 550),
+        "Control +5% (100), CP +26% (95)"
+    );
+    assert_eq!(control_bonus(1000, &[Some(custom.consumable)]), 50);
+    assert_eq!(custom.display_name(), "Patch Steak \u{e03c}");
+    let next = CustomConsumable::new("Patch Tea".to_string(), false, &[custom]);
+    assert_eq!(next.consumable.item_id, CUSTOM_CONSUMABLE_ITEM_ID + 1);
+    assert_eq!(find_custom_consumables("tea", &[next]), vec![0]);
+}
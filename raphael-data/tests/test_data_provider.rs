@@ -0,0 +1,58 @@
+use raphael_data::*;
+
+const NEW_ITEM_ID: u32 = 0x7fff_0000;
+
+fn new_patch_dataset() -> GameDataset {
+    let mut dataset = GameDataset::from_bundled("test".to_string());
+    let mut recipe = *dataset.recipes.last().unwrap();
+    recipe.item_id = NEW_ITEM_ID;
+    dataset.recipes.push(recipe);
+    dataset.items.push(DatasetItem {
+        item_id: NEW_ITEM_ID,
+        item: Item {
+            item_level: 760,
+            can_be_hq: true,
+            always_collectable: false,
+        },
+        names: [
+            "New Patch Ingot".to_string(),
+            "Neuer Patch-Barren".to_string(),
+            String::new(),
+            String::new(),
+        ],
+    });
+    dataset
+}
+
+#[test]
+fn test_install_game_dataset() {
+    let mut invalid_dataset = new_patch_dataset();
+    invalid_dataset.format += 1;
+    assert!(install_game_dataset(invalid_dataset).is_err());
+    let mut invalid_dataset = new_patch_dataset();
+    invalid_dataset.recipe_levels.clear();
+    assert!(install_game_dataset(invalid_dataset).is_err());
+    assert_eq!(installed_data_version(), None);
+    assert_eq!(game_data().recipes().len(), RECIPES.len());
+
+    install_game_dataset(new_patch_dataset()).unwrap();
+    assert_eq!(installed_data_version(), Some("test".to_string()));
+    assert_eq!(game_data().recipes().len(), RECIPES.len() + 1);
+    assert_eq!(
+        get_item_name(NEW_ITEM_ID, false, Locale::EN),
+        "New Patch Ingot"
+    );
+    assert_eq!(
+        get_item_name(NEW_ITEM_ID, true, Locale::DE),
+        "Neuer Patch-Barren \u{e03c}"
+    );
+    // untranslated names fall back to EN
+    assert_eq!(
+        get_item_name(NEW_ITEM_ID, false, Locale::FR),
+        "New Patch Ingot"
+    );
+    let search_result = find_recipes("new patch ingot", Locale::EN);
+    assert_eq!(search_result, vec![RECIPES.len()]);
+    // bundled items are still available
+    assert_eq!(get_item_name(44091, false, Locale::EN), "Rroneek Steak");
+}
@@ -1,5 +1,5 @@
 use raphael_data::*;
-use raphael_sim::{Action, ActionMask, Settings};
+use raphael_sim::{Action, ActionMask, RulesOverride, Settings, SuccessModel};
 
 fn find_recipe(item_name: &'static str) -> Option<Recipe> {
     for recipe in RECIPES.iter() {
@@ -61,6 +61,8 @@ fn test_roast_chicken() {
                 .remove(Action::HeartAndSoul)
                 .remove(Action::QuickInnovation),
             adversarial: false,
+            success_model: SuccessModel::AssumeFail,
+            rules_override: RulesOverride::NONE,
         }
     );
 }
@@ -102,6 +104,8 @@ fn test_turali_pineapple_ponzecake() {
                 .remove(Action::TrainedEye)
                 .remove(Action::QuickInnovation),
             adversarial: false,
+            success_model: SuccessModel::AssumeFail,
+            rules_override: RulesOverride::NONE,
         }
     );
     let initial_quality = get_initial_quality(recipe, [0, 0, 1, 0, 0, 0]);
@@ -145,6 +149,8 @@ fn test_smaller_water_otter_hardware() {
                 .remove(Action::HeartAndSoul)
                 .remove(Action::QuickInnovation),
             adversarial: false,
+            success_model: SuccessModel::AssumeFail,
+            rules_override: RulesOverride::NONE,
         }
     );
     let initial_quality = get_initial_quality(recipe, [0, 0, 0, 0, 0, 0]);
@@ -185,6 +191,8 @@ fn test_grade_8_tincture() {
             // Trained Eye is available
             allowed_actions: ActionMask::all().remove(Action::QuickInnovation),
             adversarial: false,
+            success_model: SuccessModel::AssumeFail,
+            rules_override: RulesOverride::NONE,
         }
     );
     let initial_quality = get_initial_quality(recipe, [0, 0, 0, 0, 0, 0]);
@@ -222,8 +230,72 @@ fn test_claro_walnut_spinning_wheel() {
                 .remove(Action::TrainedEye)
                 .remove(Action::HeartAndSoul),
             adversarial: false,
+            success_model: SuccessModel::AssumeFail,
+            rules_override: RulesOverride::NONE,
         }
     );
     let initial_quality = get_initial_quality(recipe, [0, 0, 0, 0, 0, 0]);
     assert_eq!(initial_quality, 0);
 }
+
+#[test]
+fn test_game_settings_breakdown() {
+    let recipe = find_recipe("Claro Walnut Spinning Wheel").unwrap();
+    let crafter_stats = CrafterStats {
+        craftsmanship: 4000,
+        control: 3962,
+        cp: 594,
+        level: 99,
+        manipulation: true,
+        heart_and_soul: false,
+        quick_innovation: true,
+    };
+    let breakdown = get_game_settings_breakdown(
+        recipe,
+        crafter_stats,
+        None,
+        None,
+        false,
+        GameSettingsOverrides::default(),
+    );
+    assert_eq!(
+        breakdown.settings,
+        get_game_settings(recipe, crafter_stats, None, None, false)
+    );
+    assert_eq!(breakdown.effective_stats, crafter_stats);
+    assert_eq!(breakdown.level_penalty, crafter_stats.level <= recipe.level);
+    assert_eq!(
+        breakdown.settings.base_progress,
+        breakdown.base_progress as u16
+    );
+    assert_eq!(
+        breakdown.settings.base_quality,
+        breakdown.base_quality as u16
+    );
+
+    // overriding the stats behaves the same as having the stats without consumables
+    let overridden = get_game_settings_breakdown(
+        recipe,
+        crafter_stats,
+        None,
+        None,
+        false,
+        GameSettingsOverrides {
+            craftsmanship: Some(4500),
+            control: Some(4200),
+            cp: Some(650),
+            ..GameSettingsOverrides::default()
+        },
+    );
+    let improved_stats = CrafterStats {
+        craftsmanship: 4500,
+        control: 4200,
+        cp: 650,
+        ..crafter_stats
+    };
+    assert_eq!(
+        overridden.settings,
+        get_game_settings(recipe, improved_stats, None, None, false)
+    );
+    assert_eq!(overridden.base_stats, crafter_stats);
+}
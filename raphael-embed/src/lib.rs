@@ -0,0 +1,199 @@
+//! Lightweight wasm bundle for embedding interactive rotation previews, e.g. in guides.
+//! Only exposes the simulator and its analysis, the solver and the game data are left out to
+//! keep the bundle small. Values are passed as plain JS objects, see the README for an example.
+
+use raphael_sim::{
+    Action, ActionMask, Condition, RulesOverride, Settings, SimulationState, SuccessModel, lint,
+    quality_probability_distribution,
+};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Recipe and crafter stats of the craft to simulate.
+#[derive(Debug, Clone, Deserialize)]
+struct Craft {
+    max_cp: i16,
+    max_durability: i8,
+    max_progress: u16,
+    max_quality: u16,
+    base_progress: u16,
+    base_quality: u16,
+    job_level: u8,
+    /// Whether Heart and Soul and Quick Innovation are available.
+    #[serde(default)]
+    specialist: bool,
+    #[serde(default)]
+    adversarial: bool,
+    #[serde(default)]
+    success_model: SuccessModel,
+    #[serde(default)]
+    rules_override: RulesOverride,
+}
+
+impl From<Craft> for Settings {
+    fn from(craft: Craft) -> Self {
+        let allowed_actions = match craft.specialist {
+            true => ActionMask::all(),
+            false => ActionMask::all()
+                .remove(Action::HeartAndSoul)
+                .remove(Action::QuickInnovation),
+        };
+        Self {
+            max_cp: craft.max_cp,
+            max_durability: craft.max_durability,
+            max_progress: craft.max_progress,
+            max_quality: craft.max_quality,
+            base_progress: craft.base_progress,
+            base_quality: craft.base_quality,
+            job_level: craft.job_level,
+            allowed_actions,
+            adversarial: craft.adversarial,
+            success_model: craft.success_model,
+            rules_override: craft.rules_override,
+        }
+    }
+}
+
+/// State of the craft after a step of the rotation.
+#[derive(Debug, Clone, Serialize)]
+struct Step {
+    action: Action,
+    /// Reason why the action can't be used. The state is unchanged in that case.
+    error: Option<&'static str>,
+    progress_increase: u16,
+    quality_increase: u16,
+    durability_cost: i8,
+    cp_cost: i16,
+    progress: u16,
+    quality: u16,
+    durability: i8,
+    cp: i16,
+    inner_quiet: u8,
+    waste_not: u8,
+    innovation: u8,
+    veneration: u8,
+    great_strides: u8,
+    muscle_memory: u8,
+    manipulation: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Simulation {
+    steps: Vec<Step>,
+    /// Duration of the macro in seconds.
+    duration: u32,
+    /// Whether the craft ends with 100% Progress.
+    finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Finding {
+    step: usize,
+    action: Action,
+    rule: &'static str,
+    severity: lint::Severity,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct QualityChance {
+    quality: u16,
+    probability: f32,
+}
+
+fn parse_input(craft: JsValue, actions: JsValue) -> Result<(Settings, Vec<Action>), JsError> {
+    let craft: Craft = serde_wasm_bindgen::from_value(craft)
+        .map_err(|err| JsError::new(&format!("Invalid craft: {err}")))?;
+    let actions: Vec<Action> = serde_wasm_bindgen::from_value(actions)
+        .map_err(|err| JsError::new(&format!("Invalid actions: {err}")))?;
+    Ok((Settings::from(craft), actions))
+}
+
+fn to_js_value(value: &impl Serialize) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Names of all actions, in the form that the other functions expect, e.g. `"BasicTouch"`.
+#[wasm_bindgen(js_name = actionNames)]
+pub fn action_names() -> Result<JsValue, JsError> {
+    to_js_value(&ActionMask::all().actions())
+}
+
+/// Simulates the rotation step by step. Actions that can't be used are reported and skipped.
+#[wasm_bindgen]
+pub fn simulate(craft: JsValue, actions: JsValue) -> Result<JsValue, JsError> {
+    let (settings, actions) = parse_input(craft, actions)?;
+    let mut state = SimulationState::new(&settings);
+    let mut steps = Vec::with_capacity(actions.len());
+    for action in &actions {
+        let effect = state.action_effect(*action, Condition::Normal, &settings);
+        let error = match state.use_action(*action, Condition::Normal, &settings) {
+            Ok(new_state) => {
+                state = new_state;
+                None
+            }
+            Err(reason) => Some(reason),
+        };
+        steps.push(Step {
+            action: *action,
+            error,
+            progress_increase: effect.progress_increase,
+            quality_increase: effect.quality_increase,
+            durability_cost: effect.durability_cost,
+            cp_cost: effect.cp_cost,
+            progress: state.progress,
+            quality: state.quality,
+            durability: state.durability,
+            cp: state.cp,
+            inner_quiet: state.effects.inner_quiet(),
+            waste_not: state.effects.waste_not(),
+            innovation: state.effects.innovation(),
+            veneration: state.effects.veneration(),
+            great_strides: state.effects.great_strides(),
+            muscle_memory: state.effects.muscle_memory(),
+            manipulation: state.effects.manipulation(),
+        });
+    }
+    to_js_value(&Simulation {
+        steps,
+        duration: actions
+            .iter()
+            .map(|action| u32::from(action.time_cost()))
+            .sum(),
+        finished: state.progress >= settings.max_progress,
+    })
+}
+
+/// Findings of the built-in lint rules, e.g. buffs that are overwritten before they run out.
+#[wasm_bindgen]
+pub fn lint(craft: JsValue, actions: JsValue) -> Result<JsValue, JsError> {
+    let (settings, actions) = parse_input(craft, actions)?;
+    let findings: Vec<Finding> = lint::Linter::default()
+        .lint(&settings, &actions)
+        .into_iter()
+        .map(|finding| Finding {
+            step: finding.step,
+            action: finding.action,
+            rule: finding.rule,
+            severity: finding.severity,
+            message: finding.message,
+        })
+        .collect();
+    to_js_value(&findings)
+}
+
+/// Chances of reaching each Quality when the conditions of the craft are random.
+#[wasm_bindgen(js_name = qualityDistribution)]
+pub fn quality_distribution(craft: JsValue, actions: JsValue) -> Result<JsValue, JsError> {
+    let (settings, actions) = parse_input(craft, actions)?;
+    let distribution: Vec<QualityChance> = quality_probability_distribution(settings, actions, 0)
+        .into_iter()
+        .map(|value| QualityChance {
+            quality: value.quality,
+            probability: value.probability,
+        })
+        .collect();
+    to_js_value(&distribution)
+}
@@ -0,0 +1,291 @@
+//! Python bindings of the simulator and the solver, so that parameter sweeps can be scripted
+//! from notebooks. Actions and conditions are passed by name, e.g. `"BasicTouch"` and `"Good"`,
+//! see the README for an example.
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use raphael_sim::{Action, ActionMask, Condition};
+use raphael_solver::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+const CONDITIONS: [Condition; 10] = [
+    Condition::Normal,
+    Condition::Good,
+    Condition::Excellent,
+    Condition::Poor,
+    Condition::Centered,
+    Condition::Sturdy,
+    Condition::Pliant,
+    Condition::Malleable,
+    Condition::Primed,
+    Condition::GoodOmen,
+];
+
+fn parse_action(name: &str) -> PyResult<Action> {
+    Action::ALL
+        .iter()
+        .copied()
+        .find(|action| format!("{action:?}") == name)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown action: {name}")))
+}
+
+fn parse_condition(name: &str) -> PyResult<Condition> {
+    CONDITIONS
+        .into_iter()
+        .find(|condition| format!("{condition:?}") == name)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown condition: {name}")))
+}
+
+/// Recipe and crafter stats of a craft, with the same fields as the craft of the wasm libraries.
+#[pyclass(frozen, get_all)]
+#[derive(Debug, Clone, Copy)]
+struct Settings {
+    max_cp: i16,
+    max_durability: i8,
+    max_progress: u16,
+    /// Quality to reach, not including the initial Quality of the craft.
+    max_quality: u16,
+    base_progress: u16,
+    base_quality: u16,
+    job_level: u8,
+    /// Whether Heart and Soul and Quick Innovation are available.
+    specialist: bool,
+    /// Whether Manipulation was learned.
+    manipulation: bool,
+    /// Whether Trained Eye can be used, i.e. the recipe is a non-expert recipe at least 10 levels
+    /// below the crafter.
+    trained_eye: bool,
+    adversarial: bool,
+}
+
+impl From<&Settings> for raphael_sim::Settings {
+    fn from(settings: &Settings) -> Self {
+        let mut allowed_actions = ActionMask::all();
+        if !settings.trained_eye {
+            allowed_actions = allowed_actions.remove(Action::TrainedEye);
+        }
+        if !settings.specialist {
+            allowed_actions = allowed_actions
+                .remove(Action::HeartAndSoul)
+                .remove(Action::QuickInnovation);
+        }
+        if !settings.manipulation {
+            allowed_actions = allowed_actions.remove(Action::Manipulation);
+        }
+        Self {
+            max_cp: settings.max_cp,
+            max_durability: settings.max_durability,
+            max_progress: settings.max_progress,
+            max_quality: settings.max_quality,
+            base_progress: settings.base_progress,
+            base_quality: settings.base_quality,
+            job_level: settings.job_level,
+            allowed_actions,
+            adversarial: settings.adversarial,
+            success_model: raphael_sim::SuccessModel::default(),
+            rules_override: raphael_sim::RulesOverride::default(),
+        }
+    }
+}
+
+#[pymethods]
+impl Settings {
+    /// Raises a `ValueError` if the solver can't handle the settings, e.g. because the max
+    /// durability isn't a multiple of 5.
+    #[new]
+    #[pyo3(signature = (
+        *,
+        max_cp,
+        max_durability,
+        max_progress,
+        max_quality,
+        base_progress,
+        base_quality,
+        job_level,
+        specialist = false,
+        manipulation = true,
+        trained_eye = false,
+        adversarial = false,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        max_cp: i16,
+        max_durability: i8,
+        max_progress: u16,
+        max_quality: u16,
+        base_progress: u16,
+        base_quality: u16,
+        job_level: u8,
+        specialist: bool,
+        manipulation: bool,
+        trained_eye: bool,
+        adversarial: bool,
+    ) -> PyResult<Self> {
+        let settings = Self {
+            max_cp,
+            max_durability,
+            max_progress,
+            max_quality,
+            base_progress,
+            base_quality,
+            job_level,
+            specialist,
+            manipulation,
+            trained_eye,
+            adversarial,
+        };
+        raphael_sim::Settings::from(&settings)
+            .validate()
+            .map_err(PyValueError::new_err)?;
+        Ok(settings)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+/// State of a craft after some actions.
+#[pyclass(frozen)]
+#[derive(Debug, Clone, Copy)]
+struct SimulationState {
+    inner: raphael_sim::SimulationState,
+}
+
+#[pymethods]
+impl SimulationState {
+    /// State at the start of the craft.
+    #[new]
+    fn new(settings: &Settings) -> Self {
+        Self {
+            inner: raphael_sim::SimulationState::new(&settings.into()),
+        }
+    }
+
+    /// State after the actions, starting from the start of the craft. Raises a `ValueError` if
+    /// one of the actions can't be used.
+    #[staticmethod]
+    fn from_macro(settings: &Settings, actions: Vec<String>) -> PyResult<Self> {
+        let actions = actions
+            .iter()
+            .map(|name| parse_action(name))
+            .collect::<PyResult<Vec<Action>>>()?;
+        raphael_sim::SimulationState::from_macro(&settings.into(), &actions)
+            .map(|inner| Self { inner })
+            .map_err(PyValueError::new_err)
+    }
+
+    #[getter]
+    fn cp(&self) -> i16 {
+        self.inner.cp
+    }
+
+    #[getter]
+    fn durability(&self) -> i8 {
+        self.inner.durability
+    }
+
+    #[getter]
+    fn progress(&self) -> u16 {
+        self.inner.progress
+    }
+
+    #[getter]
+    fn quality(&self) -> u16 {
+        self.inner.quality
+    }
+
+    /// Active effects by name, with their stacks (Inner Quiet) or the number of steps they last.
+    #[getter]
+    fn effects(&self) -> Vec<(String, u8)> {
+        self.inner
+            .effects
+            .active()
+            .map(|(effect, value)| (format!("{effect:?}"), value))
+            .collect()
+    }
+
+    /// State after using the action in the condition. Raises a `ValueError` if the action can't
+    /// be used, e.g. because there isn't enough CP left.
+    #[pyo3(signature = (action, settings, condition = "Normal"))]
+    fn use_action(&self, action: &str, settings: &Settings, condition: &str) -> PyResult<Self> {
+        self.inner
+            .use_action(
+                parse_action(action)?,
+                parse_condition(condition)?,
+                &settings.into(),
+            )
+            .map(|inner| Self { inner })
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Whether no more actions can be used, i.e. Progress is maxed out or durability is used up.
+    fn is_final(&self, settings: &Settings) -> bool {
+        self.inner.is_final(&settings.into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.inner)
+    }
+}
+
+/// Solves the craft for the macro with the most Quality, ties broken by the fewest steps.
+/// Returns the names of the actions. The GIL is released while solving, so other Python threads
+/// keep running, e.g. to solve several crafts at once.
+#[pyfunction]
+#[pyo3(signature = (
+    settings,
+    *,
+    backload_progress = false,
+    progress_opener = false,
+    minimize_cp = false,
+    human_friendly_bias = 0,
+    unsound_branch_pruning = false,
+))]
+fn solve(
+    py: Python<'_>,
+    settings: &Settings,
+    backload_progress: bool,
+    progress_opener: bool,
+    minimize_cp: bool,
+    human_friendly_bias: u8,
+    unsound_branch_pruning: bool,
+) -> PyResult<Vec<String>> {
+    let settings = raphael_sim::Settings::from(settings);
+    let actions = py.allow_threads(|| {
+        MacroSolver::new(
+            SolverSettings {
+                simulator_settings: settings,
+                backload_progress,
+                allow_unsound_branch_pruning: unsound_branch_pruning,
+                forbid_quality_during_muscle_memory: progress_opener,
+                first_quality_step: 0,
+            },
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            AtomicFlag::new(),
+        )
+        .minimize_cp(minimize_cp)
+        .human_friendly_bias(human_friendly_bias)
+        .solve()
+    });
+    match actions {
+        Ok(actions) => Ok(actions.iter().map(|action| format!("{action:?}")).collect()),
+        Err(SolverException::NoSolution) => Err(PyValueError::new_err("No solution")),
+        Err(SolverException::Interrupted) => Err(PyRuntimeError::new_err("Cancelled")),
+        Err(SolverException::MemoryLimitReached) => Err(PyRuntimeError::new_err("Out of memory")),
+        Err(SolverException::InternalError(message)) => Err(PyRuntimeError::new_err(message)),
+    }
+}
+
+#[pymodule]
+fn raphael(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Settings>()?;
+    module.add_class::<SimulationState>()?;
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+    let actions: Vec<String> = Action::ALL
+        .iter()
+        .map(|action| format!("{action:?}"))
+        .collect();
+    module.add("ACTIONS", actions)?;
+    Ok(())
+}
@@ -13,6 +13,8 @@ fn bench_use_action(c: &mut Criterion) {
         job_level: 100,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let state = SimulationState::new(&settings);
 
@@ -4,4 +4,132 @@ pub enum Condition {
     Good,
     Excellent,
     Poor,
+    /// Increases the success rate of the action by 25%. Only has an effect on actions that can
+    /// fail, with [`crate::SuccessModel::ExpectedValue`].
+    Centered,
+    /// Halves the durability cost of the action.
+    Sturdy,
+    /// Halves the CP cost of the action.
+    Pliant,
+    /// Increases the Progress of the action by 50%.
+    Malleable,
+    /// Increases the duration of a status effect applied by the action by 2 steps.
+    Primed,
+    /// The condition of the next step is Good.
+    GoodOmen,
+}
+
+impl Condition {
+    /// Bit of the condition in the `ConditionsFlag` of a recipe level.
+    pub const fn flag(self) -> u16 {
+        match self {
+            Self::Normal => 1 << 0,
+            Self::Good => 1 << 1,
+            Self::Excellent => 1 << 2,
+            Self::Poor => 1 << 3,
+            Self::Centered => 1 << 4,
+            Self::Sturdy => 1 << 5,
+            Self::Pliant => 1 << 6,
+            Self::Malleable => 1 << 7,
+            Self::Primed => 1 << 8,
+            Self::GoodOmen => 1 << 9,
+        }
+    }
+}
+
+/// Conditions that can occur while crafting a recipe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConditionPool {
+    flags: u16,
+}
+
+impl ConditionPool {
+    const ALL: [Condition; 10] = [
+        Condition::Normal,
+        Condition::Good,
+        Condition::Excellent,
+        Condition::Poor,
+        Condition::Centered,
+        Condition::Sturdy,
+        Condition::Pliant,
+        Condition::Malleable,
+        Condition::Primed,
+        Condition::GoodOmen,
+    ];
+
+    /// Normal, Good, Excellent and Poor, the conditions of all recipes that aren't expert recipes.
+    pub const NORMAL: Self = Self::from_flags(0b1111);
+
+    /// Creates the pool from the `ConditionsFlag` of a recipe level.
+    pub const fn from_flags(flags: u16) -> Self {
+        Self { flags }
+    }
+
+    pub const fn flags(self) -> u16 {
+        self.flags
+    }
+
+    pub const fn contains(self, condition: Condition) -> bool {
+        self.flags & condition.flag() != 0
+    }
+
+    /// Whether the pool contains any condition other than Normal, Good, Excellent and Poor.
+    pub const fn has_expert_conditions(self) -> bool {
+        self.flags & !Self::NORMAL.flags != 0
+    }
+
+    pub fn conditions(self) -> impl Iterator<Item = Condition> {
+        Self::ALL
+            .into_iter()
+            .filter(move |condition| self.contains(*condition))
+    }
+
+    /// Probability of each condition at the next step, given the condition at the current step.
+    ///
+    /// The rates of expert conditions aren't published. The weights used here are estimates
+    /// from crafting logs: 12% for Good, Pliant, Malleable, Primed and Good Omen, 15% for
+    /// Centered and Sturdy, and the rest for Normal.
+    pub fn next_condition_probabilities(
+        self,
+        current_condition: Condition,
+    ) -> Vec<(Condition, f32)> {
+        if !self.has_expert_conditions() {
+            return crate::condition_probabilities(current_condition).to_vec();
+        }
+        match current_condition {
+            Condition::GoodOmen if self.contains(Condition::Good) => {
+                return vec![(Condition::Good, 1.0)];
+            }
+            Condition::Excellent if self.contains(Condition::Poor) => {
+                return vec![(Condition::Poor, 1.0)];
+            }
+            _ => (),
+        }
+        let weight = |condition: Condition| match condition {
+            Condition::Normal | Condition::Poor => 0.0,
+            Condition::Excellent => 0.02,
+            Condition::Centered | Condition::Sturdy => 0.15,
+            Condition::Good
+            | Condition::Pliant
+            | Condition::Malleable
+            | Condition::Primed
+            | Condition::GoodOmen => 0.12,
+        };
+        let mut probabilities: Vec<(Condition, f32)> = self
+            .conditions()
+            .filter(|condition| *condition != Condition::Normal)
+            .map(|condition| (condition, weight(condition)))
+            .filter(|(_, probability)| *probability > 0.0)
+            .collect();
+        let normal_probability = 1.0 - probabilities.iter().map(|(_, p)| p).sum::<f32>();
+        probabilities.insert(0, (Condition::Normal, normal_probability.max(0.0)));
+        probabilities
+    }
+}
+
+impl Default for ConditionPool {
+    fn default() -> Self {
+        Self::NORMAL
+    }
 }
@@ -25,8 +25,23 @@ impl SingleUse {
     }
 }
 
+/// Status effect of the crafter, see [`Effects::active`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Effect {
+    InnerQuiet,
+    WasteNot,
+    Innovation,
+    Veneration,
+    GreatStrides,
+    MuscleMemory,
+    Manipulation,
+    TrainedPerfection,
+    HeartAndSoul,
+}
+
 #[bitfield_struct::bitfield(u32)]
 #[derive(PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Effects {
     #[bits(2, default=SingleUse::Available)]
     pub trained_perfection: SingleUse,
@@ -91,4 +106,29 @@ impl Effects {
         }
         self.0 -= effect_tick;
     }
+
+    /// Effects that are currently active, together with their stacks (Inner Quiet) or the
+    /// number of steps they last. Effects that last until they are used have a value of 0.
+    pub fn active(self) -> impl Iterator<Item = (Effect, u8)> {
+        let single_use = |effect: Effect, value: SingleUse| match value {
+            SingleUse::Active => Some((effect, 0)),
+            _ => None,
+        };
+        [
+            (Effect::InnerQuiet, self.inner_quiet()),
+            (Effect::WasteNot, self.waste_not()),
+            (Effect::Innovation, self.innovation()),
+            (Effect::Veneration, self.veneration()),
+            (Effect::GreatStrides, self.great_strides()),
+            (Effect::MuscleMemory, self.muscle_memory()),
+            (Effect::Manipulation, self.manipulation()),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value != 0)
+        .chain(single_use(
+            Effect::TrainedPerfection,
+            self.trained_perfection(),
+        ))
+        .chain(single_use(Effect::HeartAndSoul, self.heart_and_soul()))
+    }
 }
@@ -2,16 +2,27 @@ mod actions;
 pub use actions::*;
 
 mod conditions;
-pub use conditions::Condition;
+pub use conditions::{Condition, ConditionPool};
 
 mod effects;
-pub use effects::{Effects, SingleUse};
+pub use effects::{Effect, Effects, SingleUse};
 
 pub mod state;
-pub use state::SimulationState;
+pub use state::{ActionEffect, MacroError, SimulationState};
 
 mod settings;
-pub use settings::{ActionMask, Settings};
+pub use settings::{ActionMask, Settings, SuccessModel};
+
+mod rules_override;
+pub use rules_override::{ActionOverride, MAX_ACTION_OVERRIDES, RulesOverride};
+
+pub mod lint;
 
 mod probabilities;
-pub use probabilities::quality_probability_distribution;
+pub use probabilities::{
+    QualityDistribution, condition_probabilities, quality_probability_distribution,
+    quality_probability_distribution_from,
+};
+
+mod rollout;
+pub use rollout::{RolloutResult, rollout_batch};
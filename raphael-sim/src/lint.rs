@@ -0,0 +1,197 @@
+//! Diagnostics for rotations, e.g. to catch mistakes in manually edited macros.
+
+use crate::{Action, ActionEffect, Condition, Settings, SimulationState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Problem found by a [`LintRule`] at one step of a rotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    /// Zero-based index of the step.
+    pub step: usize,
+    pub action: Action,
+    /// Name of the rule that produced the finding.
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// One step of the rotation as seen by a [`LintRule`].
+pub struct LintStep<'a> {
+    pub settings: &'a Settings,
+    /// Zero-based index of the step.
+    pub step: usize,
+    pub action: Action,
+    /// State before the action is used.
+    pub state: &'a SimulationState,
+    /// Numbers of the action in `state`.
+    pub effect: ActionEffect,
+    /// State after the action is used, or the reason why the action can't be used.
+    pub result: Result<SimulationState, &'static str>,
+}
+
+pub trait LintRule: Send + Sync {
+    /// Unique name of the rule, used to configure it in a [`Linter`].
+    fn name(&self) -> &'static str;
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// Returns a message describing the problem if the rule applies to this step.
+    fn check(&self, step: &LintStep) -> Option<String>;
+}
+
+/// Flags actions that can't be used, e.g. due to missing CP or a failed precondition.
+pub struct InvalidAction;
+
+impl LintRule for InvalidAction {
+    fn name(&self) -> &'static str {
+        "invalid-action"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, step: &LintStep) -> Option<String> {
+        step.result.err().map(str::to_string)
+    }
+}
+
+/// Flags actions with lower efficiency than usual, e.g. Groundwork with too little durability.
+pub struct ReducedEfficiency;
+
+impl LintRule for ReducedEfficiency {
+    fn name(&self) -> &'static str {
+        "reduced-efficiency"
+    }
+
+    fn check(&self, step: &LintStep) -> Option<String> {
+        let effect = step.effect;
+        if effect.progress_efficiency >= effect.base_progress_efficiency {
+            return None;
+        }
+        Some(format!(
+            "Progress efficiency reduced from {}% to {}% (Durability {} is lower than the durability cost {})",
+            effect.base_progress_efficiency,
+            effect.progress_efficiency,
+            step.state.durability,
+            effect.durability_cost
+        ))
+    }
+}
+
+/// Flags buffs that are reapplied while more than their last step remains.
+pub struct BuffOverwritten;
+
+impl LintRule for BuffOverwritten {
+    fn name(&self) -> &'static str {
+        "buff-overwritten"
+    }
+
+    fn check(&self, step: &LintStep) -> Option<String> {
+        let effects = step.state.effects;
+        let remaining_steps = match step.action {
+            Action::WasteNot | Action::WasteNot2 => effects.waste_not(),
+            Action::Veneration => effects.veneration(),
+            Action::Innovation => effects.innovation(),
+            Action::GreatStrides => effects.great_strides(),
+            Action::Manipulation => effects.manipulation(),
+            _ => 0,
+        };
+        // the step the buff is reapplied on would have been its last step anyway
+        if remaining_steps <= 1 {
+            return None;
+        }
+        Some(format!(
+            "Overwrites the active buff, losing {} remaining steps",
+            remaining_steps - 1
+        ))
+    }
+}
+
+/// Set of rules to check rotations against.
+pub struct Linter {
+    rules: Vec<(Box<dyn LintRule>, Severity)>,
+}
+
+impl Default for Linter {
+    /// Linter with all built-in rules at their default severity.
+    fn default() -> Self {
+        Self::empty()
+            .with_rule(InvalidAction)
+            .with_rule(ReducedEfficiency)
+            .with_rule(BuffOverwritten)
+    }
+}
+
+impl Linter {
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    #[must_use]
+    pub fn with_rule(mut self, rule: impl LintRule + 'static) -> Self {
+        let severity = rule.default_severity();
+        self.rules.push((Box::new(rule), severity));
+        self
+    }
+
+    /// Names and severities of the configured rules.
+    pub fn rules(&self) -> impl Iterator<Item = (&'static str, Severity)> + '_ {
+        self.rules
+            .iter()
+            .map(|(rule, severity)| (rule.name(), *severity))
+    }
+
+    pub fn set_severity(&mut self, rule_name: &str, severity: Severity) {
+        for (rule, rule_severity) in &mut self.rules {
+            if rule.name() == rule_name {
+                *rule_severity = severity;
+            }
+        }
+    }
+
+    pub fn disable(&mut self, rule_name: &str) {
+        self.rules.retain(|(rule, _)| rule.name() != rule_name);
+    }
+
+    /// Checks each step of the rotation against all rules. Steps that can't be used are
+    /// skipped, i.e. the following steps are checked as if the failed step wasn't there.
+    pub fn lint(&self, settings: &Settings, actions: &[Action]) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        let mut state = SimulationState::new(settings);
+        for (step, action) in actions.iter().enumerate() {
+            let lint_step = LintStep {
+                settings,
+                step,
+                action: *action,
+                state: &state,
+                effect: state.action_effect(*action, Condition::Normal, settings),
+                result: state.use_action(*action, Condition::Normal, settings),
+            };
+            for (rule, severity) in &self.rules {
+                if let Some(message) = rule.check(&lint_step) {
+                    findings.push(LintFinding {
+                        step,
+                        action: *action,
+                        rule: rule.name(),
+                        severity: *severity,
+                        message,
+                    });
+                }
+            }
+            if let Ok(new_state) = lint_step.result {
+                state = new_state;
+            }
+        }
+        findings
+    }
+}
@@ -2,9 +2,16 @@ use std::collections::HashMap;
 
 use crate::{Action, Condition, Settings, SimulationState};
 
-const fn condition_probabilities(current_condition: Condition) -> &'static [(Condition, f32)] {
+/// Probability of each condition at the next step, given the condition at the current step,
+/// for recipes that only have Normal, Good, Excellent and Poor conditions.
+pub const fn condition_probabilities(current_condition: Condition) -> &'static [(Condition, f32)] {
     match current_condition {
-        Condition::Normal => &[
+        Condition::Normal
+        | Condition::Centered
+        | Condition::Sturdy
+        | Condition::Pliant
+        | Condition::Malleable
+        | Condition::Primed => &[
             (Condition::Normal, 0.86),
             (Condition::Good, 0.12),
             (Condition::Excellent, 0.02),
@@ -12,6 +19,7 @@ const fn condition_probabilities(current_condition: Condition) -> &'static [(Con
         Condition::Good => &[(Condition::Normal, 1.00)],
         Condition::Excellent => &[(Condition::Poor, 1.00)],
         Condition::Poor => &[(Condition::Normal, 1.00)],
+        Condition::GoodOmen => &[(Condition::Good, 1.00)],
     }
 }
 
@@ -34,14 +42,6 @@ pub struct QualityDistribution {
     distribution: Vec<Value>,
 }
 
-impl QualityDistribution {
-    fn zero() -> Self {
-        Self {
-            distribution: vec![Value::zero()],
-        }
-    }
-}
-
 impl IntoIterator for QualityDistribution {
     type Item = Value;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -71,6 +71,14 @@ impl QualityDistribution {
         }
         result.clamp(0.0, 1.0)
     }
+
+    /// Expected Quality, where Quality above `quality_cap` counts as `quality_cap`.
+    pub fn expected_quality(&self, quality_cap: u16) -> f32 {
+        self.distribution
+            .iter()
+            .map(|value| f32::from(std::cmp::min(value.quality, quality_cap)) * value.probability)
+            .sum()
+    }
 }
 
 pub fn quality_probability_distribution(
@@ -78,20 +86,40 @@ pub fn quality_probability_distribution(
     actions: impl Into<Box<[Action]>>,
     initial_quality: u16,
 ) -> QualityDistribution {
-    let initial_state = SimulationState::new(&settings);
+    let mut distribution =
+        quality_probability_distribution_from(settings, SimulationState::new(&settings), actions);
+    for value in &mut distribution.distribution {
+        value.quality = value.quality.saturating_add(initial_quality);
+    }
+    distribution
+}
+
+/// Like [`quality_probability_distribution`], but starts from `initial_state`, e.g. the state
+/// after a prefix of actions. The Quality of `initial_state` is included in the distribution.
+pub fn quality_probability_distribution_from(
+    settings: Settings,
+    initial_state: SimulationState,
+    actions: impl Into<Box<[Action]>>,
+) -> QualityDistribution {
     let actions: Box<[Action]> = actions.into();
     match actions.len() {
-        0 => QualityDistribution::zero(),
+        0 => QualityDistribution {
+            distribution: vec![Value {
+                quality: initial_state.quality,
+                probability: 1.0,
+            }],
+        },
         _ => {
             let mut solver = QualityDistributionSolver::new(settings, actions);
-            solver.solve(initial_state, Condition::Normal, 0);
+            let normalized_state = QualityDistributionSolver::normalize_state(initial_state);
+            solver.solve(normalized_state, Condition::Normal, 0);
             let distribution = solver
                 .memoization
-                .get(&(initial_state, Condition::Normal, 0))
+                .get(&(normalized_state, Condition::Normal, 0))
                 .expect("State not in memoization even after solving")
                 .iter()
                 .map(|value| Value {
-                    quality: initial_quality + value.quality,
+                    quality: initial_state.quality.saturating_add(value.quality),
                     probability: value.probability,
                 });
             QualityDistribution {
@@ -0,0 +1,100 @@
+use crate::{Action, Condition, Settings, SimulationState, SuccessModel, condition_probabilities};
+
+/// Final state of a single simulated craft with random conditions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RolloutResult {
+    pub progress: u16,
+    pub quality: u16,
+    /// Whether the craft reached the max Progress.
+    pub finished: bool,
+}
+
+/// SplitMix64, which is small, fast and good enough for sampling conditions.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed value in `[0, 1)`.
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+fn sample_condition(rng: &mut Rng, previous_condition: Condition) -> Condition {
+    let mut sample = rng.next_f32();
+    let probabilities = condition_probabilities(previous_condition);
+    for (condition, probability) in probabilities {
+        if sample < *probability {
+            return *condition;
+        }
+        sample -= probability;
+    }
+    probabilities[probabilities.len() - 1].0
+}
+
+fn rollout(
+    settings: &Settings,
+    initial_state: SimulationState,
+    actions: &[Action],
+    rng: &mut Rng,
+) -> RolloutResult {
+    let mut state = initial_state;
+    let mut condition = Condition::Normal;
+    for (index, action) in actions.iter().enumerate() {
+        if state.is_final(settings) {
+            break;
+        }
+        // the first step of a craft is always Normal
+        if index != 0 {
+            condition = sample_condition(rng, condition);
+        }
+        let success_rate =
+            SuccessModel::ExpectedValue.success_rate(action.success_rate(), condition);
+        let success_model = match rng.next_f32() * 100.0 < f32::from(success_rate) {
+            true => SuccessModel::AssumeSuccess,
+            false => SuccessModel::AssumeFail,
+        };
+        let step_settings = Settings {
+            success_model,
+            ..*settings
+        };
+        // like in-game, actions that can't be used are skipped
+        if let Ok(next_state) = state.use_action(*action, condition, &step_settings) {
+            state = next_state;
+        }
+    }
+    RolloutResult {
+        progress: state.progress,
+        quality: state.quality,
+        finished: state.progress >= settings.max_progress,
+    }
+}
+
+/// Simulates `actions` from `initial_state` `count` times with random conditions, which follow
+/// [`condition_probabilities`] after the first step, and random success of actions that can fail.
+///
+/// The results only depend on `seed`, so that an analysis can be reproduced. The adversarial
+/// flag and the success model of `settings` are ignored.
+pub fn rollout_batch(
+    settings: &Settings,
+    initial_state: SimulationState,
+    actions: &[Action],
+    seed: u64,
+    count: usize,
+) -> Vec<RolloutResult> {
+    let settings = Settings {
+        adversarial: false,
+        ..*settings
+    };
+    let mut rng = Rng(seed);
+    (0..count)
+        .map(|_| rollout(&settings, initial_state, actions, &mut rng))
+        .collect()
+}
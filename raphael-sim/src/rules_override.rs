@@ -0,0 +1,143 @@
+use crate::{Action, ActionMask};
+
+/// Maximum number of actions that a [`RulesOverride`] can change.
+pub const MAX_ACTION_OVERRIDES: usize = 8;
+
+/// Replacement values for the potency and costs of a single action.
+/// Values that are `None` keep the in-game value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionOverride {
+    pub action: Action,
+    /// Progress efficiency in percent, e.g. 120 for Basic Synthesis.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub progress_efficiency: Option<u16>,
+    /// Quality efficiency in percent, e.g. 100 for Basic Touch.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quality_efficiency: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cp_cost: Option<i16>,
+    /// Durability cost before Waste Not and the Sturdy condition are applied.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub durability_cost: Option<i8>,
+}
+
+impl ActionOverride {
+    pub const fn new(action: Action) -> Self {
+        Self {
+            action,
+            progress_efficiency: None,
+            quality_efficiency: None,
+            cp_cost: None,
+            durability_cost: None,
+        }
+    }
+}
+
+/// Changes to the potencies and costs of actions, used to simulate balance changes before they
+/// are released. The efficiencies and costs that depend on the state (e.g. Byregot's Blessing)
+/// are replaced as a whole. Actions that ignore efficiencies (e.g. Trained Eye) are unaffected
+/// by efficiency overrides.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<ActionOverride>", into = "Vec<ActionOverride>")
+)]
+pub struct RulesOverride {
+    /// Actions that have an entry in `overrides`, checked first to keep the common case cheap.
+    actions: ActionMask,
+    overrides: [Option<ActionOverride>; MAX_ACTION_OVERRIDES],
+}
+
+impl RulesOverride {
+    /// The in-game rules.
+    pub const NONE: Self = Self {
+        actions: ActionMask::none(),
+        overrides: [None; MAX_ACTION_OVERRIDES],
+    };
+
+    pub fn new(overrides: &[ActionOverride]) -> Result<Self, String> {
+        if overrides.len() > MAX_ACTION_OVERRIDES {
+            return Err(format!(
+                "At most {MAX_ACTION_OVERRIDES} actions can be overridden, got {}",
+                overrides.len()
+            ));
+        }
+        let mut rules = Self::NONE;
+        for (index, action_override) in overrides.iter().enumerate() {
+            if rules.actions.has(action_override.action) {
+                return Err(format!(
+                    "{:?} is overridden more than once",
+                    action_override.action
+                ));
+            }
+            if action_override.cp_cost.is_some_and(|cp_cost| cp_cost < 0) {
+                return Err(format!(
+                    "CP cost of {:?} must not be negative",
+                    action_override.action
+                ));
+            }
+            // the solver tracks durability in steps of 5
+            if action_override
+                .durability_cost
+                .is_some_and(|durability_cost| durability_cost < 0 || durability_cost % 5 != 0)
+            {
+                return Err(format!(
+                    "Durability cost of {:?} must be a non-negative multiple of 5",
+                    action_override.action
+                ));
+            }
+            rules.actions = rules.actions.add(action_override.action);
+            rules.overrides[index] = Some(*action_override);
+        }
+        Ok(rules)
+    }
+
+    pub const fn is_none(&self) -> bool {
+        self.actions.to_bits() == 0
+    }
+
+    /// Override of the action with the single-bit `action_mask`, if any.
+    pub fn get(&self, action_mask: ActionMask) -> Option<&ActionOverride> {
+        if self.actions.intersection(action_mask).to_bits() == 0 {
+            return None;
+        }
+        self.overrides
+            .iter()
+            .flatten()
+            .find(|action_override| action_mask.has(action_override.action))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ActionOverride> {
+        self.overrides.iter().flatten()
+    }
+}
+
+impl Default for RulesOverride {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Formats the overrides as a list, the same shape as the serialized form, so that logged
+/// settings can be parsed again.
+impl std::fmt::Debug for RulesOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl TryFrom<Vec<ActionOverride>> for RulesOverride {
+    type Error = String;
+
+    fn try_from(overrides: Vec<ActionOverride>) -> Result<Self, Self::Error> {
+        Self::new(&overrides)
+    }
+}
+
+impl From<RulesOverride> for Vec<ActionOverride> {
+    fn from(rules: RulesOverride) -> Self {
+        rules.iter().copied().collect()
+    }
+}
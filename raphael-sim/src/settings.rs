@@ -10,16 +10,134 @@ pub struct Settings {
     pub job_level: u8,
     pub allowed_actions: ActionMask,
     pub adversarial: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub success_model: SuccessModel,
+    /// Changes to action potencies and costs for experiments. The in-game rules unless set.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules_override: RulesOverride,
+}
+
+/// How the simulator resolves actions that only succeed with a certain chance, e.g. Rapid
+/// Synthesis. All other actions always succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SuccessModel {
+    /// The actions always fail, i.e. they only consume durability.
+    #[default]
+    AssumeFail,
+    /// The actions always succeed.
+    AssumeSuccess,
+    /// The actions increase Progress and Quality by the increase on success times the success
+    /// rate. Effects that only apply on success, e.g. Inner Quiet, are not applied.
+    ExpectedValue,
+}
+
+impl SuccessModel {
+    /// Success rate in percent that the simulator uses for an action with `base_rate`.
+    pub const fn success_rate(self, base_rate: u8, condition: Condition) -> u8 {
+        if base_rate >= 100 {
+            return 100;
+        }
+        match self {
+            Self::AssumeFail => 0,
+            Self::AssumeSuccess => 100,
+            Self::ExpectedValue => match condition {
+                Condition::Centered => {
+                    if base_rate + 25 < 100 {
+                        base_rate + 25
+                    } else {
+                        100
+                    }
+                }
+                _ => base_rate,
+            },
+        }
+    }
 }
 
 impl Settings {
+    /// Checks the values that the simulator and the solvers rely on, for settings that aren't
+    /// derived from a recipe, e.g. settings pasted from a bug report or passed to the bindings.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.max_cp < 0 {
+            return Err("Max CP must not be negative");
+        }
+        // the solvers track durability in steps of 5
+        if self.max_durability <= 0 || self.max_durability % 5 != 0 {
+            return Err("Max durability must be a positive multiple of 5");
+        }
+        if self.base_progress == 0 || self.base_quality == 0 {
+            return Err("Base Progress and base Quality must be positive");
+        }
+        // the solvers don't track Progress and Quality in all of their states, so an action that
+        // costs neither CP nor durability could be used forever
+        let in_game_rules = Self {
+            rules_override: RulesOverride::NONE,
+            ..*self
+        };
+        for action_override in self.rules_override.iter() {
+            let is_free = |settings: &Self| {
+                let effect = SimulationState::new(settings).action_effect(
+                    action_override.action,
+                    Condition::Normal,
+                    settings,
+                );
+                effect.cp_cost == 0 && effect.durability_cost == 0
+            };
+            if is_free(self) && !is_free(&in_game_rules) {
+                return Err("Rules override must not make an action free");
+            }
+        }
+        Ok(())
+    }
+
     pub fn is_action_allowed<ACTION: ActionImpl>(&self) -> bool {
         self.job_level >= ACTION::LEVEL_REQUIREMENT
             && self.allowed_actions.has_mask(ACTION::ACTION_MASK)
     }
+
+    /// Progress efficiency of the action in `state`, taking the rules override into account.
+    pub fn base_progress_increase<ACTION: ActionImpl + ?Sized>(
+        &self,
+        state: &SimulationState,
+    ) -> u16 {
+        self.rules_override
+            .get(ACTION::ACTION_MASK)
+            .and_then(|action_override| action_override.progress_efficiency)
+            .unwrap_or_else(|| ACTION::base_progress_increase(state, self))
+    }
+
+    /// Quality efficiency of the action in `state`, taking the rules override into account.
+    pub fn base_quality_increase<ACTION: ActionImpl + ?Sized>(
+        &self,
+        state: &SimulationState,
+    ) -> u16 {
+        self.rules_override
+            .get(ACTION::ACTION_MASK)
+            .and_then(|action_override| action_override.quality_efficiency)
+            .unwrap_or_else(|| ACTION::base_quality_increase(state, self))
+    }
+
+    /// Durability cost of the action in `state` before Waste Not and conditions are applied,
+    /// taking the rules override into account.
+    pub fn base_durability_cost<ACTION: ActionImpl + ?Sized>(&self, state: &SimulationState) -> i8 {
+        self.rules_override
+            .get(ACTION::ACTION_MASK)
+            .and_then(|action_override| action_override.durability_cost)
+            .unwrap_or_else(|| ACTION::base_durability_cost(state, self))
+    }
+
+    /// CP cost of the action in `state` before conditions are applied, taking the rules
+    /// override into account.
+    pub fn base_cp_cost<ACTION: ActionImpl + ?Sized>(&self, state: &SimulationState) -> i16 {
+        self.rules_override
+            .get(ACTION::ACTION_MASK)
+            .and_then(|action_override| action_override.cp_cost)
+            .unwrap_or_else(|| ACTION::base_cp_cost(state, self))
+    }
 }
 
-use crate::{Action, ActionImpl};
+use crate::{Action, ActionImpl, Condition, RulesOverride, SimulationState};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -90,7 +208,7 @@ impl ActionMask {
     }
 
     pub fn actions(self) -> Box<[Action]> {
-        ALL_ACTIONS
+        Action::ALL
             .iter()
             .copied()
             .filter(|action| ((self.mask >> *action as u64) & 1) != 0)
@@ -98,7 +216,7 @@ impl ActionMask {
     }
 
     pub fn actions_iter(self) -> impl Iterator<Item = Action> {
-        ALL_ACTIONS
+        Action::ALL
             .iter()
             .copied()
             .filter(move |action| ((self.mask >> *action as u64) & 1) != 0)
@@ -117,37 +235,3 @@ macro_rules! action_mask {
         }
     };
 }
-
-const ALL_ACTIONS: &[Action] = &[
-    Action::BasicSynthesis,
-    Action::BasicTouch,
-    Action::MasterMend,
-    Action::Observe,
-    Action::TricksOfTheTrade,
-    Action::WasteNot,
-    Action::Veneration,
-    Action::StandardTouch,
-    Action::GreatStrides,
-    Action::Innovation,
-    Action::WasteNot2,
-    Action::ByregotsBlessing,
-    Action::PreciseTouch,
-    Action::MuscleMemory,
-    Action::CarefulSynthesis,
-    Action::Manipulation,
-    Action::PrudentTouch,
-    Action::TrainedEye,
-    Action::Reflect,
-    Action::PreparatoryTouch,
-    Action::Groundwork,
-    Action::DelicateSynthesis,
-    Action::IntensiveSynthesis,
-    Action::AdvancedTouch,
-    Action::HeartAndSoul,
-    Action::PrudentSynthesis,
-    Action::TrainedFinesse,
-    Action::RefinedTouch,
-    Action::ImmaculateMend,
-    Action::TrainedPerfection,
-    Action::QuickInnovation,
-];
@@ -2,7 +2,34 @@ use crate::actions::*;
 use crate::effects::*;
 use crate::{Condition, Settings};
 
+/// First step of a macro that can't be executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MacroError {
+    /// Zero-based index of the failed action.
+    pub step: usize,
+    pub action: Action,
+    pub reason: &'static str,
+}
+
+/// Numbers of an action used in a specific state, see [`SimulationState::action_effect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActionEffect {
+    /// Progress efficiency in this state, e.g. 180 for Groundwork with low durability.
+    pub progress_efficiency: u16,
+    /// Progress efficiency at the start of the craft, for comparison.
+    pub base_progress_efficiency: u16,
+    /// Quality efficiency in this state, e.g. 300 for Byregot's Blessing at 10 Inner Quiet.
+    pub quality_efficiency: u16,
+    /// Quality efficiency at the start of the craft, for comparison.
+    pub base_quality_efficiency: u16,
+    pub progress_increase: u16,
+    pub quality_increase: u16,
+    pub durability_cost: i8,
+    pub cp_cost: i16,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SimulationState {
     pub cp: i16,
     pub durability: i8,
@@ -55,6 +82,65 @@ impl SimulationState {
         (state, errors)
     }
 
+    /// Like [`Self::from_macro`], but reports which step of the macro failed.
+    /// Useful to check whether a macro still works with different settings, e.g. a lower job level.
+    pub fn validate_macro(settings: &Settings, actions: &[Action]) -> Result<Self, MacroError> {
+        let mut state = Self::new(settings);
+        for (step, action) in actions.iter().enumerate() {
+            state = state
+                .use_action(*action, Condition::Normal, settings)
+                .map_err(|reason| MacroError {
+                    step,
+                    action: *action,
+                    reason,
+                })?;
+        }
+        Ok(state)
+    }
+
+    /// Checks invariants that every state reachable with `settings` must satisfy.
+    /// Meant for catching bugs in action implementations, e.g. effects that are applied with a
+    /// duration that can't happen in-game.
+    pub fn validate(&self, settings: &Settings) -> Result<(), &'static str> {
+        if self.cp < 0 || self.cp > settings.max_cp {
+            return Err("CP out of bounds");
+        }
+        if self.durability > settings.max_durability {
+            return Err("Durability above max durability");
+        }
+        if !settings.adversarial && self.unreliable_quality != 0 {
+            return Err("Unreliable quality in non-adversarial state");
+        }
+        // the longest durations are those of effects applied in Primed condition
+        let effect_bounds = [
+            (self.effects.inner_quiet(), 10),
+            (self.effects.waste_not(), 10),
+            (self.effects.innovation(), 6),
+            (self.effects.veneration(), 6),
+            (self.effects.great_strides(), 5),
+            (self.effects.muscle_memory(), 7),
+            (self.effects.manipulation(), 10),
+            (self.effects.guard(), 2),
+        ];
+        if effect_bounds.iter().any(|(value, bound)| value > bound) {
+            return Err("Effect out of range");
+        }
+        if self.effects.heart_and_soul() != SingleUse::Unavailable
+            && !settings.allowed_actions.has(Action::HeartAndSoul)
+        {
+            return Err("Heart and Soul available but not allowed");
+        }
+        if self.effects.quick_innovation_available()
+            && !settings.allowed_actions.has(Action::QuickInnovation)
+        {
+            return Err("Quick Innovation available but not allowed");
+        }
+        if self.combo == Combo::SynthesisBegin && (self.progress != 0 || self.quality != 0) {
+            return Err("Synthesis begin combo after Progress or Quality was increased");
+        }
+        Ok(())
+    }
+
     pub fn is_final(&self, settings: &Settings) -> bool {
         self.durability <= 0 || self.progress >= settings.max_progress
     }
@@ -89,7 +175,7 @@ impl SimulationState {
 
         A::transform_pre(&mut state, settings, condition);
 
-        if A::base_durability_cost(&state, settings) != 0 {
+        if settings.base_durability_cost::<A>(&state) != 0 {
             state.durability -= A::durability_cost(self, settings, condition);
             if state.effects.trained_perfection() == SingleUse::Active {
                 state.effects.set_trained_perfection(SingleUse::Unavailable);
@@ -98,18 +184,26 @@ impl SimulationState {
 
         state.cp -= A::cp_cost(self, settings, condition);
 
-        let progress_increase = A::progress_increase(self, settings, condition);
+        let success_rate = A::success_rate(settings, condition);
+        let progress_increase = apply_success_rate(
+            A::progress_increase(self, settings, condition),
+            success_rate,
+        );
         state.progress += progress_increase;
         if progress_increase != 0 {
             state.effects.set_muscle_memory(0);
         }
 
-        let quality_increase = A::quality_increase(self, settings, condition);
+        let quality_increase =
+            apply_success_rate(A::quality_increase(self, settings, condition), success_rate);
         if settings.adversarial {
             let adversarial_quality_increase = if state.effects.guard() != 0 {
                 quality_increase
             } else {
-                A::quality_increase(self, settings, Condition::Poor)
+                apply_success_rate(
+                    A::quality_increase(self, settings, Condition::Poor),
+                    success_rate,
+                )
             };
             if state.effects.guard() == 0 && adversarial_quality_increase == 0 {
                 state.unreliable_quality = 0;
@@ -125,7 +219,8 @@ impl SimulationState {
         } else {
             state.quality += quality_increase;
         }
-        if quality_increase != 0 && settings.job_level >= 11 {
+        // effects of a successful action aren't applied to the expected value of an action
+        if quality_increase != 0 && success_rate >= 100 && settings.job_level >= 11 {
             state.effects.set_great_strides(0);
             state
                 .effects
@@ -155,60 +250,36 @@ impl SimulationState {
         Ok(state)
     }
 
-    pub fn use_action(
+    pub fn action_effect_impl<A: ActionImpl>(
         &self,
-        action: Action,
-        condition: Condition,
         settings: &Settings,
-    ) -> Result<Self, &'static str> {
-        match action {
-            Action::BasicSynthesis => self.use_action_impl::<BasicSynthesis>(settings, condition),
-            Action::BasicTouch => self.use_action_impl::<BasicTouch>(settings, condition),
-            Action::MasterMend => self.use_action_impl::<MasterMend>(settings, condition),
-            Action::Observe => self.use_action_impl::<Observe>(settings, condition),
-            Action::TricksOfTheTrade => {
-                self.use_action_impl::<TricksOfTheTrade>(settings, condition)
-            }
-            Action::WasteNot => self.use_action_impl::<WasteNot>(settings, condition),
-            Action::Veneration => self.use_action_impl::<Veneration>(settings, condition),
-            Action::StandardTouch => self.use_action_impl::<StandardTouch>(settings, condition),
-            Action::GreatStrides => self.use_action_impl::<GreatStrides>(settings, condition),
-            Action::Innovation => self.use_action_impl::<Innovation>(settings, condition),
-            Action::WasteNot2 => self.use_action_impl::<WasteNot2>(settings, condition),
-            Action::ByregotsBlessing => {
-                self.use_action_impl::<ByregotsBlessing>(settings, condition)
-            }
-            Action::PreciseTouch => self.use_action_impl::<PreciseTouch>(settings, condition),
-            Action::MuscleMemory => self.use_action_impl::<MuscleMemory>(settings, condition),
-            Action::CarefulSynthesis => {
-                self.use_action_impl::<CarefulSynthesis>(settings, condition)
-            }
-            Action::Manipulation => self.use_action_impl::<Manipulation>(settings, condition),
-            Action::PrudentTouch => self.use_action_impl::<PrudentTouch>(settings, condition),
-            Action::AdvancedTouch => self.use_action_impl::<AdvancedTouch>(settings, condition),
-            Action::Reflect => self.use_action_impl::<Reflect>(settings, condition),
-            Action::PreparatoryTouch => {
-                self.use_action_impl::<PreparatoryTouch>(settings, condition)
-            }
-            Action::Groundwork => self.use_action_impl::<Groundwork>(settings, condition),
-            Action::DelicateSynthesis => {
-                self.use_action_impl::<DelicateSynthesis>(settings, condition)
-            }
-            Action::IntensiveSynthesis => {
-                self.use_action_impl::<IntensiveSynthesis>(settings, condition)
-            }
-            Action::TrainedEye => self.use_action_impl::<TrainedEye>(settings, condition),
-            Action::HeartAndSoul => self.use_action_impl::<HeartAndSoul>(settings, condition),
-            Action::PrudentSynthesis => {
-                self.use_action_impl::<PrudentSynthesis>(settings, condition)
-            }
-            Action::TrainedFinesse => self.use_action_impl::<TrainedFinesse>(settings, condition),
-            Action::RefinedTouch => self.use_action_impl::<RefinedTouch>(settings, condition),
-            Action::QuickInnovation => self.use_action_impl::<QuickInnovation>(settings, condition),
-            Action::ImmaculateMend => self.use_action_impl::<ImmaculateMend>(settings, condition),
-            Action::TrainedPerfection => {
-                self.use_action_impl::<TrainedPerfection>(settings, condition)
-            }
+        condition: Condition,
+    ) -> ActionEffect {
+        let initial_state = Self::new(settings);
+        let durability_cost = match settings.base_durability_cost::<A>(self) {
+            0 => 0,
+            _ => A::durability_cost(self, settings, condition),
+        };
+        ActionEffect {
+            progress_efficiency: settings.base_progress_increase::<A>(self),
+            base_progress_efficiency: settings.base_progress_increase::<A>(&initial_state),
+            quality_efficiency: settings.base_quality_increase::<A>(self),
+            base_quality_efficiency: settings.base_quality_increase::<A>(&initial_state),
+            progress_increase: apply_success_rate(
+                A::progress_increase(self, settings, condition),
+                A::success_rate(settings, condition),
+            ),
+            quality_increase: apply_success_rate(
+                A::quality_increase(self, settings, condition),
+                A::success_rate(settings, condition),
+            ),
+            durability_cost,
+            cp_cost: A::cp_cost(self, settings, condition),
         }
     }
 }
+
+/// Increase of an action that succeeds with `success_rate` percent, rounded down.
+fn apply_success_rate(increase: u16, success_rate: u8) -> u16 {
+    (u32::from(increase) * u32::from(success_rate) / 100) as u16
+}
@@ -10,6 +10,8 @@ const SETTINGS: Settings = Settings {
     job_level: 100,
     allowed_actions: ActionMask::all(),
     adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
 };
 
 /// Returns the 4 primary stats of a state:
@@ -735,3 +737,161 @@ fn test_quick_innovation() {
         Err("Quick Innovation cannot be used while Innovation is active.")
     );
 }
+
+#[test]
+fn test_rapid_synthesis() {
+    let use_rapid_synthesis = |settings: &Settings, condition: Condition| {
+        let state = SimulationState::new(settings)
+            .use_action(Action::RapidSynthesis, condition, settings)
+            .unwrap();
+        primary_stats(&state, settings)
+    };
+    assert_eq!(
+        use_rapid_synthesis(&SETTINGS, Condition::Normal),
+        (0, 0, 10, 0)
+    );
+    let settings = Settings {
+        success_model: SuccessModel::AssumeSuccess,
+        ..SETTINGS
+    };
+    assert_eq!(
+        use_rapid_synthesis(&settings, Condition::Normal),
+        (500, 0, 10, 0)
+    );
+    // Potency-increase trait not unlocked
+    let settings = Settings {
+        job_level: 62,
+        success_model: SuccessModel::AssumeSuccess,
+        ..SETTINGS
+    };
+    assert_eq!(
+        use_rapid_synthesis(&settings, Condition::Normal),
+        (250, 0, 10, 0)
+    );
+    // Centered condition increases the success rate from 50% to 75%
+    let settings = Settings {
+        success_model: SuccessModel::ExpectedValue,
+        ..SETTINGS
+    };
+    assert_eq!(
+        use_rapid_synthesis(&settings, Condition::Normal),
+        (250, 0, 10, 0)
+    );
+    assert_eq!(
+        use_rapid_synthesis(&settings, Condition::Centered),
+        (375, 0, 10, 0)
+    );
+}
+
+#[test]
+fn test_hasty_touch() {
+    let state = SimulationState::new(&SETTINGS)
+        .use_action(Action::HastyTouch, Condition::Normal, &SETTINGS)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &SETTINGS), (0, 0, 10, 0));
+    assert_eq!(state.effects.inner_quiet(), 0);
+    assert_eq!(state.combo, Combo::None);
+    let settings = Settings {
+        success_model: SuccessModel::AssumeSuccess,
+        ..SETTINGS
+    };
+    let state = SimulationState::new(&settings)
+        .use_action(Action::HastyTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &settings), (0, 100, 10, 0));
+    assert_eq!(state.effects.inner_quiet(), 1);
+    assert_eq!(state.combo, Combo::HastyTouch);
+    // Effects of a successful action are not applied to the expected value
+    let settings = Settings {
+        success_model: SuccessModel::ExpectedValue,
+        ..SETTINGS
+    };
+    let state = SimulationState::new(&settings)
+        .use_action(Action::HastyTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &settings), (0, 60, 10, 0));
+    assert_eq!(state.effects.inner_quiet(), 0);
+    assert_eq!(state.combo, Combo::None);
+}
+
+#[test]
+fn test_daring_touch() {
+    let settings = Settings {
+        success_model: SuccessModel::AssumeSuccess,
+        ..SETTINGS
+    };
+    let state = SimulationState::from_macro(&settings, &[Action::DaringTouch]);
+    assert_eq!(
+        state,
+        Err("Daring Touch can only be used after a successful Hasty Touch.")
+    );
+    let state =
+        SimulationState::from_macro(&settings, &[Action::HastyTouch, Action::DaringTouch]).unwrap();
+    assert_eq!(primary_stats(&state, &settings), (0, 265, 20, 0));
+    assert_eq!(state.effects.inner_quiet(), 2);
+    // Hasty Touch never succeeds, so Daring Touch can't be used
+    let state = SimulationState::from_macro(&SETTINGS, &[Action::HastyTouch, Action::DaringTouch]);
+    assert_eq!(
+        state,
+        Err("Daring Touch can only be used after a successful Hasty Touch.")
+    );
+}
+
+#[test]
+fn test_rules_override() {
+    let rules_override = RulesOverride::new(&[ActionOverride {
+        quality_efficiency: Some(150),
+        cp_cost: Some(30),
+        durability_cost: Some(20),
+        ..ActionOverride::new(Action::BasicTouch)
+    }])
+    .unwrap();
+    let settings = Settings {
+        rules_override,
+        ..SETTINGS
+    };
+    let state = SimulationState::new(&settings)
+        .use_action(Action::BasicTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &settings), (0, 150, 20, 30));
+    // Waste Not and conditions apply to the overridden costs
+    let initial_state = SimulationState {
+        effects: Effects::new().with_waste_not(4),
+        ..SimulationState::new(&settings)
+    };
+    let state = initial_state
+        .use_action(Action::BasicTouch, Condition::Pliant, &settings)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &settings), (0, 150, 10, 15));
+    // Other actions are unaffected
+    let state = SimulationState::new(&settings)
+        .use_action(Action::PrudentTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &settings), (0, 100, 5, 25));
+}
+
+#[test]
+fn test_invalid_rules_override() {
+    let basic_touch = ActionOverride::new(Action::BasicTouch);
+    assert!(RulesOverride::new(&[basic_touch, basic_touch]).is_err());
+    let odd_durability_cost = ActionOverride {
+        durability_cost: Some(7),
+        ..basic_touch
+    };
+    assert!(RulesOverride::new(&[odd_durability_cost]).is_err());
+    let too_many = [basic_touch; MAX_ACTION_OVERRIDES + 1];
+    assert!(RulesOverride::new(&too_many).is_err());
+}
+
+#[test]
+fn test_level_requirement() {
+    for action in ActionMask::all().actions_iter() {
+        let settings = Settings {
+            job_level: action.level_requirement() - 1,
+            ..SETTINGS
+        };
+        let result =
+            SimulationState::new(&settings).use_action(action, Condition::Normal, &settings);
+        assert_eq!(result.err(), Some("Level not high enough"), "{action:?}");
+    }
+}
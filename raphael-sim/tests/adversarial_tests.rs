@@ -1,4 +1,6 @@
-use raphael_sim::{Action, ActionMask, Condition, Settings, SimulationState};
+use raphael_sim::{
+    Action, ActionMask, Condition, RulesOverride, Settings, SimulationState, SuccessModel,
+};
 const SETTINGS: Settings = Settings {
     max_cp: 1000,
     max_durability: 80,
@@ -9,6 +11,8 @@ const SETTINGS: Settings = Settings {
     job_level: 100,
     allowed_actions: ActionMask::all(),
     adversarial: true,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
 };
 
 /// Calculate the minimum achievable Quality across all possible Condition rolls
@@ -0,0 +1,126 @@
+use raphael_sim::*;
+
+const SETTINGS: Settings = Settings {
+    max_cp: 250,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 40000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 100,
+    allowed_actions: ActionMask::all(),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+/// Returns the 4 primary stats of a state:
+/// - Progress
+/// - Quality
+/// - Durability (used)
+/// - CP (used)
+fn primary_stats(state: &SimulationState, settings: &Settings) -> (u16, u16, i8, i16) {
+    (
+        state.progress,
+        state.quality,
+        settings.max_durability - state.durability,
+        settings.max_cp - state.cp,
+    )
+}
+
+fn use_action(action: Action, condition: Condition) -> SimulationState {
+    SimulationState::new(&SETTINGS)
+        .use_action(action, condition, &SETTINGS)
+        .unwrap()
+}
+
+#[test]
+fn test_centered_and_good_omen() {
+    for condition in [Condition::Centered, Condition::GoodOmen] {
+        for action in [
+            Action::BasicSynthesis,
+            Action::BasicTouch,
+            Action::Innovation,
+        ] {
+            assert_eq!(
+                use_action(action, condition),
+                use_action(action, Condition::Normal)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_sturdy() {
+    let state = use_action(Action::BasicSynthesis, Condition::Sturdy);
+    assert_eq!(primary_stats(&state, &SETTINGS), (120, 0, 5, 0));
+    // stacks with Waste Not, rounded up
+    let state = use_action(Action::WasteNot, Condition::Normal)
+        .use_action(Action::BasicSynthesis, Condition::Sturdy, &SETTINGS)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &SETTINGS), (120, 0, 3, 56));
+}
+
+#[test]
+fn test_pliant() {
+    let state = use_action(Action::Manipulation, Condition::Pliant);
+    assert_eq!(primary_stats(&state, &SETTINGS), (0, 0, 0, 48));
+    // rounded up
+    let state = use_action(Action::PrudentTouch, Condition::Pliant);
+    assert_eq!(primary_stats(&state, &SETTINGS), (0, 100, 5, 13));
+}
+
+#[test]
+fn test_malleable() {
+    let state = use_action(Action::BasicSynthesis, Condition::Malleable);
+    assert_eq!(primary_stats(&state, &SETTINGS), (180, 0, 10, 0));
+    // doesn't affect Quality
+    let state = use_action(Action::BasicTouch, Condition::Malleable);
+    assert_eq!(primary_stats(&state, &SETTINGS), (0, 100, 10, 18));
+}
+
+#[test]
+fn test_primed() {
+    let state = use_action(Action::Innovation, Condition::Primed);
+    assert_eq!(state.effects.innovation(), 6);
+    let state = use_action(Action::Manipulation, Condition::Primed);
+    assert_eq!(state.effects.manipulation(), 10);
+    let state = use_action(Action::WasteNot2, Condition::Primed);
+    assert_eq!(state.effects.waste_not(), 10);
+    let state = use_action(Action::MuscleMemory, Condition::Primed);
+    assert_eq!(state.effects.muscle_memory(), 7);
+}
+
+#[test]
+fn test_condition_pool() {
+    let pool = ConditionPool::from_flags(483);
+    assert!(pool.has_expert_conditions());
+    assert_eq!(
+        pool.conditions().collect::<Vec<_>>(),
+        [
+            Condition::Normal,
+            Condition::Good,
+            Condition::Sturdy,
+            Condition::Pliant,
+            Condition::Malleable,
+            Condition::Primed,
+        ]
+    );
+    assert!(!ConditionPool::NORMAL.has_expert_conditions());
+    assert_eq!(ConditionPool::default(), ConditionPool::NORMAL);
+}
+
+#[test]
+fn test_good_omen_is_followed_by_good() {
+    let pool = ConditionPool::from_flags(995);
+    assert_eq!(
+        pool.next_condition_probabilities(Condition::GoodOmen),
+        [(Condition::Good, 1.0)]
+    );
+    let probabilities = pool.next_condition_probabilities(Condition::Normal);
+    let total: f32 = probabilities
+        .iter()
+        .map(|(_, probability)| probability)
+        .sum();
+    assert!((total - 1.0).abs() < 1e-4);
+}
@@ -10,6 +10,8 @@ const SETTINGS: Settings = Settings {
     job_level: 100,
     allowed_actions: ActionMask::all(),
     adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
 };
 
 /// Returns the 4 primary stats of a state:
@@ -44,3 +46,29 @@ fn test_trained_perfection() {
         .unwrap();
     assert_eq!(state.effects.trained_perfection(), SingleUse::Active);
 }
+
+#[test]
+fn test_active_effects() {
+    let state = SimulationState::new(&SETTINGS);
+    assert_eq!(state.effects.active().count(), 0);
+    let state = SimulationState::from_macro(
+        &SETTINGS,
+        &[
+            Action::Reflect,
+            Action::Innovation,
+            Action::TrainedPerfection,
+            Action::Manipulation,
+        ],
+    )
+    .unwrap();
+    let effects: Vec<_> = state.effects.active().collect();
+    assert_eq!(
+        effects,
+        [
+            (Effect::InnerQuiet, 2),
+            (Effect::Innovation, 2),
+            (Effect::Manipulation, 8),
+            (Effect::TrainedPerfection, 0)
+        ]
+    );
+}
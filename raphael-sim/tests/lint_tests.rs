@@ -0,0 +1,78 @@
+use raphael_sim::lint::{LintFinding, Linter, Severity};
+use raphael_sim::{Action, ActionMask, RulesOverride, Settings, SuccessModel};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 300,
+    max_durability: 30,
+    max_progress: 2000,
+    max_quality: 2000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 100,
+    allowed_actions: ActionMask::all(),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+#[test]
+fn test_reduced_efficiency() {
+    let actions = [Action::Groundwork, Action::Groundwork];
+    let findings = Linter::default().lint(&SETTINGS, &actions);
+    assert_eq!(
+        findings,
+        [LintFinding {
+            step: 1,
+            action: Action::Groundwork,
+            rule: "reduced-efficiency",
+            severity: Severity::Warning,
+            message: "Progress efficiency reduced from 360% to 180% (Durability 10 is lower than the durability cost 20)".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_buff_overwritten() {
+    let settings = Settings {
+        max_durability: 80,
+        ..SETTINGS
+    };
+    let actions = [
+        Action::Innovation,
+        Action::BasicTouch,
+        Action::Innovation,
+        Action::BasicTouch,
+        Action::BasicTouch,
+        Action::BasicTouch,
+        Action::Innovation,
+    ];
+    let findings = Linter::default().lint(&settings, &actions);
+    let steps: Vec<_> = findings
+        .iter()
+        .map(|finding| (finding.step, finding.rule))
+        .collect();
+    // Innovation at step 6 reapplies the buff on its last step, which is fine
+    assert_eq!(steps, [(2, "buff-overwritten")]);
+}
+
+#[test]
+fn test_invalid_action() {
+    let actions = [Action::ByregotsBlessing, Action::BasicSynthesis];
+    let findings = Linter::default().lint(&SETTINGS, &actions);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].step, 0);
+    assert_eq!(findings[0].rule, "invalid-action");
+    assert_eq!(findings[0].severity, Severity::Error);
+}
+
+#[test]
+fn test_configure_rules() {
+    let actions = [Action::Groundwork, Action::Groundwork];
+    let mut linter = Linter::default();
+    linter.set_severity("reduced-efficiency", Severity::Info);
+    let findings = linter.lint(&SETTINGS, &actions);
+    assert_eq!(findings[0].severity, Severity::Info);
+    linter.disable("reduced-efficiency");
+    assert!(linter.lint(&SETTINGS, &actions).is_empty());
+    assert!(linter.rules().all(|(rule, _)| rule != "reduced-efficiency"));
+}
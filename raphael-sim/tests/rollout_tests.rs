@@ -0,0 +1,80 @@
+use raphael_sim::{
+    Action, ActionMask, RulesOverride, Settings, SimulationState, SuccessModel,
+    quality_probability_distribution, rollout_batch,
+};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 500,
+    max_durability: 80,
+    max_progress: 1000,
+    max_quality: 40000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 100,
+    allowed_actions: ActionMask::all(),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+const ACTIONS: [Action; 9] = [
+    Action::Veneration,
+    Action::Innovation,
+    Action::BasicTouch,
+    Action::StandardTouch,
+    Action::AdvancedTouch,
+    Action::ByregotsBlessing,
+    Action::Veneration,
+    Action::Groundwork,
+    Action::Groundwork,
+];
+
+#[test]
+fn test_rollouts_are_reproducible() {
+    let initial_state = SimulationState::new(&SETTINGS);
+    let results = rollout_batch(&SETTINGS, initial_state, &ACTIONS, 42, 100);
+    assert_eq!(results.len(), 100);
+    assert_eq!(
+        results,
+        rollout_batch(&SETTINGS, initial_state, &ACTIONS, 42, 100)
+    );
+    assert_ne!(
+        results,
+        rollout_batch(&SETTINGS, initial_state, &ACTIONS, 43, 100)
+    );
+}
+
+#[test]
+fn test_rollouts_match_quality_distribution() {
+    let initial_state = SimulationState::new(&SETTINGS);
+    let results = rollout_batch(&SETTINGS, initial_state, &ACTIONS, 0, 20000);
+    assert!(results.iter().all(|result| result.finished));
+
+    // the first action doesn't increase Quality, so the condition of the first step is irrelevant
+    let distribution = quality_probability_distribution(SETTINGS, ACTIONS, 0);
+    let normal_quality = SimulationState::from_macro(&SETTINGS, &ACTIONS)
+        .unwrap()
+        .quality;
+    for quality in [normal_quality, normal_quality + 1] {
+        let expected = distribution.at_least(quality);
+        let actual = results
+            .iter()
+            .filter(|result| result.quality >= quality)
+            .count() as f32
+            / results.len() as f32;
+        assert!(
+            (expected - actual).abs() < 0.02,
+            "Quality {quality}: expected {expected}, got {actual}"
+        );
+    }
+}
+
+#[test]
+fn test_rollouts_with_failing_actions() {
+    let actions = [Action::RapidSynthesis; 4];
+    let initial_state = SimulationState::new(&SETTINGS);
+    let results = rollout_batch(&SETTINGS, initial_state, &actions, 0, 1000);
+    // some of the actions succeed and some fail, regardless of the success model of the settings
+    assert!(results.iter().any(|result| result.progress == 0));
+    assert!(results.iter().any(|result| result.progress != 0));
+}
@@ -1,4 +1,7 @@
-use raphael_sim::{Action, ActionMask, Condition, Settings, SimulationState};
+use raphael_sim::{
+    Action, ActionEffect, ActionMask, ActionOverride, Condition, MacroError, RulesOverride,
+    Settings, SimulationState, SuccessModel,
+};
 
 fn simulate(
     settings: &Settings,
@@ -35,6 +38,8 @@ fn test_level_requirement() {
         job_level: 50,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let error = SimulationState::new(&settings)
         .use_action(Action::ImmaculateMend, Condition::Normal, &settings)
@@ -42,6 +47,146 @@ fn test_level_requirement() {
     assert_eq!(error, "Level not high enough");
 }
 
+#[test]
+/// Validating a macro should report the first step that can't be used
+fn test_validate_macro() {
+    let settings = Settings {
+        max_cp: 200,
+        max_durability: 60,
+        max_progress: 300,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 50,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let actions = [
+        Action::BasicTouch,
+        Action::BasicSynthesis,
+        Action::Manipulation,
+        Action::BasicSynthesis,
+    ];
+    let error = SimulationState::validate_macro(&settings, &actions).unwrap_err();
+    assert_eq!(
+        error,
+        MacroError {
+            step: 2,
+            action: Action::Manipulation,
+            reason: "Level not high enough",
+        }
+    );
+    let settings = Settings {
+        job_level: 90,
+        ..settings
+    };
+    let state = SimulationState::validate_macro(&settings, &actions).unwrap();
+    assert_eq!(state.progress, 240);
+}
+
+#[test]
+/// Settings that would make the solvers panic or loop should be rejected
+fn test_validate_settings() {
+    let settings = Settings {
+        max_cp: 200,
+        max_durability: 60,
+        max_progress: 300,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    assert_eq!(settings.validate(), Ok(()));
+    let invalid_settings = [
+        Settings {
+            max_cp: -1,
+            ..settings
+        },
+        Settings {
+            max_durability: 0,
+            ..settings
+        },
+        Settings {
+            max_durability: 62,
+            ..settings
+        },
+        Settings {
+            base_progress: 0,
+            ..settings
+        },
+        Settings {
+            base_quality: 0,
+            ..settings
+        },
+    ];
+    for settings in invalid_settings {
+        assert!(settings.validate().is_err(), "{settings:?}");
+    }
+    let free_master_mend = RulesOverride::new(&[ActionOverride {
+        cp_cost: Some(0),
+        ..ActionOverride::new(Action::MasterMend)
+    }])
+    .unwrap();
+    let settings_with_override = Settings {
+        rules_override: free_master_mend,
+        ..settings
+    };
+    assert!(settings_with_override.validate().is_err());
+    // Trained Perfection is free in-game
+    let free_trained_perfection = RulesOverride::new(&[ActionOverride {
+        cp_cost: Some(0),
+        ..ActionOverride::new(Action::TrainedPerfection)
+    }])
+    .unwrap();
+    let settings_with_override = Settings {
+        rules_override: free_trained_perfection,
+        ..settings
+    };
+    assert_eq!(settings_with_override.validate(), Ok(()));
+}
+
+#[test]
+/// Groundwork is only half as effective when the durability cost exceeds the remaining durability
+fn test_action_effect() {
+    let settings = Settings {
+        max_cp: 200,
+        max_durability: 30,
+        max_progress: 1000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let state = SimulationState::from_macro(&settings, &[Action::Groundwork]).unwrap();
+    assert_eq!(state.durability, 10);
+    assert_eq!(
+        state.action_effect(Action::Groundwork, Condition::Normal, &settings),
+        ActionEffect {
+            progress_efficiency: 180,
+            base_progress_efficiency: 360,
+            quality_efficiency: 0,
+            base_quality_efficiency: 0,
+            progress_increase: 180,
+            quality_increase: 0,
+            durability_cost: 20,
+            cp_cost: 18,
+        }
+    );
+    let effect = state.action_effect(Action::BasicTouch, Condition::Good, &settings);
+    assert_eq!(effect.quality_efficiency, 100);
+    assert_eq!(effect.quality_increase, 150);
+}
+
 #[test]
 fn test_random_926ae85b() {
     // Copper Gorget
@@ -56,6 +201,8 @@ fn test_random_926ae85b() {
         job_level: 10,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = [
         Action::BasicSynthesis,
@@ -84,6 +231,8 @@ fn test_random_3c721e47() {
         job_level: 85,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = [
         Action::MuscleMemory,
@@ -116,6 +265,8 @@ fn test_random_3ba90d3a() {
         job_level: 81,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = [
         Action::Veneration,
@@ -151,6 +302,8 @@ fn test_random_bce2650c() {
         job_level: 90,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = [
         Action::MuscleMemory,
@@ -201,6 +354,8 @@ fn test_ingame_be9fc5c2() {
         job_level: 90,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let states = simulate(
         &settings,
@@ -272,6 +427,8 @@ fn test_ingame_d11d9c68() {
         job_level: 94,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = [
         Action::Reflect,
@@ -327,6 +484,8 @@ fn test_ingame_f9f0dac7() {
         job_level: 100,
         allowed_actions: ActionMask::all(),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = [
         Action::Reflect,
@@ -413,6 +572,8 @@ fn test_ingame_4866545e() {
         job_level: 100,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = [
         Action::Reflect,
@@ -463,3 +624,62 @@ fn test_ingame_4866545e() {
         ]
     );
 }
+
+#[test]
+fn test_validate() {
+    let settings = Settings {
+        max_cp: 540,
+        max_durability: 35,
+        max_progress: 4125,
+        max_quality: 12000,
+        base_progress: 282,
+        base_quality: 256,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let actions = [
+        Action::MuscleMemory,
+        Action::Manipulation,
+        Action::Veneration,
+        Action::WasteNot,
+        Action::Groundwork,
+        Action::Innovation,
+        Action::PreparatoryTouch,
+        Action::GreatStrides,
+        Action::ByregotsBlessing,
+    ];
+    let initial_state = SimulationState::new(&settings);
+    assert_eq!(initial_state.validate(&settings), Ok(()));
+    for state in simulate_normal(&settings, actions.into_iter()) {
+        assert_eq!(state.validate(&settings), Ok(()));
+    }
+    for condition in [Condition::Primed, Condition::Good, Condition::Pliant] {
+        let state = initial_state
+            .use_action(Action::Manipulation, condition, &settings)
+            .unwrap();
+        assert_eq!(state.validate(&settings), Ok(()));
+    }
+
+    let too_much_cp = SimulationState {
+        cp: settings.max_cp + 1,
+        ..initial_state
+    };
+    assert_eq!(too_much_cp.validate(&settings), Err("CP out of bounds"));
+    let mut too_much_innovation = initial_state;
+    too_much_innovation.effects.set_innovation(7);
+    assert_eq!(
+        too_much_innovation.validate(&settings),
+        Err("Effect out of range")
+    );
+    let unreliable_quality = SimulationState {
+        unreliable_quality: 100,
+        ..initial_state
+    };
+    assert_eq!(
+        unreliable_quality.validate(&settings),
+        Err("Unreliable quality in non-adversarial state")
+    );
+}
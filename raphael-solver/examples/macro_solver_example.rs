@@ -1,4 +1,4 @@
-use raphael_sim::{Action, ActionMask, Settings, SimulationState};
+use raphael_sim::{Action, ActionMask, RulesOverride, Settings, SimulationState, SuccessModel};
 use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
 
 fn main() {
@@ -28,12 +28,16 @@ fn main() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
 
     let solver_settings = SolverSettings {
         simulator_settings,
         backload_progress: false,
         allow_unsound_branch_pruning: false,
+        forbid_quality_during_muscle_memory: false,
+        first_quality_step: 0,
     };
 
     let mut solver = MacroSolver::new(
@@ -3,6 +3,7 @@ use raphael_sim::*;
 use crate::SolverSettings;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ActionCombo {
     TricksOfTheTrade,   // Heart and Soul + Tricks of the Trade
     IntensiveSynthesis, // Heart and Soul + Intensive Synthesis
@@ -11,6 +12,7 @@ pub enum ActionCombo {
     AdvancedTouch,      // Basic Touch + Standard Touch + Advanced Touch
     FocusedTouch,       // Observe + AdvancedTouch
     RefinedTouch,       // Basic Touch + Refined Touch
+    DaringTouch,        // Hasty Touch + Daring Touch
     Single(Action),
 }
 
@@ -28,39 +30,8 @@ impl ActionCombo {
             ],
             Self::FocusedTouch => &[Action::Observe, Action::AdvancedTouch],
             Self::RefinedTouch => &[Action::BasicTouch, Action::RefinedTouch],
-            Self::Single(action) => match action {
-                Action::BasicSynthesis => &[Action::BasicSynthesis],
-                Action::BasicTouch => &[Action::BasicTouch],
-                Action::MasterMend => &[Action::MasterMend],
-                Action::Observe => &[Action::Observe],
-                Action::TricksOfTheTrade => &[Action::TricksOfTheTrade],
-                Action::WasteNot => &[Action::WasteNot],
-                Action::Veneration => &[Action::Veneration],
-                Action::StandardTouch => &[Action::StandardTouch],
-                Action::GreatStrides => &[Action::GreatStrides],
-                Action::Innovation => &[Action::Innovation],
-                Action::WasteNot2 => &[Action::WasteNot2],
-                Action::ByregotsBlessing => &[Action::ByregotsBlessing],
-                Action::PreciseTouch => &[Action::PreciseTouch],
-                Action::MuscleMemory => &[Action::MuscleMemory],
-                Action::CarefulSynthesis => &[Action::CarefulSynthesis],
-                Action::Manipulation => &[Action::Manipulation],
-                Action::PrudentTouch => &[Action::PrudentTouch],
-                Action::AdvancedTouch => &[Action::AdvancedTouch],
-                Action::Reflect => &[Action::Reflect],
-                Action::PreparatoryTouch => &[Action::PreparatoryTouch],
-                Action::Groundwork => &[Action::Groundwork],
-                Action::DelicateSynthesis => &[Action::DelicateSynthesis],
-                Action::IntensiveSynthesis => &[Action::IntensiveSynthesis],
-                Action::TrainedEye => &[Action::TrainedEye],
-                Action::HeartAndSoul => &[Action::HeartAndSoul],
-                Action::PrudentSynthesis => &[Action::PrudentSynthesis],
-                Action::TrainedFinesse => &[Action::TrainedFinesse],
-                Action::RefinedTouch => &[Action::RefinedTouch],
-                Action::QuickInnovation => &[Action::QuickInnovation],
-                Action::ImmaculateMend => &[Action::ImmaculateMend],
-                Action::TrainedPerfection => &[Action::TrainedPerfection],
-            },
+            Self::DaringTouch => &[Action::HastyTouch, Action::DaringTouch],
+            Self::Single(action) => action.as_slice(),
         }
     }
 
@@ -81,6 +52,7 @@ pub const FULL_SEARCH_ACTIONS: &[ActionCombo] = &[
     ActionCombo::StandardTouch,
     ActionCombo::FocusedTouch,
     ActionCombo::RefinedTouch,
+    ActionCombo::DaringTouch,
     // progress
     ActionCombo::Single(Action::BasicSynthesis),
     ActionCombo::Single(Action::Veneration),
@@ -88,6 +60,7 @@ pub const FULL_SEARCH_ACTIONS: &[ActionCombo] = &[
     ActionCombo::Single(Action::CarefulSynthesis),
     ActionCombo::Single(Action::Groundwork),
     ActionCombo::Single(Action::PrudentSynthesis),
+    ActionCombo::Single(Action::RapidSynthesis),
     // quality
     ActionCombo::Single(Action::BasicTouch),
     ActionCombo::Single(Action::StandardTouch),
@@ -101,6 +74,7 @@ pub const FULL_SEARCH_ACTIONS: &[ActionCombo] = &[
     ActionCombo::Single(Action::TrainedFinesse),
     ActionCombo::Single(Action::TrainedEye),
     ActionCombo::Single(Action::QuickInnovation),
+    ActionCombo::Single(Action::HastyTouch),
     // durability
     ActionCombo::Single(Action::MasterMend),
     ActionCombo::Single(Action::WasteNot),
@@ -122,6 +96,7 @@ pub const PROGRESS_ONLY_SEARCH_ACTIONS: &[ActionCombo] = &[
     ActionCombo::Single(Action::CarefulSynthesis),
     ActionCombo::Single(Action::Groundwork),
     ActionCombo::Single(Action::PrudentSynthesis),
+    ActionCombo::Single(Action::RapidSynthesis),
     // durability
     ActionCombo::Single(Action::MasterMend),
     ActionCombo::Single(Action::WasteNot),
@@ -138,6 +113,7 @@ pub const QUALITY_ONLY_SEARCH_ACTIONS: &[ActionCombo] = &[
     ActionCombo::AdvancedTouch,
     ActionCombo::FocusedTouch,
     ActionCombo::RefinedTouch,
+    ActionCombo::DaringTouch,
     // quality
     ActionCombo::Single(Action::BasicTouch),
     ActionCombo::Single(Action::StandardTouch),
@@ -151,6 +127,7 @@ pub const QUALITY_ONLY_SEARCH_ACTIONS: &[ActionCombo] = &[
     ActionCombo::Single(Action::TrainedFinesse),
     ActionCombo::Single(Action::TrainedEye),
     ActionCombo::Single(Action::QuickInnovation),
+    ActionCombo::Single(Action::HastyTouch),
     // durability
     ActionCombo::Single(Action::MasterMend),
     ActionCombo::Single(Action::WasteNot),
@@ -160,6 +137,62 @@ pub const QUALITY_ONLY_SEARCH_ACTIONS: &[ActionCombo] = &[
     ActionCombo::Single(Action::TrainedPerfection),
 ];
 
+/// Actions that can only be used in Good or Excellent condition without Heart and Soul.
+pub const CONDITION_ACTIONS: &[Action] = &[
+    Action::TricksOfTheTrade,
+    Action::IntensiveSynthesis,
+    Action::PreciseTouch,
+];
+
+/// Actions that only succeed with a certain chance.
+const UNRELIABLE_ACTIONS: ActionMask = ActionMask::none()
+    .add(Action::RapidSynthesis)
+    .add(Action::HastyTouch)
+    .add(Action::DaringTouch);
+
+/// Whether the action only succeeds with a certain chance and the success model assumes that
+/// it always fails. The solvers never use such actions, because they would only waste
+/// durability.
+pub fn is_assumed_to_fail(settings: &SolverSettings, action: Action) -> bool {
+    settings.simulator_settings.success_model == SuccessModel::AssumeFail
+        && UNRELIABLE_ACTIONS.has(action)
+}
+
+/// Actions that increase Quality, see [`SolverSettings::forbid_quality_during_muscle_memory`]
+/// and [`SolverSettings::first_quality_step`].
+const QUALITY_ACTIONS: ActionMask = ActionMask::none()
+    .add(Action::BasicTouch)
+    .add(Action::StandardTouch)
+    .add(Action::AdvancedTouch)
+    .add(Action::ByregotsBlessing)
+    .add(Action::PreciseTouch)
+    .add(Action::PrudentTouch)
+    .add(Action::Reflect)
+    .add(Action::PreparatoryTouch)
+    .add(Action::DelicateSynthesis)
+    .add(Action::TrainedEye)
+    .add(Action::TrainedFinesse)
+    .add(Action::RefinedTouch)
+    .add(Action::HastyTouch)
+    .add(Action::DaringTouch);
+
+/// Whether the combo uses an action that increases Quality before
+/// [`SolverSettings::first_quality_step`], given the steps already used before the combo.
+pub fn uses_quality_too_early(
+    settings: &SolverSettings,
+    current_steps: u8,
+    action_combo: ActionCombo,
+) -> bool {
+    action_combo
+        .actions()
+        .iter()
+        .enumerate()
+        .any(|(index, action)| {
+            QUALITY_ACTIONS.has(*action)
+                && usize::from(current_steps) + index < usize::from(settings.first_quality_step)
+        })
+}
+
 pub fn is_progress_only_state(settings: &SolverSettings, state: &SimulationState) -> bool {
     if settings.backload_progress && state.progress != 0 {
         return true;
@@ -182,6 +215,15 @@ pub fn use_action_combo(
     action_combo: ActionCombo,
 ) -> Result<SimulationState, &'static str> {
     for action in action_combo.actions() {
+        if is_assumed_to_fail(settings, *action) {
+            return Err("Actions that are assumed to fail are never used.");
+        }
+        if settings.forbid_quality_during_muscle_memory
+            && state.effects.muscle_memory() != 0
+            && QUALITY_ACTIONS.has(*action)
+        {
+            return Err("Quality actions can't be used while Muscle Memory is active.");
+        }
         state = state.use_action(*action, Condition::Normal, &settings.simulator_settings)?;
     }
     if is_progress_only_state(settings, &state) {
@@ -194,5 +236,9 @@ pub fn use_action_combo(
         state.effects.set_quick_innovation_available(false);
     }
     state.combo = Combo::None;
+    #[cfg(feature = "validate-states")]
+    if let Err(error) = state.validate(&settings.simulator_settings) {
+        panic!("{action_combo:?} resulted in an invalid state ({error}): {state:?}");
+    }
     Ok(state)
 }
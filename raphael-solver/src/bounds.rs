@@ -0,0 +1,132 @@
+use std::num::NonZeroU8;
+
+use raphael_sim::*;
+
+use crate::actions::{FULL_SEARCH_ACTIONS, use_action_combo};
+use crate::utils::{AtomicFlag, MemoryBudget};
+use crate::{
+    FinishSolver, QualityUpperBoundSolver, SolverException, SolverSettings, StepLowerBoundSolver,
+};
+
+/// Bounds on the best rotation for a configuration, evaluated without searching for the rotation itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CraftBounds {
+    /// Upper bound on the Quality achievable while also maxing out Progress.
+    pub quality_upper_bound: u16,
+    /// Lower bound on the number of steps needed to max out both Progress and Quality.
+    /// `None` if the Quality upper bound is below the max Quality.
+    pub steps_lower_bound: Option<u8>,
+}
+
+/// Evaluates the Quality upper bound and steps lower bound of the initial state.
+/// Returns `SolverException::NoSolution` if it is impossible to max out Progress.
+pub fn craft_bounds(
+    settings: SolverSettings,
+    interrupt_signal: AtomicFlag,
+) -> Result<CraftBounds, SolverException> {
+    let initial_state = SimulationState::new(&settings.simulator_settings);
+    let max_quality = settings.simulator_settings.max_quality;
+
+    let mut finish_solver = FinishSolver::new(settings);
+    if !finish_solver.can_finish(&initial_state) {
+        return Err(SolverException::NoSolution);
+    }
+
+    // the bound solvers can't handle the initial combo, so the first action is expanded here
+    let mut child_states = Vec::new();
+    let mut quality_upper_bound = 0;
+    let mut steps_lower_bound = u8::MAX;
+    let mut quality_ub_solver =
+        QualityUpperBoundSolver::new(settings, interrupt_signal.clone(), &MemoryBudget::default());
+    for action in FULL_SEARCH_ACTIONS {
+        let Ok(state) = use_action_combo(&settings, initial_state, *action) else {
+            continue;
+        };
+        if state.is_final(&settings.simulator_settings) {
+            if state.progress >= settings.simulator_settings.max_progress {
+                let quality = std::cmp::min(state.quality, max_quality);
+                quality_upper_bound = std::cmp::max(quality_upper_bound, quality);
+                if quality >= max_quality {
+                    steps_lower_bound = std::cmp::min(steps_lower_bound, action.steps());
+                }
+            }
+        } else if finish_solver.can_finish(&state) {
+            let state_quality_ub = quality_ub_solver.quality_upper_bound(state)?;
+            quality_upper_bound = std::cmp::max(quality_upper_bound, state_quality_ub);
+            if state_quality_ub >= max_quality {
+                child_states.push((state, action.steps()));
+            }
+        }
+    }
+
+    if quality_upper_bound < max_quality {
+        return Ok(CraftBounds {
+            quality_upper_bound,
+            steps_lower_bound: None,
+        });
+    }
+
+    let mut step_lb_solver =
+        StepLowerBoundSolver::new(settings, interrupt_signal, &MemoryBudget::default());
+    for (state, steps) in child_states {
+        let state_steps_lb = step_lb_solver
+            .step_lower_bound_with_hint(state, 0)?
+            .saturating_add(steps);
+        steps_lower_bound = std::cmp::min(steps_lower_bound, state_steps_lb);
+    }
+    Ok(CraftBounds {
+        quality_upper_bound,
+        steps_lower_bound: Some(steps_lower_bound),
+    })
+}
+
+/// Upper bound on the Quality achievable while maxing out Progress for each step budget, starting at one step.
+/// The bound is 0 for step budgets that are too small to max out Progress.
+/// Stops at the first step budget that reaches the max Quality or at `max_steps`.
+pub fn quality_upper_bound_per_step(
+    settings: SolverSettings,
+    max_steps: u8,
+    interrupt_signal: AtomicFlag,
+) -> Result<Vec<u16>, SolverException> {
+    let initial_state = SimulationState::new(&settings.simulator_settings);
+    let max_quality = settings.simulator_settings.max_quality;
+
+    let mut finish_solver = FinishSolver::new(settings);
+    if !finish_solver.can_finish(&initial_state) {
+        return Err(SolverException::NoSolution);
+    }
+
+    let mut step_lb_solver =
+        StepLowerBoundSolver::new(settings, interrupt_signal, &MemoryBudget::default());
+    let mut quality_upper_bounds = Vec::new();
+    for step_budget in 1..=max_steps {
+        let mut quality_upper_bound = 0;
+        for action in FULL_SEARCH_ACTIONS {
+            let Some(remaining_steps) = step_budget.checked_sub(action.steps()) else {
+                continue;
+            };
+            let Ok(state) = use_action_combo(&settings, initial_state, *action) else {
+                continue;
+            };
+            let state_quality_ub = if state.is_final(&settings.simulator_settings) {
+                match state.progress >= settings.simulator_settings.max_progress {
+                    true => std::cmp::min(state.quality, max_quality),
+                    false => 0,
+                }
+            } else {
+                match NonZeroU8::new(remaining_steps) {
+                    Some(remaining_steps) if finish_solver.can_finish(&state) => {
+                        step_lb_solver.quality_upper_bound(state, remaining_steps)?
+                    }
+                    _ => 0,
+                }
+            };
+            quality_upper_bound = std::cmp::max(quality_upper_bound, state_quality_ub);
+        }
+        quality_upper_bounds.push(quality_upper_bound);
+        if quality_upper_bound >= max_quality {
+            break;
+        }
+    }
+    Ok(quality_upper_bounds)
+}
@@ -0,0 +1,62 @@
+use raphael_sim::*;
+
+/// Step at which Byregot's Blessing is inserted into a rotation, see [`best_byregot_placement`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByregotPlacement {
+    /// Number of actions of the rotation that are used before Byregot's Blessing.
+    pub index: usize,
+    /// Quality at the end of the rotation with Byregot's Blessing inserted.
+    pub quality: u16,
+}
+
+/// Finds where to insert Byregot's Blessing into `actions` for the most Quality, for rotations
+/// that were solved without it so that it can be used by hand on a Good or Excellent condition.
+/// `settings` must allow Byregot's Blessing, e.g. [`crate::SolveRequest::game_settings`].
+///
+/// Only placements after which the rest of the rotation can still be used and max out Progress
+/// are considered. Ties are broken towards the later placement, which leaves more steps to wait
+/// for a good condition. Returns `None` if there is no such placement, e.g. because the rotation
+/// doesn't leave enough CP or durability for Byregot's Blessing.
+pub fn best_byregot_placement(
+    settings: &Settings,
+    initial_state: SimulationState,
+    actions: &[Action],
+) -> Option<ByregotPlacement> {
+    let mut best: Option<ByregotPlacement> = None;
+    let mut state = initial_state;
+    for index in 0..=actions.len() {
+        if let Some(quality) = quality_with_byregot(settings, state, &actions[index..]) {
+            match best {
+                Some(placement) if placement.quality > quality => (),
+                _ => best = Some(ByregotPlacement { index, quality }),
+            }
+        }
+        if index == actions.len() {
+            break;
+        }
+        state = state
+            .use_action(actions[index], Condition::Normal, settings)
+            .ok()?;
+    }
+    best
+}
+
+/// Quality at the end of `actions` when Byregot's Blessing is used in `state` before them.
+fn quality_with_byregot(
+    settings: &Settings,
+    state: SimulationState,
+    actions: &[Action],
+) -> Option<u16> {
+    let mut state = state
+        .use_action(Action::ByregotsBlessing, Condition::Normal, settings)
+        .ok()?;
+    for action in actions {
+        state = state
+            .use_action(*action, Condition::Normal, settings)
+            .ok()?;
+    }
+    match state.progress >= settings.max_progress {
+        true => Some(state.quality),
+        false => None,
+    }
+}
@@ -0,0 +1,116 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use raphael_sim::*;
+
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::{
+    FinishSolver, SolverSettings,
+    actions::{ActionCombo, PROGRESS_ONLY_SEARCH_ACTIONS, use_action_combo},
+};
+
+/// Actions that max out Progress, see [`cheapest_finish`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinishSuggestion {
+    pub actions: Vec<Action>,
+    /// CP spent by the actions.
+    pub cp_cost: i16,
+}
+
+struct Node {
+    state: SimulationState,
+    parent: Option<(usize, ActionCombo)>,
+}
+
+/// Finds the actions that max out Progress from `state` for the least CP, ties broken by the
+/// number of steps. The CP left after the actions is what can still be spent on Quality.
+/// Returns `None` if Progress can't be maxed out anymore.
+///
+/// Actions that restore CP, i.e. Tricks of the Trade, are never suggested.
+pub fn cheapest_finish(
+    settings: SolverSettings,
+    state: SimulationState,
+) -> Option<FinishSuggestion> {
+    let simulator_settings = &settings.simulator_settings;
+    if state.progress >= simulator_settings.max_progress {
+        return Some(FinishSuggestion {
+            actions: Vec::new(),
+            cp_cost: 0,
+        });
+    }
+    let mut finish_solver = FinishSolver::new(settings);
+    if state.is_final(simulator_settings) || !finish_solver.can_finish(&state) {
+        return None;
+    }
+
+    let mut nodes = vec![Node {
+        state,
+        parent: None,
+    }];
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((0, 0, 0)));
+    let mut visited = HashSet::default();
+    while let Some(Reverse((cp_cost, steps, index))) = queue.pop() {
+        let state = nodes[index].state;
+        if state.progress >= simulator_settings.max_progress {
+            return Some(FinishSuggestion {
+                actions: trace_actions(&nodes, index),
+                cp_cost,
+            });
+        }
+        if !visited.insert(normalize_state(state)) {
+            continue;
+        }
+        for action in PROGRESS_ONLY_SEARCH_ACTIONS {
+            let Ok(new_state) = use_action_combo(&settings, state, *action) else {
+                continue;
+            };
+            if new_state.cp > state.cp {
+                continue;
+            }
+            let is_finished = new_state.progress >= simulator_settings.max_progress;
+            if is_finished
+                || (!new_state.is_final(simulator_settings) && finish_solver.can_finish(&new_state))
+            {
+                nodes.push(Node {
+                    state: new_state,
+                    parent: Some((index, *action)),
+                });
+                let new_cp_cost = cp_cost + state.cp - new_state.cp;
+                let new_steps = steps + action.steps();
+                queue.push(Reverse((new_cp_cost, new_steps, nodes.len() - 1)));
+            }
+        }
+    }
+    None
+}
+
+fn trace_actions(nodes: &[Node], mut index: usize) -> Vec<Action> {
+    let mut combos = Vec::new();
+    while let Some((parent, action)) = nodes[index].parent {
+        combos.push(action);
+        index = parent;
+    }
+    combos
+        .iter()
+        .rev()
+        .flat_map(|combo| combo.actions().iter().copied())
+        .collect()
+}
+
+/// Quality doesn't matter, so states that only differ in Quality are merged.
+fn normalize_state(state: SimulationState) -> SimulationState {
+    SimulationState {
+        quality: 0,
+        unreliable_quality: 0,
+        effects: state
+            .effects
+            .with_inner_quiet(0)
+            .with_innovation(0)
+            .with_great_strides(0)
+            .with_guard(0)
+            .with_quick_innovation_available(false),
+        ..state
+    }
+}
@@ -0,0 +1,125 @@
+use std::cmp::Reverse;
+
+use raphael_sim::*;
+
+use crate::actions::CONDITION_ACTIONS;
+
+/// What to do by hand when a step of a macro gets a Good or Excellent condition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcAdvice {
+    /// Keep running the macro, its action already benefits from the condition.
+    Continue,
+    /// Use the action instead of the action of the macro.
+    Replace(Action),
+    /// Use the action before the action of the macro, and continue the macro after it.
+    Insert(Action),
+}
+
+/// Advice for a condition at a step of a macro, see [`condition_cheat_sheet`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProcSuggestion {
+    /// Zero-based index of the step that gets the condition.
+    pub step: usize,
+    pub condition: Condition,
+    pub advice: ProcAdvice,
+    /// Quality at the end of the macro when following the advice, capped at the max Quality.
+    pub quality: u16,
+    /// Same as `quality`, but when continuing the macro.
+    pub continue_quality: u16,
+}
+
+/// Lists for each step of `actions` and for Good and Excellent condition whether to replace
+/// the action of the step, insert another action before it, or keep running the macro.
+///
+/// All other steps are assumed to be in Normal condition, except the step after an Excellent
+/// condition, which is always Poor. Advice is only given if the rest of the macro still maxes
+/// out Progress, preferring more Quality, then fewer steps, then more CP left.
+pub fn condition_cheat_sheet(
+    settings: &Settings,
+    initial_state: SimulationState,
+    actions: &[Action],
+) -> Vec<ProcSuggestion> {
+    let mut suggestions = Vec::new();
+    let mut state = initial_state;
+    for (step, action) in actions.iter().enumerate() {
+        for condition in [Condition::Good, Condition::Excellent] {
+            let Some(continue_outcome) =
+                outcome(settings, state, condition, *action, &actions[step + 1..])
+            else {
+                continue;
+            };
+            let mut best = (ProcAdvice::Continue, continue_outcome);
+            for proc_action in CONDITION_ACTIONS.iter().copied() {
+                let replace_outcome = match proc_action == *action {
+                    true => None,
+                    false => outcome(
+                        settings,
+                        state,
+                        condition,
+                        proc_action,
+                        &actions[step + 1..],
+                    ),
+                };
+                let insert_outcome =
+                    outcome(settings, state, condition, proc_action, &actions[step..]);
+                for (advice, outcome) in [
+                    (ProcAdvice::Replace(proc_action), replace_outcome),
+                    (ProcAdvice::Insert(proc_action), insert_outcome),
+                ] {
+                    match outcome {
+                        Some(outcome) if outcome > best.1 => best = (advice, outcome),
+                        _ => (),
+                    }
+                }
+            }
+            suggestions.push(ProcSuggestion {
+                step,
+                condition,
+                advice: best.0,
+                quality: best.1.quality,
+                continue_quality: continue_outcome.quality,
+            });
+        }
+        match state.use_action(*action, Condition::Normal, settings) {
+            Ok(next_state) if !next_state.is_final(settings) => state = next_state,
+            _ => break,
+        }
+    }
+    suggestions
+}
+
+/// End of the macro, ordered from worst to best.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Outcome {
+    quality: u16,
+    steps: Reverse<usize>,
+    cp: i16,
+}
+
+/// Outcome of using `first_action` in `condition` and then `remaining_actions`, or `None` if
+/// Progress isn't maxed out.
+fn outcome(
+    settings: &Settings,
+    state: SimulationState,
+    condition: Condition,
+    first_action: Action,
+    remaining_actions: &[Action],
+) -> Option<Outcome> {
+    let mut state = state;
+    let mut condition = condition;
+    for action in std::iter::once(&first_action).chain(remaining_actions) {
+        state = state.use_action(*action, condition, settings).ok()?;
+        condition = match condition {
+            Condition::Excellent => Condition::Poor,
+            _ => Condition::Normal,
+        };
+    }
+    match state.progress >= settings.max_progress {
+        true => Some(Outcome {
+            quality: std::cmp::min(state.quality, settings.max_quality),
+            steps: Reverse(remaining_actions.len() + 1),
+            cp: state.cp,
+        }),
+        false => None,
+    }
+}
@@ -0,0 +1,32 @@
+use raphael_sim::SimulationState;
+
+use crate::{QualityUpperBoundSolver, SolverException};
+
+/// Returns a lower bound on the CP that still has to be spent to max out both Progress and
+/// Quality from `state`, or `None` if the max Quality can't be reached with the CP of the state.
+///
+/// The Quality upper bound never decreases when a state has more CP, so the lowest amount of CP
+/// for which the upper bound reaches the max Quality is found with a binary search.
+pub fn cp_lower_bound(
+    quality_ub_solver: &mut QualityUpperBoundSolver,
+    state: SimulationState,
+    max_quality: u16,
+) -> Result<Option<i16>, SolverException> {
+    let mut reaches_max_quality = |cp: i16| -> Result<bool, SolverException> {
+        let state = SimulationState { cp, ..state };
+        Ok(quality_ub_solver.quality_upper_bound(state)? >= max_quality)
+    };
+    if !reaches_max_quality(state.cp)? {
+        return Ok(None);
+    }
+    let mut low = 0;
+    let mut high = state.cp;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match reaches_max_quality(mid)? {
+            true => high = mid,
+            false => low = mid + 1,
+        }
+    }
+    Ok(Some(high))
+}
@@ -0,0 +1,280 @@
+use raphael_sim::*;
+
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::actions::{
+    FULL_SEARCH_ACTIONS, PROGRESS_ONLY_SEARCH_ACTIONS, is_progress_only_state, use_action_combo,
+};
+use crate::utils::{AtomicFlag, MemoryBudget, ScopedTimer};
+use crate::{FinishSolver, QualityUpperBoundSolver, SolverException, SolverSettings};
+
+/// Conditions of recipes that aren't expert recipes.
+const CONDITIONS: [Condition; 4] = [
+    Condition::Normal,
+    Condition::Good,
+    Condition::Excellent,
+    Condition::Poor,
+];
+
+fn condition_index(condition: Condition) -> usize {
+    CONDITIONS
+        .iter()
+        .position(|normal_condition| *normal_condition == condition)
+        .expect("Expert conditions don't follow normal conditions")
+}
+
+/// Probability of each condition at each step, in the order of `CONDITIONS`.
+/// Uses the same condition model as [`quality_probability_distribution`].
+struct ConditionMarginals {
+    steps: Vec<[f32; 4]>,
+}
+
+impl ConditionMarginals {
+    fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    fn at_step(&mut self, step: usize) -> [f32; 4] {
+        while self.steps.len() <= step {
+            let previous = self.steps.last().copied().unwrap_or([1.0, 0.0, 0.0, 0.0]);
+            let mut next = [0.0; 4];
+            for condition in CONDITIONS {
+                for (next_condition, probability) in condition_probabilities(condition) {
+                    next[condition_index(*next_condition)] +=
+                        previous[condition_index(condition)] * probability;
+                }
+            }
+            self.steps.push(next);
+        }
+        self.steps[step]
+    }
+}
+
+#[derive(Clone)]
+struct Node {
+    /// State in which every action was used in Normal condition. Conditions only change the
+    /// Quality of an action, so all other fields of the state are the same for every sequence
+    /// of conditions.
+    state: SimulationState,
+    expected_quality: f32,
+    actions: Vec<Action>,
+}
+
+/// Searches for the macro with the highest expected Quality over the random conditions of a
+/// craft, instead of assuming Normal condition on every step.
+///
+/// The search is a beam search over the steps of the macro. Nodes are ranked by their expected
+/// Quality plus the Quality that can still be gained in Normal condition. The most promising
+/// finished macros are evaluated exactly with [`quality_probability_distribution_from`], where
+/// Quality above the max Quality doesn't count.
+///
+/// Effects that depend on the condition are ignored while searching, e.g. Heart and Soul not
+/// being consumed by Tricks of the Trade in Good condition.
+pub struct ExpectedQualitySolver {
+    settings: SolverSettings,
+    interrupt_signal: AtomicFlag,
+    beam_width: usize,
+}
+
+impl ExpectedQualitySolver {
+    const DEFAULT_BEAM_WIDTH: usize = 1024;
+    /// Number of finished macros that are evaluated exactly.
+    const EXACT_EVALUATIONS: usize = 16;
+
+    pub fn new(mut settings: SolverSettings, interrupt_signal: AtomicFlag) -> Self {
+        // the expected Quality already accounts for Poor condition
+        settings.simulator_settings.adversarial = false;
+        Self {
+            settings,
+            interrupt_signal,
+            beam_width: Self::DEFAULT_BEAM_WIDTH,
+        }
+    }
+
+    /// Number of states that are kept at each step. Wider beams find better macros but take
+    /// longer to solve.
+    #[must_use]
+    pub fn beam_width(mut self, beam_width: usize) -> Self {
+        self.beam_width = beam_width.max(1);
+        self
+    }
+
+    pub fn solve(&mut self) -> Result<Vec<Action>, SolverException> {
+        self.solve_from(SimulationState::new(&self.settings.simulator_settings))
+    }
+
+    /// Like [`Self::solve`], but continues from `initial_state`, e.g. the state after a fixed
+    /// prefix of actions. The returned actions don't include the actions of the prefix.
+    pub fn solve_from(
+        &mut self,
+        initial_state: SimulationState,
+    ) -> Result<Vec<Action>, SolverException> {
+        let mut finish_solver = FinishSolver::new(self.settings);
+        if !finish_solver.can_finish(&initial_state) {
+            return Err(SolverException::NoSolution);
+        }
+        let mut quality_ub_solver = {
+            let _timer = ScopedTimer::new("Quality UB Solver");
+            QualityUpperBoundSolver::new(
+                self.settings,
+                self.interrupt_signal.clone(),
+                &MemoryBudget::default(),
+            )
+        };
+
+        let _timer = ScopedTimer::new("Expected Quality Search");
+        let candidates = self.search(initial_state, &mut finish_solver, &mut quality_ub_solver)?;
+
+        let settings = self.settings.simulator_settings;
+        let mut best: Option<(f32, Vec<Action>)> = None;
+        for candidate in candidates {
+            if self.interrupt_signal.is_set() {
+                return Err(SolverException::Interrupted);
+            }
+            let expected_quality =
+                quality_probability_distribution_from(settings, initial_state, &*candidate.actions)
+                    .expected_quality(settings.max_quality);
+            let is_better = best.as_ref().is_none_or(|(best_quality, best_actions)| {
+                (
+                    expected_quality,
+                    std::cmp::Reverse(macro_length(&candidate.actions)),
+                ) > (*best_quality, std::cmp::Reverse(macro_length(best_actions)))
+            });
+            if is_better {
+                best = Some((expected_quality, candidate.actions));
+            }
+        }
+        best.map(|(_, actions)| actions)
+            .ok_or(SolverException::NoSolution)
+    }
+
+    /// Returns the most promising finished macros according to their estimated expected Quality.
+    fn search(
+        &self,
+        initial_state: SimulationState,
+        finish_solver: &mut FinishSolver,
+        quality_ub_solver: &mut QualityUpperBoundSolver,
+    ) -> Result<Vec<Node>, SolverException> {
+        let settings = &self.settings.simulator_settings;
+        let mut marginals = ConditionMarginals::new();
+        // nodes by number of steps, deduplicated by their state without Quality
+        let mut layers: Vec<HashMap<SimulationState, Node>> = vec![HashMap::default()];
+        layers[0].insert(
+            normalize_state(initial_state),
+            Node {
+                state: initial_state,
+                expected_quality: f32::from(initial_state.quality),
+                actions: Vec::new(),
+            },
+        );
+        let mut finished: Vec<(f32, Node)> = Vec::new();
+
+        let mut step = 0;
+        while step < layers.len() {
+            let mut nodes = Vec::new();
+            for node in std::mem::take(&mut layers[step]).into_values() {
+                if self.interrupt_signal.is_set() {
+                    return Err(SolverException::Interrupted);
+                }
+                let remaining_quality = quality_ub_solver
+                    .quality_upper_bound(node.state)?
+                    .saturating_sub(node.state.quality);
+                let estimate = f32::min(
+                    node.expected_quality + f32::from(remaining_quality),
+                    f32::from(settings.max_quality),
+                );
+                nodes.push((estimate, node));
+            }
+            nodes.sort_by(|(lhs_estimate, lhs), (rhs_estimate, rhs)| {
+                rhs_estimate
+                    .total_cmp(lhs_estimate)
+                    .then(rhs.expected_quality.total_cmp(&lhs.expected_quality))
+            });
+            nodes.truncate(self.beam_width);
+
+            for (_, node) in nodes {
+                let search_actions = match is_progress_only_state(&self.settings, &node.state) {
+                    true => PROGRESS_ONLY_SEARCH_ACTIONS,
+                    false => FULL_SEARCH_ACTIONS,
+                };
+                for action_combo in search_actions {
+                    let Ok(state) = use_action_combo(&self.settings, node.state, *action_combo)
+                    else {
+                        continue;
+                    };
+                    let mut expected_quality = node.expected_quality;
+                    let mut current_state = node.state;
+                    for (offset, action) in action_combo.actions().iter().enumerate() {
+                        let Ok(normal_state) =
+                            current_state.use_action(*action, Condition::Normal, settings)
+                        else {
+                            break;
+                        };
+                        for (condition, probability) in
+                            CONDITIONS.iter().zip(marginals.at_step(step + offset))
+                        {
+                            let quality = current_state
+                                .use_action(*action, *condition, settings)
+                                .map_or(normal_state.quality, |state| state.quality);
+                            expected_quality += probability
+                                * f32::from(quality.saturating_sub(current_state.quality));
+                        }
+                        current_state = normal_state;
+                    }
+                    let node = Node {
+                        state,
+                        expected_quality,
+                        actions: [node.actions.as_slice(), action_combo.actions()].concat(),
+                    };
+                    if state.is_final(settings) {
+                        if state.progress >= settings.max_progress {
+                            let estimate =
+                                f32::min(expected_quality, f32::from(settings.max_quality));
+                            finished.push((estimate, node));
+                        }
+                    } else if finish_solver.can_finish(&state) {
+                        let next_step = step + usize::from(action_combo.steps());
+                        if layers.len() <= next_step {
+                            layers.resize_with(next_step + 1, HashMap::default);
+                        }
+                        layers[next_step]
+                            .entry(normalize_state(state))
+                            .and_modify(|existing| {
+                                if existing.expected_quality < node.expected_quality {
+                                    *existing = node.clone();
+                                }
+                            })
+                            .or_insert(node);
+                    }
+                }
+            }
+
+            finished.sort_by(|(lhs_estimate, lhs), (rhs_estimate, rhs)| {
+                rhs_estimate
+                    .total_cmp(lhs_estimate)
+                    .then(macro_length(&lhs.actions).cmp(&macro_length(&rhs.actions)))
+            });
+            finished.truncate(Self::EXACT_EVALUATIONS);
+            step += 1;
+        }
+
+        Ok(finished.into_iter().map(|(_, node)| node).collect())
+    }
+}
+
+fn normalize_state(state: SimulationState) -> SimulationState {
+    SimulationState {
+        quality: 0,
+        unreliable_quality: 0,
+        ..state
+    }
+}
+
+/// Number of steps and duration of the macro, shorter is better.
+fn macro_length(actions: &[Action]) -> (usize, u16) {
+    let duration = actions
+        .iter()
+        .map(|action| u16::from(action.time_cost()))
+        .sum();
+    (actions.len(), duration)
+}
@@ -0,0 +1,187 @@
+use raphael_sim::*;
+
+use crate::actions::{ActionCombo, CONDITION_ACTIONS, FULL_SEARCH_ACTIONS, is_assumed_to_fail};
+use crate::utils::{AtomicFlag, MemoryBudget};
+use crate::{
+    FinishSolver, QualityUpperBoundSolver, SolverException, SolverSettings, StepLowerBoundSolver,
+};
+
+/// Suggested next step of a craft, see [`ExpertAdvisor::advise`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExpertAdvice {
+    /// The action to use next, followed by the actions it is meant to be combined with,
+    /// e.g. Standard Touch after Basic Touch.
+    pub actions: &'static [Action],
+    /// Quality upper bound after the actions, averaged over the condition of the step after
+    /// the actions.
+    pub expected_quality_upper_bound: f32,
+    /// Steps lower bound after the actions, or `None` if the max Quality can't be reached.
+    pub steps_lower_bound: Option<u8>,
+    pub cp: i16,
+}
+
+/// Suggests the next action of a craft one step at a time, for recipes whose conditions can't
+/// be planned for in a macro, i.e. expert recipes.
+///
+/// Each candidate action is used in the current condition, followed by the best action for each
+/// condition that can come next. The resulting states are compared by the Quality that can still
+/// be reached assuming Normal condition afterwards, which is the same upper bound the macro
+/// solver uses. Ties are broken by the steps lower bound and the remaining CP.
+pub struct ExpertAdvisor {
+    settings: SolverSettings,
+    condition_pool: ConditionPool,
+    finish_solver: FinishSolver,
+    quality_ub_solver: QualityUpperBoundSolver,
+    step_lb_solver: StepLowerBoundSolver,
+}
+
+impl ExpertAdvisor {
+    pub fn new(
+        mut settings: SolverSettings,
+        condition_pool: ConditionPool,
+        interrupt_signal: AtomicFlag,
+    ) -> Self {
+        settings.simulator_settings.adversarial = false;
+        let memory_budget = MemoryBudget::default();
+        Self {
+            settings,
+            condition_pool,
+            finish_solver: FinishSolver::new(settings),
+            quality_ub_solver: QualityUpperBoundSolver::new(
+                settings,
+                interrupt_signal.clone(),
+                &memory_budget,
+            ),
+            step_lb_solver: StepLowerBoundSolver::new(settings, interrupt_signal, &memory_budget),
+        }
+    }
+
+    /// Ranks the actions that can be used in `state` in `condition`, best first.
+    /// Returns an empty list if the craft can't be finished anymore.
+    pub fn advise(
+        &mut self,
+        state: SimulationState,
+        condition: Condition,
+    ) -> Result<Vec<ExpertAdvice>, SolverException> {
+        let next_conditions = self.condition_pool.next_condition_probabilities(condition);
+        let mut advice = Vec::new();
+        for action_combo in Self::candidates() {
+            let Some((next_state, quality_upper_bound)) =
+                self.evaluate(state, action_combo, condition)?
+            else {
+                continue;
+            };
+            let mut expected_quality_upper_bound = 0.0;
+            for (next_condition, probability) in &next_conditions {
+                let quality_upper_bound =
+                    match next_state.is_final(&self.settings.simulator_settings) {
+                        true => self.final_quality(&next_state),
+                        false => self.best_quality_upper_bound(next_state, *next_condition)?,
+                    };
+                expected_quality_upper_bound += probability * f32::from(quality_upper_bound);
+            }
+            let steps_lower_bound =
+                match quality_upper_bound >= self.settings.simulator_settings.max_quality {
+                    true => self.steps_lower_bound(next_state)?,
+                    false => None,
+                };
+            advice.push(ExpertAdvice {
+                actions: action_combo.actions(),
+                expected_quality_upper_bound,
+                steps_lower_bound,
+                cp: next_state.cp,
+            });
+        }
+        advice.sort_by(|lhs, rhs| {
+            rhs.expected_quality_upper_bound
+                .total_cmp(&lhs.expected_quality_upper_bound)
+                .then(
+                    lhs.steps_lower_bound
+                        .unwrap_or(u8::MAX)
+                        .cmp(&rhs.steps_lower_bound.unwrap_or(u8::MAX)),
+                )
+                .then(rhs.cp.cmp(&lhs.cp))
+        });
+        Ok(advice)
+    }
+
+    fn candidates() -> impl Iterator<Item = ActionCombo> {
+        FULL_SEARCH_ACTIONS
+            .iter()
+            .copied()
+            .chain(CONDITION_ACTIONS.iter().copied().map(ActionCombo::Single))
+    }
+
+    /// Uses the first action of the combo in `condition` and the rest in Normal condition.
+    /// Returns the resulting state and its Quality upper bound, or `None` if the actions can't
+    /// be used or the craft can't be finished afterwards.
+    fn evaluate(
+        &mut self,
+        state: SimulationState,
+        action_combo: ActionCombo,
+        condition: Condition,
+    ) -> Result<Option<(SimulationState, u16)>, SolverException> {
+        if action_combo
+            .actions()
+            .iter()
+            .any(|action| is_assumed_to_fail(&self.settings, *action))
+        {
+            return Ok(None);
+        }
+        let settings = &self.settings.simulator_settings;
+        let mut next_state = state;
+        for (index, action) in action_combo.actions().iter().enumerate() {
+            let condition = match index {
+                0 => condition,
+                _ => Condition::Normal,
+            };
+            match next_state.use_action(*action, condition, settings) {
+                Ok(state) => next_state = state,
+                Err(_) => return Ok(None),
+            }
+        }
+        next_state.combo = Combo::None;
+        if next_state.is_final(settings) {
+            return match next_state.progress >= settings.max_progress {
+                true => Ok(Some((next_state, self.final_quality(&next_state)))),
+                false => Ok(None),
+            };
+        }
+        if !self.finish_solver.can_finish(&next_state) {
+            return Ok(None);
+        }
+        let quality_upper_bound = self.quality_ub_solver.quality_upper_bound(next_state)?;
+        Ok(Some((next_state, quality_upper_bound)))
+    }
+
+    /// Quality upper bound of the best action in `condition`, or 0 if no action can finish
+    /// the craft.
+    fn best_quality_upper_bound(
+        &mut self,
+        state: SimulationState,
+        condition: Condition,
+    ) -> Result<u16, SolverException> {
+        let mut best = 0;
+        for action_combo in Self::candidates() {
+            if let Some((_, quality_upper_bound)) = self.evaluate(state, action_combo, condition)? {
+                best = std::cmp::max(best, quality_upper_bound);
+            }
+        }
+        Ok(best)
+    }
+
+    /// Only meaningful if the Quality upper bound of `state` reaches the max Quality.
+    fn steps_lower_bound(&mut self, state: SimulationState) -> Result<Option<u8>, SolverException> {
+        if state.is_final(&self.settings.simulator_settings) {
+            return Ok(Some(0));
+        }
+        match self.step_lb_solver.step_lower_bound_with_hint(state, 0)? {
+            u8::MAX => Ok(None),
+            steps => Ok(Some(steps)),
+        }
+    }
+
+    fn final_quality(&self, state: &SimulationState) -> u16 {
+        std::cmp::min(state.quality, self.settings.simulator_settings.max_quality)
+    }
+}
@@ -0,0 +1,143 @@
+use raphael_sim::*;
+
+use crate::SolverSettings;
+use crate::actions::{FULL_SEARCH_ACTIONS, PROGRESS_ONLY_SEARCH_ACTIONS};
+
+/// Values that the solvers derive from the settings to prune the search.
+///
+/// Exposed so that analysis tools can explain the decisions of the solvers without duplicating
+/// the formulas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolverHeuristics {
+    /// CP that 5 durability are worth, based on the cheapest allowed way to restore durability.
+    pub durability_cost: i16,
+    /// Minimum CP a state must have so that using Waste Not isn't worse than restoring
+    /// durability via CP.
+    pub waste_not_1_min_cp: i16,
+    /// Same as `waste_not_1_min_cp`, for Waste Not II.
+    pub waste_not_2_min_cp: i16,
+}
+
+impl SolverHeuristics {
+    pub fn new(settings: &SolverSettings) -> Self {
+        let initial_state = SimulationState::new(&settings.simulator_settings);
+
+        let mut durability_cost = 100;
+        if settings
+            .simulator_settings
+            .is_action_allowed::<MasterMend>()
+        {
+            let master_mend_cost = settings
+                .simulator_settings
+                .base_cp_cost::<MasterMend>(&initial_state);
+            durability_cost = std::cmp::min(durability_cost, master_mend_cost / 6);
+        }
+        if settings
+            .simulator_settings
+            .is_action_allowed::<Manipulation>()
+        {
+            let manipulation_cost = settings
+                .simulator_settings
+                .base_cp_cost::<Manipulation>(&initial_state);
+            durability_cost = std::cmp::min(durability_cost, manipulation_cost / 8);
+        }
+        if settings
+            .simulator_settings
+            .is_action_allowed::<ImmaculateMend>()
+        {
+            let immaculate_mend_cost = settings
+                .simulator_settings
+                .base_cp_cost::<ImmaculateMend>(&initial_state);
+            let max_restored = settings.simulator_settings.max_durability as i16 / 5 - 1;
+            if max_restored > 0 {
+                durability_cost =
+                    std::cmp::min(durability_cost, immaculate_mend_cost / max_restored);
+            }
+        }
+
+        // Free durability would let actions that only add Progress or Quality repeat forever in
+        // the reduced states of the upper bound solver, which don't track either value.
+        let durability_cost = std::cmp::max(durability_cost, 1);
+
+        let simulator_settings = &settings.simulator_settings;
+        // the cheapest action that uses 20 durability, which Waste Not halves to 10
+        let double_durability_cp = simulator_settings
+            .rules_override
+            .iter()
+            .filter(|action_override| {
+                action_override
+                    .durability_cost
+                    .is_some_and(|durability_cost| durability_cost >= 20)
+            })
+            .map(|action_override| action_override.cp_cost.unwrap_or(0))
+            .fold(
+                simulator_settings.base_cp_cost::<Groundwork>(&initial_state),
+                std::cmp::min,
+            );
+        // the formula assumes that no action saves more than 10 durability under Waste Not
+        let unbounded_savings = simulator_settings
+            .rules_override
+            .iter()
+            .any(|action_override| {
+                action_override
+                    .durability_cost
+                    .is_some_and(|durability_cost| durability_cost > 20)
+            });
+        let min_cp = |waste_not_cp_cost, effect_duration| match unbounded_savings {
+            true => 0,
+            false => waste_not_min_cp(
+                waste_not_cp_cost,
+                effect_duration,
+                durability_cost,
+                double_durability_cp,
+            ),
+        };
+
+        Self {
+            durability_cost,
+            waste_not_1_min_cp: min_cp(
+                simulator_settings.base_cp_cost::<WasteNot>(&initial_state),
+                4,
+            ),
+            waste_not_2_min_cp: min_cp(
+                simulator_settings.base_cp_cost::<WasteNot2>(&initial_state),
+                8,
+            ),
+        }
+    }
+}
+
+/// Actions in the order the solvers try them, with combos of several actions tried as a whole.
+/// Progress-only states only try the actions that don't increase Quality.
+pub fn search_action_order(progress_only: bool) -> Vec<&'static [Action]> {
+    let search_actions = match progress_only {
+        true => PROGRESS_ONLY_SEARCH_ACTIONS,
+        false => FULL_SEARCH_ACTIONS,
+    };
+    search_actions.iter().map(|combo| combo.actions()).collect()
+}
+
+/// Calculates the minimum CP a state must have so that using WasteNot is not worse than just restoring durability via CP
+///
+/// `double_durability_cp` is the CP cost of the cheapest action that uses 20 durability.
+fn waste_not_min_cp(
+    waste_not_action_cp_cost: i16,
+    effect_duration: i16,
+    durability_cost: i16,
+    double_durability_cp: i16,
+) -> i16 {
+    // no action costs less CP than Basic Synthesis
+    const BASIC_SYNTH_CP: i16 = 0;
+    // how many units of 5-durability does WasteNot have to save to be worth using over magically restoring durability?
+    let min_durability_save = (waste_not_action_cp_cost - 1) / durability_cost + 1;
+    if min_durability_save > effect_duration * 2 {
+        return i16::MAX;
+    }
+    // how many 20-durability actions and how many 10-durability actions are needed?
+    let double_dur_count = std::cmp::max(min_durability_save - effect_duration, 0);
+    let single_dur_count = min_durability_save - 2 * double_dur_count;
+    // minimum CP required to execute those actions
+    let double_dur_cost = double_dur_count * (double_durability_cp + durability_cost * 2);
+    let single_dur_cost = single_dur_count * (BASIC_SYNTH_CP + durability_cost);
+    waste_not_action_cp_cost + double_dur_cost + single_dur_cost - durability_cost
+}
@@ -9,47 +9,112 @@ use quality_upper_bound_solver::QualityUpperBoundSolver;
 mod step_lower_bound_solver;
 use step_lower_bound_solver::StepLowerBoundSolver;
 
+mod cp_lower_bound;
+use cp_lower_bound::cp_lower_bound;
+
 mod macro_solver;
-pub use macro_solver::MacroSolver;
+pub use macro_solver::{
+    CacheStats, MacroSolver, ParetoSolution, ProgressOnlyReason, SolverCheckpoint, SolverStats,
+    progress_only_reason,
+};
+
+mod expected_quality_solver;
+pub use expected_quality_solver::ExpectedQualitySolver;
+
+mod expert_advisor;
+pub use expert_advisor::{ExpertAdvice, ExpertAdvisor};
+
+mod step_advisor;
+pub use step_advisor::{StepAdvisor, StepRecommendation};
+
+mod heuristics;
+pub use heuristics::{SolverHeuristics, search_action_order};
+
+mod cheapest_finish;
+pub use cheapest_finish::{FinishSuggestion, cheapest_finish};
+
+mod rotation_repair;
+pub use rotation_repair::repairable_prefix;
+
+mod cheat_sheet;
+pub use cheat_sheet::{ProcAdvice, ProcSuggestion, condition_cheat_sheet};
+
+mod byregot_placement;
+pub use byregot_placement::{ByregotPlacement, best_byregot_placement};
+
+mod bounds;
+pub use bounds::{CraftBounds, craft_bounds, quality_upper_bound_per_step};
 
 mod utils;
-pub use utils::AtomicFlag;
+pub use utils::{AtomicFlag, estimate_peak_memory};
+
+mod thread_pool;
+pub use thread_pool::ThreadPoolSettings;
+
+mod solve_request;
+pub use solve_request::{InitialEffects, SolveRequest};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SolverException {
     NoSolution,
     Interrupted,
+    /// The estimated memory usage of the solver exceeded the memory limit.
+    MemoryLimitReached,
     InternalError(String),
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SolverSettings {
     pub simulator_settings: raphael_sim::Settings,
     pub backload_progress: bool,
     pub allow_unsound_branch_pruning: bool,
+    /// Forbids actions that increase Quality while Muscle Memory is active, so that the macro
+    /// opens with Progress like most community rotations do.
+    pub forbid_quality_during_muscle_memory: bool,
+    /// Forbids actions that increase Quality in the steps before this one, counted from 0 at
+    /// the state the search starts from, e.g. 2 to open with two Progress actions. 0 doesn't
+    /// forbid any step.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub first_quality_step: u8,
 }
 
 pub mod test_utils {
     use crate::{MacroSolver, SolverException, SolverSettings, utils::AtomicFlag};
     use raphael_sim::*;
 
+    /// Solver settings with all solver options turned off.
+    pub fn solver_settings(simulator_settings: Settings) -> SolverSettings {
+        SolverSettings {
+            simulator_settings,
+            backload_progress: false,
+            allow_unsound_branch_pruning: false,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
+        }
+    }
+
+    /// Macro solver without callbacks that can't be interrupted.
+    pub fn macro_solver<'a>(settings: SolverSettings) -> MacroSolver<'a> {
+        MacroSolver::new(
+            settings,
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            AtomicFlag::new(),
+        )
+    }
+
     pub fn solve(
         settings: &Settings,
         backload_progress: bool,
         allow_unsound_branch_pruning: bool,
     ) -> Result<Vec<Action>, SolverException> {
-        let solver_settings = SolverSettings {
-            simulator_settings: *settings,
+        macro_solver(SolverSettings {
             backload_progress,
             allow_unsound_branch_pruning,
-        };
-        MacroSolver::new(
-            solver_settings,
-            Box::new(|_| {}),
-            Box::new(|_| {}),
-            AtomicFlag::new(),
-        )
+            ..solver_settings(*settings)
+        })
         .solve()
     }
 
@@ -0,0 +1,70 @@
+use raphael_sim::{Action, SimulationState};
+
+use super::search_queue::SearchQueueCheckpoint;
+use super::solver::Solution;
+use crate::utils::SolvedStatesCheckpoint;
+use crate::{SolverException, SolverSettings, quality_upper_bound_solver, step_lower_bound_solver};
+
+/// State of a stopped search.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct SearchProgress {
+    pub search_queue: SearchQueueCheckpoint,
+    pub solution: Option<Solution>,
+    pub popped: usize,
+}
+
+/// Progress of a [`crate::MacroSolver`] search that was stopped with
+/// [`crate::MacroSolver::checkpoint_signal`]: the search queue, the best macro found so far and
+/// the solved states of the bound solvers. With the `serde` feature, a checkpoint can be written
+/// to disk to continue the solve after a restart, see [`crate::MacroSolver::resume`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverCheckpoint {
+    pub(super) settings: SolverSettings,
+    pub(super) initial_state: SimulationState,
+    pub(super) minimize_cp: bool,
+    pub(super) human_friendly_bias: u8,
+    pub(super) search: SearchProgress,
+    pub(super) quality_ub_states: SolvedStatesCheckpoint<quality_upper_bound_solver::ReducedState>,
+    pub(super) step_lb_states: SolvedStatesCheckpoint<step_lower_bound_solver::ReducedState>,
+}
+
+impl SolverCheckpoint {
+    /// Settings of the stopped solver, which the resuming solver must be created with.
+    pub fn settings(&self) -> SolverSettings {
+        self.settings
+    }
+
+    /// State that the stopped solve started from, which the resumed solve must start from.
+    pub fn initial_state(&self) -> SimulationState {
+        self.initial_state
+    }
+
+    /// Number of search nodes visited before the search was stopped.
+    pub fn nodes_visited(&self) -> usize {
+        self.search.popped
+    }
+
+    /// Best macro found before the search was stopped, if any.
+    pub fn best_actions(&self) -> Option<Vec<Action>> {
+        self.search.solution.as_ref().map(Solution::actions)
+    }
+
+    pub(super) fn check_matches(
+        &self,
+        settings: &SolverSettings,
+        initial_state: SimulationState,
+        minimize_cp: bool,
+        human_friendly_bias: u8,
+    ) -> Result<(), SolverException> {
+        match self.settings == *settings
+            && self.initial_state == initial_state
+            && self.minimize_cp == minimize_cp
+            && self.human_friendly_bias == human_friendly_bias
+        {
+            true => Ok(()),
+            false => Err(SolverException::InternalError(
+                "The checkpoint was saved by a solve with different settings".to_owned(),
+            )),
+        }
+    }
+}
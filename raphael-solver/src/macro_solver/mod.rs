@@ -1,6 +1,12 @@
+mod checkpoint;
 mod fast_lower_bound;
 mod pareto_front;
+mod progress_only;
 mod search_queue;
 mod solver;
+mod stats;
 
-pub use solver::MacroSolver;
+pub use checkpoint::SolverCheckpoint;
+pub use progress_only::{ProgressOnlyReason, progress_only_reason};
+pub use solver::{MacroSolver, ParetoSolution};
+pub use stats::{CacheStats, SolverStats};
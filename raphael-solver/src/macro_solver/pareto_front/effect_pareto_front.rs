@@ -2,9 +2,11 @@ use raphael_sim::SimulationState;
 use rustc_hash::FxHashMap;
 
 use super::{Dominate, ParetoFront};
+use crate::utils::hash_map_memory_usage;
 
 #[bitfield_struct::bitfield(u32)]
 #[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Value {
     #[bits(3)]
     veneration: u8,
@@ -50,6 +52,7 @@ impl Dominate for Value {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Key {
     state: SimulationState,
 }
@@ -68,16 +71,25 @@ impl Key {
 }
 
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EffectParetoFront {
     buckets: FxHashMap<Key, ParetoFront<Value>>,
+    /// Number of values in all buckets, for estimating the memory usage.
+    values: usize,
 }
 
 impl EffectParetoFront {
     pub fn insert(&mut self, state: SimulationState) -> bool {
-        self.buckets
-            .entry(Key::new(state))
-            .or_default()
-            .insert(Value::from(state))
+        let front = self.buckets.entry(Key::new(state)).or_default();
+        let len = front.len();
+        let inserted = front.insert(Value::from(state));
+        self.values = self.values - len + front.len();
+        inserted
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        hash_map_memory_usage::<Key, ParetoFront<Value>>(self.buckets.capacity())
+            + self.values * std::mem::size_of::<Value>()
     }
 }
 
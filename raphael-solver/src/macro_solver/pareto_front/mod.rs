@@ -8,6 +8,7 @@ trait Dominate {
     fn dominate(&self, other: &Self) -> bool;
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct ParetoFront<T: Clone + Copy + Dominate> {
     values: Vec<T>,
 }
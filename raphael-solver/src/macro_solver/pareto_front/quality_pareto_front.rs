@@ -2,8 +2,10 @@ use raphael_sim::{Combo, Effects, SimulationState};
 use rustc_hash::FxHashMap;
 
 use super::{Dominate, ParetoFront};
+use crate::utils::hash_map_memory_usage;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Value {
     cp: i16,
     quality: u16,
@@ -36,6 +38,7 @@ impl Dominate for Value {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Key {
     progress: u16,
     effects: Effects,
@@ -53,16 +56,25 @@ impl Key {
 }
 
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QualityParetoFront {
     buckets: FxHashMap<Key, ParetoFront<Value>>,
+    /// Number of values in all buckets, for estimating the memory usage.
+    values: usize,
 }
 
 impl QualityParetoFront {
     pub fn insert(&mut self, state: SimulationState) -> bool {
-        self.buckets
-            .entry(Key::new(state))
-            .or_default()
-            .insert(Value::new(state))
+        let front = self.buckets.entry(Key::new(state)).or_default();
+        let len = front.len();
+        let inserted = front.insert(Value::new(state));
+        self.values = self.values - len + front.len();
+        inserted
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        hash_map_memory_usage::<Key, ParetoFront<Value>>(self.buckets.capacity())
+            + self.values * std::mem::size_of::<Value>()
     }
 }
 
@@ -0,0 +1,143 @@
+use raphael_sim::*;
+
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::{
+    AtomicFlag, SolverException, SolverSettings,
+    actions::{ActionCombo, PROGRESS_ONLY_SEARCH_ACTIONS, use_action_combo},
+    finish_solver::FinishSolver,
+    utils::ScopedTimer,
+};
+
+/// Why a solve only has to max out Progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressOnlyReason {
+    /// The initial state already has the max Quality, e.g. because the Quality target is
+    /// reached with HQ ingredients alone.
+    QualityReached,
+    /// Trained Eye maxes out Quality in a single step. The Progress-only macro is only used if
+    /// no macro without Trained Eye could be shorter.
+    TrainedEye,
+}
+
+impl ProgressOnlyReason {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::QualityReached => "Quality target already reached, only solving for Progress",
+            Self::TrainedEye => {
+                "Trained Eye reaches the quality target, trying to only solve for Progress"
+            }
+        }
+    }
+
+    /// Actions that max out Quality before the Progress-only part of the macro.
+    pub const fn prefix(self) -> &'static [Action] {
+        match self {
+            Self::QualityReached => &[],
+            Self::TrainedEye => &[Action::TrainedEye],
+        }
+    }
+}
+
+/// Returns why a solve from `initial_state` only has to max out Progress, or `None` if the
+/// solve also has to increase Quality.
+pub fn progress_only_reason(
+    settings: &Settings,
+    initial_state: &SimulationState,
+) -> Option<ProgressOnlyReason> {
+    if initial_state.quality >= settings.max_quality {
+        return Some(ProgressOnlyReason::QualityReached);
+    }
+    match initial_state.use_action(Action::TrainedEye, Condition::Normal, settings) {
+        Ok(state) if state.quality >= settings.max_quality => Some(ProgressOnlyReason::TrainedEye),
+        _ => None,
+    }
+}
+
+/// Finds the macro with the fewest steps, then the shortest duration, that maxes out Progress
+/// from `initial_state`. Only uses the `FinishSolver` to prune states, because Quality doesn't
+/// matter.
+pub fn solve_progress_only(
+    initial_state: SimulationState,
+    settings: &SolverSettings,
+    interrupt_signal: &AtomicFlag,
+    finish_solver: &mut FinishSolver,
+) -> Result<Vec<Action>, SolverException> {
+    let _timer = ScopedTimer::new("Progress only");
+    let simulator_settings = &settings.simulator_settings;
+
+    // states by number of steps, with the shortest duration to reach each state
+    let mut layers: Vec<HashMap<SimulationState, (u8, Vec<ActionCombo>)>> =
+        vec![HashMap::default()];
+    layers[0].insert(normalize_state(initial_state), (0, Vec::new()));
+    let mut solution: Option<(usize, u8, Vec<ActionCombo>)> = None;
+
+    let mut steps = 0;
+    while steps < layers.len()
+        && solution
+            .as_ref()
+            .is_none_or(|(solution_steps, _, _)| steps < *solution_steps)
+    {
+        for (state, (duration, combos)) in std::mem::take(&mut layers[steps]) {
+            if interrupt_signal.is_set() {
+                return Err(SolverException::Interrupted);
+            }
+            for action in PROGRESS_ONLY_SEARCH_ACTIONS {
+                let Ok(new_state) = use_action_combo(settings, state, *action) else {
+                    continue;
+                };
+                let new_steps = steps + usize::from(action.steps());
+                let new_duration = duration + action.duration();
+                if new_state.progress >= simulator_settings.max_progress {
+                    let is_better =
+                        solution
+                            .as_ref()
+                            .is_none_or(|(solution_steps, solution_duration, _)| {
+                                (new_steps, new_duration) < (*solution_steps, *solution_duration)
+                            });
+                    if is_better {
+                        let combos = combos.iter().copied().chain([*action]).collect();
+                        solution = Some((new_steps, new_duration, combos));
+                    }
+                } else if !new_state.is_final(simulator_settings)
+                    && finish_solver.can_finish(&new_state)
+                {
+                    if layers.len() <= new_steps {
+                        layers.resize_with(new_steps + 1, HashMap::default);
+                    }
+                    let entry = layers[new_steps].entry(normalize_state(new_state));
+                    let entry = entry.or_insert_with(|| (u8::MAX, Vec::new()));
+                    if new_duration < entry.0 {
+                        *entry = (
+                            new_duration,
+                            combos.iter().copied().chain([*action]).collect(),
+                        );
+                    }
+                }
+            }
+        }
+        steps += 1;
+    }
+
+    let (_, _, combos) = solution.ok_or(SolverException::NoSolution)?;
+    Ok(combos
+        .iter()
+        .flat_map(|combo| combo.actions().iter().copied())
+        .collect())
+}
+
+/// Quality doesn't matter, so states that only differ in Quality are merged.
+fn normalize_state(state: SimulationState) -> SimulationState {
+    SimulationState {
+        quality: 0,
+        unreliable_quality: 0,
+        effects: state
+            .effects
+            .with_inner_quiet(0)
+            .with_innovation(0)
+            .with_great_strides(0)
+            .with_guard(0)
+            .with_quick_innovation_available(false),
+        ..state
+    }
+}
@@ -2,35 +2,56 @@ use std::collections::BTreeMap;
 
 use raphael_sim::{Action, SimulationState};
 
-use crate::{actions::ActionCombo, utils::Backtracking};
+use crate::{
+    SolverException,
+    actions::ActionCombo,
+    utils::{Backtracking, MemoryBudget, MemoryTracker},
+};
 
 use super::pareto_front::{EffectParetoFront, QualityParetoFront};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SearchScore {
     pub quality_upper_bound: u16,
+    /// Lower bound on the total CP spent by the macro. Only used when minimizing CP,
+    /// otherwise it is `i16::MAX` for all scores.
+    pub cp_lower_bound: i16,
     pub steps_lower_bound: u8,
     pub duration_lower_bound: u8,
     pub current_steps: u8,
     pub current_duration: u8,
+    /// Weighted number of action blocks, see [`crate::MacroSolver::human_friendly_bias`].
+    /// Always 0 if the bias is disabled.
+    pub human_friendly_penalty: u16,
 }
 
 impl SearchScore {
     pub const MIN: Self = Self {
         quality_upper_bound: 0,
+        cp_lower_bound: i16::MAX,
         steps_lower_bound: u8::MAX,
         duration_lower_bound: u8::MAX,
         current_steps: u8::MAX,
         current_duration: u8::MAX,
+        human_friendly_penalty: u16::MAX,
     };
 
     pub const MAX: Self = Self {
         quality_upper_bound: u16::MAX,
+        cp_lower_bound: 0,
         steps_lower_bound: 0,
         duration_lower_bound: 0,
         current_steps: 0,
         current_duration: 0,
+        human_friendly_penalty: 0,
     };
+
+    /// Duration lower bound in tenths of a second, with the human-friendly penalty added as a
+    /// tie-break term.
+    fn weighted_duration_lower_bound(&self) -> u32 {
+        u32::from(self.duration_lower_bound) * 10 + u32::from(self.human_friendly_penalty)
+    }
 }
 
 impl std::cmp::PartialOrd for SearchScore {
@@ -43,20 +64,44 @@ impl std::cmp::Ord for SearchScore {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.quality_upper_bound
             .cmp(&other.quality_upper_bound)
+            .then(other.cp_lower_bound.cmp(&self.cp_lower_bound))
             .then(other.steps_lower_bound.cmp(&self.steps_lower_bound))
-            .then(other.duration_lower_bound.cmp(&self.duration_lower_bound))
+            .then(
+                other
+                    .weighted_duration_lower_bound()
+                    .cmp(&self.weighted_duration_lower_bound()),
+            )
             .then(other.current_steps.cmp(&self.current_steps))
             .then(other.current_duration.cmp(&self.current_duration))
+            .then(
+                other
+                    .human_friendly_penalty
+                    .cmp(&self.human_friendly_penalty),
+            )
     }
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct SearchNode {
     state: SimulationState,
     action: ActionCombo,
     parent_id: usize,
 }
 
+/// Contents of a [`SearchQueue`] without its memory tracker, see [`crate::SolverCheckpoint`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchQueueCheckpoint {
+    quality_pareto_front: QualityParetoFront,
+    effect_pareto_front: EffectParetoFront,
+    buckets: BTreeMap<SearchScore, Vec<SearchNode>>,
+    backtracking: Backtracking<ActionCombo>,
+    current_score: SearchScore,
+    current_nodes: Vec<(SimulationState, usize)>,
+    minimum_score: SearchScore,
+    queued_nodes: usize,
+}
+
 pub struct SearchQueue {
     quality_pareto_front: QualityParetoFront,
     effect_pareto_front: EffectParetoFront,
@@ -65,10 +110,17 @@ pub struct SearchQueue {
     current_score: SearchScore,
     current_nodes: Vec<(SimulationState, usize)>,
     minimum_score: SearchScore,
+    /// Number of nodes in all buckets, for estimating the memory usage.
+    queued_nodes: usize,
+    memory_tracker: MemoryTracker,
 }
 
 impl SearchQueue {
-    pub fn new(initial_state: SimulationState, minimum_score: SearchScore) -> Self {
+    pub fn new(
+        initial_state: SimulationState,
+        minimum_score: SearchScore,
+        memory_budget: &MemoryBudget,
+    ) -> Self {
         log::debug!("New minimum score: {:?}", minimum_score);
         Self {
             quality_pareto_front: QualityParetoFront::default(),
@@ -78,6 +130,38 @@ impl SearchQueue {
             current_score: SearchScore::MAX,
             current_nodes: vec![(initial_state, Backtracking::<Action>::SENTINEL)],
             minimum_score,
+            queued_nodes: 0,
+            memory_tracker: memory_budget.tracker(),
+        }
+    }
+
+    pub fn from_checkpoint(
+        checkpoint: SearchQueueCheckpoint,
+        memory_budget: &MemoryBudget,
+    ) -> Self {
+        Self {
+            quality_pareto_front: checkpoint.quality_pareto_front,
+            effect_pareto_front: checkpoint.effect_pareto_front,
+            buckets: checkpoint.buckets,
+            backtracking: checkpoint.backtracking,
+            current_score: checkpoint.current_score,
+            current_nodes: checkpoint.current_nodes,
+            minimum_score: checkpoint.minimum_score,
+            queued_nodes: checkpoint.queued_nodes,
+            memory_tracker: memory_budget.tracker(),
+        }
+    }
+
+    pub fn into_checkpoint(self) -> SearchQueueCheckpoint {
+        SearchQueueCheckpoint {
+            quality_pareto_front: self.quality_pareto_front,
+            effect_pareto_front: self.effect_pareto_front,
+            buckets: self.buckets,
+            backtracking: self.backtracking,
+            current_score: self.current_score,
+            current_nodes: self.current_nodes,
+            minimum_score: self.minimum_score,
+            queued_nodes: self.queued_nodes,
         }
     }
 
@@ -93,6 +177,7 @@ impl SearchQueue {
             }
             dropped += self.buckets.pop_first().unwrap().1.len();
         }
+        self.queued_nodes -= dropped;
         log::debug!("New minimum score: {:?}", score);
         log::debug!("Nodes dropped: {}", dropped);
     }
@@ -112,6 +197,7 @@ impl SearchQueue {
                 action,
                 parent_id,
             });
+            self.queued_nodes += 1;
         }
     }
 
@@ -123,6 +209,7 @@ impl SearchQueue {
                     pareto_weight(&rhs.state).cmp(&pareto_weight(&lhs.state))
                 });
                 self.current_score = score;
+                self.queued_nodes -= bucket.len();
                 self.current_nodes = bucket
                     .into_iter()
                     .filter(|node| {
@@ -142,9 +229,23 @@ impl SearchQueue {
         Some((state, self.current_score, backtrack_id))
     }
 
+    /// Reports the memory usage of the queue, the Pareto fronts, and the backtracking tree.
+    pub fn update_memory_usage(&mut self) -> Result<(), SolverException> {
+        let usage = self.quality_pareto_front.memory_usage()
+            + self.effect_pareto_front.memory_usage()
+            + self.backtracking.memory_usage()
+            + self.queued_nodes * std::mem::size_of::<SearchNode>();
+        self.memory_tracker.update(usage)
+    }
+
     pub fn backtrack(&self, backtrack_id: usize) -> impl Iterator<Item = ActionCombo> {
         self.backtracking.get_items(backtrack_id)
     }
+
+    /// The action that led to the node, `None` for the initial state.
+    pub fn last_action(&self, backtrack_id: usize) -> Option<ActionCombo> {
+        self.backtracking.get(backtrack_id)
+    }
 }
 
 fn pareto_weight(state: &SimulationState) -> u32 {
@@ -1,28 +1,37 @@
 use raphael_sim::*;
 
+use super::checkpoint::{SearchProgress, SolverCheckpoint};
 use super::search_queue::SearchScore;
+use super::stats::SolverStats;
 use crate::actions::{
     ActionCombo, FULL_SEARCH_ACTIONS, PROGRESS_ONLY_SEARCH_ACTIONS, is_progress_only_state,
-    use_action_combo,
+    use_action_combo, uses_quality_too_early,
 };
 use crate::macro_solver::fast_lower_bound::fast_lower_bound;
+use crate::macro_solver::progress_only::{
+    ProgressOnlyReason, progress_only_reason, solve_progress_only,
+};
 use crate::macro_solver::search_queue::SearchQueue;
 use crate::utils::AtomicFlag;
+use crate::utils::MemoryBudget;
 use crate::utils::ScopedTimer;
 use crate::{
-    FinishSolver, QualityUpperBoundSolver, SolverException, SolverSettings, StepLowerBoundSolver,
+    FinishSolver, QualityUpperBoundSolver, SolverException, SolverHeuristics, SolverSettings,
+    StepLowerBoundSolver, cp_lower_bound,
 };
 
+use std::num::NonZeroU8;
 use std::vec::Vec;
 
 #[derive(Clone)]
-struct Solution {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct Solution {
     score: (SearchScore, u16),
     solver_actions: Vec<ActionCombo>,
 }
 
 impl Solution {
-    fn actions(&self) -> Vec<Action> {
+    pub(super) fn actions(&self) -> Vec<Action> {
         let mut actions = Vec::new();
         for solver_action in &self.solver_actions {
             actions.extend_from_slice(solver_action.actions());
@@ -31,6 +40,32 @@ impl Solution {
     }
 }
 
+/// A macro on the frontier of steps vs. Quality, see [`MacroSolver::solve_pareto_front`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParetoSolution {
+    pub actions: Vec<Action>,
+    /// Quality at the end of the macro, capped at the max Quality.
+    pub quality: u16,
+}
+
+/// Openers that players know by heart, see [`MacroSolver::human_friendly_bias`].
+const COMMON_OPENERS: &[ActionCombo] = &[
+    ActionCombo::Single(Action::MuscleMemory),
+    ActionCombo::Single(Action::Reflect),
+    ActionCombo::Single(Action::TrainedEye),
+];
+
+/// The search only tracks combos within its action combos, so states after a prefix that ends
+/// in the middle of a combo are searched as if no combo was started. A combo only makes actions
+/// cheaper or stronger, so the found macro still works after the prefix.
+fn without_prefix_combo(mut state: SimulationState) -> SimulationState {
+    if state.combo != Combo::SynthesisBegin {
+        state.combo = Combo::None;
+    }
+    state
+}
+
 type SolutionCallback<'a> = dyn Fn(&[Action]) + 'a;
 type ProgressCallback<'a> = dyn Fn(usize) + 'a;
 
@@ -39,6 +74,12 @@ pub struct MacroSolver<'a> {
     solution_callback: Box<SolutionCallback<'a>>,
     progress_callback: Box<ProgressCallback<'a>>,
     interrupt_signal: AtomicFlag,
+    memory_budget: MemoryBudget,
+    minimize_cp: bool,
+    human_friendly_bias: u8,
+    checkpoint_signal: AtomicFlag,
+    checkpoint: Option<SolverCheckpoint>,
+    stats: SolverStats,
 }
 
 impl<'a> MacroSolver<'a> {
@@ -53,12 +94,112 @@ impl<'a> MacroSolver<'a> {
             solution_callback,
             progress_callback,
             interrupt_signal,
+            memory_budget: MemoryBudget::default(),
+            minimize_cp: false,
+            human_friendly_bias: 0,
+            checkpoint_signal: AtomicFlag::new(),
+            checkpoint: None,
+            stats: SolverStats::default(),
         }
     }
 
+    /// Among the macros that reach the max Quality, prefer the one that spends the least CP,
+    /// before preferring fewer steps and a shorter duration.
+    #[must_use]
+    pub fn minimize_cp(mut self, minimize_cp: bool) -> Self {
+        self.minimize_cp = minimize_cp;
+        self
+    }
+
+    /// Among the macros with the same Quality and steps, prefer macros that are easier to execute
+    /// by hand, i.e. that repeat an action back to back instead of switching actions and that
+    /// start with a common opener. `weight` is the macro duration in tenths of a second that
+    /// one fewer block of actions is worth, 0 disables the bias.
+    #[must_use]
+    pub fn human_friendly_bias(mut self, weight: u8) -> Self {
+        self.human_friendly_bias = weight;
+        self
+    }
+
+    /// Limits the estimated memory usage of the solver to `limit` bytes. The solver fails with
+    /// `SolverException::MemoryLimitReached` when the limit is exceeded.
+    #[must_use]
+    pub fn memory_limit(mut self, limit: Option<usize>) -> Self {
+        let memory_budget = MemoryBudget::new(limit);
+        #[cfg(feature = "disk-spill")]
+        let memory_budget =
+            memory_budget.with_spill_dir(self.memory_budget.spill_dir().map(Into::into));
+        self.memory_budget = memory_budget;
+        self
+    }
+
+    /// Keeps the solved states of the quality upper bound solver in temporary memory-mapped
+    /// files in `dir` instead of in memory, for solves whose states don't fit into RAM, e.g.
+    /// adversarial solves that minimize steps. `None` keeps them in memory.
+    #[cfg(feature = "disk-spill")]
+    #[must_use]
+    pub fn spill_to_disk(mut self, dir: Option<std::path::PathBuf>) -> Self {
+        self.memory_budget = self.memory_budget.with_spill_dir(dir);
+        self
+    }
+
+    /// Stops the search when `signal` is set and keeps its progress, which
+    /// [`Self::take_checkpoint`] returns after the solve failed with
+    /// `SolverException::Interrupted`. Only [`Self::solve`] and [`Self::solve_from`] keep their
+    /// progress, other solves are just interrupted.
+    #[must_use]
+    pub fn checkpoint_signal(mut self, signal: AtomicFlag) -> Self {
+        self.checkpoint_signal = signal;
+        self
+    }
+
+    /// Continues the search of `checkpoint` instead of starting a new one. The solver must be
+    /// created with [`SolverCheckpoint::settings`], use the same options as the stopped solver
+    /// and solve from [`SolverCheckpoint::initial_state`].
+    #[must_use]
+    pub fn resume(mut self, checkpoint: Option<SolverCheckpoint>) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    /// Progress of the search stopped by [`Self::checkpoint_signal`], `None` if the last solve
+    /// wasn't stopped that way.
+    pub fn take_checkpoint(&mut self) -> Option<SolverCheckpoint> {
+        self.checkpoint.take()
+    }
+
+    /// Performance counters of the last solve, also after the solve failed.
+    pub fn stats(&self) -> &SolverStats {
+        &self.stats
+    }
+
+    /// Branch pruning values that the solver derives from its settings.
+    pub fn heuristics(&self) -> SolverHeuristics {
+        SolverHeuristics::new(&self.settings)
+    }
+
     pub fn solve(&mut self) -> Result<Vec<Action>, SolverException> {
-        let initial_state = SimulationState::new(&self.settings.simulator_settings);
+        self.solve_from(SimulationState::new(&self.settings.simulator_settings))
+    }
 
+    /// Like [`Self::solve`], but continues from `initial_state`, e.g. the state after a fixed
+    /// prefix of actions. The returned actions don't include the actions of the prefix.
+    /// A combo started by the last action of the prefix isn't continued.
+    pub fn solve_from(
+        &mut self,
+        initial_state: SimulationState,
+    ) -> Result<Vec<Action>, SolverException> {
+        let start = self.reset_stats();
+        let initial_state = without_prefix_combo(initial_state);
+        let checkpoint = self.checkpoint.take();
+        if let Some(checkpoint) = &checkpoint {
+            checkpoint.check_matches(
+                &self.settings,
+                initial_state,
+                self.minimize_cp,
+                self.human_friendly_bias,
+            )?;
+        }
         let mut finish_solver = FinishSolver::new(self.settings);
         let timer = ScopedTimer::new("Finish Solver");
         if !finish_solver.can_finish(&initial_state) {
@@ -66,14 +207,157 @@ impl<'a> MacroSolver<'a> {
         }
         drop(timer);
 
+        // the Progress-only solve doesn't minimize CP, and a checkpoint is only saved by a search
+        let progress_only_actions = match self.minimize_cp || checkpoint.is_some() {
+            true => None,
+            false => self.try_solve_progress_only(initial_state, &mut finish_solver)?,
+        };
+        if let Some(actions) = progress_only_actions {
+            self.stats.elapsed = start.elapsed();
+            (self.solution_callback)(&actions);
+            return Ok(actions);
+        }
+
+        let (mut quality_ub_solver, mut step_lb_solver) = match &checkpoint {
+            Some(checkpoint) => self.restore_bound_solvers(checkpoint)?,
+            None => self.initialize_bound_solvers()?,
+        };
+        let mut search_progress = checkpoint.map(|checkpoint| checkpoint.search);
+
+        let _timer = ScopedTimer::new("Search");
+        let result = self.do_solve(
+            initial_state,
+            &mut finish_solver,
+            &mut quality_ub_solver,
+            &mut step_lb_solver,
+            None,
+            &mut search_progress,
+        );
+        self.record_stats(start, &quality_ub_solver, &step_lb_solver);
+        if let Some(search) = search_progress {
+            self.checkpoint = Some(SolverCheckpoint {
+                settings: self.settings,
+                initial_state,
+                minimize_cp: self.minimize_cp,
+                human_friendly_bias: self.human_friendly_bias,
+                search,
+                quality_ub_states: quality_ub_solver.checkpoint(),
+                step_lb_states: step_lb_solver.checkpoint(),
+            });
+        }
+        Ok(result?.actions())
+    }
+
+    /// Finds the macros on the frontier of steps vs. Quality, i.e. the macros for which no
+    /// other macro reaches the same Quality in fewer steps. Ordered from most to fewest steps,
+    /// so the first macro is the one [`Self::solve`] would find.
+    pub fn solve_pareto_front(&mut self) -> Result<Vec<ParetoSolution>, SolverException> {
+        self.solve_pareto_front_from(SimulationState::new(&self.settings.simulator_settings))
+    }
+
+    /// Like [`Self::solve_pareto_front`], but continues from `initial_state`, see
+    /// [`Self::solve_from`].
+    pub fn solve_pareto_front_from(
+        &mut self,
+        initial_state: SimulationState,
+    ) -> Result<Vec<ParetoSolution>, SolverException> {
+        let start = self.reset_stats();
+        let initial_state = without_prefix_combo(initial_state);
+        let mut finish_solver = FinishSolver::new(self.settings);
+        if !finish_solver.can_finish(&initial_state) {
+            return Err(SolverException::NoSolution);
+        }
+        let (mut quality_ub_solver, mut step_lb_solver) = self.initialize_bound_solvers()?;
+
+        let _timer = ScopedTimer::new("Pareto front search");
+        let mut front: Vec<ParetoSolution> = Vec::new();
+        let mut max_steps = None;
+        loop {
+            // the bound solvers are shared by all searches
+            let solution = match self.do_solve(
+                initial_state,
+                &mut finish_solver,
+                &mut quality_ub_solver,
+                &mut step_lb_solver,
+                max_steps,
+                &mut None,
+            ) {
+                Ok(solution) => solution,
+                Err(SolverException::NoSolution) => break,
+                Err(exception) => {
+                    self.record_stats(start, &quality_ub_solver, &step_lb_solver);
+                    return Err(exception);
+                }
+            };
+            let (score, _) = solution.score;
+            let solution = ParetoSolution {
+                actions: solution.actions(),
+                quality: score.quality_upper_bound,
+            };
+            // the search only minimizes steps among macros that reach the max Quality
+            if front
+                .last()
+                .is_some_and(|last| last.quality <= solution.quality)
+            {
+                front.pop();
+            }
+            front.push(solution);
+            match score.current_steps {
+                0 | 1 => break,
+                steps => max_steps = Some(steps - 1),
+            }
+        }
+        self.record_stats(start, &quality_ub_solver, &step_lb_solver);
+        match front.is_empty() {
+            true => Err(SolverException::NoSolution),
+            false => Ok(front),
+        }
+    }
+
+    /// Clears the counters of the previous solve and returns the start time of the new one.
+    fn reset_stats(&mut self) -> web_time::Instant {
+        self.stats = SolverStats {
+            threads: rayon::current_num_threads(),
+            ..SolverStats::default()
+        };
+        web_time::Instant::now()
+    }
+
+    /// Fills in the counters of [`Self::stats`] that are collected at the end of a solve.
+    fn record_stats(
+        &mut self,
+        start: web_time::Instant,
+        quality_ub_solver: &QualityUpperBoundSolver,
+        step_lb_solver: &StepLowerBoundSolver,
+    ) {
+        self.stats.elapsed = start.elapsed();
+        self.stats.peak_memory = self.memory_budget.peak();
+        self.stats.quality_ub_cache = quality_ub_solver.cache_stats();
+        self.stats.step_lb_cache = step_lb_solver.cache_stats();
+    }
+
+    fn initialize_bound_solvers(
+        &self,
+    ) -> Result<(QualityUpperBoundSolver, StepLowerBoundSolver), SolverException> {
         fn initialize_quality_ub_solver(
             settings: SolverSettings,
             interrupt_signal: AtomicFlag,
+            memory_budget: &MemoryBudget,
         ) -> QualityUpperBoundSolver {
             let _timer = ScopedTimer::new("Quality UB Solver");
             let mut seed_state = SimulationState::new(&settings.simulator_settings);
             seed_state.combo = Combo::None;
-            let mut quality_ub_solver = QualityUpperBoundSolver::new(settings, interrupt_signal);
+            let mut quality_ub_solver =
+                QualityUpperBoundSolver::new(settings, interrupt_signal, memory_budget);
+            #[cfg(feature = "gpu")]
+            match crate::quality_upper_bound_solver::GpuFrontMerger::new() {
+                Ok(gpu) => {
+                    if let Err(error) = quality_ub_solver.precompute_with_gpu(seed_state, &gpu) {
+                        log::warn!("GPU precomputation failed: {error:?}");
+                    }
+                }
+                Err(error) => log::warn!("GPU unavailable: {error}"),
+            }
             _ = quality_ub_solver.quality_upper_bound(seed_state);
             quality_ub_solver
         }
@@ -81,29 +365,115 @@ impl<'a> MacroSolver<'a> {
         fn initialize_step_lb_solver(
             settings: SolverSettings,
             interrupt_signal: AtomicFlag,
+            memory_budget: &MemoryBudget,
         ) -> StepLowerBoundSolver {
             let _timer = ScopedTimer::new("Step LB Solver");
             let mut seed_state = SimulationState::new(&settings.simulator_settings);
             seed_state.combo = Combo::None;
-            let mut step_lb_solver = StepLowerBoundSolver::new(settings, interrupt_signal);
+            let mut step_lb_solver =
+                StepLowerBoundSolver::new(settings, interrupt_signal, memory_budget);
             _ = step_lb_solver.step_lower_bound_with_hint(seed_state, 0);
             step_lb_solver
         }
 
-        let (mut quality_ub_solver, mut step_lb_solver) = rayon::join(
-            || initialize_quality_ub_solver(self.settings, self.interrupt_signal.clone()),
-            || initialize_step_lb_solver(self.settings, self.interrupt_signal.clone()),
+        // the closures must not capture `self`, as the callbacks are not `Sync`
+        let settings = self.settings;
+        let interrupt_signal = &self.interrupt_signal;
+        let memory_budget = &self.memory_budget;
+        let (quality_ub_solver, step_lb_solver) = rayon::join(
+            || initialize_quality_ub_solver(settings, interrupt_signal.clone(), memory_budget),
+            || initialize_step_lb_solver(settings, interrupt_signal.clone(), memory_budget),
         );
 
-        let _timer = ScopedTimer::new("Search");
-        Ok(self
-            .do_solve(
-                initial_state,
-                &mut finish_solver,
-                &mut quality_ub_solver,
-                &mut step_lb_solver,
-            )?
-            .actions())
+        // the memory limit may already have been reached during initialization
+        self.memory_budget.check()?;
+        Ok((quality_ub_solver, step_lb_solver))
+    }
+
+    /// Bound solvers with the solved states of `checkpoint`.
+    fn restore_bound_solvers(
+        &self,
+        checkpoint: &SolverCheckpoint,
+    ) -> Result<(QualityUpperBoundSolver, StepLowerBoundSolver), SolverException> {
+        let mut quality_ub_solver = QualityUpperBoundSolver::new(
+            self.settings,
+            self.interrupt_signal.clone(),
+            &self.memory_budget,
+        );
+        quality_ub_solver.restore_checkpoint(&checkpoint.quality_ub_states)?;
+        let mut step_lb_solver = StepLowerBoundSolver::new(
+            self.settings,
+            self.interrupt_signal.clone(),
+            &self.memory_budget,
+        );
+        step_lb_solver.restore_checkpoint(&checkpoint.step_lb_states)?;
+        Ok((quality_ub_solver, step_lb_solver))
+    }
+
+    /// Skips the Quality bounds entirely if Quality is already maxed out or Trained Eye can
+    /// max it out. Returns `None` if the solve also has to increase Quality.
+    fn try_solve_progress_only(
+        &self,
+        initial_state: SimulationState,
+        finish_solver: &mut FinishSolver,
+    ) -> Result<Option<Vec<Action>>, SolverException> {
+        let settings = &self.settings.simulator_settings;
+        let Some(reason) = progress_only_reason(settings, &initial_state) else {
+            return Ok(None);
+        };
+        let mut state = initial_state;
+        for action in reason.prefix() {
+            state = state
+                .use_action(*action, Condition::Normal, settings)
+                .map_err(|error| SolverException::InternalError(error.to_string()))?;
+        }
+        if !finish_solver.can_finish(&state) {
+            // e.g. Trained Eye costs too much CP to finish the craft afterwards
+            return Ok(None);
+        }
+        let actions =
+            solve_progress_only(state, &self.settings, &self.interrupt_signal, finish_solver)?;
+        let actions = [reason.prefix(), actions.as_slice()].concat();
+        if reason == ProgressOnlyReason::TrainedEye
+            && !self.trained_eye_is_optimal(initial_state, actions.len())?
+        {
+            return Ok(None);
+        }
+        log::info!("{}", reason.description());
+        Ok(Some(actions))
+    }
+
+    /// Whether every macro that maxes out Quality without starting with Trained Eye takes more
+    /// than `steps` steps, i.e. the normal solve can't find a better macro.
+    fn trained_eye_is_optimal(
+        &self,
+        initial_state: SimulationState,
+        steps: usize,
+    ) -> Result<bool, SolverException> {
+        let settings = &self.settings.simulator_settings;
+        let mut step_lb_solver = StepLowerBoundSolver::new(
+            self.settings,
+            self.interrupt_signal.clone(),
+            &self.memory_budget,
+        );
+        for action in FULL_SEARCH_ACTIONS {
+            if *action == ActionCombo::Single(Action::TrainedEye) {
+                continue;
+            }
+            let Ok(state) = use_action_combo(&self.settings, initial_state, *action) else {
+                continue;
+            };
+            let steps_lower_bound = match state.progress >= settings.max_progress {
+                true if state.quality >= settings.max_quality => 0,
+                true => continue,
+                false if state.is_final(settings) => continue,
+                false => step_lb_solver.step_lower_bound_with_hint(state, 0)?,
+            };
+            if usize::from(action.steps()) + usize::from(steps_lower_bound) <= steps {
+                return Ok(false);
+            }
+        }
+        Ok(true)
     }
 
     fn do_solve(
@@ -112,35 +482,80 @@ impl<'a> MacroSolver<'a> {
         finish_solver: &mut FinishSolver,
         quality_ub_solver: &mut QualityUpperBoundSolver,
         step_lb_solver: &mut StepLowerBoundSolver,
+        max_steps: Option<u8>,
+        search_progress: &mut Option<SearchProgress>,
     ) -> Result<Solution, SolverException> {
-        let mut search_queue = {
-            let quality_lower_bound = fast_lower_bound(
-                state,
-                &self.settings,
-                self.interrupt_signal.clone(),
+        if let Some(progress) = search_progress.take() {
+            let search_queue =
+                SearchQueue::from_checkpoint(progress.search_queue, &self.memory_budget);
+            return self.search(
+                search_queue,
+                progress.solution,
+                progress.popped,
                 finish_solver,
                 quality_ub_solver,
-            )?;
+                step_lb_solver,
+                max_steps,
+                search_progress,
+            );
+        }
+        let search_queue = {
+            // the macro of the fast lower bound may have more steps than allowed
+            let quality_lower_bound = match max_steps {
+                Some(_) => 0,
+                None => fast_lower_bound(
+                    state,
+                    &self.settings,
+                    self.interrupt_signal.clone(),
+                    finish_solver,
+                    quality_ub_solver,
+                )?,
+            };
             let minimum_score = SearchScore {
                 quality_upper_bound: quality_lower_bound,
                 ..SearchScore::MIN
             };
-            SearchQueue::new(state, minimum_score)
+            SearchQueue::new(state, minimum_score, &self.memory_budget)
         };
+        self.search(
+            search_queue,
+            None,
+            0,
+            finish_solver,
+            quality_ub_solver,
+            step_lb_solver,
+            max_steps,
+            search_progress,
+        )
+    }
 
-        let mut solution: Option<Solution> = None;
-
-        let mut popped = 0;
+    /// Searches the queue until it is empty. If the checkpoint signal is set, the queue and the
+    /// best solution so far are moved into `search_progress`.
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &mut self,
+        mut search_queue: SearchQueue,
+        mut solution: Option<Solution>,
+        mut popped: usize,
+        finish_solver: &mut FinishSolver,
+        quality_ub_solver: &mut QualityUpperBoundSolver,
+        step_lb_solver: &mut StepLowerBoundSolver,
+        max_steps: Option<u8>,
+        search_progress: &mut Option<SearchProgress>,
+    ) -> Result<Solution, SolverException> {
         while let Some((state, score, backtrack_id)) = search_queue.pop() {
             if self.interrupt_signal.is_set() {
                 return Err(SolverException::Interrupted);
             }
 
             popped += 1;
-            if popped % (1 << 12) == 0 {
+            self.stats.nodes += 1;
+            if popped.is_multiple_of(1 << 12) {
                 (self.progress_callback)(popped);
+                search_queue.update_memory_usage()?;
             }
 
+            let previous_action = search_queue.last_action(backtrack_id);
             let progress_only = is_progress_only_state(&self.settings, &state);
             let search_actions = match progress_only {
                 true => PROGRESS_ONLY_SEARCH_ACTIONS,
@@ -148,6 +563,16 @@ impl<'a> MacroSolver<'a> {
             };
 
             for action in search_actions {
+                let current_steps = score.current_steps + action.steps();
+                let human_friendly_penalty = score
+                    .human_friendly_penalty
+                    .saturating_add(self.human_friendly_penalty(previous_action, *action));
+                if max_steps.is_some_and(|max_steps| current_steps > max_steps) {
+                    continue;
+                }
+                if uses_quality_too_early(&self.settings, score.current_steps, *action) {
+                    continue;
+                }
                 if let Ok(state) = use_action_combo(&self.settings, state, *action) {
                     if !state.is_final(&self.settings.simulator_settings) {
                         if !finish_solver.can_finish(&state) {
@@ -155,13 +580,17 @@ impl<'a> MacroSolver<'a> {
                             continue;
                         }
 
-                        search_queue.update_min_score(SearchScore {
-                            quality_upper_bound: std::cmp::min(
-                                state.quality,
-                                self.settings.simulator_settings.max_quality,
-                            ),
-                            ..SearchScore::MIN
-                        });
+                        // with a step limit, the Quality of a state may not be reachable by a
+                        // macro that still maxes out Progress in time
+                        if max_steps.is_none() {
+                            search_queue.update_min_score(SearchScore {
+                                quality_upper_bound: std::cmp::min(
+                                    state.quality,
+                                    self.settings.simulator_settings.max_quality,
+                                ),
+                                ..SearchScore::MIN
+                            });
+                        }
 
                         let quality_upper_bound =
                             if state.quality >= self.settings.simulator_settings.max_quality {
@@ -172,6 +601,36 @@ impl<'a> MacroSolver<'a> {
                                     quality_ub_solver.quality_upper_bound(state)?,
                                 )
                             };
+                        let quality_upper_bound = match max_steps {
+                            Some(max_steps) => match NonZeroU8::new(max_steps - current_steps) {
+                                Some(remaining_steps) => std::cmp::min(
+                                    quality_upper_bound,
+                                    step_lb_solver.quality_upper_bound(state, remaining_steps)?,
+                                ),
+                                // Progress can't be maxed out without another step
+                                None => continue,
+                            },
+                            None => quality_upper_bound,
+                        };
+
+                        let cp_lower_bound = match self.minimize_cp {
+                            true => {
+                                let cp_spent = self.settings.simulator_settings.max_cp - state.cp;
+                                let remaining_cp = match quality_upper_bound
+                                    >= self.settings.simulator_settings.max_quality
+                                {
+                                    true => cp_lower_bound(
+                                        quality_ub_solver,
+                                        state,
+                                        self.settings.simulator_settings.max_quality,
+                                    )?
+                                    .unwrap_or(0),
+                                    false => 0,
+                                };
+                                std::cmp::max(score.cp_lower_bound, cp_spent + remaining_cp)
+                            }
+                            false => i16::MAX,
+                        };
 
                         let step_lb_hint = score
                             .steps_lower_bound
@@ -189,12 +648,14 @@ impl<'a> MacroSolver<'a> {
                             state,
                             SearchScore {
                                 quality_upper_bound,
+                                cp_lower_bound,
                                 steps_lower_bound,
                                 duration_lower_bound: score.current_duration
                                     + action.duration()
                                     + 3,
                                 current_steps: score.current_steps + action.steps(),
                                 current_duration: score.current_duration + action.duration(),
+                                human_friendly_penalty,
                             },
                             *action,
                             backtrack_id,
@@ -205,10 +666,15 @@ impl<'a> MacroSolver<'a> {
                                 state.quality,
                                 self.settings.simulator_settings.max_quality,
                             ),
+                            cp_lower_bound: match self.minimize_cp {
+                                true => self.settings.simulator_settings.max_cp - state.cp,
+                                false => i16::MAX,
+                            },
                             steps_lower_bound: score.current_steps + action.steps(),
                             duration_lower_bound: score.current_duration + action.duration(),
                             current_steps: score.current_steps + action.steps(),
                             current_duration: score.current_duration + action.duration(),
+                            human_friendly_penalty,
                         };
                         search_queue.update_min_score(solution_score);
                         if solution.is_none()
@@ -226,8 +692,33 @@ impl<'a> MacroSolver<'a> {
                     }
                 }
             }
+
+            if self.checkpoint_signal.is_set() {
+                *search_progress = Some(SearchProgress {
+                    search_queue: search_queue.into_checkpoint(),
+                    solution,
+                    popped,
+                });
+                return Err(SolverException::Interrupted);
+            }
         }
 
         solution.ok_or(SolverException::NoSolution)
     }
+
+    /// Penalty of using `action` after `previous_action`, see [`Self::human_friendly_bias`].
+    fn human_friendly_penalty(
+        &self,
+        previous_action: Option<ActionCombo>,
+        action: ActionCombo,
+    ) -> u16 {
+        let starts_new_block = previous_action.map_or_else(
+            || !COMMON_OPENERS.contains(&action),
+            |previous_action| previous_action != action,
+        );
+        match starts_new_block {
+            true => u16::from(self.human_friendly_bias),
+            false => 0,
+        }
+    }
 }
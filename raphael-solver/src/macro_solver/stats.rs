@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+/// Lookups of states in the solved states of a bound solver.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheStats {
+    /// Lookups of states that were already solved.
+    pub hits: u64,
+    /// Lookups of states that had to be solved first.
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Fraction of the lookups that were hits, `None` if there were no lookups.
+    pub fn hit_rate(&self) -> Option<f32> {
+        match self.hits + self.misses {
+            0 => None,
+            lookups => Some(self.hits as f32 / lookups as f32),
+        }
+    }
+}
+
+/// Performance counters of the last solve of a [`crate::MacroSolver`], see
+/// [`crate::MacroSolver::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverStats {
+    /// Wall time of the solve, including the initialization of the bound solvers.
+    pub elapsed: Duration,
+    /// Nodes popped from the search queue.
+    pub nodes: usize,
+    /// Highest estimated memory usage of the solvers, in bytes.
+    pub peak_memory: usize,
+    pub quality_ub_cache: CacheStats,
+    pub step_lb_cache: CacheStats,
+    /// Threads of the thread pool that the solve ran on.
+    pub threads: usize,
+}
+
+impl SolverStats {
+    /// Searched nodes per second, `None` if no time has elapsed.
+    pub fn nodes_per_second(&self) -> Option<f32> {
+        match self.elapsed.is_zero() {
+            true => None,
+            false => Some(self.nodes as f32 / self.elapsed.as_secs_f32()),
+        }
+    }
+}
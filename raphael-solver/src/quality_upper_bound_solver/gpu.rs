@@ -0,0 +1,325 @@
+//! Experimental GPU offload of the Pareto front DP in [`super::QualityUpperBoundSolver`].
+//!
+//! Reduced states are solved bottom-up in layers: all states whose children are already solved
+//! are merged in a single compute dispatch, one invocation per state.
+
+use crate::utils::ParetoValue;
+
+/// Maximum number of values in a single Pareto front. The precomputation fails on longer fronts,
+/// which the CPU merge handles, and the solver falls back to the CPU.
+const MAX_FRONT_LENGTH: u32 = 1024;
+/// Maximum number of states per dispatch, keeps the output buffers within the default binding size limit.
+const MAX_JOBS_PER_DISPATCH: usize = 8192;
+/// Maximum number of input values per dispatch, keeps the input buffer within the default binding size limit.
+const MAX_VALUES_PER_DISPATCH: usize = 1 << 24;
+const WORKGROUP_SIZE: u32 = 64;
+const OVERFLOW: u32 = u32::MAX;
+
+fn pack(value: ParetoValue<u16, u16>) -> u32 {
+    value.first as u32 | ((value.second as u32) << 16)
+}
+
+fn unpack(value: u32) -> ParetoValue<u16, u16> {
+    ParetoValue::new(value as u16, (value >> 16) as u16)
+}
+
+/// A batch of Pareto front merge jobs.
+/// Each job computes the Pareto front of the union of its (shifted) children.
+#[derive(Default)]
+pub struct MergeBatch {
+    values: Vec<u32>,
+    children: Vec<[u32; 4]>,
+    jobs: Vec<[u32; 2]>,
+}
+
+impl MergeBatch {
+    pub fn begin_job(&mut self) {
+        let begin = self.children.len() as u32;
+        self.jobs.push([begin, begin]);
+    }
+
+    /// Adds a child front to the last job. Each value of the front is shifted by `first_shift` and `second_shift`.
+    pub fn add_child(
+        &mut self,
+        front: &[ParetoValue<u16, u16>],
+        first_shift: u16,
+        second_shift: u16,
+    ) {
+        let offset = self.values.len() as u32;
+        self.values.extend(front.iter().copied().map(pack));
+        self.children.push([
+            offset,
+            front.len() as u32,
+            first_shift as u32,
+            second_shift as u32,
+        ]);
+        self.jobs.last_mut().unwrap()[1] = self.children.len() as u32;
+    }
+
+    fn values_begin(&self, job: usize) -> usize {
+        let [begin, end] = self.jobs[job];
+        match begin < end {
+            true => self.children[begin as usize][0] as usize,
+            false => self.values_end_before(begin),
+        }
+    }
+
+    fn values_end(&self, job: usize) -> usize {
+        self.values_end_before(self.jobs[job][1])
+    }
+
+    /// End of the values of all children before `child`.
+    fn values_end_before(&self, child: u32) -> usize {
+        child.checked_sub(1).map_or(0, |last| {
+            let [offset, length, _, _] = self.children[last as usize];
+            (offset + length) as usize
+        })
+    }
+}
+
+pub struct GpuFrontMerger {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl GpuFrontMerger {
+    pub fn new() -> Result<Self, String> {
+        pollster::block_on(async {
+            let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+            let adapter = instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::HighPerformance,
+                    ..Default::default()
+                })
+                .await
+                .map_err(|error| error.to_string())?;
+            log::debug!("GpuFrontMerger - adapter: {:?}", adapter.get_info());
+            let (device, queue) = adapter
+                .request_device(&wgpu::DeviceDescriptor::default())
+                .await
+                .map_err(|error| error.to_string())?;
+            let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("pareto_front_merge"),
+                source: wgpu::ShaderSource::Wgsl(include_str!("merge.wgsl").into()),
+            });
+            let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("pareto_front_merge"),
+                layout: None,
+                module: &module,
+                entry_point: Some("main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                cache: None,
+            });
+            Ok(Self {
+                device,
+                queue,
+                pipeline,
+            })
+        })
+    }
+
+    /// Runs all jobs of the batch and returns the resulting Pareto fronts in job order.
+    /// Values are cut off at `max_first` and `max_second` the same way `ParetoFrontBuilder` does.
+    pub fn merge(
+        &self,
+        batch: &MergeBatch,
+        max_first: u16,
+        max_second: u16,
+    ) -> Result<Vec<Vec<ParetoValue<u16, u16>>>, String> {
+        let mut fronts = Vec::with_capacity(batch.jobs.len());
+        let mut chunk_begin = 0;
+        while chunk_begin < batch.jobs.len() {
+            // values of consecutive jobs are stored contiguously
+            let values_begin = batch.values_begin(chunk_begin);
+            let mut chunk_end = chunk_begin + 1;
+            while chunk_end < batch.jobs.len()
+                && chunk_end - chunk_begin < MAX_JOBS_PER_DISPATCH
+                && batch.values_end(chunk_end) - values_begin <= MAX_VALUES_PER_DISPATCH
+            {
+                chunk_end += 1;
+            }
+            let values_end = batch.values_end(chunk_end - 1);
+            if values_end - values_begin > MAX_VALUES_PER_DISPATCH {
+                return Err("Too many values in a single merge job".to_string());
+            }
+
+            let children_begin = batch.jobs[chunk_begin][0];
+            let children_end = batch.jobs[chunk_end - 1][1];
+            let children: Vec<[u32; 4]> = batch.children
+                [children_begin as usize..children_end as usize]
+                .iter()
+                .map(|&[offset, length, first_shift, second_shift]| {
+                    [
+                        offset - values_begin as u32,
+                        length,
+                        first_shift,
+                        second_shift,
+                    ]
+                })
+                .collect();
+            let jobs: Vec<[u32; 2]> = batch.jobs[chunk_begin..chunk_end]
+                .iter()
+                .map(|&[begin, end]| [begin - children_begin, end - children_begin])
+                .collect();
+            fronts.extend(self.dispatch(
+                &batch.values[values_begin..values_end],
+                &children,
+                &jobs,
+                max_first,
+                max_second,
+            )?);
+            chunk_begin = chunk_end;
+        }
+        Ok(fronts)
+    }
+
+    fn dispatch(
+        &self,
+        values: &[u32],
+        children: &[[u32; 4]],
+        jobs: &[[u32; 2]],
+        max_first: u16,
+        max_second: u16,
+    ) -> Result<Vec<Vec<ParetoValue<u16, u16>>>, String> {
+        use wgpu::util::DeviceExt;
+
+        let num_jobs = jobs.len() as u32;
+        let params = [
+            max_first as u32,
+            max_second as u32,
+            MAX_FRONT_LENGTH,
+            num_jobs,
+        ];
+        let init_buffer = |label: &str, contents: &[u8], usage: wgpu::BufferUsages| {
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(label),
+                    // zero-sized bindings are not allowed
+                    contents: if contents.is_empty() {
+                        &[0; 4]
+                    } else {
+                        contents
+                    },
+                    usage,
+                })
+        };
+        let empty_buffer = |label: &str, size: u64, usage: wgpu::BufferUsages| {
+            self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+
+        let params_buffer = init_buffer("params", &to_bytes(&params), wgpu::BufferUsages::UNIFORM);
+        let values_buffer = init_buffer("values", &to_bytes(values), wgpu::BufferUsages::STORAGE);
+        let children_buffer = init_buffer(
+            "children",
+            &to_bytes(children.as_flattened()),
+            wgpu::BufferUsages::STORAGE,
+        );
+        let jobs_buffer = init_buffer(
+            "jobs",
+            &to_bytes(jobs.as_flattened()),
+            wgpu::BufferUsages::STORAGE,
+        );
+
+        let fronts_size = num_jobs as u64 * MAX_FRONT_LENGTH as u64 * 4;
+        let lengths_size = num_jobs as u64 * 4;
+        let fronts_buffer = empty_buffer(
+            "fronts",
+            fronts_size,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let scratch_buffer = empty_buffer("scratch", fronts_size, wgpu::BufferUsages::STORAGE);
+        let lengths_buffer = empty_buffer(
+            "lengths",
+            lengths_size,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let readback_buffer = empty_buffer(
+            "readback",
+            fronts_size + lengths_size,
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("pareto_front_merge"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                &params_buffer,
+                &values_buffer,
+                &children_buffer,
+                &jobs_buffer,
+                &fronts_buffer,
+                &scratch_buffer,
+                &lengths_buffer,
+            ]
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect::<Vec<_>>(),
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(num_jobs.div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&fronts_buffer, 0, &readback_buffer, 0, fronts_size);
+        encoder.copy_buffer_to_buffer(
+            &lengths_buffer,
+            0,
+            &readback_buffer,
+            fronts_size,
+            lengths_size,
+        );
+        self.queue.submit([encoder.finish()]);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        readback_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| _ = tx.send(result));
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|error| error.to_string())?;
+        rx.recv()
+            .map_err(|error| error.to_string())?
+            .map_err(|error| error.to_string())?;
+
+        let data = readback_buffer.slice(..).get_mapped_range();
+        let words: Vec<u32> = data
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        drop(data);
+        readback_buffer.unmap();
+
+        let (fronts, lengths) = words.split_at(num_jobs as usize * MAX_FRONT_LENGTH as usize);
+        fronts
+            .chunks_exact(MAX_FRONT_LENGTH as usize)
+            .zip(lengths)
+            .map(|(front, &length)| match length {
+                OVERFLOW => Err("Pareto front exceeds maximum length".to_string()),
+                length => Ok(front[..length as usize]
+                    .iter()
+                    .copied()
+                    .map(unpack)
+                    .collect()),
+            })
+            .collect()
+    }
+}
+
+fn to_bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
@@ -0,0 +1,102 @@
+// Builds the Pareto front of one reduced state per invocation by merging the
+// (shifted) Pareto fronts of all its children, mirroring `ParetoFrontBuilder::merge`.
+// Values are packed as `first | (second << 16)`.
+
+struct Params {
+    max_first: u32,
+    max_second: u32,
+    max_front: u32,
+    num_jobs: u32,
+}
+
+struct Child {
+    offset: u32,
+    length: u32,
+    first_shift: u32,
+    second_shift: u32,
+}
+
+const OVERFLOW: u32 = 0xFFFFFFFFu;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> values: array<u32>;
+@group(0) @binding(2) var<storage, read> children: array<Child>;
+@group(0) @binding(3) var<storage, read> jobs: array<vec2<u32>>;
+@group(0) @binding(4) var<storage, read_write> fronts: array<u32>;
+@group(0) @binding(5) var<storage, read_write> scratch: array<u32>;
+@group(0) @binding(6) var<storage, read_write> lengths: array<u32>;
+
+fn first(value: u32) -> u32 {
+    return value & 0xFFFFu;
+}
+
+fn second(value: u32) -> u32 {
+    return value >> 16u;
+}
+
+fn shifted(value: u32, child: Child) -> u32 {
+    let a = min(first(value) + child.first_shift, 0xFFFFu);
+    let b = min(second(value) + child.second_shift, 0xFFFFu);
+    return a | (b << 16u);
+}
+
+// Returns true if `a` comes before `b` when iterating in descending order of `first`
+// (ties are broken by descending `second`).
+fn comes_first(a: u32, b: u32) -> bool {
+    return first(a) > first(b) || (first(a) == first(b) && second(a) >= second(b));
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let job = id.x;
+    if job >= params.num_jobs {
+        return;
+    }
+    let base = job * params.max_front;
+    var length = 0u;
+    for (var c = jobs[job].x; c < jobs[job].y; c++) {
+        let child = children[c];
+        // Merge the current front and the child front in descending order of `first`,
+        // keeping only values whose `second` is strictly greater than all values before.
+        var i = length;
+        var j = child.length;
+        var count = 0u;
+        var best = 0u;
+        var has_best = false;
+        while i > 0u || j > 0u {
+            var value: u32;
+            if j == 0u || (i > 0u && comes_first(fronts[base + i - 1u], shifted(values[child.offset + j - 1u], child))) {
+                value = fronts[base + i - 1u];
+                i--;
+            } else {
+                value = shifted(values[child.offset + j - 1u], child);
+                j--;
+            }
+            if !has_best || second(value) > best {
+                if count >= params.max_front {
+                    lengths[job] = OVERFLOW;
+                    return;
+                }
+                scratch[base + count] = value;
+                count++;
+                best = second(value);
+                has_best = true;
+            }
+        }
+        // `scratch` holds the merged front in descending order of `first`.
+        // Copy it back in ascending order while cutting off values beyond the maximums.
+        var begin = 0u;
+        var end = count;
+        while begin + 1u < end && second(scratch[base + count - 2u - begin]) >= params.max_second {
+            begin++;
+        }
+        while begin + 1u < end && first(scratch[base + count + 1u - end]) >= params.max_first {
+            end--;
+        }
+        for (var k = begin; k < end; k++) {
+            fronts[base + k - begin] = scratch[base + count - 1u - k];
+        }
+        length = end - begin;
+    }
+    lengths[job] = length;
+}
@@ -1,7 +1,13 @@
 mod solver;
 mod state;
 
+#[cfg(feature = "gpu")]
+mod gpu;
+
+#[cfg(feature = "gpu")]
+pub use gpu::GpuFrontMerger;
 pub use solver::QualityUpperBoundSolver;
+pub use state::ReducedState;
 
 #[cfg(test)]
 mod tests;
@@ -1,7 +1,10 @@
 use crate::{
-    SolverException, SolverSettings,
+    CacheStats, SolverException, SolverHeuristics, SolverSettings,
     actions::{ActionCombo, FULL_SEARCH_ACTIONS, PROGRESS_ONLY_SEARCH_ACTIONS},
-    utils::{AtomicFlag, ParetoFrontBuilder, ParetoFrontId, ParetoValue},
+    utils::{
+        AtomicFlag, MemoryBudget, MemoryTracker, ParetoFrontBuilder, ParetoFrontId, ParetoValue,
+        SolvedStatesCheckpoint, hash_map_memory_usage,
+    },
 };
 use raphael_sim::*;
 
@@ -9,19 +12,79 @@ use rustc_hash::FxHashMap as HashMap;
 
 use super::state::ReducedState;
 
+/// Pareto front of each solved state, in memory or in a temporary file, see
+/// [`MemoryBudget::spill_dir`].
+enum SolvedStates {
+    Memory(HashMap<ReducedState, ParetoFrontId>),
+    #[cfg(feature = "disk-spill")]
+    Disk(crate::utils::SpillHashMap<ReducedState, ParetoFrontId>),
+}
+
+impl SolvedStates {
+    fn get(&self, state: &ReducedState) -> Option<ParetoFrontId> {
+        match self {
+            Self::Memory(states) => states.get(state).copied(),
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(states) => states.get(state),
+        }
+    }
+
+    #[cfg(feature = "gpu")]
+    fn contains_key(&self, state: &ReducedState) -> bool {
+        match self {
+            Self::Memory(states) => states.contains_key(state),
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(states) => states.get(state).is_some(),
+        }
+    }
+
+    fn insert(&mut self, state: ReducedState, id: ParetoFrontId) -> Result<(), SolverException> {
+        match self {
+            Self::Memory(states) => {
+                states.insert(state, id);
+                Ok(())
+            }
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(states) => states.insert(state, id),
+        }
+    }
+
+    fn entries(&self) -> Vec<(ReducedState, ParetoFrontId)> {
+        match self {
+            Self::Memory(states) => states.iter().map(|(state, id)| (*state, *id)).collect(),
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(states) => states.iter().collect(),
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        match self {
+            Self::Memory(states) => {
+                hash_map_memory_usage::<ReducedState, ParetoFrontId>(states.capacity())
+            }
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(_) => 0,
+        }
+    }
+}
+
 pub struct QualityUpperBoundSolver {
     settings: SolverSettings,
-    solved_states: HashMap<ReducedState, ParetoFrontId>,
+    solved_states: SolvedStates,
     pareto_front_builder: ParetoFrontBuilder<u16, u16>,
     interrupt_signal: AtomicFlag,
+    memory_tracker: MemoryTracker,
+    cache_stats: CacheStats,
     // pre-computed branch pruning values
-    waste_not_1_min_cp: i16,
-    waste_not_2_min_cp: i16,
-    durability_cost: i16,
+    heuristics: SolverHeuristics,
 }
 
 impl QualityUpperBoundSolver {
-    pub fn new(mut settings: SolverSettings, interrupt_signal: AtomicFlag) -> Self {
+    pub fn new(
+        mut settings: SolverSettings,
+        interrupt_signal: AtomicFlag,
+        memory_budget: &MemoryBudget,
+    ) -> Self {
         log::trace!(
             "ReducedState (QualityUpperBoundSolver) - size: {}, align: {}",
             std::mem::size_of::<ReducedState>(),
@@ -30,49 +93,64 @@ impl QualityUpperBoundSolver {
 
         settings.simulator_settings.max_cp = i16::MAX;
 
-        let initial_state = SimulationState::new(&settings.simulator_settings);
-
-        let mut durability_cost = 100;
-        if settings
-            .simulator_settings
-            .is_action_allowed::<MasterMend>()
-        {
-            let master_mend_cost =
-                MasterMend::base_cp_cost(&initial_state, &settings.simulator_settings);
-            durability_cost = std::cmp::min(durability_cost, master_mend_cost / 6);
-        }
-        if settings
-            .simulator_settings
-            .is_action_allowed::<Manipulation>()
-        {
-            let manipulation_cost =
-                Manipulation::base_cp_cost(&initial_state, &settings.simulator_settings);
-            durability_cost = std::cmp::min(durability_cost, manipulation_cost / 8);
-        }
-        if settings
-            .simulator_settings
-            .is_action_allowed::<ImmaculateMend>()
-        {
-            let immaculate_mend_cost =
-                ImmaculateMend::base_cp_cost(&initial_state, &settings.simulator_settings);
-            let max_restored = settings.simulator_settings.max_durability as i16 / 5 - 1;
-            durability_cost = std::cmp::min(durability_cost, immaculate_mend_cost / max_restored);
-        }
+        let heuristics = SolverHeuristics::new(&settings);
 
-        Self {
+        #[allow(unused_mut)]
+        let mut solver = Self {
             settings,
-            solved_states: HashMap::default(),
+            solved_states: SolvedStates::Memory(HashMap::default()),
             pareto_front_builder: ParetoFrontBuilder::new(
                 settings.simulator_settings.max_progress,
                 settings.simulator_settings.max_quality,
             ),
             interrupt_signal,
-            durability_cost,
-            waste_not_1_min_cp: waste_not_min_cp(56, 4, durability_cost),
-            waste_not_2_min_cp: waste_not_min_cp(98, 8, durability_cost),
+            memory_tracker: memory_budget.tracker(),
+            cache_stats: CacheStats::default(),
+            heuristics,
+        };
+        #[cfg(feature = "disk-spill")]
+        if let Some(Err(error)) = memory_budget
+            .spill_dir()
+            .map(|dir| solver.spill_to_disk(dir))
+        {
+            log::warn!("QualityUpperBoundSolver - keeping states in memory: {error:?}");
+        }
+        solver
+    }
+
+    /// Keeps the solved states and their Pareto fronts in temporary files in `dir`.
+    #[cfg(feature = "disk-spill")]
+    fn spill_to_disk(&mut self, dir: &std::path::Path) -> Result<(), SolverException> {
+        self.solved_states = SolvedStates::Disk(crate::utils::SpillHashMap::new(dir)?);
+        self.pareto_front_builder.spill_to_disk(dir)
+    }
+
+    /// Lookups of the states of [`Self::quality_upper_bound`] in the solved states.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache_stats
+    }
+
+    /// Solved states and their Pareto fronts, see [`crate::SolverCheckpoint`].
+    pub fn checkpoint(&self) -> SolvedStatesCheckpoint<ReducedState> {
+        SolvedStatesCheckpoint {
+            states: self.solved_states.entries(),
+            pareto_values: self.pareto_front_builder.saved_values().to_vec(),
         }
     }
 
+    /// Restores the states of [`Self::checkpoint`] into a new solver with the same settings.
+    pub fn restore_checkpoint(
+        &mut self,
+        checkpoint: &SolvedStatesCheckpoint<ReducedState>,
+    ) -> Result<(), SolverException> {
+        self.pareto_front_builder
+            .restore_saved_values(&checkpoint.pareto_values)?;
+        for (state, id) in &checkpoint.states {
+            self.solved_states.insert(*state, *id)?;
+        }
+        self.update_memory_usage()
+    }
+
     /// Returns an upper-bound on the maximum Quality achievable from this state while also maxing out Progress.
     /// There is no guarantee on the tightness of the upper-bound.
     pub fn quality_upper_bound(&mut self, state: SimulationState) -> Result<u16, SolverException> {
@@ -85,11 +163,18 @@ impl QualityUpperBoundSolver {
                 state.combo
             )));
         }
-        let reduced_state =
-            ReducedState::from_simulation_state(state, &self.settings, self.durability_cost);
+        let reduced_state = ReducedState::from_simulation_state(
+            state,
+            &self.settings,
+            self.heuristics.durability_cost,
+        );
         let pareto_front = match self.solved_states.get(&reduced_state) {
-            Some(id) => self.pareto_front_builder.retrieve(*id),
+            Some(id) => {
+                self.cache_stats.hits += 1;
+                self.pareto_front_builder.retrieve(id)
+            }
             None => {
+                self.cache_stats.misses += 1;
                 self.pareto_front_builder.clear();
                 self.solve_state(reduced_state)?;
                 self.pareto_front_builder.peek().unwrap()
@@ -127,9 +212,14 @@ impl QualityUpperBoundSolver {
                 break;
             }
         }
-        let id = self.pareto_front_builder.save().unwrap();
-        self.solved_states.insert(state, id);
-        Ok(())
+        let id = self.pareto_front_builder.save()?;
+        self.solved_states.insert(state, id)?;
+        self.update_memory_usage()
+    }
+
+    fn update_memory_usage(&mut self) -> Result<(), SolverException> {
+        let usage = self.solved_states.memory_usage() + self.pareto_front_builder.memory_usage();
+        self.memory_tracker.update(usage)
     }
 
     fn build_child_front(
@@ -138,11 +228,11 @@ impl QualityUpperBoundSolver {
         action: ActionCombo,
     ) -> Result<(), SolverException> {
         if let Ok((new_state, action_progress, action_quality)) =
-            state.use_action(action, &self.settings, self.durability_cost)
+            state.use_action(action, &self.settings, self.heuristics.durability_cost)
         {
-            if new_state.cp >= self.durability_cost {
+            if new_state.cp >= self.heuristics.durability_cost {
                 match self.solved_states.get(&new_state) {
-                    Some(id) => self.pareto_front_builder.push_id(*id),
+                    Some(id) => self.pareto_front_builder.push_id(id),
                     None => self.solve_state(new_state)?,
                 }
                 self.pareto_front_builder
@@ -154,7 +244,7 @@ impl QualityUpperBoundSolver {
                         value.second = value.second.saturating_add(action_quality);
                     });
                 self.pareto_front_builder.merge();
-            } else if new_state.cp >= -self.durability_cost && action_progress != 0 {
+            } else if new_state.cp >= -self.heuristics.durability_cost && action_progress != 0 {
                 // "durability" must not go lower than -5
                 // last action must be a progress increase
                 self.pareto_front_builder
@@ -168,31 +258,146 @@ impl QualityUpperBoundSolver {
 
     fn should_use_action(&self, state: ReducedState, action: ActionCombo) -> bool {
         match action {
-            ActionCombo::Single(Action::WasteNot) => state.cp >= self.waste_not_1_min_cp,
-            ActionCombo::Single(Action::WasteNot2) => state.cp >= self.waste_not_2_min_cp,
+            ActionCombo::Single(Action::WasteNot) => state.cp >= self.heuristics.waste_not_1_min_cp,
+            ActionCombo::Single(Action::WasteNot2) => {
+                state.cp >= self.heuristics.waste_not_2_min_cp
+            }
             _ => true,
         }
     }
 }
 
-/// Calculates the minimum CP a state must have so that using WasteNot is not worse than just restoring durability via CP
-fn waste_not_min_cp(
-    waste_not_action_cp_cost: i16,
-    effect_duration: i16,
-    durability_cost: i16,
-) -> i16 {
-    const BASIC_SYNTH_CP: i16 = 0;
-    const GROUNDWORK_CP: i16 = 18;
-    // how many units of 5-durability does WasteNot have to save to be worth using over magically restoring durability?
-    let min_durability_save = (waste_not_action_cp_cost - 1) / durability_cost + 1;
-    if min_durability_save > effect_duration * 2 {
-        return i16::MAX;
-    }
-    // how many 20-durability actions and how many 10-durability actions are needed?
-    let double_dur_count = min_durability_save.saturating_sub(effect_duration);
-    let single_dur_count = min_durability_save.abs_diff(effect_duration) as i16;
-    // minimum CP required to execute those actions
-    let double_dur_cost = double_dur_count * (GROUNDWORK_CP + durability_cost * 2);
-    let single_dur_cost = single_dur_count * (BASIC_SYNTH_CP + durability_cost);
-    waste_not_action_cp_cost + double_dur_cost + single_dur_cost - durability_cost
+#[cfg(feature = "gpu")]
+enum GpuChild {
+    State(ReducedState, u16, u16),
+    Terminal(u16, u16),
+}
+
+#[cfg(feature = "gpu")]
+impl QualityUpperBoundSolver {
+    /// Solves all states reachable from `state` on the GPU, one layer of states per dispatch.
+    /// Results are identical to the lazy CPU search, which remains responsible for any states not precomputed here.
+    pub fn precompute_with_gpu(
+        &mut self,
+        state: SimulationState,
+        gpu: &super::gpu::GpuFrontMerger,
+    ) -> Result<(), SolverException> {
+        let root = ReducedState::from_simulation_state(
+            state,
+            &self.settings,
+            self.heuristics.durability_cost,
+        );
+
+        // Enumerate all unsolved states and assign each state a height such that
+        // all children of a state have a strictly lower height.
+        let mut children: HashMap<ReducedState, Vec<GpuChild>> = HashMap::default();
+        let mut heights: HashMap<ReducedState, usize> = HashMap::default();
+        let mut stack = vec![(root, false)];
+        while let Some((state, expanded)) = stack.pop() {
+            if expanded {
+                let height = children[&state]
+                    .iter()
+                    .filter_map(|child| match child {
+                        GpuChild::State(child, _, _) => heights.get(child).map(|h| h + 1),
+                        GpuChild::Terminal(_, _) => None,
+                    })
+                    .max()
+                    .unwrap_or(0);
+                heights.insert(state, height);
+                continue;
+            }
+            if heights.contains_key(&state) || self.solved_states.contains_key(&state) {
+                continue;
+            }
+            if children.contains_key(&state) {
+                return Err(SolverException::InternalError(
+                    "Cycle in quality upper bound state graph".to_owned(),
+                ));
+            }
+            let state_children = self.gpu_children(state);
+            stack.push((state, true));
+            for child in &state_children {
+                if let GpuChild::State(child, _, _) = child {
+                    stack.push((*child, false));
+                }
+            }
+            children.insert(state, state_children);
+        }
+
+        let num_layers = heights.values().max().map_or(0, |height| height + 1);
+        let mut layers = vec![Vec::new(); num_layers];
+        for (state, height) in heights {
+            layers[height].push(state);
+        }
+        log::debug!(
+            "QualityUpperBoundSolver - gpu states: {}, layers: {}",
+            children.len(),
+            num_layers
+        );
+
+        for layer in layers {
+            if self.interrupt_signal.is_set() {
+                return Err(SolverException::Interrupted);
+            }
+            let mut batch = super::gpu::MergeBatch::default();
+            for state in &layer {
+                batch.begin_job();
+                for child in &children[state] {
+                    match *child {
+                        GpuChild::State(child, progress, quality) => {
+                            let id = self.solved_states.get(&child).unwrap();
+                            batch.add_child(
+                                self.pareto_front_builder.retrieve(id),
+                                progress,
+                                quality,
+                            );
+                        }
+                        GpuChild::Terminal(progress, quality) => {
+                            batch.add_child(&[ParetoValue::new(progress, quality)], 0, 0);
+                        }
+                    }
+                }
+            }
+            let fronts = gpu
+                .merge(
+                    &batch,
+                    self.settings.simulator_settings.max_progress,
+                    self.settings.simulator_settings.max_quality,
+                )
+                .map_err(SolverException::InternalError)?;
+            for (state, front) in layer.into_iter().zip(fronts) {
+                self.pareto_front_builder.clear();
+                self.pareto_front_builder.push_slice(&front);
+                let id = self.pareto_front_builder.save()?;
+                self.solved_states.insert(state, id)?;
+            }
+            self.update_memory_usage()?;
+        }
+        Ok(())
+    }
+
+    /// Same transitions as `build_child_front`, without solving the children.
+    fn gpu_children(&self, state: ReducedState) -> Vec<GpuChild> {
+        let search_actions = match state.progress_only {
+            true => PROGRESS_ONLY_SEARCH_ACTIONS,
+            false => FULL_SEARCH_ACTIONS,
+        };
+        let mut children = Vec::new();
+        for action in search_actions {
+            if !self.should_use_action(state, *action) {
+                continue;
+            }
+            let Ok((new_state, action_progress, action_quality)) =
+                state.use_action(*action, &self.settings, self.heuristics.durability_cost)
+            else {
+                continue;
+            };
+            if new_state.cp >= self.heuristics.durability_cost {
+                children.push(GpuChild::State(new_state, action_progress, action_quality));
+            } else if new_state.cp >= -self.heuristics.durability_cost && action_progress != 0 {
+                children.push(GpuChild::Terminal(action_progress, action_quality));
+            }
+        }
+        children
+    }
 }
@@ -6,6 +6,7 @@ use crate::{
 use raphael_sim::*;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReducedState {
     pub cp: i16,
     pub unreliable_quality: u8,
@@ -20,7 +21,10 @@ impl ReducedState {
         durability_cost: i16,
     ) -> Self {
         state.cp += state.effects.manipulation() as i16
-            * (Manipulation::base_cp_cost(&state, &settings.simulator_settings) / 8);
+            * (settings
+                .simulator_settings
+                .base_cp_cost::<Manipulation>(&state)
+                / 8);
         if state.effects.trained_perfection() != SingleUse::Unavailable
             && settings
                 .simulator_settings
@@ -4,6 +4,7 @@ use raphael_sim::*;
 use crate::{
     SolverSettings,
     actions::{FULL_SEARCH_ACTIONS, use_action_combo},
+    utils::MemoryBudget,
 };
 
 use super::*;
@@ -17,10 +18,16 @@ fn solve(simulator_settings: Settings, actions: &[Action]) -> u16 {
         simulator_settings,
         backload_progress: false,
         allow_unsound_branch_pruning: false,
+        forbid_quality_during_muscle_memory: false,
+        first_quality_step: 0,
     };
-    QualityUpperBoundSolver::new(solver_settings, Default::default())
-        .quality_upper_bound(state)
-        .unwrap()
+    QualityUpperBoundSolver::new(
+        solver_settings,
+        Default::default(),
+        &MemoryBudget::default(),
+    )
+    .quality_upper_bound(state)
+    .unwrap()
 }
 
 #[test]
@@ -38,6 +45,8 @@ fn test_01() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -71,6 +80,8 @@ fn test_adversarial_01() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -104,6 +115,8 @@ fn test_02() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -134,6 +147,8 @@ fn test_adversarial_02() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -164,6 +179,8 @@ fn test_03() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -199,6 +216,8 @@ fn test_adversarial_03() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -234,6 +253,8 @@ fn test_04() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 2075);
@@ -254,6 +275,8 @@ fn test_adversarial_04() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 1888);
@@ -274,6 +297,8 @@ fn test_05() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 2000);
@@ -294,6 +319,8 @@ fn test_adversarial_05() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 2000);
@@ -314,6 +341,8 @@ fn test_06() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 4438);
@@ -334,6 +363,8 @@ fn test_adversarial_06() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 3745);
@@ -354,6 +385,8 @@ fn test_07() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::Reflect]);
     assert_eq!(result, 4449);
@@ -374,6 +407,8 @@ fn test_08() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::PrudentTouch]);
     assert_eq!(result, 10000);
@@ -395,6 +430,8 @@ fn test_09() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 4079);
@@ -416,6 +453,8 @@ fn test_10() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 3929);
@@ -437,11 +476,48 @@ fn test_11() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 2481);
 }
 
+#[test]
+fn test_unreliable_actions() {
+    let settings = Settings {
+        max_cp: 320,
+        max_durability: 80,
+        max_progress: 1600,
+        max_quality: 24000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    // actions that are assumed to fail are never used, even if they are allowed
+    let reliable_settings = Settings {
+        allowed_actions: settings
+            .allowed_actions
+            .remove(Action::RapidSynthesis)
+            .remove(Action::HastyTouch)
+            .remove(Action::DaringTouch),
+        ..settings
+    };
+    assert_eq!(solve(settings, &[]), solve(reliable_settings, &[]));
+    let settings = Settings {
+        success_model: SuccessModel::AssumeSuccess,
+        ..settings
+    };
+    assert!(solve(settings, &[]) > solve(reliable_settings, &[]));
+}
+
 fn random_effects(adversarial: bool) -> Effects {
     Effects::default()
         .with_inner_quiet(rand::thread_rng().gen_range(0..=10))
@@ -479,8 +555,14 @@ fn monotonic_fuzz_check(simulator_settings: Settings) {
         simulator_settings,
         backload_progress: false,
         allow_unsound_branch_pruning: false,
+        forbid_quality_during_muscle_memory: false,
+        first_quality_step: 0,
     };
-    let mut solver = QualityUpperBoundSolver::new(solver_settings, Default::default());
+    let mut solver = QualityUpperBoundSolver::new(
+        solver_settings,
+        Default::default(),
+        &MemoryBudget::default(),
+    );
     for _ in 0..10000 {
         let state = random_state(&simulator_settings);
         let state_upper_bound = solver.quality_upper_bound(state).unwrap();
@@ -515,6 +597,8 @@ fn test_monotonic_normal_sim() {
         job_level: 100,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     monotonic_fuzz_check(settings);
 }
@@ -532,6 +616,60 @@ fn test_monotonic_adversarial_sim() {
         job_level: 100,
         allowed_actions: ActionMask::all(),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     monotonic_fuzz_check(settings);
 }
+
+#[cfg(feature = "gpu")]
+#[test]
+fn test_gpu_precompute_matches_cpu() {
+    let gpu = match GpuFrontMerger::new() {
+        Ok(gpu) => gpu,
+        Err(error) => {
+            eprintln!("Skipping test, no GPU available: {error}");
+            return;
+        }
+    };
+    let simulator_settings = Settings {
+        max_cp: 360,
+        max_durability: 70,
+        max_progress: 1000,
+        max_quality: 20000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let solver_settings = SolverSettings {
+        simulator_settings,
+        backload_progress: false,
+        allow_unsound_branch_pruning: false,
+        forbid_quality_during_muscle_memory: false,
+        first_quality_step: 0,
+    };
+    let mut seed_state = SimulationState::new(&simulator_settings);
+    seed_state.combo = Combo::None;
+    let mut cpu_solver = QualityUpperBoundSolver::new(
+        solver_settings,
+        Default::default(),
+        &MemoryBudget::default(),
+    );
+    let mut gpu_solver = QualityUpperBoundSolver::new(
+        solver_settings,
+        Default::default(),
+        &MemoryBudget::default(),
+    );
+    gpu_solver.precompute_with_gpu(seed_state, &gpu).unwrap();
+    for _ in 0..10000 {
+        let state = random_state(&simulator_settings);
+        assert_eq!(
+            gpu_solver.quality_upper_bound(state).unwrap(),
+            cpu_solver.quality_upper_bound(state).unwrap()
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use raphael_sim::*;
+
+use crate::{FinishSolver, SolverSettings};
+
+/// Length of the longest prefix of `actions` that can be kept when the rest of the rotation is
+/// solved again, e.g. after the crafter stats or consumables changed.
+///
+/// Every action of the prefix must still be usable from `initial_state` and Progress must still
+/// be able to reach the max Progress after the prefix. The prefix never finishes the craft, so
+/// that there is always something left to solve.
+pub fn repairable_prefix(
+    settings: SolverSettings,
+    initial_state: SimulationState,
+    actions: &[Action],
+) -> usize {
+    let simulator_settings = settings.simulator_settings;
+    let mut finish_solver = FinishSolver::new(settings);
+    let mut state = initial_state;
+    let mut prefix_len = 0;
+    for action in actions {
+        let Ok(new_state) = state.use_action(*action, Condition::Normal, &simulator_settings)
+        else {
+            break;
+        };
+        // no continuation of a state that can't be finished can be finished either
+        if new_state.is_final(&simulator_settings) || !finish_solver.can_finish(&new_state) {
+            break;
+        }
+        state = new_state;
+        prefix_len += 1;
+    }
+    prefix_len
+}
@@ -0,0 +1,274 @@
+use raphael_data::{Consumable, CrafterStats, Recipe, get_game_settings};
+use raphael_sim::{
+    Action, ByregotsBlessing, Effects, RulesOverride, Settings, SimulationState, SingleUse,
+    SuccessModel,
+};
+
+use crate::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+/// Effects that are already active when the craft starts, e.g. to theorycraft solving the
+/// rest of a craft after Heart and Soul was used or Inner Quiet was built up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InitialEffects {
+    pub inner_quiet: u8,
+    pub waste_not: u8,
+    pub innovation: u8,
+    pub veneration: u8,
+    pub great_strides: u8,
+    pub muscle_memory: u8,
+    pub manipulation: u8,
+    pub heart_and_soul: bool,
+    pub trained_perfection: bool,
+}
+
+impl InitialEffects {
+    /// Initial state of the craft with these effects applied. Effect durations are clamped to
+    /// what the simulator supports.
+    pub fn initial_state(&self, settings: &Settings) -> SimulationState {
+        let mut state = SimulationState::new(settings);
+        state.effects = self.apply(state.effects);
+        state
+    }
+
+    fn apply(&self, effects: Effects) -> Effects {
+        let mut effects = effects
+            .with_inner_quiet(self.inner_quiet.min(10))
+            .with_waste_not(self.waste_not.min(8))
+            .with_innovation(self.innovation.min(4))
+            .with_veneration(self.veneration.min(4))
+            .with_great_strides(self.great_strides.min(3))
+            .with_muscle_memory(self.muscle_memory.min(5))
+            .with_manipulation(self.manipulation.min(8));
+        if self.heart_and_soul {
+            effects.set_heart_and_soul(SingleUse::Active);
+        }
+        if self.trained_perfection {
+            effects.set_trained_perfection(SingleUse::Active);
+        }
+        effects
+    }
+}
+
+/// Describes what to solve in terms of the in-game recipe and crafter setup.
+///
+/// Derives the simulator settings and the Quality the solver has to reach, so that all
+/// front-ends solve a recipe the same way.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[must_use]
+pub struct SolveRequest {
+    recipe: Recipe,
+    crafter_stats: CrafterStats,
+    food: Option<Consumable>,
+    potion: Option<Consumable>,
+    initial_quality: u16,
+    /// Defaults to the max Quality of the recipe.
+    target_quality: Option<u16>,
+    adversarial: bool,
+    backload_progress: bool,
+    allow_unsound_branch_pruning: bool,
+    initial_effects: InitialEffects,
+    #[cfg_attr(feature = "serde", serde(default))]
+    minimize_cp: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    success_model: SuccessModel,
+    #[cfg_attr(feature = "serde", serde(default))]
+    forbid_quality_during_muscle_memory: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    first_quality_step: u8,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rules_override: RulesOverride,
+    #[cfg_attr(feature = "serde", serde(default))]
+    human_friendly_bias: u8,
+    #[cfg_attr(feature = "serde", serde(default))]
+    exclude_byregot: bool,
+}
+
+impl SolveRequest {
+    pub fn new(recipe: Recipe, crafter_stats: CrafterStats) -> Self {
+        Self {
+            recipe,
+            crafter_stats,
+            food: None,
+            potion: None,
+            initial_quality: 0,
+            target_quality: None,
+            adversarial: false,
+            backload_progress: false,
+            allow_unsound_branch_pruning: false,
+            initial_effects: InitialEffects::default(),
+            minimize_cp: false,
+            success_model: SuccessModel::AssumeFail,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
+            rules_override: RulesOverride::NONE,
+            human_friendly_bias: 0,
+            exclude_byregot: false,
+        }
+    }
+
+    pub fn food(mut self, food: Option<Consumable>) -> Self {
+        self.food = food;
+        self
+    }
+
+    pub fn potion(mut self, potion: Option<Consumable>) -> Self {
+        self.potion = potion;
+        self
+    }
+
+    /// Quality the craft starts with, e.g. from HQ ingredients.
+    pub fn initial_quality(mut self, initial_quality: u16) -> Self {
+        self.initial_quality = initial_quality;
+        self
+    }
+
+    /// Quality to reach, including the initial Quality. Clamped to the max Quality of the recipe.
+    pub fn target_quality(mut self, target_quality: u16) -> Self {
+        self.target_quality = Some(target_quality);
+        self
+    }
+
+    pub fn adversarial(mut self, adversarial: bool) -> Self {
+        self.adversarial = adversarial;
+        self
+    }
+
+    pub fn backload_progress(mut self, backload_progress: bool) -> Self {
+        self.backload_progress = backload_progress;
+        self
+    }
+
+    pub fn allow_unsound_branch_pruning(mut self, allow_unsound_branch_pruning: bool) -> Self {
+        self.allow_unsound_branch_pruning = allow_unsound_branch_pruning;
+        self
+    }
+
+    /// See [`SolverSettings::forbid_quality_during_muscle_memory`].
+    pub fn forbid_quality_during_muscle_memory(
+        mut self,
+        forbid_quality_during_muscle_memory: bool,
+    ) -> Self {
+        self.forbid_quality_during_muscle_memory = forbid_quality_during_muscle_memory;
+        self
+    }
+
+    /// See [`SolverSettings::first_quality_step`].
+    pub fn first_quality_step(mut self, first_quality_step: u8) -> Self {
+        self.first_quality_step = first_quality_step;
+        self
+    }
+
+    pub fn initial_effects(mut self, initial_effects: InitialEffects) -> Self {
+        self.initial_effects = initial_effects;
+        self
+    }
+
+    /// Prefer macros that spend less CP, see [`MacroSolver::minimize_cp`].
+    pub fn minimize_cp(mut self, minimize_cp: bool) -> Self {
+        self.minimize_cp = minimize_cp;
+        self
+    }
+
+    /// Prefer macros that are easier to execute by hand, see [`MacroSolver::human_friendly_bias`].
+    pub fn human_friendly_bias(mut self, weight: u8) -> Self {
+        self.human_friendly_bias = weight;
+        self
+    }
+
+    /// How the solver treats actions with a success rate below 100%, see [`SuccessModel`].
+    pub fn success_model(mut self, success_model: SuccessModel) -> Self {
+        self.success_model = success_model;
+        self
+    }
+
+    /// Solves without Byregot's Blessing and keeps its CP and durability unspent, so that it can be used by
+    /// hand on a Good or Excellent condition. See [`crate::best_byregot_placement`].
+    pub fn exclude_byregot(mut self, exclude_byregot: bool) -> Self {
+        self.exclude_byregot = exclude_byregot;
+        self
+    }
+
+    /// Changes to action potencies and costs, to simulate balance changes that aren't in the
+    /// game yet. See [`RulesOverride`].
+    pub fn rules_override(mut self, rules_override: RulesOverride) -> Self {
+        self.rules_override = rules_override;
+        self
+    }
+
+    /// Simulator settings of the recipe, with the full max Quality of the recipe.
+    pub fn game_settings(&self) -> Settings {
+        Settings {
+            success_model: self.success_model,
+            rules_override: self.rules_override,
+            ..get_game_settings(
+                self.recipe,
+                self.crafter_stats,
+                self.food,
+                self.potion,
+                self.adversarial,
+            )
+        }
+    }
+
+    /// Quality to reach, including the initial Quality.
+    pub fn get_target_quality(&self) -> u16 {
+        let max_quality = self.game_settings().max_quality;
+        self.target_quality.map_or(max_quality, |target_quality| {
+            target_quality.min(max_quality)
+        })
+    }
+
+    /// Simulator settings with the max Quality lowered to the Quality that is still missing
+    /// to reach the target Quality. These are the settings the solver works with.
+    /// With [`Self::exclude_byregot`], Byregot's Blessing is disallowed and its CP and
+    /// durability are reserved.
+    pub fn simulator_settings(&self) -> Settings {
+        let mut settings = self.game_settings();
+        settings.max_quality = self
+            .get_target_quality()
+            .saturating_sub(self.initial_quality);
+        if self.exclude_byregot {
+            // every state of the rotation then has the CP and durability left to insert
+            // Byregot's Blessing
+            let state = SimulationState::new(&settings);
+            let cp_cost = settings.base_cp_cost::<ByregotsBlessing>(&state);
+            let durability_cost = settings.base_durability_cost::<ByregotsBlessing>(&state);
+            settings.allowed_actions = settings.allowed_actions.remove(Action::ByregotsBlessing);
+            settings.max_cp = (settings.max_cp - cp_cost).max(0);
+            settings.max_durability = (settings.max_durability - durability_cost).max(0);
+        }
+        settings
+    }
+
+    pub fn solver_settings(&self) -> SolverSettings {
+        SolverSettings {
+            simulator_settings: self.simulator_settings(),
+            backload_progress: self.backload_progress,
+            allow_unsound_branch_pruning: self.allow_unsound_branch_pruning,
+            forbid_quality_during_muscle_memory: self.forbid_quality_during_muscle_memory,
+            first_quality_step: self.first_quality_step,
+        }
+    }
+
+    /// State the solver starts from, with the initial effects applied.
+    pub fn initial_state(&self) -> SimulationState {
+        self.initial_effects
+            .initial_state(&self.simulator_settings())
+    }
+
+    /// Solves the request on the current thread pool, without reporting any progress.
+    pub fn solve(&self, interrupt_signal: AtomicFlag) -> Result<Vec<Action>, SolverException> {
+        MacroSolver::new(
+            self.solver_settings(),
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            interrupt_signal,
+        )
+        .minimize_cp(self.minimize_cp)
+        .human_friendly_bias(self.human_friendly_bias)
+        .solve_from(self.initial_state())
+    }
+}
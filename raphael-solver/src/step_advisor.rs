@@ -0,0 +1,148 @@
+use raphael_sim::*;
+
+use crate::actions::CONDITION_ACTIONS;
+use crate::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+/// Next step of a craft, see [`StepAdvisor::recommend`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepRecommendation {
+    /// The action to use in the current step.
+    pub action: Action,
+    /// State after the action.
+    pub state: SimulationState,
+    /// Rest of the rotation after the action, assuming Normal condition on every step.
+    pub remaining_actions: Vec<Action>,
+    /// Quality at the end of the rotation, capped at the max Quality.
+    pub quality: u16,
+}
+
+/// Recommends the next action of a craft one step at a time, given the condition of the
+/// current step in-game.
+///
+/// The advisor keeps the rotation of its last recommendation. As long as the conditions are
+/// Normal the craft follows that rotation and no new solve is needed. In any other condition,
+/// the next action of the rotation is compared against the actions that the condition allows,
+/// by solving the rest of the craft from the state after each of them.
+pub struct StepAdvisor {
+    settings: SolverSettings,
+    interrupt_signal: AtomicFlag,
+    /// State that the rotation of the last recommendation continues from.
+    plan: Option<(SimulationState, Vec<Action>)>,
+}
+
+impl StepAdvisor {
+    pub fn new(mut settings: SolverSettings, interrupt_signal: AtomicFlag) -> Self {
+        // the conditions are known, so there is nothing to be unlucky about
+        settings.simulator_settings.adversarial = false;
+        Self {
+            settings,
+            interrupt_signal,
+            plan: None,
+        }
+    }
+
+    /// Recommends the action to use in `state` in `condition`.
+    /// Fails with `SolverException::NoSolution` if the craft is finished or can't be finished
+    /// anymore.
+    pub fn recommend(
+        &mut self,
+        state: SimulationState,
+        condition: Condition,
+    ) -> Result<StepRecommendation, SolverException> {
+        if state.is_final(&self.settings.simulator_settings) {
+            return Err(SolverException::NoSolution);
+        }
+        let plan = match self.plan.take() {
+            Some((plan_state, actions)) if plan_state == state => actions,
+            _ => self.solve_from(state)?,
+        };
+        let Some(planned_action) = plan.first().copied() else {
+            return Err(SolverException::NoSolution);
+        };
+
+        let settings = &self.settings.simulator_settings;
+        let mut best: Option<StepRecommendation> = None;
+        // in Normal condition, the plan is already the best continuation
+        let candidates = std::iter::once(planned_action).chain(
+            CONDITION_ACTIONS
+                .iter()
+                .copied()
+                .filter(|action| condition != Condition::Normal && *action != planned_action),
+        );
+        for action in candidates {
+            let Ok(next_state) = state.use_action(action, condition, settings) else {
+                continue;
+            };
+            let remaining_actions = match (action == planned_action, condition) {
+                // the state after the action is the state the plan expects
+                (true, Condition::Normal) => plan[1..].to_vec(),
+                _ => match self.finish_from(next_state) {
+                    Ok(actions) => actions,
+                    Err(SolverException::NoSolution) => continue,
+                    Err(exception) => return Err(exception),
+                },
+            };
+            let recommendation = StepRecommendation {
+                action,
+                state: next_state,
+                quality: self.final_quality(next_state, &remaining_actions),
+                remaining_actions,
+            };
+            if best
+                .as_ref()
+                .is_none_or(|best| is_better(&recommendation, best))
+            {
+                best = Some(recommendation);
+            }
+        }
+
+        let best = best.ok_or(SolverException::NoSolution)?;
+        self.plan = Some((best.state, best.remaining_actions.clone()));
+        Ok(best)
+    }
+
+    /// Actions that finish the craft from `state`, which may already be finished.
+    fn finish_from(&self, state: SimulationState) -> Result<Vec<Action>, SolverException> {
+        let settings = &self.settings.simulator_settings;
+        match state.is_final(settings) {
+            true if state.progress >= settings.max_progress => Ok(Vec::new()),
+            true => Err(SolverException::NoSolution),
+            false => self.solve_from(state),
+        }
+    }
+
+    fn solve_from(&self, state: SimulationState) -> Result<Vec<Action>, SolverException> {
+        MacroSolver::new(
+            self.settings,
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            self.interrupt_signal.clone(),
+        )
+        .solve_from(state)
+    }
+
+    fn final_quality(&self, state: SimulationState, actions: &[Action]) -> u16 {
+        let settings = &self.settings.simulator_settings;
+        let mut state = state;
+        for action in actions {
+            match state.use_action(*action, Condition::Normal, settings) {
+                Ok(next_state) => state = next_state,
+                Err(_) => break,
+            }
+        }
+        std::cmp::min(state.quality, settings.max_quality)
+    }
+}
+
+/// Higher Quality first, then fewer remaining steps, then more CP left over.
+fn is_better(lhs: &StepRecommendation, rhs: &StepRecommendation) -> bool {
+    (
+        lhs.quality,
+        std::cmp::Reverse(lhs.remaining_actions.len()),
+        lhs.state.cp,
+    ) > (
+        rhs.quality,
+        std::cmp::Reverse(rhs.remaining_actions.len()),
+        rhs.state.cp,
+    )
+}
@@ -2,6 +2,7 @@ mod solver;
 mod state;
 
 pub use solver::StepLowerBoundSolver;
+pub use state::ReducedState;
 
 #[cfg(test)]
 mod tests;
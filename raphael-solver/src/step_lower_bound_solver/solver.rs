@@ -1,12 +1,15 @@
 use std::num::NonZeroU8;
 
 use crate::{
-    SolverException, SolverSettings,
+    CacheStats, SolverException, SolverSettings,
     actions::{
         ActionCombo, FULL_SEARCH_ACTIONS, PROGRESS_ONLY_SEARCH_ACTIONS, is_progress_only_state,
         use_action_combo,
     },
-    utils::{AtomicFlag, ParetoFrontBuilder, ParetoFrontId, ParetoValue},
+    utils::{
+        AtomicFlag, MemoryBudget, MemoryTracker, ParetoFrontBuilder, ParetoFrontId, ParetoValue,
+        SolvedStatesCheckpoint, hash_map_memory_usage,
+    },
 };
 use raphael_sim::*;
 
@@ -19,17 +22,25 @@ pub struct StepLowerBoundSolver {
     solved_states: HashMap<ReducedState, ParetoFrontId>,
     pareto_front_builder: ParetoFrontBuilder<u16, u16>,
     interrupt_signal: AtomicFlag,
+    memory_tracker: MemoryTracker,
+    cache_stats: CacheStats,
     single_step_states: usize,
 }
 
 impl StepLowerBoundSolver {
-    pub fn new(mut settings: SolverSettings, interrupt_signal: AtomicFlag) -> Self {
+    pub fn new(
+        mut settings: SolverSettings,
+        interrupt_signal: AtomicFlag,
+        memory_budget: &MemoryBudget,
+    ) -> Self {
         log::trace!(
             "ReducedState (StepLowerBoundSolver) - size: {}, align: {}",
             std::mem::size_of::<ReducedState>(),
             std::mem::align_of::<ReducedState>()
         );
         ReducedState::optimize_action_mask(&mut settings.simulator_settings);
+        // CP is not tracked, reduced states are expanded with a fixed amount of CP
+        settings.simulator_settings.max_cp = i16::MAX;
         Self {
             settings,
             solved_states: HashMap::default(),
@@ -38,10 +49,40 @@ impl StepLowerBoundSolver {
                 settings.simulator_settings.max_quality,
             ),
             interrupt_signal,
+            memory_tracker: memory_budget.tracker(),
+            cache_stats: CacheStats::default(),
             single_step_states: 0,
         }
     }
 
+    /// Lookups of the states of [`Self::quality_upper_bound`] in the solved states.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache_stats
+    }
+
+    /// Solved states and their Pareto fronts, see [`crate::SolverCheckpoint`].
+    pub fn checkpoint(&self) -> SolvedStatesCheckpoint<ReducedState> {
+        SolvedStatesCheckpoint {
+            states: self
+                .solved_states
+                .iter()
+                .map(|(state, id)| (*state, *id))
+                .collect(),
+            pareto_values: self.pareto_front_builder.saved_values().to_vec(),
+        }
+    }
+
+    /// Restores the states of [`Self::checkpoint`] into a new solver with the same settings.
+    pub fn restore_checkpoint(
+        &mut self,
+        checkpoint: &SolvedStatesCheckpoint<ReducedState>,
+    ) -> Result<(), SolverException> {
+        self.pareto_front_builder
+            .restore_saved_values(&checkpoint.pareto_values)?;
+        self.solved_states.extend(checkpoint.states.iter().copied());
+        self.update_memory_usage()
+    }
+
     pub fn step_lower_bound_with_hint(
         &mut self,
         state: SimulationState,
@@ -62,7 +103,8 @@ impl StepLowerBoundSolver {
         Ok(hint.get())
     }
 
-    fn quality_upper_bound(
+    /// Returns an upper-bound on the maximum Quality achievable from this state within the step budget while also maxing out Progress.
+    pub fn quality_upper_bound(
         &mut self,
         state: SimulationState,
         step_budget: NonZeroU8,
@@ -81,8 +123,12 @@ impl StepLowerBoundSolver {
         let reduced_state = ReducedState::from_state(state, step_budget, progress_only);
 
         let pareto_front = match self.solved_states.get(&reduced_state) {
-            Some(id) => self.pareto_front_builder.retrieve(*id),
+            Some(id) => {
+                self.cache_stats.hits += 1;
+                self.pareto_front_builder.retrieve(*id)
+            }
             None => {
+                self.cache_stats.misses += 1;
                 self.pareto_front_builder.clear();
                 self.solve_state(reduced_state)?;
                 self.pareto_front_builder.peek().unwrap()
@@ -122,9 +168,16 @@ impl StepLowerBoundSolver {
                 }
             }
         }
-        let id = self.pareto_front_builder.save().unwrap();
+        let id = self.pareto_front_builder.save()?;
         self.solved_states.insert(reduced_state, id);
-        Ok(())
+        self.update_memory_usage()
+    }
+
+    fn update_memory_usage(&mut self) -> Result<(), SolverException> {
+        let usage =
+            hash_map_memory_usage::<ReducedState, ParetoFrontId>(self.solved_states.capacity())
+                + self.pareto_front_builder.memory_usage();
+        self.memory_tracker.update(usage)
     }
 
     fn build_child_front(
@@ -3,6 +3,7 @@ use std::num::NonZeroU8;
 use raphael_sim::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReducedState {
     pub steps_budget: NonZeroU8,
     pub progress_only: bool,
@@ -4,6 +4,7 @@ use raphael_sim::*;
 use crate::{
     SolverSettings,
     actions::{FULL_SEARCH_ACTIONS, use_action_combo},
+    utils::MemoryBudget,
 };
 
 use super::*;
@@ -17,10 +18,16 @@ fn solve(simulator_settings: Settings, actions: &[Action]) -> u8 {
         simulator_settings,
         backload_progress: false,
         allow_unsound_branch_pruning: false,
+        forbid_quality_during_muscle_memory: false,
+        first_quality_step: 0,
     };
-    StepLowerBoundSolver::new(solver_settings, Default::default())
-        .step_lower_bound_with_hint(state, 0)
-        .unwrap()
+    StepLowerBoundSolver::new(
+        solver_settings,
+        Default::default(),
+        &MemoryBudget::default(),
+    )
+    .step_lower_bound_with_hint(state, 0)
+    .unwrap()
 }
 
 #[test]
@@ -38,6 +45,8 @@ fn test_01() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -71,6 +80,8 @@ fn test_adversarial_01() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -104,6 +115,8 @@ fn test_02() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -134,6 +147,8 @@ fn test_adversarial_02() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -164,6 +179,8 @@ fn test_03() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -199,6 +216,8 @@ fn test_adversarial_03() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(
         settings,
@@ -234,6 +253,8 @@ fn test_04() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 19);
@@ -254,6 +275,8 @@ fn test_adversarial_04() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 14);
@@ -274,6 +297,8 @@ fn test_05() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 12);
@@ -294,6 +319,8 @@ fn test_adversarial_05() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 12);
@@ -314,6 +341,8 @@ fn test_06() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 16);
@@ -334,6 +363,8 @@ fn test_adversarial_06() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 11);
@@ -354,6 +385,8 @@ fn test_07() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::Reflect]);
     assert_eq!(result, 15);
@@ -374,6 +407,8 @@ fn test_08() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[Action::PrudentTouch]);
     assert_eq!(result, 1);
@@ -395,6 +430,8 @@ fn test_09() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 17);
@@ -416,6 +453,8 @@ fn test_10() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 11);
@@ -437,6 +476,8 @@ fn test_11() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 11);
@@ -457,6 +498,8 @@ fn test_12() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 11);
@@ -499,8 +542,14 @@ fn monotonic_fuzz_check(simulator_settings: Settings) {
         simulator_settings,
         backload_progress: false,
         allow_unsound_branch_pruning: false,
+        forbid_quality_during_muscle_memory: false,
+        first_quality_step: 0,
     };
-    let mut solver = StepLowerBoundSolver::new(solver_settings, Default::default());
+    let mut solver = StepLowerBoundSolver::new(
+        solver_settings,
+        Default::default(),
+        &MemoryBudget::default(),
+    );
     for _ in 0..10000 {
         let state = random_state(&simulator_settings);
         let state_lower_bound = solver.step_lower_bound_with_hint(state, 0).unwrap();
@@ -537,6 +586,8 @@ fn test_monotonic_normal_sim() {
         job_level: 100,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     monotonic_fuzz_check(settings);
 }
@@ -553,6 +604,8 @@ fn test_monotonic_adversarial_sim() {
         job_level: 100,
         allowed_actions: ActionMask::all(),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     monotonic_fuzz_check(settings);
 }
@@ -0,0 +1,55 @@
+/// Configuration of the thread pool the solver runs on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThreadPoolSettings {
+    /// Number of worker threads. Zero means one thread per logical core.
+    pub num_threads: usize,
+    /// Run the worker threads below normal priority so that other applications stay responsive.
+    pub background_priority: bool,
+}
+
+impl ThreadPoolSettings {
+    pub fn build(self) -> Result<rayon::ThreadPool, rayon::ThreadPoolBuildError> {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(self.num_threads)
+            .thread_name(|index| format!("raphael-solver-{index}"))
+            .start_handler(move |_| {
+                if self.background_priority {
+                    lower_current_thread_priority();
+                }
+            })
+            .build()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn lower_current_thread_priority() {
+    // On Linux, `setpriority` with `PRIO_PROCESS` and id 0 only affects the calling thread.
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, 10) };
+    if result != 0 {
+        log::warn!("Failed to lower solver thread priority");
+    }
+}
+
+#[cfg(target_vendor = "apple")]
+fn lower_current_thread_priority() {
+    let result =
+        unsafe { libc::pthread_set_qos_class_self_np(libc::qos_class_t::QOS_CLASS_UTILITY, 0) };
+    if result != 0 {
+        log::warn!("Failed to lower solver thread priority");
+    }
+}
+
+#[cfg(windows)]
+fn lower_current_thread_priority() {
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_BELOW_NORMAL,
+    };
+    let result = unsafe { SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY_BELOW_NORMAL) };
+    if result == 0 {
+        log::warn!("Failed to lower solver thread priority");
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_vendor = "apple", windows)))]
+fn lower_current_thread_priority() {}
@@ -0,0 +1,235 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{SolverException, SolverSettings};
+
+/// Memory shared by all solvers of a single solve.
+///
+/// Memory isn't tracked per allocation. Instead, each solver periodically reports an estimate of
+/// the memory used by its hash maps and Pareto front storage through a [`MemoryTracker`].
+#[derive(Clone, Debug, Default)]
+pub struct MemoryBudget {
+    /// Limit in bytes, or `None` if the memory usage is unlimited.
+    limit: Option<usize>,
+    used: Arc<AtomicUsize>,
+    /// Highest value of `used` so far.
+    peak: Arc<AtomicUsize>,
+    /// Directory of the temporary files that hold the solved states, see [`Self::with_spill_dir`].
+    #[cfg(feature = "disk-spill")]
+    spill_dir: Option<std::path::PathBuf>,
+}
+
+impl MemoryBudget {
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            used: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "disk-spill")]
+            spill_dir: None,
+        }
+    }
+
+    /// Keeps the solved states of the quality upper bound solver in memory-mapped files in `dir`,
+    /// so that the operating system can page them out. Memory-mapped storage isn't counted
+    /// towards the limit.
+    #[cfg(feature = "disk-spill")]
+    #[must_use]
+    pub fn with_spill_dir(mut self, dir: Option<std::path::PathBuf>) -> Self {
+        self.spill_dir = dir;
+        self
+    }
+
+    #[cfg(feature = "disk-spill")]
+    pub fn spill_dir(&self) -> Option<&std::path::Path> {
+        self.spill_dir.as_deref()
+    }
+
+    pub fn tracker(&self) -> MemoryTracker {
+        MemoryTracker {
+            budget: self.clone(),
+            reported: 0,
+        }
+    }
+
+    /// Estimated memory usage of all solvers, in bytes.
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Highest estimated memory usage of all solvers so far, in bytes.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Returns `MemoryLimitReached` if the usage of all solvers exceeds the limit.
+    pub fn check(&self) -> Result<(), SolverException> {
+        match self.limit {
+            Some(limit) if self.used() > limit => Err(SolverException::MemoryLimitReached),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Reports the memory usage of a single solver to the shared [`MemoryBudget`].
+pub struct MemoryTracker {
+    budget: MemoryBudget,
+    reported: usize,
+}
+
+impl MemoryTracker {
+    /// Usage changes smaller than this are not reported, to keep the overhead of the
+    /// shared counter low.
+    const GRANULARITY: usize = 1 << 20;
+
+    /// Updates the memory usage of the solver to `usage` bytes.
+    /// Returns `MemoryLimitReached` if the total usage of all solvers exceeds the limit.
+    pub fn update(&mut self, usage: usize) -> Result<(), SolverException> {
+        if usage.abs_diff(self.reported) >= Self::GRANULARITY {
+            match usage >= self.reported {
+                true => {
+                    let increase = usage - self.reported;
+                    let used = self.budget.used.fetch_add(increase, Ordering::Relaxed) + increase;
+                    self.budget.peak.fetch_max(used, Ordering::Relaxed);
+                }
+                false => {
+                    self.budget
+                        .used
+                        .fetch_sub(self.reported - usage, Ordering::Relaxed);
+                }
+            }
+            self.reported = usage;
+        }
+        self.budget.check()
+    }
+}
+
+impl Drop for MemoryTracker {
+    fn drop(&mut self) {
+        self.budget.used.fetch_sub(self.reported, Ordering::Relaxed);
+    }
+}
+
+/// Estimated heap size of a hash map with the given capacity, ignoring the control bytes.
+pub const fn hash_map_memory_usage<K, V>(capacity: usize) -> usize {
+    capacity * std::mem::size_of::<(K, V)>()
+}
+
+/// Estimate of the peak memory usage of a solve with the given settings, in bytes.
+///
+/// The model is fitted to the peak that [`MemoryTracker`] measured on a set of reference recipes
+/// between 300 and 700 CP and between 35 and 80 durability, and stays within a factor of 2 of the
+/// measured peak on all of them. Most of the memory is taken by the bound solvers, whose state
+/// count grows with the CP and the durability. The Quality cap has no noticeable influence.
+/// `minimize_steps` forces the full search, like a solve without branch pruning.
+pub fn estimate_peak_memory(settings: &SolverSettings, minimize_steps: bool) -> usize {
+    // peak of a pruned, non-adversarial solve of a recipe with 600 CP and 70 durability
+    const REFERENCE_PEAK: f64 = (60 << 20) as f64;
+    const REFERENCE_CP: f64 = 600.0;
+    const REFERENCE_DURABILITY: f64 = 70.0;
+    const CP_EXPONENT: f64 = 2.2;
+    const DURABILITY_EXPONENT: f64 = 1.5;
+    const ADVERSARIAL_FACTOR: f64 = 2.0;
+    const FULL_SEARCH_FACTOR: f64 = 4.3;
+    // the full search without backloaded Progress
+    const PROGRESS_FACTOR: f64 = 1.4;
+
+    let simulator_settings = &settings.simulator_settings;
+    let cp = f64::from(simulator_settings.max_cp.max(0)) / REFERENCE_CP;
+    let durability = f64::from(simulator_settings.max_durability.max(0)) / REFERENCE_DURABILITY;
+    let mut bytes = REFERENCE_PEAK * cp.powf(CP_EXPONENT) * durability.powf(DURABILITY_EXPONENT);
+    if simulator_settings.adversarial {
+        bytes *= ADVERSARIAL_FACTOR;
+    }
+    if minimize_steps || !settings.allow_unsound_branch_pruning {
+        bytes *= FULL_SEARCH_FACTOR;
+        if !settings.backload_progress {
+            bytes *= PROGRESS_FACTOR;
+        }
+    }
+    bytes as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_budget() {
+        let budget = MemoryBudget::new(Some(4 << 20));
+        let mut tracker_a = budget.tracker();
+        let mut tracker_b = budget.tracker();
+
+        // small changes are not reported
+        assert_eq!(tracker_a.update(1000), Ok(()));
+        assert_eq!(budget.used(), 0);
+
+        assert_eq!(tracker_a.update(2 << 20), Ok(()));
+        assert_eq!(tracker_b.update(2 << 20), Ok(()));
+        assert_eq!(budget.used(), 4 << 20);
+        assert_eq!(
+            tracker_b.update(3 << 20),
+            Err(SolverException::MemoryLimitReached)
+        );
+
+        assert_eq!(budget.peak(), 5 << 20);
+
+        drop(tracker_b);
+        assert_eq!(budget.used(), 2 << 20);
+        assert_eq!(tracker_a.update(0), Ok(()));
+        assert_eq!(budget.used(), 0);
+        assert_eq!(budget.peak(), 5 << 20);
+    }
+
+    #[test]
+    fn test_unlimited_memory_budget() {
+        let budget = MemoryBudget::default();
+        let mut tracker = budget.tracker();
+        assert_eq!(tracker.update(usize::MAX / 2), Ok(()));
+        assert_eq!(budget.used(), usize::MAX / 2);
+    }
+
+    #[test]
+    fn test_estimate_peak_memory() {
+        let settings = SolverSettings {
+            simulator_settings: raphael_sim::Settings {
+                max_cp: 600,
+                max_durability: 70,
+                max_progress: 6000,
+                max_quality: 12000,
+                base_progress: 250,
+                base_quality: 300,
+                job_level: 100,
+                allowed_actions: raphael_sim::ActionMask::all(),
+                adversarial: false,
+                success_model: raphael_sim::SuccessModel::default(),
+                rules_override: raphael_sim::RulesOverride::default(),
+            },
+            backload_progress: false,
+            allow_unsound_branch_pruning: true,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
+        };
+        let normal = estimate_peak_memory(&settings, false);
+        let adversarial_settings = SolverSettings {
+            simulator_settings: raphael_sim::Settings {
+                adversarial: true,
+                ..settings.simulator_settings
+            },
+            ..settings
+        };
+        let adversarial = estimate_peak_memory(&adversarial_settings, false);
+        assert!(adversarial > normal);
+        assert!(estimate_peak_memory(&adversarial_settings, true) > adversarial);
+        let low_cp_settings = SolverSettings {
+            simulator_settings: raphael_sim::Settings {
+                max_cp: 300,
+                ..adversarial_settings.simulator_settings
+            },
+            ..adversarial_settings
+        };
+        assert!(estimate_peak_memory(&low_cp_settings, false) < adversarial);
+    }
+}
@@ -1,8 +1,16 @@
 mod atomic_flag;
+mod memory_budget;
 mod pareto_front_builder;
+#[cfg(feature = "disk-spill")]
+mod spill;
 
 pub use atomic_flag::AtomicFlag;
-pub use pareto_front_builder::{ParetoFrontBuilder, ParetoFrontId, ParetoValue};
+pub use memory_budget::{MemoryBudget, MemoryTracker, estimate_peak_memory, hash_map_memory_usage};
+pub use pareto_front_builder::{
+    ParetoFrontBuilder, ParetoFrontId, ParetoValue, SolvedStatesCheckpoint,
+};
+#[cfg(feature = "disk-spill")]
+pub use spill::{SpillHashMap, SpillVec};
 
 pub struct ScopedTimer {
     name: &'static str,
@@ -28,12 +36,14 @@ impl Drop for ScopedTimer {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Entry<T> {
     item: T,
     depth: u8,
     parent_index: usize,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Backtracking<T: Copy> {
     entries: Vec<Entry<T>>,
 }
@@ -56,6 +66,14 @@ impl<T: Copy> Backtracking<T> {
         items.into_iter().rev()
     }
 
+    /// The item at `index`, `None` for the sentinel.
+    pub fn get(&self, index: usize) -> Option<T> {
+        match index == Self::SENTINEL {
+            true => None,
+            false => Some(self.entries[index].item),
+        }
+    }
+
     pub fn push(&mut self, item: T, parent_index: usize) -> usize {
         let depth = if parent_index == Self::SENTINEL {
             1
@@ -69,6 +87,10 @@ impl<T: Copy> Backtracking<T> {
         });
         self.entries.len() - 1
     }
+
+    pub fn memory_usage(&self) -> usize {
+        self.entries.capacity() * std::mem::size_of::<Entry<T>>()
+    }
 }
 
 impl<T: Copy> Drop for Backtracking<T> {
@@ -1,4 +1,5 @@
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParetoValue<T, U> {
     pub first: T,
     pub second: U,
@@ -10,21 +11,78 @@ impl<T, U> ParetoValue<T, U> {
     }
 }
 
+use crate::SolverException;
+
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParetoFrontId {
     offset: usize,
     length: usize,
 }
 
+/// Solved states of a bound solver with their saved Pareto fronts, for continuing a solve later,
+/// see [`crate::SolverCheckpoint`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolvedStatesCheckpoint<S> {
+    pub states: Vec<(S, ParetoFrontId)>,
+    pub pareto_values: Vec<ParetoValue<u16, u16>>,
+}
+
+/// Storage of the saved Pareto fronts, see [`ParetoFrontBuilder::spill_to_disk`].
+enum FrontStorage<T: Copy> {
+    Memory(Vec<T>),
+    #[cfg(feature = "disk-spill")]
+    Disk(super::SpillVec<T>),
+}
+
+impl<T: Copy> FrontStorage<T> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Memory(storage) => storage.len(),
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(storage) => storage.len(),
+        }
+    }
+
+    fn as_slice(&self) -> &[T] {
+        match self {
+            Self::Memory(storage) => storage,
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(storage) => storage.as_slice(),
+        }
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) -> Result<(), SolverException> {
+        match self {
+            Self::Memory(storage) => {
+                storage.extend_from_slice(values);
+                Ok(())
+            }
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(storage) => storage.extend_from_slice(values),
+        }
+    }
+
+    /// Heap size in bytes, file-backed storage doesn't count.
+    fn memory_usage(&self) -> usize {
+        match self {
+            Self::Memory(storage) => storage.capacity() * std::mem::size_of::<T>(),
+            #[cfg(feature = "disk-spill")]
+            Self::Disk(_) => 0,
+        }
+    }
+}
+
 pub struct ParetoFrontBuilder<T, U>
 where
     T: Copy + std::cmp::Ord + std::default::Default + std::fmt::Debug,
     U: Copy + std::cmp::Ord + std::default::Default + std::fmt::Debug,
 {
-    storage: Vec<ParetoValue<T, U>>,
+    storage: FrontStorage<ParetoValue<T, U>>,
     segments: Vec<usize>, // indices to the beginning of each segment
     buffer: Vec<ParetoValue<T, U>>,
-    merge_buffer: [ParetoValue<T, U>; 1024],
+    /// Scratch space of [`Self::merge`], grown to fit the two merged segments.
+    merge_buffer: Vec<ParetoValue<T, U>>,
     // cut-off values
     max_first: T,
     max_second: U,
@@ -41,10 +99,10 @@ where
 {
     pub fn new(max_first: T, max_second: U) -> Self {
         Self {
-            storage: Vec::with_capacity(1 << 18),
+            storage: FrontStorage::Memory(Vec::with_capacity(1 << 18)),
             segments: Vec::with_capacity(1 << 12),
             buffer: Vec::with_capacity(1 << 12),
-            merge_buffer: [ParetoValue::default(); 1024],
+            merge_buffer: vec![ParetoValue::default(); 1 << 10],
             max_first,
             max_second,
             fronts_generated: 0,
@@ -53,6 +111,43 @@ where
         }
     }
 
+    /// Heap size of the stored Pareto fronts and the working buffers, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.storage.memory_usage()
+            + (self.buffer.capacity() + self.merge_buffer.capacity())
+                * std::mem::size_of::<ParetoValue<T, U>>()
+            + self.segments.capacity() * std::mem::size_of::<usize>()
+    }
+
+    /// Moves the saved Pareto fronts to a temporary file in `dir` and saves all further fronts
+    /// there, see [`super::SpillVec`].
+    #[cfg(feature = "disk-spill")]
+    pub fn spill_to_disk(&mut self, dir: &std::path::Path) -> Result<(), SolverException> {
+        let mut storage = super::SpillVec::new(dir, 1 << 18)?;
+        storage.extend_from_slice(self.storage.as_slice())?;
+        self.storage = FrontStorage::Disk(storage);
+        Ok(())
+    }
+
+    /// All values of the saved Pareto fronts, which the [`ParetoFrontId`]s point into.
+    pub fn saved_values(&self) -> &[ParetoValue<T, U>] {
+        self.storage.as_slice()
+    }
+
+    /// Restores the values returned by [`Self::saved_values`], so that the [`ParetoFrontId`]s
+    /// of a previous builder point to the same fronts. Must be called before any front is saved.
+    pub fn restore_saved_values(
+        &mut self,
+        values: &[ParetoValue<T, U>],
+    ) -> Result<(), SolverException> {
+        if self.storage.len() != 0 {
+            return Err(SolverException::InternalError(
+                "Pareto fronts were saved before restoring a checkpoint".to_owned(),
+            ));
+        }
+        self.storage.extend_from_slice(values)
+    }
+
     pub fn clear(&mut self) {
         self.segments.clear();
         self.buffer.clear();
@@ -68,7 +163,7 @@ where
     }
 
     pub fn push_id(&mut self, id: ParetoFrontId) {
-        let slice = &self.storage[id.offset..id.offset + id.length];
+        let slice = &self.storage.as_slice()[id.offset..id.offset + id.length];
         self.segments.push(self.buffer.len());
         self.buffer.extend_from_slice(slice);
     }
@@ -80,6 +175,12 @@ where
         let begin_b = self.segments.pop().unwrap();
         let begin_a = self.segments.last().copied().unwrap();
 
+        // the merged segment is at most as long as both segments together
+        let merged_len = self.buffer.len() - begin_a;
+        if self.merge_buffer.len() < merged_len {
+            self.merge_buffer.resize(merged_len, ParetoValue::default());
+        }
+
         let mut begin_c = 0;
         let mut end_c = {
             assert!(begin_a <= begin_b && begin_b <= self.buffer.len());
@@ -122,7 +223,7 @@ where
             }
         };
 
-        assert!(end_c <= self.merge_buffer.len());
+        debug_assert!(end_c <= self.merge_buffer.len());
         while begin_c + 1 < end_c && self.merge_buffer[begin_c + 1].second >= self.max_second {
             begin_c += 1;
         }
@@ -233,21 +334,22 @@ where
         idx_c
     }
 
-    /// Saves the last segment to storage and returns an identifier to retrieve the segment
-    pub fn save(&mut self) -> Option<ParetoFrontId> {
-        match self.segments.last().copied() {
-            Some(segment_begin) => {
-                self.fronts_generated += 1;
-                let slice = &self.buffer[segment_begin..];
-                let id = ParetoFrontId {
-                    offset: self.storage.len(),
-                    length: self.buffer.len() - segment_begin,
-                };
-                self.storage.extend_from_slice(slice);
-                Some(id)
-            }
-            None => None,
-        }
+    /// Saves the last segment to storage and returns an identifier to retrieve the segment.
+    /// Fails if there is no segment or the storage can't be written.
+    pub fn save(&mut self) -> Result<ParetoFrontId, SolverException> {
+        let Some(segment_begin) = self.segments.last().copied() else {
+            return Err(SolverException::InternalError(
+                "No Pareto front to save".to_owned(),
+            ));
+        };
+        self.fronts_generated += 1;
+        let slice = &self.buffer[segment_begin..];
+        let id = ParetoFrontId {
+            offset: self.storage.len(),
+            length: self.buffer.len() - segment_begin,
+        };
+        self.storage.extend_from_slice(slice)?;
+        Ok(id)
     }
 
     pub fn peek(&self) -> Option<&[ParetoValue<T, U>]> {
@@ -264,7 +366,7 @@ where
 
     /// Retrieves a Pareto front from storage
     pub fn retrieve(&self, id: ParetoFrontId) -> &[ParetoValue<T, U>] {
-        &self.storage[id.offset..id.offset + id.length]
+        &self.storage.as_slice()[id.offset..id.offset + id.length]
     }
 
     pub fn is_max(&self) -> bool {
@@ -392,6 +494,29 @@ mod tests {
         builder.check_invariants();
     }
 
+    #[test]
+    fn test_merge_large_fronts() {
+        // interleaved fronts whose merged front is longer than the initial merge buffer
+        let front = |offset: usize| -> Vec<ParetoValue<usize, usize>> {
+            (0..1500)
+                .map(|i| ParetoValue::new(2 * i + offset, 10_000 - 2 * i - offset))
+                .collect()
+        };
+        let mut builder = ParetoFrontBuilder::new(usize::MAX, usize::MAX);
+        builder.push_slice(&front(0));
+        builder.push_slice(&front(1));
+        builder.merge();
+        builder.check_invariants();
+        let result = builder.peek().unwrap();
+        assert_eq!(result.len(), 3000);
+        assert!(
+            result
+                .iter()
+                .enumerate()
+                .all(|(i, value)| *value == ParetoValue::new(i, 10_000 - i))
+        );
+    }
+
     #[test]
     fn test_merge_fuzz() {
         let mut rng = rand::thread_rng();
@@ -0,0 +1,224 @@
+//! Storage in memory-mapped temporary files, so that the operating system can page solver state
+//! out to disk instead of running out of memory. The files are deleted when the storage is
+//! dropped. Pages of a file mapping don't count as process memory, so their size isn't reported
+//! to the [`super::MemoryBudget`].
+
+use std::{
+    fs::File,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    path::{Path, PathBuf},
+};
+
+use memmap2::MmapMut;
+
+use crate::SolverException;
+
+fn spill_error(error: std::io::Error) -> SolverException {
+    SolverException::InternalError(format!("Failed to write the spill file: {error}"))
+}
+
+/// Temporary file that is mapped into memory as a whole.
+struct MappedFile {
+    file: File,
+    mmap: MmapMut,
+}
+
+impl MappedFile {
+    /// Creates a file of `len` zero bytes in `dir`.
+    fn new(dir: &Path, len: usize) -> Result<Self, SolverException> {
+        let file = tempfile::tempfile_in(dir).map_err(spill_error)?;
+        let mmap = Self::map(&file, len)?;
+        Ok(Self { file, mmap })
+    }
+
+    fn map(file: &File, len: usize) -> Result<MmapMut, SolverException> {
+        // mapping an empty file fails on some platforms
+        file.set_len(len.max(1) as u64).map_err(spill_error)?;
+        // SAFETY: the file is an unnamed temporary file, so no other process can modify it
+        unsafe { MmapMut::map_mut(file) }.map_err(spill_error)
+    }
+
+    /// Grows the file to `len` bytes, the new bytes are zero.
+    fn grow(&mut self, len: usize) -> Result<(), SolverException> {
+        self.mmap = Self::map(&self.file, len)?;
+        Ok(())
+    }
+
+    fn as_ptr<T>(&self) -> *const T {
+        self.mmap.as_ptr().cast()
+    }
+
+    fn as_mut_ptr<T>(&mut self) -> *mut T {
+        self.mmap.as_mut_ptr().cast()
+    }
+}
+
+/// Growable array in a temporary file, for the Pareto fronts of
+/// [`super::ParetoFrontBuilder`].
+pub struct SpillVec<T: Copy> {
+    file: MappedFile,
+    len: usize,
+    capacity: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Copy> SpillVec<T> {
+    pub fn new(dir: &Path, capacity: usize) -> Result<Self, SolverException> {
+        let capacity = capacity.max(1);
+        Ok(Self {
+            file: MappedFile::new(dir, capacity * std::mem::size_of::<T>())?,
+            len: 0,
+            capacity,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn extend_from_slice(&mut self, values: &[T]) -> Result<(), SolverException> {
+        if self.len + values.len() > self.capacity {
+            self.capacity = std::cmp::max(2 * self.capacity, self.len + values.len());
+            self.file.grow(self.capacity * std::mem::size_of::<T>())?;
+        }
+        // SAFETY: the file has room for `capacity` values and the mapping is page-aligned
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                values.as_ptr(),
+                self.file.as_mut_ptr::<T>().add(self.len),
+                values.len(),
+            );
+        }
+        self.len += values.len();
+        Ok(())
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` values were written by `extend_from_slice`
+        unsafe { std::slice::from_raw_parts(self.file.as_ptr(), self.len) }
+    }
+}
+
+/// Slot of a [`SpillHashMap`]. All-zero bytes are a valid empty slot, which is what new parts
+/// of the file contain.
+#[derive(Clone, Copy)]
+struct Slot<K: Copy, V: Copy> {
+    occupied: bool,
+    key: MaybeUninit<K>,
+    value: MaybeUninit<V>,
+}
+
+/// Hash map with linear probing in a temporary file, for the solved states of the bound solvers.
+/// Entries can't be removed.
+pub struct SpillHashMap<K: Copy + Eq + Hash, V: Copy> {
+    dir: PathBuf,
+    file: MappedFile,
+    len: usize,
+    /// Number of slots, always a power of two.
+    capacity: usize,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K: Copy + Eq + Hash, V: Copy> SpillHashMap<K, V> {
+    const INITIAL_CAPACITY: usize = 1 << 16;
+
+    pub fn new(dir: &Path) -> Result<Self, SolverException> {
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file: MappedFile::new(
+                dir,
+                Self::INITIAL_CAPACITY * std::mem::size_of::<Slot<K, V>>(),
+            )?,
+            len: 0,
+            capacity: Self::INITIAL_CAPACITY,
+            _marker: PhantomData,
+        })
+    }
+
+    fn slot(&self, index: usize) -> &Slot<K, V> {
+        debug_assert!(index < self.capacity);
+        // SAFETY: the file contains `capacity` slots, for which any bytes written by `insert`
+        // or left zero are valid
+        unsafe { &*self.file.as_ptr::<Slot<K, V>>().add(index) }
+    }
+
+    fn slot_mut(&mut self, index: usize) -> &mut Slot<K, V> {
+        debug_assert!(index < self.capacity);
+        // SAFETY: see `slot`
+        unsafe { &mut *self.file.as_mut_ptr::<Slot<K, V>>().add(index) }
+    }
+
+    /// Index of the slot that contains `key`, or of the empty slot where it would be inserted.
+    fn find(&self, key: &K) -> usize {
+        let mut hasher = rustc_hash::FxHasher::default();
+        key.hash(&mut hasher);
+        let mut index = hasher.finish() as usize & (self.capacity - 1);
+        loop {
+            let slot = self.slot(index);
+            // SAFETY: the key of an occupied slot was written by `insert`
+            if !slot.occupied || unsafe { slot.key.assume_init_ref() } == key {
+                return index;
+            }
+            index = (index + 1) & (self.capacity - 1);
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        let slot = self.slot(self.find(key));
+        match slot.occupied {
+            // SAFETY: the value of an occupied slot was written by `insert`
+            true => Some(unsafe { slot.value.assume_init() }),
+            false => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (K, V)> + '_ {
+        (0..self.capacity).filter_map(|index| {
+            let slot = self.slot(index);
+            // SAFETY: the key and value of an occupied slot were written by `insert`
+            slot.occupied
+                .then(|| unsafe { (slot.key.assume_init(), slot.value.assume_init()) })
+        })
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Result<(), SolverException> {
+        if 2 * (self.len + 1) > self.capacity {
+            self.rehash(2 * self.capacity)?;
+        }
+        let index = self.find(&key);
+        if !self.slot(index).occupied {
+            self.len += 1;
+        }
+        *self.slot_mut(index) = Slot {
+            occupied: true,
+            key: MaybeUninit::new(key),
+            value: MaybeUninit::new(value),
+        };
+        Ok(())
+    }
+
+    /// Moves all entries into a new file with `capacity` slots.
+    fn rehash(&mut self, capacity: usize) -> Result<(), SolverException> {
+        let mut map = Self {
+            dir: self.dir.clone(),
+            file: MappedFile::new(&self.dir, capacity * std::mem::size_of::<Slot<K, V>>())?,
+            len: 0,
+            capacity,
+            _marker: PhantomData,
+        };
+        for index in 0..self.capacity {
+            let slot = *self.slot(index);
+            if slot.occupied {
+                // SAFETY: the key of an occupied slot was written by `insert`
+                let new_index = map.find(unsafe { slot.key.assume_init_ref() });
+                *map.slot_mut(new_index) = slot;
+                map.len += 1;
+            }
+        }
+        *self = map;
+        Ok(())
+    }
+}
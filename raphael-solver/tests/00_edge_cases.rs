@@ -1,5 +1,7 @@
 use raphael_sim::*;
-use raphael_solver::{SolverException, test_utils::*};
+use raphael_solver::{
+    ProgressOnlyReason, SolverException, SolverSettings, progress_only_reason, test_utils::*,
+};
 
 #[test]
 fn unsolvable() {
@@ -16,6 +18,8 @@ fn unsolvable() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false);
     assert_eq!(actions, Err(SolverException::NoSolution));
@@ -36,12 +40,68 @@ fn zero_quality() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
     assert_eq!(score, (0, 5, 14, 0));
 }
 
+#[test]
+fn quality_target_reached() {
+    let settings = Settings {
+        max_cp: 80,
+        max_durability: 60,
+        max_progress: 1920,
+        max_quality: 0,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    assert_eq!(
+        progress_only_reason(&settings, &SimulationState::new(&settings)),
+        Some(ProgressOnlyReason::QualityReached)
+    );
+    let actions = solve(&settings, false, false).unwrap();
+    let score = get_score_quad(&settings, &actions);
+    assert_eq!(score, (0, 5, 14, 0));
+}
+
+#[test]
+fn trained_eye_progress_only() {
+    let settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    assert_eq!(
+        progress_only_reason(&settings, &SimulationState::new(&settings)),
+        Some(ProgressOnlyReason::TrainedEye)
+    );
+    let actions = solve(&settings, false, false).unwrap();
+    assert_eq!(actions[0], Action::TrainedEye);
+    let score = get_score_quad(&settings, &actions);
+    assert_eq!(score.0, 1000);
+}
+
 #[test]
 fn max_quality() {
     let settings = Settings {
@@ -57,12 +117,93 @@ fn max_quality() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
     assert_eq!(score, (1000, 11, 28, 100));
 }
 
+#[test]
+fn no_quality_during_muscle_memory() {
+    let settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let solver_settings = SolverSettings {
+        simulator_settings: settings,
+        backload_progress: false,
+        allow_unsound_branch_pruning: false,
+        forbid_quality_during_muscle_memory: true,
+        first_quality_step: 0,
+    };
+    let actions = macro_solver(solver_settings).solve().unwrap();
+    let mut state = SimulationState::new(&settings);
+    for action in &actions {
+        let next_state = state
+            .use_action(*action, Condition::Normal, &settings)
+            .unwrap();
+        if state.effects.muscle_memory() != 0 {
+            assert_eq!(next_state.quality, state.quality);
+        }
+        state = next_state;
+    }
+    let score = get_score_quad(&settings, &actions);
+    let unconstrained_score = get_score_quad(&settings, &solve(&settings, false, false).unwrap());
+    assert!(score.0 <= unconstrained_score.0);
+}
+
+#[test]
+fn first_quality_step() {
+    let settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let solver_settings = SolverSettings {
+        simulator_settings: settings,
+        backload_progress: false,
+        allow_unsound_branch_pruning: false,
+        forbid_quality_during_muscle_memory: false,
+        first_quality_step: 3,
+    };
+    let actions = macro_solver(solver_settings).solve().unwrap();
+    let mut state = SimulationState::new(&settings);
+    for action in &actions[..3] {
+        state = state
+            .use_action(*action, Condition::Normal, &settings)
+            .unwrap();
+    }
+    assert_eq!(state.quality, 0);
+    let score = get_score_quad(&settings, &actions);
+    let unconstrained_score = get_score_quad(&settings, &solve(&settings, false, false).unwrap());
+    assert!(score.0 <= unconstrained_score.0);
+}
+
 #[test]
 fn large_progress_quality_increase() {
     let settings = Settings {
@@ -75,8 +216,142 @@ fn large_progress_quality_increase() {
         job_level: 100,
         allowed_actions: ActionMask::all(),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
     assert_eq!(score, (100, 1, 3, 4900));
 }
+
+#[test]
+fn unreliable_actions() {
+    let settings = Settings {
+        max_cp: 0,
+        max_durability: 60,
+        max_progress: 1000,
+        max_quality: 100,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let actions = solve(&settings, false, false);
+    assert_eq!(actions, Err(SolverException::NoSolution));
+    let settings = Settings {
+        success_model: SuccessModel::AssumeSuccess,
+        ..settings
+    };
+    let actions = solve(&settings, false, false).unwrap();
+    let score = get_score_quad(&settings, &actions);
+    assert_eq!(score, (100, 3, 9, 0));
+}
+
+#[test]
+fn rules_override() {
+    let settings = Settings {
+        max_cp: 200,
+        max_durability: 40,
+        max_progress: 2000,
+        max_quality: 20000,
+        base_progress: 229,
+        base_quality: 224,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let actions = solve(&settings, false, false).unwrap();
+    let score = get_score_quad(&settings, &actions);
+    assert_eq!(score, (2239, 10, 26, 0));
+    // cheaper Waste Not must not be pruned by the heuristics tuned for the in-game cost
+    let settings = Settings {
+        rules_override: RulesOverride::new(&[ActionOverride {
+            cp_cost: Some(10),
+            ..ActionOverride::new(Action::WasteNot2)
+        }])
+        .unwrap(),
+        ..settings
+    };
+    let actions = solve(&settings, false, false).unwrap();
+    let score = get_score_quad(&settings, &actions);
+    assert_eq!(score, (3032, 10, 26, 0));
+    // restoring durability for less than 1 CP per 5 durability
+    let settings = Settings {
+        rules_override: RulesOverride::new(&[ActionOverride {
+            cp_cost: Some(5),
+            ..ActionOverride::new(Action::MasterMend)
+        }])
+        .unwrap(),
+        ..settings
+    };
+    let actions = solve(&settings, false, false).unwrap();
+    let score = get_score_quad(&settings, &actions);
+    assert_eq!(score, (5688, 23, 68, 0));
+}
+
+#[test]
+fn memory_limit_reached() {
+    let settings = Settings {
+        max_cp: 680,
+        max_durability: 70,
+        max_progress: 5060,
+        max_quality: 12628,
+        base_progress: 229,
+        base_quality: 224,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let solver_settings = solver_settings(settings);
+    let result = macro_solver(solver_settings)
+        .memory_limit(Some(1 << 20))
+        .solve();
+    assert_eq!(result, Err(SolverException::MemoryLimitReached));
+}
+
+#[cfg(feature = "disk-spill")]
+#[test]
+fn spill_to_disk() {
+    let settings = Settings {
+        max_cp: 300,
+        max_durability: 40,
+        max_progress: 2000,
+        max_quality: 4000,
+        base_progress: 229,
+        base_quality: 224,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let solver_settings = solver_settings(settings);
+    let actions = macro_solver(solver_settings)
+        .spill_to_disk(Some(std::env::temp_dir()))
+        .solve()
+        .unwrap();
+    let expected = solve(&settings, false, false).unwrap();
+    assert_eq!(
+        get_score_quad(&settings, &actions),
+        get_score_quad(&settings, &expected)
+    );
+}
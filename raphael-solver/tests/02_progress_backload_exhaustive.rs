@@ -16,6 +16,8 @@ fn rinascita_3700_3280() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -38,6 +40,8 @@ fn pactmaker_3240_3130() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -59,6 +63,8 @@ fn pactmaker_3240_3130_heart_and_soul() {
             .remove(Action::TrainedEye)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -81,6 +87,8 @@ fn diadochos_4021_3660() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -103,6 +111,8 @@ fn indagator_3858_4057() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -125,6 +135,8 @@ fn rarefied_tacos_de_carne_asada_4785_4758() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -149,6 +161,8 @@ fn stuffed_peppers_2() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -172,6 +186,8 @@ fn stuffed_peppers_2_heart_and_soul() {
             .remove(Action::TrainedEye)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -195,6 +211,8 @@ fn stuffed_peppers_2_quick_innovation() {
             .remove(Action::TrainedEye)
             .remove(Action::HeartAndSoul),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -217,6 +235,8 @@ fn rakaznar_lapidary_hammer_4462_4391() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -239,6 +259,8 @@ fn black_star_4048_3997() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -261,6 +283,8 @@ fn claro_walnut_lumber_4900_4800() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -283,6 +307,8 @@ fn rakaznar_lapidary_hammer_4900_4800() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -305,6 +331,8 @@ fn rarefied_tacos_de_carne_asada_4966_4817() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -327,6 +355,8 @@ fn archeo_kingdom_broadsword_4966_4914() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -16,6 +16,8 @@ fn rinascita_3700_3280() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -37,6 +39,8 @@ fn pactmaker_3240_3130() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -57,6 +61,8 @@ fn pactmaker_3240_3130_heart_and_soul() {
             .remove(Action::TrainedEye)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -78,6 +84,8 @@ fn diadochos_4021_3660() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -99,6 +107,8 @@ fn indagator_3858_4057() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -120,6 +130,8 @@ fn rarefied_tacos_de_carne_asada_4785_4758() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -143,6 +155,8 @@ fn stuffed_peppers_2() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -165,6 +179,8 @@ fn stuffed_peppers_2_heart_and_soul() {
             .remove(Action::TrainedEye)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -187,6 +203,8 @@ fn stuffed_peppers_2_quick_innovation() {
             .remove(Action::TrainedEye)
             .remove(Action::HeartAndSoul),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -208,6 +226,8 @@ fn rakaznar_lapidary_hammer_4462_4391() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -229,6 +249,8 @@ fn black_star_4048_3997() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -250,6 +272,8 @@ fn claro_walnut_lumber_4900_4800() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -271,6 +295,8 @@ fn rakaznar_lapidary_hammer_4900_4800() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -292,6 +318,8 @@ fn rarefied_tacos_de_carne_asada_4966_4817() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -313,6 +341,8 @@ fn archeo_kingdom_broadsword_4966_4914() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -14,6 +14,8 @@ const SETTINGS: Settings = Settings {
         .remove(Action::HeartAndSoul)
         .remove(Action::QuickInnovation),
     adversarial: true,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
 };
 
 #[test]
@@ -51,6 +53,8 @@ fn test_rare_tacos_2() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -75,6 +79,8 @@ fn test_mountain_chromite_ingot_no_manipulation() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -96,6 +102,8 @@ fn test_indagator_3858_4057() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, false, false).unwrap();
     let score = get_score_quad(&settings, &actions);
@@ -118,6 +126,8 @@ fn test_rare_tacos_4628_4410() {
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: true,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
     };
     let actions = solve(&settings, true, true).unwrap();
     let score = get_score_quad(&settings, &actions);
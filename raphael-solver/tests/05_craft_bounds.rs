@@ -0,0 +1,107 @@
+use raphael_sim::*;
+use raphael_solver::{
+    AtomicFlag, SolverException, craft_bounds, quality_upper_bound_per_step, test_utils::*,
+};
+
+#[test]
+fn unsolvable() {
+    let settings = Settings {
+        max_cp: 100,
+        max_durability: 60,
+        max_progress: 4000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let bounds = craft_bounds(solver_settings(settings), AtomicFlag::new());
+    assert_eq!(bounds, Err(SolverException::NoSolution));
+}
+
+#[test]
+fn rinascita_3700_3280() {
+    let settings = Settings {
+        max_cp: 680,
+        max_durability: 70,
+        max_progress: 5060,
+        max_quality: 12628,
+        base_progress: 229,
+        base_quality: 224,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let bounds = craft_bounds(solver_settings(settings), AtomicFlag::new()).unwrap();
+    let actions = solve(&settings, false, false).unwrap();
+    let (quality, _, _, _) = get_score_quad(&settings, &actions);
+    assert!(bounds.quality_upper_bound >= quality);
+}
+
+#[test]
+fn max_quality() {
+    let settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let bounds = craft_bounds(solver_settings(settings), AtomicFlag::new()).unwrap();
+    let actions = solve(&settings, false, false).unwrap();
+    let (quality, steps, _, _) = get_score_quad(&settings, &actions);
+    assert_eq!(quality, settings.max_quality);
+    assert_eq!(bounds.quality_upper_bound, settings.max_quality);
+    assert!(
+        bounds
+            .steps_lower_bound
+            .is_some_and(|lower_bound| lower_bound <= steps)
+    );
+}
+
+#[test]
+fn quality_per_step() {
+    let settings = Settings {
+        max_cp: 680,
+        max_durability: 70,
+        max_progress: 5060,
+        max_quality: 12628,
+        base_progress: 229,
+        base_quality: 224,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let quality_upper_bounds =
+        quality_upper_bound_per_step(solver_settings(settings), 40, AtomicFlag::new()).unwrap();
+    assert!(quality_upper_bounds.is_sorted());
+    let actions = solve(&settings, false, false).unwrap();
+    let (quality, steps, _, _) = get_score_quad(&settings, &actions);
+    let steps = std::cmp::min(usize::from(steps), quality_upper_bounds.len());
+    assert!(quality_upper_bounds[steps - 1] >= quality);
+}
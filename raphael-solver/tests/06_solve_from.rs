@@ -0,0 +1,64 @@
+use raphael_sim::*;
+use raphael_solver::test_utils::*;
+
+fn solve_from(settings: &Settings, prefix: &[Action]) -> Vec<Action> {
+    let initial_state = SimulationState::from_macro(settings, prefix).unwrap();
+    macro_solver(solver_settings(*settings))
+        .solve_from(initial_state)
+        .unwrap()
+}
+
+#[test]
+fn rinascita_3700_3280() {
+    let settings = Settings {
+        max_cp: 680,
+        max_durability: 70,
+        max_progress: 5060,
+        max_quality: 12628,
+        base_progress: 229,
+        base_quality: 224,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let prefix = [Action::Reflect, Action::Manipulation, Action::BasicTouch];
+    let continuation = solve_from(&settings, &prefix);
+    let actions = [prefix.as_slice(), continuation.as_slice()].concat();
+    let (quality, _, _, _) = get_score_quad(&settings, &actions);
+
+    // fixing a prefix can't result in a better macro
+    let (best_quality, _, _, _) =
+        get_score_quad(&settings, &solve(&settings, false, false).unwrap());
+    assert!(quality <= best_quality);
+    assert!(quality > 0);
+}
+
+#[test]
+fn empty_prefix() {
+    let settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        success_model: SuccessModel::AssumeFail,
+        rules_override: RulesOverride::NONE,
+    };
+    let actions = solve_from(&settings, &[]);
+    assert_eq!(
+        get_score_quad(&settings, &actions),
+        get_score_quad(&settings, &solve(&settings, false, false).unwrap())
+    );
+}
@@ -0,0 +1,149 @@
+use raphael_data::{CrafterStats, Locale, RECIPES, Recipe, get_game_settings, get_item_name};
+use raphael_sim::{Action, Condition, SimulationState, SingleUse};
+use raphael_solver::{
+    AtomicFlag, InitialEffects, SolveRequest, best_byregot_placement, test_utils::*,
+};
+
+fn find_recipe(item_name: &'static str) -> Recipe {
+    *RECIPES
+        .iter()
+        .find(|recipe| get_item_name(recipe.item_id, false, Locale::EN) == item_name)
+        .unwrap()
+}
+
+const CRAFTER_STATS: CrafterStats = CrafterStats {
+    craftsmanship: 4956,
+    control: 4963,
+    cp: 627,
+    level: 100,
+    manipulation: true,
+    heart_and_soul: false,
+    quick_innovation: false,
+};
+
+#[test]
+fn game_settings() {
+    let recipe = find_recipe("Roast Chicken");
+    let request = SolveRequest::new(recipe, CRAFTER_STATS).adversarial(true);
+    assert_eq!(
+        request.game_settings(),
+        get_game_settings(recipe, CRAFTER_STATS, None, None, true)
+    );
+    // without a target, the full max Quality of the recipe is targeted
+    assert_eq!(request.get_target_quality(), recipe.quality);
+    assert_eq!(request.simulator_settings().max_quality, recipe.quality);
+}
+
+#[test]
+fn target_and_initial_quality() {
+    let recipe = find_recipe("Roast Chicken");
+    let request = SolveRequest::new(recipe, CRAFTER_STATS)
+        .initial_quality(1000)
+        .target_quality(5000);
+    assert_eq!(request.get_target_quality(), 5000);
+    assert_eq!(request.simulator_settings().max_quality, 4000);
+
+    // the target is clamped to the max Quality of the recipe
+    let request = request.target_quality(u16::MAX);
+    assert_eq!(request.get_target_quality(), recipe.quality);
+    assert_eq!(
+        request.simulator_settings().max_quality,
+        recipe.quality - 1000
+    );
+
+    // the initial Quality alone reaches the target
+    let request = request.target_quality(500);
+    assert_eq!(request.simulator_settings().max_quality, 0);
+}
+
+#[test]
+fn solve() {
+    let recipe = find_recipe("Roast Chicken");
+    let request = SolveRequest::new(recipe, CRAFTER_STATS).target_quality(recipe.quality / 2);
+    let settings = request.simulator_settings();
+    let actions = request.solve(AtomicFlag::new()).unwrap();
+    let (quality, _, _, _) = get_score_quad(&settings, &actions);
+    assert_eq!(quality, settings.max_quality);
+}
+
+#[test]
+fn minimize_cp() {
+    let recipe = find_recipe("Roast Chicken");
+    let request = SolveRequest::new(recipe, CRAFTER_STATS).target_quality(recipe.quality / 2);
+    let settings = request.simulator_settings();
+    let cp_spent = |actions: &[Action]| {
+        settings.max_cp - SimulationState::from_macro(&settings, actions).unwrap().cp
+    };
+
+    let actions = request.solve(AtomicFlag::new()).unwrap();
+    let min_cp_actions = request.minimize_cp(true).solve(AtomicFlag::new()).unwrap();
+    let (quality, _, _, _) = get_score_quad(&settings, &min_cp_actions);
+    assert_eq!(quality, settings.max_quality);
+    assert!(cp_spent(&min_cp_actions) <= cp_spent(&actions));
+}
+
+#[test]
+fn initial_effects() {
+    let recipe = find_recipe("Roast Chicken");
+    let request = SolveRequest::new(recipe, CRAFTER_STATS);
+    assert_eq!(
+        request.initial_state(),
+        SimulationState::new(&request.simulator_settings())
+    );
+
+    let initial_effects = InitialEffects {
+        inner_quiet: 12,
+        heart_and_soul: true,
+        ..InitialEffects::default()
+    };
+    let state = request.initial_effects(initial_effects).initial_state();
+    // clamped to the max Inner Quiet stacks
+    assert_eq!(state.effects.inner_quiet(), 10);
+    assert_eq!(state.effects.heart_and_soul(), SingleUse::Active);
+    assert_eq!(state.effects.waste_not(), 0);
+}
+
+#[test]
+fn exclude_byregot() {
+    let recipe = find_recipe("Roast Chicken");
+    let request = SolveRequest::new(recipe, CRAFTER_STATS).exclude_byregot(true);
+    let settings = request.simulator_settings();
+    assert!(!settings.allowed_actions.has(Action::ByregotsBlessing));
+    // the CP and durability of Byregot's Blessing are reserved
+    assert_eq!(settings.max_cp, request.game_settings().max_cp - 24);
+    assert_eq!(
+        settings.max_durability,
+        request.game_settings().max_durability - 10
+    );
+
+    let actions = request.solve(AtomicFlag::new()).unwrap();
+    assert!(!actions.contains(&Action::ByregotsBlessing));
+
+    let game_settings = request.game_settings();
+    let initial_state = SimulationState::new(&game_settings);
+    let placement = best_byregot_placement(&game_settings, initial_state, &actions).unwrap();
+    let (quality, _, _, _) = get_score_quad(&game_settings, &actions);
+    assert!(placement.quality > quality);
+
+    // the placement is exactly the rotation with Byregot's Blessing inserted
+    let mut byregot_actions = actions.clone();
+    byregot_actions.insert(placement.index, Action::ByregotsBlessing);
+    let state = SimulationState::from_macro(&game_settings, &byregot_actions).unwrap();
+    assert_eq!(state.quality, placement.quality);
+    assert!(state.progress >= game_settings.max_progress);
+}
+
+#[test]
+fn byregot_placement_needs_inner_quiet() {
+    let recipe = find_recipe("Roast Chicken");
+    let settings = SolveRequest::new(recipe, CRAFTER_STATS).game_settings();
+    let state = SimulationState::new(&settings);
+    // Byregot's Blessing can't be used without Inner Quiet
+    let actions = [Action::MuscleMemory, Action::Veneration, Action::Groundwork];
+    assert!(
+        state
+            .use_action(Action::ByregotsBlessing, Condition::Normal, &settings)
+            .is_err()
+    );
+    assert_eq!(best_byregot_placement(&settings, state, &actions), None);
+}
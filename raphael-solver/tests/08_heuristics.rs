@@ -0,0 +1,79 @@
+use raphael_sim::*;
+use raphael_solver::{SolverHeuristics, search_action_order, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 680,
+    max_durability: 70,
+    max_progress: 5060,
+    max_quality: 12628,
+    base_progress: 229,
+    base_quality: 224,
+    job_level: 100,
+    allowed_actions: ActionMask::all(),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+fn heuristics(settings: Settings) -> SolverHeuristics {
+    SolverHeuristics::new(&solver_settings(settings))
+}
+
+#[test]
+fn durability_cost() {
+    // Immaculate Mend restores up to 65 durability for 112 CP
+    assert_eq!(heuristics(SETTINGS).durability_cost, 112 / 13);
+    // Manipulation restores 40 durability for 96 CP
+    let settings = Settings {
+        allowed_actions: SETTINGS.allowed_actions.remove(Action::ImmaculateMend),
+        ..SETTINGS
+    };
+    assert_eq!(heuristics(settings).durability_cost, 96 / 8);
+    // Master's Mend restores 30 durability for 88 CP
+    let settings = Settings {
+        allowed_actions: settings.allowed_actions.remove(Action::Manipulation),
+        ..SETTINGS
+    };
+    assert_eq!(heuristics(settings).durability_cost, 88 / 6);
+}
+
+#[test]
+fn waste_not_min_cp() {
+    let heuristics = heuristics(SETTINGS);
+    assert_eq!(heuristics.waste_not_1_min_cp, 158);
+    assert_eq!(heuristics.waste_not_2_min_cp, 284);
+}
+
+#[test]
+fn rules_override() {
+    let rules_override = RulesOverride::new(&[ActionOverride {
+        cp_cost: Some(20),
+        ..ActionOverride::new(Action::WasteNot)
+    }])
+    .unwrap();
+    let settings = Settings {
+        rules_override,
+        ..SETTINGS
+    };
+    assert_eq!(heuristics(settings).waste_not_1_min_cp, 36);
+    // Master's Mend restores 30 durability for 5 CP, which rounds down to 0 CP per 5 durability
+    let rules_override = RulesOverride::new(&[ActionOverride {
+        cp_cost: Some(5),
+        ..ActionOverride::new(Action::MasterMend)
+    }])
+    .unwrap();
+    let settings = Settings {
+        rules_override,
+        ..SETTINGS
+    };
+    assert_eq!(heuristics(settings).durability_cost, 1);
+}
+
+#[test]
+fn action_order() {
+    let full = search_action_order(false);
+    let progress_only = search_action_order(true);
+    assert!(full.contains(&[Action::BasicTouch].as_slice()));
+    assert!(!progress_only.contains(&[Action::BasicTouch].as_slice()));
+    assert!(progress_only.iter().all(|actions| full.contains(actions)));
+}
@@ -0,0 +1,78 @@
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, ExpectedQualitySolver, SolverException, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 400,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 3000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+fn solver(settings: &Settings, interrupt_signal: AtomicFlag) -> ExpectedQualitySolver {
+    ExpectedQualitySolver::new(solver_settings(*settings), interrupt_signal).beam_width(256)
+}
+
+fn expected_quality(settings: &Settings, actions: &[Action]) -> f32 {
+    quality_probability_distribution(*settings, actions, 0).expected_quality(settings.max_quality)
+}
+
+#[test]
+fn finishes_the_craft() {
+    let actions = solver(&SETTINGS, AtomicFlag::new()).solve().unwrap();
+    let state = SimulationState::from_macro(&SETTINGS, &actions).unwrap();
+    assert!(state.progress >= SETTINGS.max_progress);
+    assert_eq!(expected_quality(&SETTINGS, &actions).round(), 2193.0);
+}
+
+#[test]
+fn not_worse_than_normal_conditions() {
+    let actions = solver(&SETTINGS, AtomicFlag::new()).solve().unwrap();
+    // Good condition is more likely than Poor condition, so the expected Quality should be
+    // close to the best Quality in Normal condition if the max Quality can't be reached
+    let normal_actions = solve(&SETTINGS, false, false).unwrap();
+    assert_eq!(get_quality(&SETTINGS, &normal_actions), 2013);
+    assert!(
+        expected_quality(&SETTINGS, &actions)
+            >= 0.9 * f32::from(get_quality(&SETTINGS, &normal_actions))
+    );
+}
+
+#[test]
+fn solve_from_prefix() {
+    let prefix = [Action::MuscleMemory, Action::Manipulation];
+    let initial_state = SimulationState::from_macro(&SETTINGS, &prefix).unwrap();
+    let continuation = solver(&SETTINGS, AtomicFlag::new())
+        .solve_from(initial_state)
+        .unwrap();
+    let actions = [prefix.as_slice(), continuation.as_slice()].concat();
+    let state = SimulationState::from_macro(&SETTINGS, &actions).unwrap();
+    assert!(state.progress >= SETTINGS.max_progress);
+}
+
+#[test]
+fn no_solution() {
+    let settings = Settings {
+        max_progress: 6000,
+        ..SETTINGS
+    };
+    let result = solver(&settings, AtomicFlag::new()).solve();
+    assert_eq!(result, Err(SolverException::NoSolution));
+}
+
+#[test]
+fn interrupted() {
+    let interrupt_signal = AtomicFlag::new();
+    interrupt_signal.set();
+    let result = solver(&SETTINGS, interrupt_signal).solve();
+    assert_eq!(result, Err(SolverException::Interrupted));
+}
@@ -0,0 +1,84 @@
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, ExpertAdvisor, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 400,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 3000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 100,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+/// Conditions of expert recipes with Malleable and Primed condition.
+const CONDITION_POOL: ConditionPool = ConditionPool::from_flags(483);
+
+fn advisor(settings: &Settings) -> ExpertAdvisor {
+    ExpertAdvisor::new(
+        solver_settings(*settings),
+        CONDITION_POOL,
+        AtomicFlag::new(),
+    )
+}
+
+/// Follows the advice until the craft is finished, with the given condition at each step.
+fn follow_advice(conditions: impl Fn(usize) -> Condition) -> SimulationState {
+    let mut advisor = advisor(&SETTINGS);
+    let mut state = SimulationState::new(&SETTINGS);
+    let mut step = 0;
+    while !state.is_final(&SETTINGS) {
+        assert!(step < 100);
+        let condition = conditions(step);
+        let advice = advisor.advise(state, condition).unwrap();
+        let action = advice.first().expect("no advice").actions[0];
+        state = state.use_action(action, condition, &SETTINGS).unwrap();
+        step += 1;
+    }
+    state
+}
+
+#[test]
+fn finishes_the_craft_in_normal_condition() {
+    let state = follow_advice(|_| Condition::Normal);
+    assert!(state.progress >= SETTINGS.max_progress);
+    assert_eq!(state.quality, 1610);
+}
+
+#[test]
+fn finishes_the_craft_with_expert_conditions() {
+    let conditions: Vec<Condition> = CONDITION_POOL.conditions().collect();
+    let state = follow_advice(|step| conditions[step % conditions.len()]);
+    assert!(state.progress >= SETTINGS.max_progress);
+    assert!(state.quality > 0);
+}
+
+#[test]
+fn advice_is_sorted() {
+    let state = SimulationState::new(&SETTINGS)
+        .use_action(Action::Reflect, Condition::Normal, &SETTINGS)
+        .unwrap();
+    let advice = advisor(&SETTINGS).advise(state, Condition::Pliant).unwrap();
+    assert!(!advice.is_empty());
+    for pair in advice.windows(2) {
+        assert!(pair[0].expected_quality_upper_bound >= pair[1].expected_quality_upper_bound);
+    }
+}
+
+#[test]
+fn no_advice_if_the_craft_cant_be_finished() {
+    let settings = Settings {
+        max_progress: 6000,
+        ..SETTINGS
+    };
+    let state = SimulationState::new(&settings);
+    let advice = advisor(&settings).advise(state, Condition::Normal).unwrap();
+    assert!(advice.is_empty());
+}
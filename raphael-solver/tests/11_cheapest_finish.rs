@@ -0,0 +1,95 @@
+use raphael_sim::*;
+use raphael_solver::{cheapest_finish, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 300,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 1000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+/// Checks that the suggested actions max out Progress for the CP they claim to cost.
+fn check_suggestion(settings: &Settings, state: SimulationState) -> i16 {
+    let suggestion = cheapest_finish(solver_settings(*settings), state).unwrap();
+    let mut final_state = state;
+    for action in &suggestion.actions {
+        final_state = final_state
+            .use_action(*action, Condition::Normal, settings)
+            .unwrap();
+    }
+    assert!(final_state.progress >= settings.max_progress);
+    assert_eq!(state.cp - final_state.cp, suggestion.cp_cost);
+    suggestion.cp_cost
+}
+
+#[test]
+fn free_finish() {
+    let settings = Settings {
+        max_progress: 200,
+        ..SETTINGS
+    };
+    let cp_cost = check_suggestion(&settings, SimulationState::new(&settings));
+    assert_eq!(cp_cost, 0);
+}
+
+#[test]
+fn initial_state() {
+    let cp_cost = check_suggestion(&SETTINGS, SimulationState::new(&SETTINGS));
+    assert_eq!(cp_cost, 63);
+}
+
+#[test]
+fn after_quality_actions() {
+    let state = SimulationState::from_macro(
+        &SETTINGS,
+        &[
+            Action::Reflect,
+            Action::Innovation,
+            Action::PreparatoryTouch,
+            Action::BasicTouch,
+        ],
+    )
+    .unwrap();
+    // the cheapest finish doesn't depend on Quality
+    let state_without_quality = SimulationState {
+        quality: 0,
+        effects: state.effects.with_inner_quiet(0).with_innovation(0),
+        ..state
+    };
+    assert_eq!(
+        check_suggestion(&SETTINGS, state),
+        check_suggestion(&SETTINGS, state_without_quality)
+    );
+}
+
+#[test]
+fn already_finished() {
+    let state = SimulationState {
+        progress: SETTINGS.max_progress,
+        ..SimulationState::new(&SETTINGS)
+    };
+    let suggestion = cheapest_finish(solver_settings(SETTINGS), state).unwrap();
+    assert!(suggestion.actions.is_empty());
+    assert_eq!(suggestion.cp_cost, 0);
+}
+
+#[test]
+fn cannot_finish() {
+    let settings = Settings {
+        max_cp: 0,
+        max_durability: 10,
+        ..SETTINGS
+    };
+    let state = SimulationState::new(&settings);
+    assert_eq!(cheapest_finish(solver_settings(settings), state), None);
+}
@@ -0,0 +1,87 @@
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, SolverException, StepAdvisor, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 400,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 1000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+fn advisor(settings: &Settings) -> StepAdvisor {
+    StepAdvisor::new(solver_settings(*settings), AtomicFlag::new())
+}
+
+/// Follows the recommendations until the craft is finished, with the given condition at each
+/// step.
+fn follow_recommendations(conditions: impl Fn(usize) -> Condition) -> SimulationState {
+    let mut advisor = advisor(&SETTINGS);
+    let mut state = SimulationState::new(&SETTINGS);
+    let mut step = 0;
+    while !state.is_final(&SETTINGS) {
+        assert!(step < 100);
+        let condition = conditions(step);
+        let recommendation = advisor.recommend(state, condition).unwrap();
+        state = state
+            .use_action(recommendation.action, condition, &SETTINGS)
+            .unwrap();
+        assert_eq!(state, recommendation.state);
+        step += 1;
+    }
+    state
+}
+
+#[test]
+fn follows_the_macro_in_normal_condition() {
+    let state = follow_recommendations(|_| Condition::Normal);
+    assert!(state.progress >= SETTINGS.max_progress);
+    let actions = solve(&SETTINGS, false, false).unwrap();
+    let (quality, _, _, _) = get_score_quad(&SETTINGS, &actions);
+    assert_eq!(quality, 1000);
+    assert_eq!(std::cmp::min(state.quality, SETTINGS.max_quality), quality);
+}
+
+#[test]
+fn good_condition_does_not_decrease_quality() {
+    let normal = follow_recommendations(|_| Condition::Normal);
+    let good = follow_recommendations(|step| match step {
+        2 => Condition::Good,
+        _ => Condition::Normal,
+    });
+    assert!(good.progress >= SETTINGS.max_progress);
+    assert!(good.quality >= std::cmp::min(normal.quality, SETTINGS.max_quality));
+}
+
+#[test]
+fn excellent_condition_does_not_decrease_quality() {
+    let state = SimulationState::from_macro(&SETTINGS, &[Action::MuscleMemory]).unwrap();
+    let normal = advisor(&SETTINGS)
+        .recommend(state, Condition::Normal)
+        .unwrap();
+    let excellent = advisor(&SETTINGS)
+        .recommend(state, Condition::Excellent)
+        .unwrap();
+    assert!(excellent.quality >= normal.quality);
+}
+
+#[test]
+fn finished_craft() {
+    let state = SimulationState {
+        progress: SETTINGS.max_progress,
+        ..SimulationState::new(&SETTINGS)
+    };
+    assert_eq!(
+        advisor(&SETTINGS).recommend(state, Condition::Normal),
+        Err(SolverException::NoSolution)
+    );
+}
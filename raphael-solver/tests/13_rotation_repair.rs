@@ -0,0 +1,63 @@
+use raphael_sim::*;
+use raphael_solver::{repairable_prefix, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 400,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 1000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+fn prefix_len(settings: Settings, actions: &[Action]) -> usize {
+    repairable_prefix(
+        solver_settings(settings),
+        SimulationState::new(&settings),
+        actions,
+    )
+}
+
+#[test]
+fn unchanged_settings() {
+    let actions = solve(&SETTINGS, false, false).unwrap();
+    // everything but the action that finishes the craft is kept
+    assert_eq!(prefix_len(SETTINGS, &actions), actions.len() - 1);
+}
+
+#[test]
+fn empty_rotation() {
+    assert_eq!(prefix_len(SETTINGS, &[]), 0);
+}
+
+#[test]
+fn stops_at_unusable_action() {
+    let mut actions = solve(&SETTINGS, false, false).unwrap();
+    // Tricks of the Trade can't be used in Normal condition
+    actions.insert(1, Action::TricksOfTheTrade);
+    assert_eq!(prefix_len(SETTINGS, &actions), 1);
+}
+
+#[test]
+fn stops_where_progress_can_no_longer_be_finished() {
+    // not enough CP left to max out Progress after the last Master's Mend
+    let actions = [Action::MasterMend; 4];
+    let prefix_len = prefix_len(SETTINGS, &actions);
+    assert_eq!(prefix_len, 3);
+    // the rest of the craft can be solved from the kept prefix
+    let state = SimulationState::from_macro(&SETTINGS, &actions[..prefix_len]).unwrap();
+    let continuation = macro_solver(solver_settings(SETTINGS))
+        .solve_from(state)
+        .unwrap();
+    let rotation = [&actions[..prefix_len], continuation.as_slice()].concat();
+    let state = SimulationState::from_macro(&SETTINGS, &rotation).unwrap();
+    assert!(state.progress >= SETTINGS.max_progress);
+}
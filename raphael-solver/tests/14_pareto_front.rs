@@ -0,0 +1,72 @@
+use raphael_sim::*;
+use raphael_solver::{ParetoSolution, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 400,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 1000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+fn solve_pareto_front(settings: &Settings) -> Vec<ParetoSolution> {
+    macro_solver(solver_settings(*settings))
+        .solve_pareto_front()
+        .unwrap()
+}
+
+#[test]
+fn frontier_is_strictly_decreasing() {
+    let front = solve_pareto_front(&SETTINGS);
+    assert_eq!(front.len(), 7);
+    for solution in &front {
+        let (quality, steps, _, _) = get_score_quad(&SETTINGS, &solution.actions);
+        assert_eq!(quality, solution.quality);
+        assert_eq!(usize::from(steps), solution.actions.len());
+        let state = SimulationState::from_macro(&SETTINGS, &solution.actions).unwrap();
+        assert!(state.progress >= SETTINGS.max_progress);
+    }
+    for pair in front.windows(2) {
+        assert!(pair[0].actions.len() > pair[1].actions.len());
+        assert!(pair[0].quality > pair[1].quality);
+    }
+}
+
+#[test]
+fn endpoints() {
+    let front = solve_pareto_front(&SETTINGS);
+    // the first macro is the macro of a normal solve
+    let actions = solve(&SETTINGS, false, false).unwrap();
+    let (quality, steps, _, _) = get_score_quad(&SETTINGS, &actions);
+    assert_eq!(quality, 1000);
+    assert_eq!(front[0].quality, quality);
+    assert!(front[0].actions.len() <= usize::from(steps));
+    // the last macro has the fewest steps that max out Progress
+    let progress_only_settings = Settings {
+        max_quality: 0,
+        ..SETTINGS
+    };
+    let actions = solve(&progress_only_settings, false, false).unwrap();
+    assert_eq!(actions.len(), 5);
+    assert_eq!(front.last().unwrap().actions.len(), actions.len());
+}
+
+#[test]
+fn no_solution() {
+    let settings = Settings {
+        max_cp: 0,
+        max_durability: 10,
+        ..SETTINGS
+    };
+    let result = macro_solver(solver_settings(settings)).solve_pareto_front();
+    assert_eq!(result, Err(raphael_solver::SolverException::NoSolution));
+}
@@ -0,0 +1,49 @@
+use raphael_sim::*;
+use raphael_solver::test_utils::*;
+
+const SETTINGS: Settings = Settings {
+    max_cp: 500,
+    max_durability: 70,
+    max_progress: 2500,
+    max_quality: 5000,
+    base_progress: 200,
+    base_quality: 200,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+fn solve_with_bias(settings: &Settings, weight: u8) -> Vec<Action> {
+    macro_solver(solver_settings(*settings))
+        .human_friendly_bias(weight)
+        .solve()
+        .unwrap()
+}
+
+#[test]
+fn zero_weight_matches_default_solve() {
+    let actions = solve(&SETTINGS, false, false).unwrap();
+    let unbiased = solve_with_bias(&SETTINGS, 0);
+    assert_eq!(
+        get_score_quad(&SETTINGS, &unbiased),
+        get_score_quad(&SETTINGS, &actions)
+    );
+}
+
+#[test]
+fn bias_keeps_quality_and_steps() {
+    let (quality, steps, _, _) = get_score_quad(&SETTINGS, &solve_with_bias(&SETTINGS, 0));
+    assert_eq!((quality, steps), (5000, 12));
+    for weight in [1, 50] {
+        // the bias may only trade duration for fewer action blocks
+        let actions = solve_with_bias(&SETTINGS, weight);
+        let (biased_quality, biased_steps, _, _) = get_score_quad(&SETTINGS, &actions);
+        assert_eq!(biased_quality, quality);
+        assert_eq!(biased_steps, steps);
+    }
+}
@@ -0,0 +1,82 @@
+use raphael_sim::*;
+use raphael_solver::{ProcAdvice, ProcSuggestion, condition_cheat_sheet, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 400,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 3000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+/// Uses the macro with the suggestion applied and Normal condition at all other steps, except the
+/// step after an Excellent condition.
+fn follow_suggestion(actions: &[Action], suggestion: &ProcSuggestion) -> SimulationState {
+    let mut actions = actions.to_vec();
+    match suggestion.advice {
+        ProcAdvice::Continue => (),
+        ProcAdvice::Replace(action) => actions[suggestion.step] = action,
+        ProcAdvice::Insert(action) => actions.insert(suggestion.step, action),
+    }
+    let mut state = SimulationState::new(&SETTINGS);
+    for (step, action) in actions.iter().enumerate() {
+        let condition = match step {
+            _ if step == suggestion.step => suggestion.condition,
+            _ if step == suggestion.step + 1 && suggestion.condition == Condition::Excellent => {
+                Condition::Poor
+            }
+            _ => Condition::Normal,
+        };
+        state = state.use_action(*action, condition, &SETTINGS).unwrap();
+    }
+    state
+}
+
+#[test]
+fn following_suggestions_finishes_the_craft() {
+    let actions = solve(&SETTINGS, false, false).unwrap();
+    let suggestions = condition_cheat_sheet(&SETTINGS, SimulationState::new(&SETTINGS), &actions);
+    assert!(!suggestions.is_empty());
+    for suggestion in suggestions {
+        assert!(suggestion.quality >= suggestion.continue_quality);
+        let state = follow_suggestion(&actions, &suggestion);
+        assert!(state.progress >= SETTINGS.max_progress);
+        assert_eq!(
+            std::cmp::min(state.quality, SETTINGS.max_quality),
+            suggestion.quality
+        );
+    }
+}
+
+#[test]
+fn good_condition_on_touch_uses_precise_touch() {
+    let actions = [
+        Action::Reflect,
+        Action::BasicTouch,
+        Action::CarefulSynthesis,
+        Action::CarefulSynthesis,
+        Action::CarefulSynthesis,
+    ];
+    let settings = Settings {
+        max_durability: 50,
+        max_progress: 500,
+        ..SETTINGS
+    };
+    let suggestions = condition_cheat_sheet(&settings, SimulationState::new(&settings), &actions);
+    let suggestion = suggestions
+        .iter()
+        .find(|suggestion| suggestion.step == 1 && suggestion.condition == Condition::Good)
+        .unwrap();
+    assert_eq!(suggestion.advice, ProcAdvice::Replace(Action::PreciseTouch));
+    assert_eq!(suggestion.quality, 570);
+    assert_eq!(suggestion.continue_quality, 480);
+}
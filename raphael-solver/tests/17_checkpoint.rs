@@ -0,0 +1,85 @@
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, MacroSolver, SolverCheckpoint, SolverException, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 400,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 3000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+/// Solves until the first search node is expanded and returns the progress of the search.
+fn solve_one_node(solver: MacroSolver) -> SolverCheckpoint {
+    let checkpoint_signal = AtomicFlag::new();
+    checkpoint_signal.set();
+    let mut solver = solver.checkpoint_signal(checkpoint_signal);
+    assert_eq!(solver.solve(), Err(SolverException::Interrupted));
+    solver.take_checkpoint().unwrap()
+}
+
+#[test]
+fn resume_finds_the_same_macro() {
+    let mut solver = macro_solver(solver_settings(SETTINGS));
+    let expected = solver.solve().unwrap();
+    assert_eq!(get_score_quad(&SETTINGS, &expected), (2013, 15, 41, 0));
+    assert!(solver.take_checkpoint().is_none());
+
+    let checkpoint = solve_one_node(macro_solver(solver_settings(SETTINGS)));
+    assert_eq!(checkpoint.nodes_visited(), 1);
+    let actions = macro_solver(checkpoint.settings())
+        .resume(Some(checkpoint))
+        .solve()
+        .unwrap();
+    assert_eq!(
+        get_score_quad(&SETTINGS, &actions),
+        get_score_quad(&SETTINGS, &expected)
+    );
+}
+
+#[test]
+fn resume_several_times() {
+    let expected = solve(&SETTINGS, false, false).unwrap();
+    let mut checkpoint = solve_one_node(macro_solver(solver_settings(SETTINGS)));
+    for nodes_visited in 2..=4 {
+        checkpoint = solve_one_node(macro_solver(checkpoint.settings()).resume(Some(checkpoint)));
+        assert_eq!(checkpoint.nodes_visited(), nodes_visited);
+    }
+    let actions = macro_solver(checkpoint.settings())
+        .resume(Some(checkpoint))
+        .solve()
+        .unwrap();
+    assert_eq!(
+        get_score_quad(&SETTINGS, &actions),
+        get_score_quad(&SETTINGS, &expected)
+    );
+}
+
+#[test]
+fn resume_with_different_settings() {
+    let checkpoint = solve_one_node(macro_solver(solver_settings(SETTINGS)));
+    let settings = Settings {
+        max_quality: 2000,
+        ..SETTINGS
+    };
+    let result = macro_solver(solver_settings(settings))
+        .resume(Some(checkpoint))
+        .solve();
+    assert!(matches!(result, Err(SolverException::InternalError(_))));
+
+    let checkpoint = solve_one_node(macro_solver(solver_settings(SETTINGS)));
+    let result = macro_solver(checkpoint.settings())
+        .resume(Some(checkpoint))
+        .minimize_cp(true)
+        .solve();
+    assert!(matches!(result, Err(SolverException::InternalError(_))));
+}
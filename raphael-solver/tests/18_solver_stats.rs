@@ -0,0 +1,84 @@
+use raphael_sim::*;
+use raphael_solver::{SolverSettings, estimate_peak_memory, test_utils::*};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 400,
+    max_durability: 60,
+    max_progress: 2000,
+    max_quality: 3000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+#[test]
+fn search_counters() {
+    let mut solver = macro_solver(solver_settings(SETTINGS));
+    assert!(solver.solve().is_ok());
+    let stats = *solver.stats();
+    assert_eq!(stats.nodes, 362);
+    assert!(stats.nodes_per_second().is_some());
+    assert!(stats.threads >= 1);
+    assert!(stats.quality_ub_cache.hits > 0);
+    assert!(stats.quality_ub_cache.misses > 0);
+    assert!(stats.step_lb_cache.misses > 0);
+    let hit_rate = stats.quality_ub_cache.hit_rate().unwrap();
+    assert!((0.0..=1.0).contains(&hit_rate));
+}
+
+#[test]
+fn progress_only_solve_has_no_search() {
+    let settings = Settings {
+        max_quality: 0,
+        ..SETTINGS
+    };
+    let mut solver = macro_solver(solver_settings(settings));
+    assert!(solver.solve().is_ok());
+    let stats = *solver.stats();
+    assert_eq!(stats.nodes, 0);
+    assert_eq!(stats.quality_ub_cache.hit_rate(), None);
+}
+
+#[test]
+fn counters_are_reset_between_solves() {
+    let mut solver = macro_solver(solver_settings(SETTINGS));
+    assert!(solver.solve().is_ok());
+    let first = *solver.stats();
+    assert!(solver.solve().is_ok());
+    assert_eq!(solver.stats().nodes, first.nodes);
+    assert_eq!(solver.stats().quality_ub_cache, first.quality_ub_cache);
+}
+
+#[test]
+fn peak_memory_estimate() {
+    let adversarial = Settings {
+        adversarial: true,
+        ..SETTINGS
+    };
+    for (settings, pruning) in [(SETTINGS, true), (SETTINGS, false), (adversarial, true)] {
+        let solver_settings = SolverSettings {
+            simulator_settings: settings,
+            backload_progress: pruning,
+            allow_unsound_branch_pruning: pruning,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
+        };
+        let mut solver = macro_solver(solver_settings);
+        assert!(solver.solve().is_ok());
+        let peak = solver.stats().peak_memory;
+        let estimate = estimate_peak_memory(&solver_settings, false);
+        // the estimate must stay within a factor of 2 of the measured peak
+        assert!(
+            estimate <= 2 * peak && peak <= 2 * estimate,
+            "estimate of {estimate} bytes for a peak of {peak} bytes (adversarial: {}, pruning: {pruning})",
+            settings.adversarial
+        );
+    }
+}
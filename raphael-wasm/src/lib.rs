@@ -0,0 +1,190 @@
+//! The solver as a wasm library, so that web tools other than the app can solve rotations.
+//! Values are passed as plain JS objects whose shape is the stable schema of this library, see
+//! the README for an example. The schema only changes in a compatible way, i.e. by adding
+//! optional fields, unless [`SCHEMA_VERSION`] is increased.
+
+use raphael_sim::{Action, ActionMask, RulesOverride, Settings, SimulationState, SuccessModel};
+use raphael_solver::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Version of the schema of the values that are passed to and returned from this library.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Recipe and crafter stats of the craft to solve. Accepts the craft of `raphael-embed`, so that
+/// tools can use both libraries with the same values, plus the availability of actions that
+/// the simulator alone doesn't need.
+#[derive(Debug, Clone, Deserialize)]
+struct Craft {
+    max_cp: i16,
+    max_durability: i8,
+    max_progress: u16,
+    /// Quality to reach, not including the initial Quality of the craft.
+    max_quality: u16,
+    base_progress: u16,
+    base_quality: u16,
+    job_level: u8,
+    /// Whether Heart and Soul and Quick Innovation are available.
+    #[serde(default)]
+    specialist: bool,
+    /// Whether Manipulation was learned.
+    #[serde(default = "default_manipulation")]
+    manipulation: bool,
+    /// Whether Trained Eye can be used, i.e. the recipe is a non-expert recipe at least 10 levels
+    /// below the crafter.
+    #[serde(default)]
+    trained_eye: bool,
+    #[serde(default)]
+    adversarial: bool,
+    #[serde(default)]
+    success_model: SuccessModel,
+    #[serde(default)]
+    rules_override: RulesOverride,
+}
+
+const fn default_manipulation() -> bool {
+    true
+}
+
+impl TryFrom<Craft> for Settings {
+    type Error = JsError;
+
+    fn try_from(craft: Craft) -> Result<Self, JsError> {
+        let mut allowed_actions = ActionMask::all();
+        if !craft.trained_eye {
+            allowed_actions = allowed_actions.remove(Action::TrainedEye);
+        }
+        if !craft.specialist {
+            allowed_actions = allowed_actions
+                .remove(Action::HeartAndSoul)
+                .remove(Action::QuickInnovation);
+        }
+        if !craft.manipulation {
+            allowed_actions = allowed_actions.remove(Action::Manipulation);
+        }
+        let settings = Self {
+            max_cp: craft.max_cp,
+            max_durability: craft.max_durability,
+            max_progress: craft.max_progress,
+            max_quality: craft.max_quality,
+            base_progress: craft.base_progress,
+            base_quality: craft.base_quality,
+            job_level: craft.job_level,
+            allowed_actions,
+            adversarial: craft.adversarial,
+            success_model: craft.success_model,
+            rules_override: craft.rules_override,
+        };
+        settings
+            .validate()
+            .map_err(|error| JsError::new(&format!("Invalid craft: {error}")))?;
+        Ok(settings)
+    }
+}
+
+/// Options of the solver, all of which are off by default.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+struct SolverConfig {
+    /// Only use Progress-increasing actions at the end of the macro.
+    backload_progress: bool,
+    /// Don't use Quality actions while Muscle Memory is active.
+    progress_opener: bool,
+    /// Among the macros that reach the target Quality, prefer the one that spends the least CP.
+    minimize_cp: bool,
+    /// Prefer macros that are easier to execute by hand, see
+    /// [`MacroSolver::human_friendly_bias`].
+    human_friendly_bias: u8,
+    /// Solves faster, but may miss the best macro.
+    unsound_branch_pruning: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Solution {
+    actions: Vec<Action>,
+    progress: u16,
+    quality: u16,
+    steps: usize,
+    /// Duration of the macro in seconds.
+    duration: u32,
+}
+
+fn to_js_value(value: &impl Serialize) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+fn solver_error(exception: SolverException) -> JsError {
+    match exception {
+        SolverException::NoSolution => JsError::new("No solution"),
+        SolverException::Interrupted => JsError::new("Cancelled"),
+        SolverException::MemoryLimitReached => JsError::new("Out of memory"),
+        SolverException::InternalError(message) => JsError::new(&message),
+    }
+}
+
+/// Version of the schema of the values of this library, see [`SCHEMA_VERSION`].
+#[wasm_bindgen(js_name = schemaVersion)]
+pub fn schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+/// Solves the craft for the macro with the most Quality, ties broken by the fewest steps.
+/// `on_progress` is called with the number of states the solver has explored so far, and
+/// `on_solution` with the actions of each better macro that is found while solving.
+#[wasm_bindgen]
+pub fn solve(
+    craft: JsValue,
+    config: JsValue,
+    on_progress: Option<js_sys::Function>,
+    on_solution: Option<js_sys::Function>,
+) -> Result<JsValue, JsError> {
+    let craft: Craft = serde_wasm_bindgen::from_value(craft)
+        .map_err(|err| JsError::new(&format!("Invalid craft: {err}")))?;
+    let config: SolverConfig = match config.is_undefined() || config.is_null() {
+        true => SolverConfig::default(),
+        false => serde_wasm_bindgen::from_value(config)
+            .map_err(|err| JsError::new(&format!("Invalid config: {err}")))?,
+    };
+    let settings = Settings::try_from(craft)?;
+    let solution_callback = |actions: &[Action]| {
+        if let (Some(callback), Ok(actions)) = (&on_solution, to_js_value(&actions)) {
+            _ = callback.call1(&JsValue::NULL, &actions);
+        }
+    };
+    let progress_callback = |progress: usize| {
+        if let Some(callback) = &on_progress {
+            _ = callback.call1(&JsValue::NULL, &JsValue::from(progress));
+        }
+    };
+    let actions = MacroSolver::new(
+        SolverSettings {
+            simulator_settings: settings,
+            backload_progress: config.backload_progress,
+            allow_unsound_branch_pruning: config.unsound_branch_pruning,
+            forbid_quality_during_muscle_memory: config.progress_opener,
+            first_quality_step: 0,
+        },
+        Box::new(solution_callback),
+        Box::new(progress_callback),
+        AtomicFlag::new(),
+    )
+    .minimize_cp(config.minimize_cp)
+    .human_friendly_bias(config.human_friendly_bias)
+    .solve()
+    .map_err(solver_error)?;
+
+    let state = SimulationState::from_macro(&settings, &actions)
+        .map_err(|reason| JsError::new(&format!("Invalid solution: {reason}")))?;
+    to_js_value(&Solution {
+        progress: state.progress,
+        quality: state.quality,
+        steps: actions.len(),
+        duration: actions
+            .iter()
+            .map(|action| u32::from(action.time_cost()))
+            .sum(),
+        actions,
+    })
+}
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+use raphael_sim::Action;
+
+/// Maximum number of rotations that can be restored with undo.
+const MAX_UNDO_STEPS: usize = 50;
+
+/// Undo and redo history of the rotation shown in the simulator.
+/// Changes are recorded by comparing the rotation with the one of the last [`Self::record`], so
+/// edits, solves and opened files are all covered without recording them individually.
+#[derive(Debug)]
+pub struct ActionHistory {
+    current: Vec<Action>,
+    undo_stack: VecDeque<Vec<Action>>,
+    redo_stack: Vec<Vec<Action>>,
+}
+
+impl ActionHistory {
+    pub fn new(actions: &[Action]) -> Self {
+        Self {
+            current: actions.to_vec(),
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    /// Adds an undo step if `actions` changed since the last call.
+    pub fn record(&mut self, actions: &[Action]) {
+        if self.current == actions {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.current, actions.to_vec());
+        if self.undo_stack.len() == MAX_UNDO_STEPS {
+            self.undo_stack.pop_front();
+        }
+        self.undo_stack.push_back(previous);
+        self.redo_stack.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Returns the rotation before the last change.
+    pub fn undo(&mut self) -> Option<Vec<Action>> {
+        let previous = self.undo_stack.pop_back()?;
+        let current = std::mem::replace(&mut self.current, previous.clone());
+        self.redo_stack.push(current);
+        Some(previous)
+    }
+
+    /// Returns the rotation before the last undo.
+    pub fn redo(&mut self) -> Option<Vec<Action>> {
+        let next = self.redo_stack.pop()?;
+        let current = std::mem::replace(&mut self.current, next.clone());
+        self.undo_stack.push_back(current);
+        Some(next)
+    }
+}
@@ -1,29 +1,66 @@
 use std::cell::Cell;
 use std::rc::Rc;
 
-use raphael_solver::SolverException;
-use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use raphael_solver::{
+    CacheStats, InitialEffects, ParetoSolution, ProgressOnlyReason, SolveRequest, SolverException,
+    SolverStats, progress_only_reason,
+};
+use serde::{Deserialize, Serialize};
 
 use egui::{Align, CursorIcon, Id, Layout, TextStyle, Visuals};
-use raphael_data::{Consumable, Locale, action_name, get_initial_quality, get_job_name};
+use raphael_data::{
+    Consumable, Locale, action_name, excluded_actions_for, get_initial_quality, get_job_name,
+};
 
-use raphael_sim::{Action, ActionImpl, HeartAndSoul, Manipulation, QuickInnovation, Settings};
+use raphael_sim::{
+    Action, ActionImpl, Condition, HeartAndSoul, Manipulation, QuickInnovation, Settings,
+    SuccessModel,
+};
 
-use crate::config::{CrafterConfig, QualitySource, QualityTarget, RecipeConfiguration};
+use crate::action_history::ActionHistory;
+use crate::config::{
+    CrafterConfig, JobContext, JobContexts, QualitySource, QualityTarget, RecipeConfiguration,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::data_update::spawn_game_data_update;
+use crate::debug_bundle::{self, DebugBundle};
+use crate::macro_export::{MacroExportConfig, MacroExportFormat};
+use crate::macro_import::parse_macro_text;
+use crate::planner::BatchPlan;
+use crate::plugin_export::PluginCraft;
+use crate::recipe_book::RecipeBook;
+use crate::rotation_file::{FILE_EXTENSION, RotationFile};
+#[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+use crate::scripting::{ScriptContext, ScriptEvent, ScriptRun, spawn_script};
+use crate::settings_import::{parse_settings, settings_to_json};
+use crate::share_code::ShareCode;
+use crate::share_link::ShareLink;
+use crate::solve_notification::{SolveNotificationConfig, request_notification_permission};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::solve_progress;
+#[cfg(target_arch = "wasm32")]
+use crate::storage::TabLock;
+use crate::storage::{Storage, store};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::update_check::{UpdateInfo, spawn_update_check};
 use crate::widgets::*;
-use crate::worker::BridgeType;
-
-fn load<T: DeserializeOwned>(cc: &eframe::CreationContext<'_>, key: &'static str, default: T) -> T {
-    match cc.storage {
-        Some(storage) => eframe::get_value(storage, key).unwrap_or(default),
-        None => default,
-    }
-}
+use crate::worker::remote::{RemoteBridge, RemoteSolveConfig};
+use crate::worker::{BridgeType, WorkerConfig, available_memory};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+// the settings are large because of the rules override, but only one input is sent per solve
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SolverInput {
-    Start(Settings, SolverConfig),
+    /// Solves for the actions after the given prefix of actions.
+    Start(Settings, SolverConfig, WorkerConfig, Vec<Action>),
     Cancel,
+    /// Stops the running solve and saves its progress to the file, along with the given
+    /// `.raphael` file of the app.
+    #[cfg(not(target_arch = "wasm32"))]
+    SaveProgress(std::path::PathBuf, String),
+    /// Continues the solve whose progress was saved to the file.
+    #[cfg(not(target_arch = "wasm32"))]
+    Resume(std::path::PathBuf, WorkerConfig),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,7 +68,78 @@ pub enum SolverEvent {
     Progress(usize),
     IntermediateSolution(Vec<Action>),
     FinalSolution(Vec<Action>),
+    /// Final result when solving for the steps vs. Quality trade-offs, see
+    /// [`SolverConfig::pareto_front`]. Ordered from most to fewest steps.
+    ParetoFront(Vec<ParetoSolution>),
+    /// Performance counters of the search, sent right before the final event.
+    Stats(SolverStats),
     Error(SolverException),
+    /// The web worker that ran the solve stopped responding, e.g. because it ran out of memory.
+    WorkerCrashed,
+    /// Final event when the solve was stopped to save its progress to the file.
+    #[cfg(not(target_arch = "wasm32"))]
+    ProgressSaved(std::path::PathBuf),
+}
+
+impl SolverEvent {
+    /// Whether the event ends the solve, i.e. no more events of the solve follow.
+    pub fn is_final(&self) -> bool {
+        !matches!(
+            self,
+            Self::Progress(_) | Self::IntermediateSolution(_) | Self::Stats(_)
+        )
+    }
+}
+
+/// A crashed web worker is detected through its `error` event, see `worker::web`. This timeout
+/// is only the last resort for a worker that hangs without an error. The initialization of the
+/// bound solvers sends no events and can take minutes for large recipes, so the timeout is
+/// far longer than any initialization.
+#[cfg(target_arch = "wasm32")]
+const WORKER_SILENCE_TIMEOUT: web_time::Duration = web_time::Duration::from_secs(30 * 60);
+
+/// Preset for how much time the solver spends on guaranteeing the best macro.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolverEffort {
+    /// Only search with unsound branch pruning, which may miss the best macro.
+    Fast,
+    /// Search with unsound branch pruning first, and without it if the target isn't reached.
+    #[default]
+    Balanced,
+    /// Only search without unsound branch pruning, and keep more states in beam searches.
+    Exhaustive,
+}
+
+impl SolverEffort {
+    pub const ALL: [Self; 3] = [Self::Fast, Self::Balanced, Self::Exhaustive];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Fast => "Fast",
+            Self::Balanced => "Balanced",
+            Self::Exhaustive => "Exhaustive",
+        }
+    }
+
+    /// Whether to start with a search that uses unsound branch pruning.
+    pub fn unsound_search(self) -> bool {
+        self != Self::Exhaustive
+    }
+
+    /// Whether to search again without unsound branch pruning if the first search didn't
+    /// reach the max Quality.
+    pub fn sound_search(self) -> bool {
+        self != Self::Fast
+    }
+
+    /// Beam width of the expected Quality solver.
+    pub fn beam_width(self) -> usize {
+        match self {
+            Self::Fast => 256,
+            Self::Balanced => 1024,
+            Self::Exhaustive => 4096,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,6 +148,137 @@ pub struct SolverConfig {
     pub backload_progress: bool,
     pub adversarial: bool,
     pub minimize_steps: bool,
+    /// Prefer macros that leave more CP unused, e.g. to recover from mistakes.
+    #[serde(default)]
+    pub minimize_cp: bool,
+    /// Maximize the expected Quality over random conditions instead of the Quality in Normal
+    /// condition.
+    #[serde(default)]
+    pub expected_quality: bool,
+    /// Effects that are already active at the start of the craft, for theorycrafting.
+    #[serde(default)]
+    pub initial_effects: InitialEffects,
+    /// How actions that can fail, e.g. Hasty Touch, are treated.
+    #[serde(default)]
+    pub success_model: SuccessModel,
+    /// Don't use Quality actions while Muscle Memory is active.
+    #[serde(default)]
+    pub forbid_quality_during_muscle_memory: bool,
+    /// Don't use Quality actions before this step.
+    #[serde(default)]
+    pub first_quality_step: u8,
+    /// Find the best macro for each number of steps instead of a single macro.
+    #[serde(default)]
+    pub pareto_front: bool,
+    /// Weight of the preference for macros that are easier to execute by hand, 0 disables it.
+    #[serde(default)]
+    pub human_friendly_bias: u8,
+    /// Trade-off between solve time and how thoroughly the best macro is searched for.
+    #[serde(default)]
+    pub effort: SolverEffort,
+    /// Solve without Byregot's Blessing, so that it can be used by hand on a good condition.
+    #[serde(default)]
+    pub exclude_byregot: bool,
+}
+
+impl SolverConfig {
+    /// Request for solving `recipe` with this configuration, targeting the Quality given by
+    /// the Quality target.
+    pub fn solve_request(
+        &self,
+        recipe: raphael_data::Recipe,
+        crafter_stats: raphael_data::CrafterStats,
+        food: Option<Consumable>,
+        potion: Option<Consumable>,
+        initial_quality: u16,
+    ) -> SolveRequest {
+        SolveRequest::new(recipe, crafter_stats)
+            .food(food)
+            .potion(potion)
+            .initial_quality(initial_quality)
+            .target_quality(self.quality_target.get_target(recipe.quality))
+            .adversarial(self.adversarial)
+            .backload_progress(self.backload_progress)
+            .initial_effects(self.initial_effects)
+            .minimize_cp(self.minimize_cp)
+            .human_friendly_bias(self.human_friendly_bias)
+            .allow_unsound_branch_pruning(!self.effort.sound_search())
+            .success_model(self.success_model)
+            .forbid_quality_during_muscle_memory(self.forbid_quality_during_muscle_memory)
+            .first_quality_step(self.first_quality_step)
+            .exclude_byregot(self.exclude_byregot)
+    }
+}
+
+type SolveParams = (Settings, u16, SolverConfig);
+
+/// Snapshot of the current configuration for computations that run on a background thread.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy)]
+pub struct CraftParams {
+    pub recipe: raphael_data::Recipe,
+    pub crafter_stats: raphael_data::CrafterStats,
+    pub food: Option<Consumable>,
+    pub potion: Option<Consumable>,
+    pub solver_config: SolverConfig,
+    pub initial_quality: u16,
+    pub thread_pool_settings: raphael_solver::ThreadPoolSettings,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CraftParams {
+    /// Request for solving the recipe with the current configuration.
+    pub fn solve_request(&self) -> SolveRequest {
+        self.solver_config.solve_request(
+            self.recipe,
+            self.crafter_stats,
+            self.food,
+            self.potion,
+            self.initial_quality,
+        )
+    }
+
+    /// Simulator settings for the given stats, with the max Quality lowered to the Quality
+    /// that is still missing to reach the Quality target.
+    pub fn solver_settings(
+        &self,
+        crafter_stats: raphael_data::CrafterStats,
+        adversarial: bool,
+    ) -> Settings {
+        let solver_config = SolverConfig {
+            adversarial,
+            ..self.solver_config
+        };
+        solver_config
+            .solve_request(
+                self.recipe,
+                crafter_stats,
+                self.food,
+                self.potion,
+                self.initial_quality,
+            )
+            .simulator_settings()
+    }
+
+    pub fn target_quality(&self) -> u16 {
+        self.solver_config
+            .quality_target
+            .get_target(self.recipe.quality)
+    }
+}
+
+/// Transient UI state that is restored on startup, so that a crash or a page refresh
+/// brings the user back to where they were, even if the rotation was never saved.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SessionState {
+    actions: Vec<Action>,
+    notes: String,
+    last_solve_params: Option<SolveParams>,
+    stats_edit_window_open: bool,
+    saved_rotations_window_open: bool,
+    rotation_file_window_open: bool,
+    macro_export_window_open: bool,
 }
 
 pub struct MacroSolverApp {
@@ -48,24 +287,121 @@ pub struct MacroSolverApp {
     selected_food: Option<Consumable>,
     selected_potion: Option<Consumable>,
     crafter_config: CrafterConfig,
+    job_contexts: JobContexts,
     solver_config: SolverConfig,
     macro_view_config: MacroViewConfig,
+    solve_notification_config: SolveNotificationConfig,
+    remote_solve_config: RemoteSolveConfig,
+    macro_export_config: MacroExportConfig,
     saved_rotations_data: SavedRotationsData,
+    recipe_book: RecipeBook,
+    delivery_tracker: DeliveryTracker,
+    batch_plan: BatchPlan,
+    custom_consumables: CustomConsumables,
 
     stats_edit_window_open: bool,
     saved_rotations_window_open: bool,
+    rotation_file_window_open: bool,
+    macro_export_window_open: bool,
+    rotation_check_window_open: bool,
+    rotation_check_level: u8,
+    share_window_open: bool,
+    share_macro_text: bool,
+    share_code_input: String,
+    share_code_error: Option<String>,
+    macro_text_input: String,
+    macro_text_error: Option<String>,
+    raw_settings_window_open: bool,
+    raw_settings_input: String,
+    delivery_tracker_window_open: bool,
+    batch_planner_window_open: bool,
+    custom_consumables_window_open: bool,
+
+    analysis_plugins: AnalysisPlugins,
+    solution_analysis_data: SolutionAnalysisData,
+    step_selection: StepSelection,
 
+    action_history: ActionHistory,
     actions: Vec<Action>,
+    notes: String,
+    last_solve_params: Option<SolveParams>,
+    file_error: Option<String>,
+    /// Set when an opened rotation can't be executed with the crafter setup stored in the file.
+    file_warning: Option<String>,
     solver_pending: bool,
     solver_interrupt_pending: bool,
+    /// The solve was stopped to save its progress, see `SolverInput::SaveProgress`.
+    solver_save_pending: bool,
     solver_progress: usize,
+    /// When the worker last sent an event, see [`MacroSolverApp::worker_watchdog`].
+    #[cfg(target_arch = "wasm32")]
+    last_worker_event: web_time::Instant,
+    /// Performance counters of the last solve, see `SolverEvent::Stats`.
+    solver_stats: Option<SolverStats>,
+    /// Set if the pending solve only has to max out Progress.
+    progress_only_reason: Option<ProgressOnlyReason>,
+    /// Saved macros that are similar to the result of the last solve.
+    similar_rotations: Vec<SimilarRotation>,
+    /// Macros of the last steps vs. Quality solve, see [`SolverConfig::pareto_front`].
+    pareto_front: Vec<ParetoSolution>,
     start_time: web_time::Instant,
     duration: web_time::Duration,
     solver_error: Option<SolverException>,
+    /// Whether the web worker of the last solve crashed, see [`SolverEvent::WorkerCrashed`].
+    worker_crashed: bool,
 
     bridge: BridgeType,
+    /// Connection of the pending solve if it runs on a remote server, see [`RemoteSolveConfig`].
+    remote_bridge: Option<RemoteBridge>,
     pub progress_update: Rc<Cell<Option<SolverEvent>>>,
     pub solution_update: Rc<Cell<Option<SolverEvent>>>,
+    file_update: Rc<Cell<Option<Result<RotationFile, String>>>>,
+
+    #[cfg(target_arch = "wasm32")]
+    tab_lock: TabLock,
+    #[cfg(target_arch = "wasm32")]
+    is_storage_writer: bool,
+
+    #[cfg(not(target_arch = "wasm32"))]
+    check_for_updates: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    update_check: Option<std::sync::mpsc::Receiver<UpdateInfo>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    available_update: Option<UpdateInfo>,
+    #[cfg(not(target_arch = "wasm32"))]
+    update_game_data: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    game_data_update: Option<std::sync::mpsc::Receiver<raphael_data::GameDataset>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    thread_pool_settings: raphael_solver::ThreadPoolSettings,
+    #[cfg(not(target_arch = "wasm32"))]
+    batch_solve_data: BatchSolveData,
+    #[cfg(not(target_arch = "wasm32"))]
+    stat_sweep_window_open: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    stat_sweep_data: StatSweepData,
+    #[cfg(not(target_arch = "wasm32"))]
+    reliability_report_window_open: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    reliability_report_data: ReliabilityReportData,
+    #[cfg(not(target_arch = "wasm32"))]
+    step_advisor_window_open: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    step_advisor_data: StepAdvisorData,
+
+    #[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+    solver_comparison_window_open: bool,
+    #[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+    solver_comparison_data: SolverComparisonData,
+
+    #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+    scripting_window_open: bool,
+    #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+    script: String,
+    #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+    script_output: String,
+    #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+    script_run: Option<ScriptRun>,
 }
 
 impl MacroSolverApp {
@@ -75,15 +411,19 @@ impl MacroSolverApp {
         progress_update: Rc<Cell<Option<SolverEvent>>>,
         solution_update: Rc<Cell<Option<SolverEvent>>>,
     ) -> BridgeType {
-        <crate::worker::Worker as gloo_worker::Spawnable>::spawner()
-            .callback(move |response| {
+        BridgeType::new(
+            concat!("./webworker", env!("RANDOM_SUFFIX"), ".js"),
+            move |response| {
+                // the stats follow all progress, so they hide no progress
                 match response {
-                    SolverEvent::Progress(_) => progress_update.set(Some(response)),
+                    SolverEvent::Progress(_) | SolverEvent::Stats(_) => {
+                        progress_update.set(Some(response))
+                    }
                     _ => solution_update.set(Some(response)),
                 }
                 ctx.request_repaint();
-            })
-            .spawn(concat!("./webworker", env!("RANDOM_SUFFIX"), ".js"))
+            },
+        )
     }
 
     /// Called once before the first frame.
@@ -116,32 +456,198 @@ impl MacroSolverApp {
         });
 
         load_fonts(&cc.egui_ctx);
+        set_low_spec_mode(&cc.egui_ctx, low_spec_mode(&cc.egui_ctx));
+
+        let storage = Storage::new(cc.storage);
+        let session = storage.load("SESSION", SessionState::default());
+        if let Some(last_solve_params) = session.last_solve_params {
+            cc.egui_ctx.data_mut(|data| {
+                data.insert_temp(Id::new("LAST_SOLVE_PARAMS"), last_solve_params);
+            });
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let check_for_updates = storage.load("CHECK_FOR_UPDATES", false);
+        #[cfg(not(target_arch = "wasm32"))]
+        let update_game_data = storage.load("UPDATE_GAME_DATA", false);
+
+        let recipe_config = storage.load("RECIPE_CONFIG", RecipeConfiguration::default());
 
-        Self {
-            locale: load(cc, "LOCALE", Locale::EN),
-            recipe_config: load(cc, "RECIPE_CONFIG", RecipeConfiguration::default()),
-            selected_food: load(cc, "SELECTED_FOOD", None),
-            selected_potion: load(cc, "SELECTED_POTION", None),
-            crafter_config: load(cc, "CRAFTER_CONFIG", CrafterConfig::default()),
-            solver_config: load(cc, "SOLVER_CONFIG", SolverConfig::default()),
-            macro_view_config: load(cc, "MACRO_VIEW_CONFIG", MacroViewConfig::default()),
-            saved_rotations_data: load(cc, "SAVED_ROTATIONS", SavedRotationsData::default()),
+        let app = Self {
+            locale: storage.load("LOCALE", Locale::EN),
+            recipe_config,
+            selected_food: storage.load("SELECTED_FOOD", None),
+            selected_potion: storage.load("SELECTED_POTION", None),
+            crafter_config: storage.load("CRAFTER_CONFIG", CrafterConfig::default()),
+            job_contexts: storage.load("JOB_CONTEXTS", JobContexts::default()),
+            solver_config: storage.load("SOLVER_CONFIG", SolverConfig::default()),
+            macro_view_config: storage.load("MACRO_VIEW_CONFIG", MacroViewConfig::default()),
+            solve_notification_config: storage.load(
+                "SOLVE_NOTIFICATION_CONFIG",
+                SolveNotificationConfig::default(),
+            ),
+            remote_solve_config: storage.load("REMOTE_SOLVE_CONFIG", RemoteSolveConfig::default()),
+            macro_export_config: storage.load("MACRO_EXPORT_CONFIG", MacroExportConfig::default()),
+            saved_rotations_data: storage.load("SAVED_ROTATIONS", SavedRotationsData::default()),
+            recipe_book: storage.load("RECIPE_BOOK", RecipeBook::default()),
+            delivery_tracker: storage.load("DELIVERY_TRACKER", DeliveryTracker::default()),
+            batch_plan: storage.load("BATCH_PLAN", BatchPlan::default()),
+            custom_consumables: storage.load("CUSTOM_CONSUMABLES", CustomConsumables::default()),
 
-            stats_edit_window_open: false,
-            saved_rotations_window_open: false,
+            stats_edit_window_open: session.stats_edit_window_open,
+            saved_rotations_window_open: session.saved_rotations_window_open,
+            rotation_file_window_open: session.rotation_file_window_open,
+            macro_export_window_open: session.macro_export_window_open,
+            rotation_check_window_open: false,
+            rotation_check_level: 100,
+            share_window_open: false,
+            share_macro_text: false,
+            share_code_input: String::new(),
+            share_code_error: None,
+            macro_text_input: String::new(),
+            macro_text_error: None,
+            raw_settings_window_open: false,
+            raw_settings_input: String::new(),
+            delivery_tracker_window_open: false,
+            batch_planner_window_open: false,
+            custom_consumables_window_open: false,
 
-            actions: Vec::new(),
+            analysis_plugins: AnalysisPlugins::default(),
+            solution_analysis_data: SolutionAnalysisData::default(),
+            step_selection: StepSelection::default(),
+
+            action_history: ActionHistory::new(&session.actions),
+            actions: session.actions,
+            notes: session.notes,
+            last_solve_params: session.last_solve_params,
+            file_error: None,
+            file_warning: None,
             solver_pending: false,
             solver_interrupt_pending: false,
+            solver_save_pending: false,
             solver_progress: 0,
+            #[cfg(target_arch = "wasm32")]
+            last_worker_event: web_time::Instant::now(),
+            solver_stats: None,
+            progress_only_reason: None,
             start_time: web_time::Instant::now(),
             duration: web_time::Duration::ZERO,
             solver_error: None,
+            worker_crashed: false,
+            similar_rotations: Vec::new(),
+            pareto_front: Vec::new(),
 
             bridge,
+            remote_bridge: None,
             progress_update,
             solution_update,
+            file_update: Rc::new(Cell::new(None)),
+
+            #[cfg(target_arch = "wasm32")]
+            tab_lock: TabLock::new(),
+            #[cfg(target_arch = "wasm32")]
+            is_storage_writer: true,
+
+            #[cfg(not(target_arch = "wasm32"))]
+            check_for_updates,
+            #[cfg(not(target_arch = "wasm32"))]
+            update_check: check_for_updates.then(|| spawn_update_check(cc.egui_ctx.clone())),
+            #[cfg(not(target_arch = "wasm32"))]
+            available_update: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            update_game_data,
+            #[cfg(not(target_arch = "wasm32"))]
+            game_data_update: update_game_data.then(|| spawn_game_data_update(cc.egui_ctx.clone())),
+            #[cfg(not(target_arch = "wasm32"))]
+            thread_pool_settings: storage.load(
+                "THREAD_POOL_SETTINGS",
+                raphael_solver::ThreadPoolSettings::default(),
+            ),
+            #[cfg(not(target_arch = "wasm32"))]
+            batch_solve_data: BatchSolveData::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            stat_sweep_window_open: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            stat_sweep_data: StatSweepData::new(
+                storage.load("STAT_SWEEP_CONFIG", StatSweepConfig::default()),
+            ),
+            #[cfg(not(target_arch = "wasm32"))]
+            reliability_report_window_open: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            reliability_report_data: ReliabilityReportData::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            step_advisor_window_open: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            step_advisor_data: StepAdvisorData::default(),
+
+            #[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+            solver_comparison_window_open: false,
+            #[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+            solver_comparison_data: SolverComparisonData::new(
+                storage.load("SOLVER_COMPARISON_LIBRARY", String::new()),
+            ),
+
+            #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+            scripting_window_open: false,
+            #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+            script: storage.load("SCRIPT", crate::scripting::EXAMPLE_SCRIPT.to_string()),
+            #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+            script_output: String::new(),
+            #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+            script_run: None,
+        };
+        #[cfg(target_arch = "wasm32")]
+        let app = {
+            let mut app = app;
+            app.open_page_share_link();
+            app
+        };
+        app
+    }
+
+    /// Loads the rotation and configuration of a share link, see [`ShareLink`].
+    pub fn open_share_link(&mut self, url: &str) {
+        match ShareLink::from_url(url) {
+            Ok(share_link) => {
+                self.apply_share_code(share_link.share_code);
+                if let Some(solver_config) = share_link.solver_config {
+                    self.solver_config = solver_config;
+                }
+            }
+            Err(error) => {
+                self.share_code_error = Some(error);
+                self.share_window_open = true;
+            }
+        }
+    }
+
+    /// Opens the share link that the page was opened with, if any.
+    #[cfg(target_arch = "wasm32")]
+    fn open_page_share_link(&mut self) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let location = window.location();
+        let search = location.search().unwrap_or_default();
+        if search.is_empty() {
+            return;
         }
+        self.open_share_link(&search);
+        // reloading the page shouldn't overwrite changes made after opening the link
+        if let (Ok(pathname), Ok(history)) = (location.pathname(), window.history()) {
+            history
+                .replace_state_with_url(&web_sys::wasm_bindgen::JsValue::NULL, "", Some(&pathname))
+                .ok();
+        }
+    }
+
+    /// Opens a `.raphael` file, e.g. when the app is launched through a file association.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open_file_path(&mut self, path: &std::path::Path) {
+        let result = std::fs::read(path)
+            .map_err(|error| error.to_string())
+            .and_then(|bytes| RotationFile::from_bytes(&bytes));
+        self.on_file_opened(result);
     }
 }
 
@@ -150,8 +656,49 @@ impl eframe::App for MacroSolverApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         #[cfg(target_arch = "wasm32")]
         self.load_fonts_dyn(ctx);
+        #[cfg(target_arch = "wasm32")]
+        self.tab_lock_update(ctx, _frame);
+
+        self.solver_update(ctx);
+        self.rotation_file_update(ctx);
+        self.action_history_update(ctx);
+        self.job_context_update();
+        #[cfg(not(target_arch = "wasm32"))]
+        self.update_check_update(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.game_data_update_update(ctx);
+        #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+        self.script_update(ctx);
+
+        if let Some(error) = self.file_error.clone() {
+            egui::Modal::new(egui::Id::new("file_error")).show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                ui.label(egui::RichText::new("Unable to open file").strong());
+                ui.separator();
+                ui.label(error);
+                ui.separator();
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Close").clicked() {
+                        self.file_error = None;
+                    }
+                });
+            });
+        }
 
-        self.solver_update();
+        if let Some(warning) = self.file_warning.clone() {
+            egui::Modal::new(egui::Id::new("file_warning")).show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                ui.label(egui::RichText::new("Rotation may not work").strong());
+                ui.separator();
+                ui.label(warning);
+                ui.separator();
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Close").clicked() {
+                        self.file_warning = None;
+                    }
+                });
+            });
+        }
 
         if let Some(error) = self.solver_error.clone() {
             egui::Modal::new(egui::Id::new("solver_error")).show(ctx, |ui| {
@@ -163,11 +710,20 @@ impl eframe::App for MacroSolverApp {
                         ui.label("Make sure your stats are enough to craft this item.");
                     }
                     SolverException::Interrupted => self.solver_error = None,
+                    SolverException::MemoryLimitReached => {
+                        ui.label(egui::RichText::new("Out of memory").strong());
+                        ui.separator();
+                        ui.label("The solver reached its memory limit before finding a solution.");
+                        ui.label("Try solving without \"Ensure 100% reliability\" or \"Minimize steps\".");
+                    }
                     SolverException::InternalError(message) => {
                         ui.label(egui::RichText::new("Error").strong());
                         ui.separator();
                         ui.label(message);
                         ui.label("This is an internal error. Please submit a bug report :)");
+                        if ui.button("Export debug bundle").clicked() {
+                            self.export_debug_bundle();
+                        }
                     }
                 }
                 ui.separator();
@@ -179,18 +735,42 @@ impl eframe::App for MacroSolverApp {
             });
         }
 
+        if self.worker_crashed {
+            egui::Modal::new(egui::Id::new("worker_crashed")).show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                ui.label(egui::RichText::new("Solver crashed").strong());
+                ui.separator();
+                ui.label("The solver stopped responding, most likely because it ran out of memory.");
+                ui.label("Try solving without \"Ensure 100% reliability\" or \"Minimize steps\", or use the native app.");
+                ui.separator();
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Close").clicked() {
+                        self.worker_crashed = false;
+                    }
+                });
+            });
+        }
+
         if self.solver_pending {
+            if low_spec_mode(ctx) {
+                // there is no spinner animation to keep the elapsed time and progress up to date
+                ctx.request_repaint_after(background_repaint_interval(ctx));
+            }
             egui::Modal::new(egui::Id::new("solver_busy")).show(ctx, |ui| {
                 ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
                 ui.set_width(180.0);
                 ui.horizontal(|ui| {
-                    ui.spinner();
+                    busy_indicator(ui);
                     ui.vertical(|ui| {
                         ui.horizontal(|ui| {
                             ui.label(
-                                egui::RichText::new(match self.solver_interrupt_pending {
-                                    true => "Cancelling ...",
-                                    false => "Solving ...",
+                                egui::RichText::new(match (
+                                    self.solver_interrupt_pending,
+                                    self.solver_save_pending,
+                                ) {
+                                    (true, true) => "Saving progress ...",
+                                    (true, false) => "Cancelling ...",
+                                    (false, _) => "Solving ...",
                                 })
                                 .strong(),
                             );
@@ -212,18 +792,42 @@ impl eframe::App for MacroSolverApp {
                                 .join(",");
                             ui.label(format!("{} nodes visited", num));
                         }
+                        if let Some(reason) = self.progress_only_reason {
+                            ui.label(egui::RichText::new(reason.description()).small());
+                        }
                     });
                 });
 
-                #[cfg(not(target_arch = "wasm32"))]
                 ui.vertical_centered_justified(|ui| {
                     ui.separator();
-                    let response =
-                        ui.add_enabled(!self.solver_interrupt_pending, egui::Button::new("Cancel"));
+                    let response = ui.add_enabled(
+                        !self.solver_interrupt_pending,
+                        egui::Button::new("Cancel"),
+                    );
                     if response.clicked() {
-                        self.bridge.send(SolverInput::Cancel);
+                        match &mut self.remote_bridge {
+                            Some(remote_bridge) => remote_bridge.send(&SolverInput::Cancel),
+                            None => self.bridge.send(SolverInput::Cancel),
+                        }
                         self.solver_interrupt_pending = true;
                     }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if self.remote_bridge.is_none()
+                        && !self.solver_config.expected_quality
+                        && !self.solver_config.pareto_front
+                    {
+                        let response = ui
+                            .add_enabled(
+                                !self.solver_interrupt_pending,
+                                egui::Button::new("Save progress"),
+                            )
+                            .on_hover_text(
+                                "Stop the solve and save its progress to a file, to continue it later",
+                            );
+                        if response.clicked() {
+                            self.on_save_progress_clicked();
+                        }
+                    }
                 });
             });
         }
@@ -235,6 +839,27 @@ impl eframe::App for MacroSolverApp {
                     egui::containers::menu::Bar::new().ui(ui, |ui| {
                         ui.label(egui::RichText::new("Raphael  |  FFXIV Crafting Solver").strong());
                         ui.label(format!("v{}", env!("CARGO_PKG_VERSION")));
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui
+                            .checkbox(&mut self.check_for_updates, "Check for updates")
+                            .on_hover_text("Check GitHub for a newer release on startup")
+                            .changed()
+                            && self.check_for_updates
+                        {
+                            self.update_check = Some(spawn_update_check(ctx.clone()));
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui
+                            .checkbox(&mut self.update_game_data, "Update game data")
+                            .on_hover_text(raphael_data::installed_data_version().map_or_else(
+                                || "Download the latest recipes and items on startup, e.g. for a new patch".to_string(),
+                                |version| format!("Using the downloaded game data ({version})"),
+                            ))
+                            .changed()
+                            && self.update_game_data
+                        {
+                            self.game_data_update = Some(spawn_game_data_update(ctx.clone()));
+                        }
 
                         egui::ComboBox::from_id_salt("LOCALE")
                             .selected_text(format!("{}", self.locale))
@@ -262,6 +887,75 @@ impl eframe::App for MacroSolverApp {
                                 );
                             });
 
+                        if ui.button("📂 Open").clicked() {
+                            self.on_open_file_clicked(ctx);
+                        }
+                        if ui.button("💾 Save").clicked() {
+                            self.rotation_file_window_open = true;
+                        }
+                        if ui.button("📱 Share").clicked() {
+                            self.share_window_open = true;
+                        }
+                        if ui.button("🔍 Check").clicked() {
+                            self.rotation_check_level = self.crafter_config.active_stats().level;
+                            self.rotation_check_window_open = true;
+                        }
+                        if ui
+                            .add_enabled(
+                                self.action_history.can_undo() && !self.solver_pending,
+                                egui::Button::new("⟲ Undo"),
+                            )
+                            .on_hover_text("Undo the last change of the rotation (Ctrl+Z)")
+                            .clicked()
+                        {
+                            self.undo_actions();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.action_history.can_redo() && !self.solver_pending,
+                                egui::Button::new("⟳ Redo"),
+                            )
+                            .on_hover_text("Redo the last undone change of the rotation (Ctrl+Y)")
+                            .clicked()
+                        {
+                            self.redo_actions();
+                        }
+                        if ui.button("🛠 Raw settings").clicked() {
+                            self.raw_settings_window_open = true;
+                        }
+                        if ui
+                            .button("🐞 Debug bundle")
+                            .on_hover_text("Save the settings, solver configuration, macro and recent logs to a file that can be attached to a bug report")
+                            .clicked()
+                        {
+                            self.export_debug_bundle();
+                        }
+                        if ui.button("📅 Deliveries").clicked() {
+                            self.delivery_tracker_window_open = true;
+                        }
+                        if ui.button("🧾 Plan").clicked() {
+                            self.batch_planner_window_open = true;
+                        }
+                        if ui.button("🍲 Custom consumables").clicked() {
+                            self.custom_consumables_window_open = true;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button("📊 Stat sweep").clicked() {
+                            self.stat_sweep_window_open = true;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button("👣 Step by step").clicked() {
+                            self.step_advisor_window_open = true;
+                        }
+                        #[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+                        if ui.button("⚖ Compare solvers").clicked() {
+                            self.solver_comparison_window_open = true;
+                        }
+                        #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+                        if ui.button("📜 Script").clicked() {
+                            self.scripting_window_open = true;
+                        }
+
                         let mut visuals = ctx.style().visuals.clone();
                         ui.selectable_value(&mut visuals, Visuals::light(), "☀ Light");
                         ui.selectable_value(&mut visuals, Visuals::dark(), "🌙 Dark");
@@ -271,6 +965,15 @@ impl eframe::App for MacroSolverApp {
                         });
                         ctx.set_visuals(visuals);
 
+                        let mut low_spec = low_spec_mode(ctx);
+                        if ui
+                            .checkbox(&mut low_spec, "Low-spec mode")
+                            .on_hover_text("Reduce animations and repaints, e.g. when running Raphael alongside the game on weak hardware")
+                            .changed()
+                        {
+                            set_low_spec_mode(ctx, low_spec);
+                        }
+
                         ui.add(
                             egui::Hyperlink::from_label_and_url(
                                 "View source on GitHub",
@@ -393,135 +1096,1432 @@ impl eframe::App for MacroSolverApp {
         .default_size((400.0, 600.0))
         .show(ctx, |ui| {
             ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            if ui
+                .add_enabled(
+                    !self.saved_rotations_data.pinned().is_empty(),
+                    egui::Button::new("Export all saved macros"),
+                )
+                .clicked()
+            {
+                self.macro_export_window_open = true;
+            }
             ui.add(SavedRotationsWidget::new(
                 self.locale,
                 &mut self.saved_rotations_data,
                 &mut self.actions,
+                &mut self.notes,
             ));
         });
-    }
 
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, "LOCALE", &self.locale);
-        eframe::set_value(storage, "RECIPE_CONFIG", &self.recipe_config);
-        eframe::set_value(storage, "SELECTED_FOOD", &self.selected_food);
-        eframe::set_value(storage, "SELECTED_POTION", &self.selected_potion);
-        eframe::set_value(storage, "CRAFTER_CONFIG", &self.crafter_config);
-        eframe::set_value(storage, "SOLVER_CONFIG", &self.solver_config);
-        eframe::set_value(storage, "MACRO_VIEW_CONFIG", &self.macro_view_config);
-        eframe::set_value(storage, "SAVED_ROTATIONS", &self.saved_rotations_data);
-    }
+        let mut rotation_file_window_open = self.rotation_file_window_open;
+        egui::Window::new(
+            egui::RichText::new("Rotation file")
+                .strong()
+                .text_style(TextStyle::Body),
+        )
+        .open(&mut rotation_file_window_open)
+        .collapsible(false)
+        .resizable(false)
+        .min_width(400.0)
+        .max_width(400.0)
+        .show(ctx, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            self.draw_rotation_file_widget(ui);
+        });
+        self.rotation_file_window_open &= rotation_file_window_open;
 
-    fn auto_save_interval(&self) -> std::time::Duration {
-        std::time::Duration::from_secs(1)
-    }
-}
+        let mut macro_export_window_open = self.macro_export_window_open;
+        egui::Window::new(
+            egui::RichText::new("Export saved macros")
+                .strong()
+                .text_style(TextStyle::Body),
+        )
+        .open(&mut macro_export_window_open)
+        .collapsible(false)
+        .resizable(false)
+        .min_width(400.0)
+        .max_width(400.0)
+        .show(ctx, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            self.draw_macro_export_widget(ui);
+        });
+        self.macro_export_window_open &= macro_export_window_open;
 
-impl MacroSolverApp {
-    fn on_solver_event(&mut self, event: SolverEvent) {
-        match event {
-            SolverEvent::Progress(progress) => self.solver_progress = progress,
-            SolverEvent::IntermediateSolution(actions) => self.actions = actions,
-            SolverEvent::FinalSolution(actions) => {
-                self.actions = actions;
-                self.duration = self.start_time.elapsed();
-                self.solver_pending = false;
-                self.saved_rotations_data.add_solved_rotation(Rotation::new(
-                    raphael_data::get_item_name(
-                        self.recipe_config.recipe.item_id,
-                        false,
-                        self.locale,
-                    ),
-                    self.actions.clone(),
-                    &self.recipe_config.recipe,
-                    self.selected_food,
-                    self.selected_potion,
-                    &self.crafter_config,
-                    &self.solver_config,
-                ));
-            }
-            SolverEvent::Error(error) => {
-                self.actions.clear();
-                self.duration = self.start_time.elapsed();
-                self.solver_pending = false;
-                if error != SolverException::Interrupted {
-                    self.solver_error = Some(error);
-                }
-            }
-        }
-    }
+        let mut rotation_check_window_open = self.rotation_check_window_open;
+        let initial_quality = self.initial_quality();
+        egui::Window::new(
+            egui::RichText::new("Check rotation at another level")
+                .strong()
+                .text_style(TextStyle::Body),
+        )
+        .open(&mut rotation_check_window_open)
+        .collapsible(false)
+        .resizable(false)
+        .min_width(400.0)
+        .max_width(400.0)
+        .show(ctx, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.add(RotationCheck::new(
+                &mut self.rotation_check_level,
+                self.recipe_config.recipe,
+                self.crafter_config.active_stats(),
+                self.selected_food,
+                self.selected_potion,
+                initial_quality,
+                &self.actions,
+                self.locale,
+            ));
+        });
+        self.rotation_check_window_open = rotation_check_window_open;
 
-    fn solver_update(&mut self) {
-        #[cfg(not(target_arch = "wasm32"))]
-        if let Ok(event) = self.bridge.rx.try_recv() {
-            self.on_solver_event(event);
-        }
-        #[cfg(target_arch = "wasm32")]
-        if let Some(event) = self.progress_update.take() {
-            self.on_solver_event(event);
+        let mut share_window_open = self.share_window_open;
+        egui::Window::new(
+            egui::RichText::new("Share rotation")
+                .strong()
+                .text_style(TextStyle::Body),
+        )
+        .open(&mut share_window_open)
+        .collapsible(false)
+        .resizable(false)
+        .min_width(400.0)
+        .max_width(400.0)
+        .show(ctx, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            self.draw_share_widget(ui);
+        });
+        self.share_window_open &= share_window_open;
+
+        let mut raw_settings_window_open = self.raw_settings_window_open;
+        egui::Window::new(
+            egui::RichText::new("Solve with raw settings")
+                .strong()
+                .text_style(TextStyle::Body),
+        )
+        .open(&mut raw_settings_window_open)
+        .collapsible(false)
+        .resizable(false)
+        .min_width(400.0)
+        .max_width(400.0)
+        .show(ctx, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            self.draw_raw_settings_widget(ui);
+        });
+        self.raw_settings_window_open &= raw_settings_window_open;
+
+        egui::Window::new(
+            egui::RichText::new("Deliveries")
+                .strong()
+                .text_style(TextStyle::Body),
+        )
+        .open(&mut self.delivery_tracker_window_open)
+        .collapsible(false)
+        .resizable(false)
+        .min_width(400.0)
+        .max_width(400.0)
+        .show(ctx, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.add(DeliveryTrackerWidget::new(
+                &mut self.delivery_tracker,
+                self.locale,
+            ));
+        });
+
+        egui::Window::new(
+            egui::RichText::new("Custom consumables")
+                .strong()
+                .text_style(TextStyle::Body),
+        )
+        .open(&mut self.custom_consumables_window_open)
+        .collapsible(false)
+        .resizable(false)
+        .min_width(400.0)
+        .show(ctx, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.add(CustomConsumablesWidget::new(&mut self.custom_consumables));
+        });
+        CustomConsumables::update_selected(&self.custom_consumables.meals, &mut self.selected_food);
+        CustomConsumables::update_selected(
+            &self.custom_consumables.potions,
+            &mut self.selected_potion,
+        );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let params = self.craft_params();
+        egui::Window::new(
+            egui::RichText::new("Batch plan")
+                .strong()
+                .text_style(TextStyle::Body),
+        )
+        .open(&mut self.batch_planner_window_open)
+        .collapsible(false)
+        .resizable(false)
+        .min_width(400.0)
+        .max_width(400.0)
+        .show(ctx, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.add(BatchPlannerWidget::new(
+                &mut self.batch_plan,
+                &self.recipe_config,
+                self.locale,
+            ));
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                ui.separator();
+                ui.add(BatchSolveWidget::new(
+                    &mut self.batch_solve_data,
+                    &self.batch_plan,
+                    std::array::from_fn(|job_id| self.crafter_config.job_stats(job_id as u8)),
+                    params,
+                    &self.macro_view_config,
+                    self.locale,
+                ));
+            }
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let params = self.craft_params();
+            egui::Window::new(
+                egui::RichText::new("Stat sweep")
+                    .strong()
+                    .text_style(TextStyle::Body),
+            )
+            .open(&mut self.stat_sweep_window_open)
+            .collapsible(false)
+            .default_size((500.0, 400.0))
+            .show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                ui.add(StatSweepWidget::new(&mut self.stat_sweep_data, params));
+            });
+
+            egui::Window::new(
+                egui::RichText::new("Regular vs 100% reliable macro")
+                    .strong()
+                    .text_style(TextStyle::Body),
+            )
+            .open(&mut self.reliability_report_window_open)
+            .collapsible(false)
+            .resizable(false)
+            .min_width(400.0)
+            .max_width(400.0)
+            .show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                ui.add(ReliabilityReportWidget::new(
+                    &mut self.reliability_report_data,
+                    params,
+                    &mut self.actions,
+                ));
+            });
+
+            egui::Window::new(
+                egui::RichText::new("Step-by-step advisor")
+                    .strong()
+                    .text_style(TextStyle::Body),
+            )
+            .open(&mut self.step_advisor_window_open)
+            .collapsible(false)
+            .resizable(false)
+            .min_width(400.0)
+            .max_width(400.0)
+            .show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                ui.add(StepAdvisorWidget::new(
+                    &mut self.step_advisor_data,
+                    params,
+                    self.locale,
+                ));
+            });
+
+            #[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+            egui::Window::new(
+                egui::RichText::new("Current vs previous solver")
+                    .strong()
+                    .text_style(TextStyle::Body),
+            )
+            .open(&mut self.solver_comparison_window_open)
+            .collapsible(false)
+            .resizable(false)
+            .min_width(400.0)
+            .max_width(400.0)
+            .show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                ui.add(SolverComparisonWidget::new(
+                    &mut self.solver_comparison_data,
+                    params,
+                    &mut self.actions,
+                ));
+            });
+        }
+
+        #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+        {
+            let mut scripting_window_open = self.scripting_window_open;
+            egui::Window::new(
+                egui::RichText::new("Script console")
+                    .strong()
+                    .text_style(TextStyle::Body),
+            )
+            .open(&mut scripting_window_open)
+            .collapsible(false)
+            .default_size((500.0, 500.0))
+            .show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                self.draw_scripting_widget(ui);
+            });
+            self.scripting_window_open &= scripting_window_open;
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        #[cfg(target_arch = "wasm32")]
+        if !self.is_storage_writer {
+            // another tab is responsible for persisting state
+            return;
+        }
+        store(storage, "LOCALE", &self.locale);
+        store(storage, "RECIPE_CONFIG", &self.recipe_config);
+        store(storage, "SELECTED_FOOD", &self.selected_food);
+        store(storage, "SELECTED_POTION", &self.selected_potion);
+        store(storage, "CRAFTER_CONFIG", &self.crafter_config);
+        store(storage, "JOB_CONTEXTS", &self.job_contexts);
+        store(storage, "SOLVER_CONFIG", &self.solver_config);
+        store(storage, "MACRO_VIEW_CONFIG", &self.macro_view_config);
+        store(
+            storage,
+            "SOLVE_NOTIFICATION_CONFIG",
+            &self.solve_notification_config,
+        );
+        store(storage, "REMOTE_SOLVE_CONFIG", &self.remote_solve_config);
+        store(storage, "MACRO_EXPORT_CONFIG", &self.macro_export_config);
+        store(storage, "SAVED_ROTATIONS", &self.saved_rotations_data);
+        store(storage, "RECIPE_BOOK", &self.recipe_book);
+        store(storage, "DELIVERY_TRACKER", &self.delivery_tracker);
+        store(storage, "BATCH_PLAN", &self.batch_plan);
+        store(storage, "CUSTOM_CONSUMABLES", &self.custom_consumables);
+        store(
+            storage,
+            "SESSION",
+            &SessionState {
+                actions: self.actions.clone(),
+                notes: self.notes.clone(),
+                last_solve_params: self.last_solve_params,
+                stats_edit_window_open: self.stats_edit_window_open,
+                saved_rotations_window_open: self.saved_rotations_window_open,
+                rotation_file_window_open: self.rotation_file_window_open,
+                macro_export_window_open: self.macro_export_window_open,
+            },
+        );
+        #[cfg(not(target_arch = "wasm32"))]
+        store(storage, "CHECK_FOR_UPDATES", &self.check_for_updates);
+        #[cfg(not(target_arch = "wasm32"))]
+        store(storage, "UPDATE_GAME_DATA", &self.update_game_data);
+        #[cfg(not(target_arch = "wasm32"))]
+        store(storage, "THREAD_POOL_SETTINGS", &self.thread_pool_settings);
+        #[cfg(not(target_arch = "wasm32"))]
+        store(storage, "STAT_SWEEP_CONFIG", &self.stat_sweep_data.config);
+        #[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+        store(
+            storage,
+            "SOLVER_COMPARISON_LIBRARY",
+            &self.solver_comparison_data.library_path,
+        );
+        #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+        store(storage, "SCRIPT", &self.script);
+    }
+
+    fn auto_save_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(1)
+    }
+}
+
+impl MacroSolverApp {
+    /// Adds an analysis panel below the simulator, see [`AnalysisPlugin`].
+    pub fn register_analysis_plugin(&mut self, plugin: Box<dyn AnalysisPlugin>) {
+        self.analysis_plugins.register(plugin);
+    }
+
+    fn on_solver_event(&mut self, ctx: &egui::Context, event: SolverEvent) {
+        match event {
+            SolverEvent::Progress(progress) => self.solver_progress = progress,
+            SolverEvent::IntermediateSolution(actions) => self.actions = actions,
+            SolverEvent::Stats(stats) => self.solver_stats = Some(stats),
+            SolverEvent::FinalSolution(actions) => self.on_final_solution(ctx, actions),
+            SolverEvent::ParetoFront(pareto_front) => {
+                let actions = pareto_front
+                    .first()
+                    .map(|solution| solution.actions.clone())
+                    .unwrap_or_default();
+                self.pareto_front = pareto_front;
+                self.on_final_solution(ctx, actions);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            SolverEvent::ProgressSaved(path) => {
+                self.duration = self.start_time.elapsed();
+                self.solver_pending = false;
+                self.solve_notification_config
+                    .notify(ctx, &format!("Solve progress saved to {}", path.display()));
+            }
+            SolverEvent::Error(error) => {
+                self.actions.clear();
+                self.duration = self.start_time.elapsed();
+                self.solver_pending = false;
+                if error != SolverException::Interrupted {
+                    let message = match error {
+                        SolverException::NoSolution => "Solve failed: no solution found",
+                        SolverException::MemoryLimitReached => "Solve failed: out of memory",
+                        _ => "Solve failed: internal error",
+                    };
+                    self.solve_notification_config.notify(ctx, message);
+                    self.solver_error = Some(error);
+                }
+            }
+            SolverEvent::WorkerCrashed => {
+                self.actions.clear();
+                self.duration = self.start_time.elapsed();
+                self.solver_pending = false;
+                self.solve_notification_config
+                    .notify(ctx, "Solve failed: the solver crashed");
+                self.worker_crashed = true;
+            }
+        }
+    }
+
+    fn on_final_solution(&mut self, ctx: &egui::Context, actions: Vec<Action>) {
+        self.actions = actions;
+        self.duration = self.start_time.elapsed();
+        self.solver_pending = false;
+        if let Some((settings, _, _)) = self.last_solve_params {
+            self.analysis_plugins.on_solve(&settings, &self.actions);
+        }
+        self.similar_rotations = self.saved_rotations_data.similar_rotations(
+            &self.actions,
+            self.crafter_config.selected_job,
+            self.actions.len() / 5,
+        );
+        self.saved_rotations_data.add_solved_rotation(Rotation::new(
+            raphael_data::get_item_name(self.recipe_config.recipe.item_id, false, self.locale),
+            self.actions.clone(),
+            &self.recipe_config.recipe,
+            self.selected_food,
+            self.selected_potion,
+            &self.crafter_config,
+            &self.solver_config,
+            self.initial_quality(),
+        ));
+        self.solve_notification_config.notify(
+            ctx,
+            &format!(
+                "Solve finished: {} steps in {:.1}s",
+                self.actions.len(),
+                self.duration.as_secs_f32()
+            ),
+        );
+    }
+
+    fn solver_update(&mut self, ctx: &egui::Context) {
+        if let Some(result) = self.remote_bridge.as_mut().and_then(RemoteBridge::try_recv) {
+            let event = result.unwrap_or_else(|error| {
+                SolverEvent::Error(SolverException::InternalError(format!(
+                    "Remote solve failed: {error}"
+                )))
+            });
+            if event.is_final() {
+                self.remote_bridge = None;
+            }
+            self.on_solver_event(ctx, event);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Ok(event) = self.bridge.rx.try_recv() {
+            self.on_solver_event(ctx, event);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(event) = self.progress_update.take() {
+            self.last_worker_event = web_time::Instant::now();
+            self.on_solver_event(ctx, event);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(event) = self.solution_update.take() {
+            self.last_worker_event = web_time::Instant::now();
+            self.on_solver_event(ctx, event);
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.worker_watchdog(ctx);
+    }
+
+    /// Fails the solve with `SolverEvent::WorkerCrashed` if the worker has been silent for
+    /// longer than [`WORKER_SILENCE_TIMEOUT`], and replaces the worker for the next solve.
+    #[cfg(target_arch = "wasm32")]
+    fn worker_watchdog(&mut self, ctx: &egui::Context) {
+        if !self.solver_pending || self.remote_bridge.is_some() {
+            return;
+        }
+        let silence = self.last_worker_event.elapsed();
+        if silence < WORKER_SILENCE_TIMEOUT {
+            ctx.request_repaint_after(WORKER_SILENCE_TIMEOUT - silence);
+            return;
+        }
+        log::error!(
+            "Solver worker sent no events for {:.0}s",
+            silence.as_secs_f32()
+        );
+        self.bridge.restart();
+        self.on_solver_event(ctx, SolverEvent::WorkerCrashed);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn update_check_update(&mut self, ctx: &egui::Context) {
+        if let Some(update) = self.update_check.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.update_check = None;
+            self.available_update = Some(update);
+        }
+        if let Some(update) = self.available_update.clone() {
+            egui::Modal::new(egui::Id::new("update_available")).show(ctx, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                ui.label(egui::RichText::new("Update available").strong());
+                ui.separator();
+                ui.label(format!(
+                    "Raphael {} is available (current version: v{}).",
+                    update.version,
+                    env!("CARGO_PKG_VERSION")
+                ));
+                ui.hyperlink_to("Download the latest release", &update.url);
+                ui.separator();
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Close").clicked() {
+                        self.available_update = None;
+                    }
+                });
+            });
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn game_data_update_update(&mut self, ctx: &egui::Context) {
+        let Some(dataset) = self
+            .game_data_update
+            .as_ref()
+            .and_then(|rx| rx.try_recv().ok())
+        else {
+            return;
+        };
+        self.game_data_update = None;
+        let data_version = dataset.data_version.clone();
+        match raphael_data::install_game_dataset(dataset) {
+            Ok(()) => {
+                log::info!("Using game data {data_version}");
+                // cached search results are indices into the replaced tables
+                ctx.memory_mut(|mem| mem.caches = egui::cache::CacheStorage::default());
+            }
+            Err(error) => log::warn!("Game data {data_version} is invalid: {error}"),
+        }
+    }
+
+    #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+    fn script_update(&mut self, ctx: &egui::Context) {
+        let Some(run) = self.script_run.as_ref() else {
+            return;
+        };
+        while let Ok(event) = run.events.try_recv() {
+            match event {
+                ScriptEvent::Output(text) => {
+                    self.script_output.push_str(&text);
+                    self.script_output.push('\n');
+                }
+                ScriptEvent::Finished(result) => {
+                    if let Err(error) = result {
+                        self.script_output.push_str(&error);
+                        self.script_output.push('\n');
+                    }
+                    self.script_run = None;
+                    return;
+                }
+            }
+        }
+        // the script runs on a background thread, so keep polling for its output
+        ctx.request_repaint_after(background_repaint_interval(ctx));
+    }
+
+    #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+    fn draw_scripting_widget(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if let Some(run) = self.script_run.as_ref() {
+                busy_indicator(ui);
+                if ui.button("Cancel").clicked() {
+                    run.cancel();
+                }
+            } else if ui.button("Run").clicked() {
+                self.script_output.clear();
+                self.script_run = Some(spawn_script(self.script.clone(), self.script_context()));
+            }
+            if ui.button("Clear output").clicked() {
+                self.script_output.clear();
+            }
+            ui.add(HelpText::new(crate::scripting::HELP_TEXT));
+        });
+        ui.separator();
+        egui::ScrollArea::vertical()
+            .id_salt("script_editor")
+            .max_height(250.0)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.script)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(12),
+                );
+            });
+        ui.separator();
+        egui::ScrollArea::vertical()
+            .id_salt("script_output")
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::Label::new(egui::RichText::new(&self.script_output).monospace())
+                        .selectable(true),
+                );
+            });
+    }
+
+    #[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+    fn script_context(&self) -> ScriptContext {
+        ScriptContext {
+            recipe: self.recipe_config.recipe,
+            crafter_stats: self.crafter_config.active_stats(),
+            food: self.selected_food,
+            potion: self.selected_potion,
+            solver_config: self.solver_config,
+            initial_quality: self.initial_quality(),
+            locale: self.locale,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn tab_lock_update(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let was_storage_writer = self.is_storage_writer;
+        self.is_storage_writer = self.tab_lock.try_acquire();
+        if self.is_storage_writer {
+            if !was_storage_writer {
+                self.merge_saved_rotations(frame);
+            }
+            return;
+        }
+        egui::TopBottomPanel::top("tab_lock_panel").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "⚠ Raphael is open in another tab. Changes made in this tab will not be saved.",
+                    )
+                    .color(ui.visuals().warn_fg_color),
+                );
+                if ui.button("Save changes from this tab instead").clicked() {
+                    self.tab_lock.force_acquire();
+                    self.is_storage_writer = true;
+                    self.merge_saved_rotations(frame);
+                }
+            });
+        });
+        // keep polling the lock so that this tab takes over once the other tab is closed
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    }
+
+    /// Keeps rotations that were saved by another tab while this tab was not the writer.
+    #[cfg(target_arch = "wasm32")]
+    fn merge_saved_rotations(&mut self, frame: &eframe::Frame) {
+        let storage = Storage::new(frame.storage());
+        self.saved_rotations_data
+            .merge(storage.load("SAVED_ROTATIONS", SavedRotationsData::default()));
+    }
+
+    /// Loads the default rotation of the selected recipe, if it has one.
+    ///
+    /// This only runs when a recipe is selected in the recipe list, so that rotations loaded
+    /// from files, share links and gists aren't replaced by the default rotation.
+    fn load_recipe_default(&mut self) {
+        if self.solver_pending {
+            return;
+        }
+        let fingerprint = RecipeFingerprint::new(
+            &self.recipe_config.recipe,
+            self.selected_food,
+            self.selected_potion,
+            &self.crafter_config,
+        );
+        if let Some(rotation) = self.saved_rotations_data.recipe_default(&fingerprint) {
+            self.actions.clone_from(&rotation.actions);
+            self.notes.clone_from(&rotation.notes);
+            self.step_selection = StepSelection::default();
+        }
+    }
+
+    /// Keeps the context of the selected job up to date, so that it can be restored after
+    /// switching to another job, see [`Self::select_job`].
+    fn job_context_update(&mut self) {
+        self.job_contexts.set(
+            self.crafter_config.selected_job,
+            JobContext {
+                recipe_config: self.recipe_config,
+                food: self.selected_food,
+                potion: self.selected_potion,
+                quality_target: self.solver_config.quality_target,
+            },
+        );
+    }
+
+    /// Selects the job and restores the recipe, consumables and Quality target it was last used
+    /// with. Jobs that were never used keep the current ones.
+    fn select_job(&mut self, job_id: u8) {
+        self.crafter_config.selected_job = job_id;
+        if let Some(context) = self.job_contexts.get(job_id) {
+            self.recipe_config = context.recipe_config;
+            self.selected_food = context.food;
+            self.selected_potion = context.potion;
+            self.solver_config.quality_target = context.quality_target;
+        }
+    }
+
+    /// Records changes of the rotation and handles the undo and redo shortcuts. Intermediate
+    /// solutions aren't recorded, a solve is undone as a whole.
+    fn action_history_update(&mut self, ctx: &egui::Context) {
+        if self.solver_pending {
+            return;
+        }
+        self.action_history.record(&self.actions);
+        if ctx.wants_keyboard_input() {
+            // text fields have their own undo
+            return;
+        }
+        // redo is checked first because Ctrl+Z also matches Ctrl+Shift+Z
+        let (redo, undo) = ctx.input_mut(|input| {
+            let redo = input.consume_shortcut(&egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND,
+                egui::Key::Y,
+            )) || input.consume_shortcut(&egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                egui::Key::Z,
+            ));
+            let undo = input.consume_shortcut(&egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND,
+                egui::Key::Z,
+            ));
+            (redo, undo)
+        });
+        if redo {
+            self.redo_actions();
+        } else if undo {
+            self.undo_actions();
+        }
+    }
+
+    fn undo_actions(&mut self) {
+        if let Some(actions) = self.action_history.undo() {
+            self.actions = actions;
+            self.step_selection = StepSelection::default();
+        }
+    }
+
+    fn redo_actions(&mut self) {
+        if let Some(actions) = self.action_history.redo() {
+            self.actions = actions;
+            self.step_selection = StepSelection::default();
+        }
+    }
+
+    fn rotation_file_update(&mut self, ctx: &egui::Context) {
+        let dropped_files = ctx.input(|input| input.raw.dropped_files.clone());
+        for file in dropped_files {
+            if let Some(bytes) = file.bytes {
+                self.on_file_opened(RotationFile::from_bytes(&bytes));
+            } else {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(path) = file.path {
+                    self.open_file_path(&path);
+                }
+            }
+        }
+        if let Some(result) = self.file_update.take() {
+            self.on_file_opened(result);
+        }
+    }
+
+    fn on_file_opened(&mut self, result: Result<RotationFile, String>) {
+        match result {
+            Ok(file) => {
+                self.recipe_config = file.recipe_config;
+                self.selected_food = file.selected_food;
+                self.selected_potion = file.selected_potion;
+                self.crafter_config = file.crafter_config;
+                self.solver_config = file.solver_config;
+                self.actions = file.actions;
+                self.notes = file.notes;
+                self.step_selection = StepSelection::default();
+                self.file_warning = rotation_error_text(
+                    self.recipe_config.recipe,
+                    self.crafter_config.active_stats(),
+                    self.selected_food,
+                    self.selected_potion,
+                    &self.actions,
+                    self.locale,
+                );
+                self.rotation_file_window_open = !self.notes.is_empty();
+            }
+            Err(error) => self.file_error = Some(error),
+        }
+    }
+
+    fn current_rotation_file(&self) -> RotationFile {
+        RotationFile {
+            version: RotationFile::VERSION,
+            recipe_config: self.recipe_config,
+            selected_food: self.selected_food,
+            selected_potion: self.selected_potion,
+            crafter_config: self.crafter_config,
+            solver_config: self.solver_config,
+            actions: self.actions.clone(),
+            notes: self.notes.clone(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn on_open_file_clicked(&mut self, _ctx: &egui::Context) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Raphael rotation", &[FILE_EXTENSION])
+            .pick_file()
+        {
+            self.open_file_path(&path);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn on_open_file_clicked(&mut self, ctx: &egui::Context) {
+        let file_update = self.file_update.clone();
+        let ctx = ctx.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("Raphael rotation", &[FILE_EXTENSION])
+                .pick_file()
+                .await
+            {
+                file_update.set(Some(RotationFile::from_bytes(&file.read().await)));
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_rotation_file(&mut self) {
+        let file = self.current_rotation_file();
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Raphael rotation", &[FILE_EXTENSION])
+            .set_file_name(file.file_name())
+            .save_file()
+        else {
+            return;
+        };
+        if let Err(error) = std::fs::write(&path, file.to_ron()) {
+            log::error!("Failed to write {}: {}", path.display(), error);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn save_rotation_file(&mut self) {
+        let file = self.current_rotation_file();
+        if download_file(&file.file_name(), file.to_ron().as_bytes()).is_none() {
+            log::error!("Failed to download {}", file.file_name());
+        }
+    }
+
+    fn draw_rotation_file_widget(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Notes").strong());
+            ui.add(HelpText::new(format!(
+                "A .{} file contains the recipe, crafter stats, consumables, solver settings, macro and these notes.\nOpen a file via the \"Open\" button or by dropping it onto the window.",
+                FILE_EXTENSION
+            )));
+        });
+        ui.add(
+            egui::TextEdit::multiline(&mut self.notes)
+                .desired_rows(6)
+                .desired_width(f32::INFINITY),
+        );
+        ui.separator();
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(!self.actions.is_empty(), egui::Button::new("Save to file"))
+                .clicked()
+            {
+                self.save_rotation_file();
+            }
+        });
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.separator();
+            self.draw_gist_widget(ui);
+        }
+    }
+
+    /// Exports the rotation file and the macro to a GitHub gist, or loads a rotation file from
+    /// a gist, see [`crate::gist_share`].
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_gist_widget(&mut self, ui: &mut egui::Ui) {
+        let token_id = Id::new("gist_token");
+        let gist_url_id = Id::new("gist_url");
+        let export_result_id = Id::new("gist_export_result");
+        let import_result_id = Id::new("gist_import_result");
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("GitHub gist").strong());
+            ui.add(HelpText::new(format!(
+                "Shares the macro and the .{} file as a secret gist, e.g. to post the link on Discord. Exporting needs a GitHub token with the \"gist\" scope, which is only kept until Raphael is closed.",
+                FILE_EXTENSION
+            )));
+        });
+        let mut token = ui
+            .ctx()
+            .data(|data| data.get_temp::<String>(token_id))
+            .unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.label("Token");
+            if ui
+                .add(
+                    egui::TextEdit::singleline(&mut token)
+                        .password(true)
+                        .desired_width(200.0),
+                )
+                .changed()
+            {
+                ui.ctx()
+                    .data_mut(|data| data.insert_temp(token_id, token.clone()));
+            }
+            ui.hyperlink_to("Create token", crate::gist_share::TOKEN_URL);
+        });
+        let export_enabled = !self.actions.is_empty() && !token.trim().is_empty();
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(export_enabled, egui::Button::new("Export to gist"))
+                .clicked()
+            {
+                let file = self.current_rotation_file();
+                let variables = MacroTemplateVariables::new(
+                    self.recipe_config.recipe.item_id,
+                    self.recipe_config.recipe.job_id,
+                    self.locale,
+                );
+                let macro_text = self
+                    .macro_view_config
+                    .macro_texts(&self.actions, &variables, "\n", self.locale)
+                    .join("\n\n");
+                crate::gist_share::export_gist(
+                    ui.ctx().clone(),
+                    &token,
+                    &file,
+                    macro_text,
+                    export_result_id,
+                );
+            }
+        });
+        match ui
+            .ctx()
+            .data(|data| data.get_temp::<Result<String, String>>(export_result_id))
+        {
+            Some(Ok(url)) => {
+                ui.horizontal(|ui| {
+                    ui.hyperlink(&url);
+                    if ui.button("Copy").clicked() {
+                        copy_to_clipboard(ui.ctx(), url.clone());
+                    }
+                });
+            }
+            Some(Err(error)) => {
+                ui.label(
+                    egui::RichText::new(format!("Export failed: {error}"))
+                        .color(ui.visuals().error_fg_color),
+                );
+            }
+            None => (),
+        }
+
+        let mut gist_url = ui
+            .ctx()
+            .data(|data| data.get_temp::<String>(gist_url_id))
+            .unwrap_or_default();
+        ui.horizontal(|ui| {
+            if ui
+                .add(
+                    egui::TextEdit::singleline(&mut gist_url)
+                        .hint_text("Gist URL")
+                        .desired_width(280.0),
+                )
+                .changed()
+            {
+                ui.ctx()
+                    .data_mut(|data| data.insert_temp(gist_url_id, gist_url.clone()));
+            }
+            let gist_id = crate::gist_share::parse_gist_id(&gist_url);
+            if let Some(gist_id) = gist_id.filter(|_| ui.button("Load").clicked()) {
+                crate::gist_share::import_gist(ui.ctx().clone(), gist_id, import_result_id);
+            }
+        });
+        let result = ui.ctx().data_mut(|data| {
+            let result = data.get_temp::<Result<RotationFile, String>>(import_result_id);
+            data.remove::<Result<RotationFile, String>>(import_result_id);
+            result
+        });
+        if let Some(result) = result {
+            self.on_file_opened(
+                result.map_err(|error| format!("Failed to load the gist: {error}")),
+            );
+        }
+    }
+
+    fn current_share_code(&self) -> ShareCode {
+        ShareCode {
+            recipe: self.recipe_config.recipe,
+            crafter_stats: self.crafter_config.active_stats(),
+            food: self.selected_food,
+            potion: self.selected_potion,
+            initial_quality: self.initial_quality(),
+            actions: self.actions.clone(),
+        }
+    }
+
+    fn apply_share_code(&mut self, share_code: ShareCode) {
+        self.recipe_config = RecipeConfiguration {
+            recipe: share_code.recipe,
+            quality_source: QualitySource::Value(share_code.initial_quality),
+        };
+        self.crafter_config.selected_job = share_code.recipe.job_id;
+        *self.crafter_config.active_stats_mut() = share_code.crafter_stats;
+        self.selected_food = share_code.food;
+        self.selected_potion = share_code.potion;
+        self.actions = share_code.actions;
+        self.step_selection = StepSelection::default();
+    }
+
+    fn draw_raw_settings_widget(&mut self, ui: &mut egui::Ui) {
+        ui.label("Solves with exactly the given simulator settings, bypassing the selected recipe, crafter stats and consumables. Useful to reproduce the solver input from a bug report.");
+        ui.horizontal(|ui| {
+            if ui.button("Copy current settings").clicked() {
+                let settings = self.current_solve_request().simulator_settings();
+                copy_to_clipboard(ui.ctx(), settings_to_json(&settings));
+            }
+            ui.add(HelpText::new(
+                "Copies the settings the solver would use for the selected recipe as JSON.",
+            ));
+        });
+        ui.separator();
+        ui.label("Paste settings as JSON or as logged when starting the solver:");
+        ui.add(
+            egui::TextEdit::multiline(&mut self.raw_settings_input)
+                .code_editor()
+                .desired_rows(6)
+                .desired_width(f32::INFINITY),
+        );
+        if self.raw_settings_input.trim().is_empty() {
+            return;
+        }
+        match parse_settings(&self.raw_settings_input) {
+            Ok(settings) => {
+                ui.label(format!(
+                    "Progress {}, Quality {}, Durability {}, CP {}, Level {}",
+                    settings.max_progress,
+                    settings.max_quality,
+                    settings.max_durability,
+                    settings.max_cp,
+                    settings.job_level
+                ));
+                ui.label(format!(
+                    "{} Progress and {} Quality per 100% efficiency",
+                    settings.base_progress, settings.base_quality
+                ));
+                if !settings.rules_override.is_none() {
+                    let actions: Vec<String> = settings
+                        .rules_override
+                        .iter()
+                        .map(|action_override| format!("{:?}", action_override.action))
+                        .collect();
+                    ui.label(format!("Rules overridden for {}", actions.join(", ")));
+                }
+                if ui
+                    .add_enabled(!self.solver_pending, egui::Button::new("Solve"))
+                    .clicked()
+                {
+                    let ctx = ui.ctx().clone();
+                    self.start_solver_with_settings(
+                        &ctx,
+                        settings,
+                        (settings, 0, self.solver_config),
+                        Vec::new(),
+                    );
+                }
+                ui.label(
+                    egui::RichText::new(
+                        "The simulator keeps showing the selected recipe and crafter stats.",
+                    )
+                    .small(),
+                );
+            }
+            Err(error) => {
+                ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+        }
+    }
+
+    fn draw_share_widget(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.share_macro_text, false, "Share code");
+            ui.radio_value(&mut self.share_macro_text, true, "Macro text");
+            ui.add(HelpText::new(
+                "Share code: recipe, crafter stats, consumables and macro. Can be imported below on another device.\nMacro text: the action names, e.g. to read the macro on a phone while entering it on a console.",
+            ));
+        });
+        let text = match self.share_macro_text {
+            true => self
+                .actions
+                .iter()
+                .enumerate()
+                .map(|(index, action)| {
+                    format!("{}. {}", index + 1, action_name(*action, self.locale))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            false => self.current_share_code().encode(),
+        };
+        if self.actions.is_empty() {
+            ui.label("No rotation to share");
+        } else {
+            ui.horizontal(|ui| {
+                if ui.button("Copy").clicked() {
+                    copy_to_clipboard(ui.ctx(), text.clone());
+                }
+                ui.label(format!("{} characters", text.len()));
+                if ui
+                    .button("Copy link")
+                    .on_hover_text(
+                        "Link that opens the rotation and solver configuration in the web app",
+                    )
+                    .clicked()
+                {
+                    let share_link = ShareLink {
+                        share_code: self.current_share_code(),
+                        solver_config: Some(self.solver_config),
+                    };
+                    copy_to_clipboard(ui.ctx(), share_link.to_url());
+                }
+            });
+            ui.vertical_centered(|ui| {
+                ui.add(QrCode::new(&text));
+            });
+        }
+
+        ui.separator();
+        ui.label(egui::RichText::new("Import share code").strong());
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.share_code_input)
+                    .hint_text("RPH1...")
+                    .desired_width(300.0),
+            );
+            if ui.button("Load").clicked() {
+                match ShareCode::decode(&self.share_code_input) {
+                    Ok(share_code) => {
+                        self.apply_share_code(share_code);
+                        self.share_code_input.clear();
+                        self.share_code_error = None;
+                    }
+                    Err(error) => self.share_code_error = Some(error),
+                }
+            }
+        });
+        if let Some(error) = self.share_code_error.as_ref() {
+            ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Import macro text").strong());
+            ui.add(HelpText::new(
+                "Loads a macro copied from the game into the simulator, e.g. to analyze an existing macro. Macros in any game language are recognized and several macros can be pasted at once.\nThe selected recipe and crafter stats are kept.",
+            ));
+        });
+        ui.add(
+            egui::TextEdit::multiline(&mut self.macro_text_input)
+                .hint_text("/ac \"Muscle Memory\" <wait.3>")
+                .code_editor()
+                .desired_rows(4)
+                .desired_width(f32::INFINITY),
+        );
+        if ui.button("Load").clicked() {
+            match parse_macro_text(&self.macro_text_input) {
+                Ok(actions) => {
+                    self.actions = actions;
+                    self.step_selection = StepSelection::default();
+                    self.macro_text_input.clear();
+                    self.macro_text_error = None;
+                }
+                Err(error) => self.macro_text_error = Some(error),
+            }
+        }
+        if let Some(error) = self.macro_text_error.as_ref() {
+            ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+        }
+    }
+
+    fn draw_macro_export_widget(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Name template");
+            ui.add(HelpText::new(MacroExportConfig::TEMPLATE_HELP));
+            ui.add(egui::TextEdit::singleline(
+                &mut self.macro_export_config.name_template,
+            ));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Format");
+            ui.radio_value(
+                &mut self.macro_export_config.format,
+                MacroExportFormat::SingleFile,
+                "Single text file",
+            );
+            ui.radio_value(
+                &mut self.macro_export_config.format,
+                MacroExportFormat::Zip,
+                "Zip (one file per macro)",
+            );
+        });
+        ui.separator();
+        ui.label(egui::RichText::new("Preview").strong());
+        let rotations = self.saved_rotations_data.pinned();
+        for (index, rotation) in rotations.iter().enumerate().take(5) {
+            ui.label(
+                self.macro_export_config
+                    .rotation_name(index + 1, rotation, self.locale),
+            );
+        }
+        if rotations.len() > 5 {
+            ui.label(format!("... and {} more", rotations.len() - 5));
+        }
+        ui.separator();
+        ui.label("Macro formatting follows the settings of the macro view.");
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(!rotations.is_empty(), egui::Button::new("Export"))
+                .clicked()
+            {
+                self.export_macros(ui.ctx());
+            }
+        });
+    }
+
+    fn export_macros(&self, ctx: &egui::Context) {
+        let file_name = self.macro_export_config.file_name();
+        let contents = match self.macro_export_config.export(
+            self.saved_rotations_data.pinned(),
+            &self.macro_view_config,
+            macro_newline(ctx),
+            self.locale,
+        ) {
+            Ok(contents) => contents,
+            Err(error) => {
+                log::error!("Failed to export macros: {}", error);
+                return;
+            }
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let Some(path) = rfd::FileDialog::new().set_file_name(file_name).save_file() else {
+                return;
+            };
+            if let Err(error) = std::fs::write(&path, contents) {
+                log::error!("Failed to write {}: {}", path.display(), error);
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        if download_file(file_name, &contents).is_none() {
+            log::error!("Failed to download {}", file_name);
+        }
+    }
+
+    fn export_debug_bundle(&self) {
+        let solve_request = self.current_solve_request();
+        let bundle = DebugBundle::new(
+            self.last_solve_params.map_or_else(
+                || solve_request.simulator_settings(),
+                |(settings, _, _)| settings,
+            ),
+            solve_request,
+            self.solver_config,
+            self.actions.clone(),
+            self.solver_error.clone(),
+        );
+        let contents = bundle.to_json();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let Some(path) = rfd::FileDialog::new()
+                .add_filter("JSON", &["json"])
+                .set_file_name(debug_bundle::FILE_NAME)
+                .save_file()
+            else {
+                return;
+            };
+            if let Err(error) = std::fs::write(&path, contents) {
+                log::error!("Failed to write {}: {}", path.display(), error);
+            }
         }
         #[cfg(target_arch = "wasm32")]
-        if let Some(event) = self.solution_update.take() {
-            self.on_solver_event(event);
+        if download_file(debug_bundle::FILE_NAME, contents.as_bytes()).is_none() {
+            log::error!("Failed to download {}", debug_bundle::FILE_NAME);
         }
     }
 
     fn draw_simulator_and_analysis_widgets(&mut self, ui: &mut egui::Ui) {
-        let game_settings = raphael_data::get_game_settings(
-            self.recipe_config.recipe,
-            *self.crafter_config.active_stats(),
-            self.selected_food,
-            self.selected_potion,
-            self.solver_config.adversarial,
-        );
+        let game_settings = Settings {
+            success_model: self.solver_config.success_model,
+            ..raphael_data::get_game_settings(
+                self.recipe_config.recipe,
+                self.crafter_config.active_stats(),
+                self.selected_food,
+                self.selected_potion,
+                self.solver_config.adversarial,
+            )
+        };
         let initial_quality = match self.recipe_config.quality_source {
             QualitySource::HqMaterialList(hq_materials) => {
                 raphael_data::get_initial_quality(self.recipe_config.recipe, hq_materials)
             }
             QualitySource::Value(quality) => quality,
         };
-        let item = raphael_data::ITEMS
-            .get(&self.recipe_config.recipe.item_id)
-            .unwrap();
         ui.add(Simulator::new(
             &game_settings,
             initial_quality,
             self.solver_config,
             &self.crafter_config,
-            &self.actions,
-            item,
+            &mut self.actions,
+            &mut self.step_selection,
+            self.recipe_config.recipe.item_id,
             self.locale,
         ));
-        // let target_quality = self
-        //     .solver_config
-        //     .quality_target
-        //     .get_target(game_settings.max_quality);
-        // ui.add(SolutionAnalysis::new(
-        //     game_settings,
-        //     initial_quality,
-        //     target_quality,
-        //     &self.actions,
-        //     self.recipe_config.recipe.is_expert,
-        // ));
+        if std::mem::take(&mut self.step_selection.resolve_requested) && !self.solver_pending {
+            let prefix_len = self.step_selection.step.map_or(0, |step| step + 1);
+            let prefix = self.actions[..prefix_len].to_vec();
+            self.start_solver(ui.ctx(), prefix);
+        }
+        if std::mem::take(&mut self.step_selection.repair_requested) && !self.solver_pending {
+            let request = self.current_solve_request();
+            let prefix_len = raphael_solver::repairable_prefix(
+                request.solver_settings(),
+                request.initial_state(),
+                &self.actions,
+            );
+            let prefix = self.actions[..prefix_len].to_vec();
+            self.start_solver(ui.ctx(), prefix);
+        }
+        self.draw_pareto_front(ui);
+        self.draw_similar_rotations(ui);
+        self.analysis_plugins.ui(ui, self.locale);
+        let target_quality = self
+            .solver_config
+            .quality_target
+            .get_target(game_settings.max_quality);
+        ui.add(SolutionAnalysis::new(
+            &mut self.solution_analysis_data,
+            game_settings,
+            initial_quality,
+            target_quality,
+            &self.actions,
+            self.recipe_config.recipe.is_expert,
+        ));
+    }
+
+    fn draw_pareto_front(&mut self, ui: &mut egui::Ui) {
+        if self.pareto_front.is_empty() {
+            return;
+        }
+        let Some((settings, initial_quality, _)) = self.last_solve_params else {
+            return;
+        };
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Steps vs. Quality").strong());
+                ui.add(HelpText::new(
+                    "The best macro for each number of steps. No other macro reaches the same Quality in fewer steps. Click a macro to show it in the simulator.",
+                ));
+            });
+            ui.separator();
+            for solution in &self.pareto_front {
+                let quality = initial_quality + solution.quality;
+                let text = format!(
+                    "{} steps: {} Quality ({}%)",
+                    solution.actions.len(),
+                    quality,
+                    u32::from(quality) * 100 / u32::from(settings.max_quality.max(1))
+                );
+                if ui
+                    .selectable_label(self.actions == solution.actions, text)
+                    .clicked()
+                {
+                    self.actions.clone_from(&solution.actions);
+                    self.step_selection = StepSelection::default();
+                }
+            }
+        });
+    }
+
+    fn draw_similar_rotations(&mut self, ui: &mut egui::Ui) {
+        if self.similar_rotations.is_empty() {
+            return;
+        }
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Similar saved macros").strong());
+                ui.add(HelpText::new(
+                    "Saved macros that only differ from the solved macro in a few actions. You might already have a macro for this craft in-game.",
+                ));
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui.button("Show saved macros").clicked() {
+                        self.saved_rotations_window_open = true;
+                    }
+                });
+            });
+            ui.separator();
+            for rotation in self.similar_rotations.iter().take(3) {
+                let difference = match rotation.distance {
+                    0 => "identical".to_string(),
+                    1 => "1 action differs".to_string(),
+                    distance => format!("{distance} actions differ"),
+                };
+                ui.label(format!("{} ({difference})", rotation.name));
+            }
+        });
     }
 
     fn draw_list_select_widgets(&mut self, ui: &mut egui::Ui) {
         ui.vertical(|ui| {
-            ui.add(RecipeSelect::new(
-                &mut self.crafter_config,
-                &mut self.recipe_config,
-                self.selected_food,
-                self.selected_potion,
-                self.locale,
-            ));
+            if ui
+                .add(RecipeSelect::new(
+                    &mut self.crafter_config,
+                    &mut self.recipe_config,
+                    &mut self.recipe_book,
+                    self.selected_food,
+                    self.selected_potion,
+                    self.locale,
+                ))
+                .changed()
+            {
+                self.load_recipe_default();
+            }
             ui.add(FoodSelect::new(
-                self.crafter_config.crafter_stats[self.crafter_config.selected_job as usize],
+                self.crafter_config.active_stats(),
                 &mut self.selected_food,
+                &self.custom_consumables.meals,
                 self.locale,
             ));
             ui.add(PotionSelect::new(
-                self.crafter_config.crafter_stats[self.crafter_config.selected_job as usize],
+                self.crafter_config.active_stats(),
                 &mut self.selected_potion,
+                &self.custom_consumables.potions,
                 self.locale,
             ));
         });
@@ -537,6 +2537,14 @@ impl MacroSolverApp {
                     if ui.button("📑").clicked() {
                         self.saved_rotations_window_open = true;
                     }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui
+                        .add_enabled(!self.solver_pending, egui::Button::new("⏯"))
+                        .on_hover_text("Continue a solve from a saved progress file")
+                        .clicked()
+                    {
+                        self.on_resume_solve_clicked(ui.ctx());
+                    }
                     ui.add_space(-5.0);
                     ui.vertical_centered_justified(|ui| {
                         let text_color = ui.ctx().style().visuals.selection.stroke.color;
@@ -551,6 +2559,9 @@ impl MacroSolverApp {
                 ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
                     ui.label(format!("Elapsed time: {:.2}s", self.duration.as_secs_f32()));
                 });
+                if let Some(stats) = self.solver_stats.filter(|_| !self.solver_pending) {
+                    draw_performance_details(ui, &stats);
+                }
                 // fill the remaining space
                 ui.with_layout(Layout::bottom_up(Align::LEFT), |_| {});
             });
@@ -570,11 +2581,14 @@ impl MacroSolverApp {
                     .selected_text(get_job_name(self.crafter_config.selected_job, self.locale))
                     .show_ui(ui, |ui| {
                         for i in 0..8 {
-                            ui.selectable_value(
-                                &mut self.crafter_config.selected_job,
-                                i,
-                                get_job_name(i, self.locale),
-                            );
+                            let selected = self.crafter_config.selected_job == i;
+                            if ui
+                                .selectable_label(selected, get_job_name(i, self.locale))
+                                .clicked()
+                                && !selected
+                            {
+                                self.select_job(i);
+                            }
                         }
                     });
             });
@@ -628,10 +2642,24 @@ impl MacroSolverApp {
         ui.horizontal(|ui| {
             ui.label("Job level");
             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                ui.add(
-                    egui::DragValue::new(&mut self.crafter_config.active_stats_mut().level)
-                        .range(1..=100),
-                );
+                match &mut self.crafter_config.planning_level {
+                    Some(planning_level) => {
+                        ui.add(egui::DragValue::new(planning_level).range(1..=100));
+                        ui.label(
+                            egui::RichText::new("Planning")
+                                .color(ui.visuals().warn_fg_color),
+                        )
+                        .on_hover_text("All jobs are treated as this level. Turn off \"Plan for level\" in the recipe list to use the level of each job again.");
+                    }
+                    None => {
+                        ui.add(
+                            egui::DragValue::new(
+                                &mut self.crafter_config.active_stats_mut().level,
+                            )
+                            .range(1..=100),
+                        );
+                    }
+                }
             });
         });
         ui.separator();
@@ -643,7 +2671,7 @@ impl MacroSolverApp {
             &mut self.recipe_config.quality_source
         {
             for (index, ingredient) in recipe_ingredients.into_iter().enumerate() {
-                if let Some(item) = raphael_data::ITEMS.get(&ingredient.item_id) {
+                if let Some(item) = raphael_data::game_data().item(ingredient.item_id) {
                     if item.can_be_hq {
                         has_hq_ingredient = true;
                         ui.horizontal(|ui| {
@@ -710,6 +2738,21 @@ impl MacroSolverApp {
                 ),
             );
         }
+        let excluded_actions = excluded_actions_for(
+            self.recipe_config.recipe,
+            self.crafter_config.active_stats(),
+        );
+        for excluded in excluded_actions {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} unavailable: {}",
+                    action_name(excluded.action, self.locale),
+                    excluded.reason.description()
+                ))
+                .small()
+                .weak(),
+            );
+        }
         ui.separator();
 
         ui.label(egui::RichText::new("Solver settings").strong());
@@ -719,7 +2762,7 @@ impl MacroSolverApp {
                 ui.style_mut().spacing.item_spacing = [4.0, 4.0].into();
                 let game_settings = raphael_data::get_game_settings(
                     self.recipe_config.recipe,
-                    self.crafter_config.crafter_stats[self.crafter_config.selected_job as usize],
+                    self.crafter_config.active_stats(),
                     self.selected_food,
                     self.selected_potion,
                     self.solver_config.adversarial,
@@ -773,6 +2816,21 @@ impl MacroSolverApp {
             });
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Effort:");
+            let mut effort_index = SolverEffort::ALL
+                .iter()
+                .position(|effort| *effort == self.solver_config.effort)
+                .unwrap_or_default();
+            ui.add(
+                egui::Slider::new(&mut effort_index, 0..=SolverEffort::ALL.len() - 1)
+                    .show_value(false),
+            );
+            self.solver_config.effort = SolverEffort::ALL[effort_index];
+            ui.label(self.solver_config.effort.name());
+            ui.add(HelpText::new("How thoroughly the solver searches for the best rotation.\n  - Fast: shortest solve time, but the rotation may not reach the best possible quality.\n  - Balanced: finds the best rotation, and is fast for most recipes.\n  - Exhaustive: always does the full search, which takes longer but may find better rotations when maximizing expected quality."));
+        });
+
         ui.horizontal(|ui| {
             ui.checkbox(
                 &mut self.solver_config.backload_progress,
@@ -781,6 +2839,28 @@ impl MacroSolverApp {
             ui.add(HelpText::new("Find a rotation that only uses Progress-increasing actions at the end of the rotation.\n  - May decrease achievable Quality.\n  - May increase macro duration."));
         });
 
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.solver_config.forbid_quality_during_muscle_memory,
+                "Progress opener",
+            );
+            ui.add(HelpText::new("Don't use Quality actions while Muscle Memory is active, so that the rotation opens like most community rotations and is easier to memorize.\n  - May decrease achievable Quality."));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("First Quality step");
+            ui.add(egui::DragValue::new(&mut self.solver_config.first_quality_step).range(0..=20));
+            ui.add(HelpText::new("Don't use Quality actions in the steps before this one, e.g. 2 to open with two Progress actions. 0 allows Quality actions from the first step.\n  - May decrease achievable Quality."));
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.solver_config.exclude_byregot,
+                "Manual Byregot's Blessing",
+            );
+            ui.add(HelpText::new("Solve the rotation without Byregot's Blessing and keep its CP and durability unspent, so that it can be used by hand when the condition turns Good or Excellent. The simulator shows the step where it gives the most Quality.\n  - The rest of the rotation can be run as a macro."));
+        });
+
         if self.recipe_config.recipe.is_expert {
             self.solver_config.adversarial = false;
         }
@@ -794,6 +2874,19 @@ impl MacroSolverApp {
             );
             ui.add(HelpText::new("Find a rotation that can reach the target quality no matter how unlucky the random conditions are.\n  - May decrease achievable Quality.\n  - May increase macro duration.\n  - Much longer solve time.\n
             The solver never tries to use Tricks of the Trade to \"eat\" Excellent quality procs, so in some cases this option does not produce the optimal macro."));
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .add_enabled(
+                    !self.recipe_config.recipe.is_expert,
+                    egui::Button::new("Compare"),
+                )
+                .on_hover_text("Solve with and without 100% reliability and compare both macros")
+                .clicked()
+            {
+                let params = self.craft_params();
+                self.reliability_report_window_open = true;
+                self.reliability_report_data.start(ui.ctx().clone(), params);
+            }
         });
         if self.solver_config.adversarial {
             ui.label(
@@ -816,58 +2909,444 @@ impl MacroSolverApp {
                     .color(ui.visuals().warn_fg_color),
             );
         }
+        self.draw_memory_estimate(ui);
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.solver_config.minimize_cp, "Minimize CP usage");
+            ui.add(HelpText::new(
+                "Among the rotations that reach the target quality, find the one that spends the least CP. The CP that is left over can be used to recover from mistakes.\n  - Longer solve time.",
+            ));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Human-friendly bias:");
+            ui.add(
+                egui::DragValue::new(&mut self.solver_config.human_friendly_bias).range(0..=50),
+            );
+            ui.add(HelpText::new(
+                "Among rotations with the same quality and steps, prefer rotations that repeat the same action back to back and start with a common opener, which are easier to execute manually or remember.\n  - The value is how many tenths of a second of macro duration avoiding one switch between actions is worth.\n  - 0 disables the bias.",
+            ));
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.solver_config.expected_quality,
+                "Maximize expected quality",
+            );
+            ui.add(HelpText::new(
+                "Find the rotation with the highest average quality when the condition changes randomly (Good and Excellent procs), instead of assuming Normal condition on every step.\n  - Ignores 100% reliability and minimize options.\n  - The rotation isn't guaranteed to be optimal.",
+            ));
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.solver_config.pareto_front, "Steps vs. Quality trade-offs");
+            ui.add(HelpText::new(
+                "Find the best macro for each number of steps, e.g. to choose between 17 steps for 96% Quality and 21 steps for 100% Quality.\n  - Ignores the minimize options.\n  - Much longer solve time.",
+            ));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Unreliable actions:");
+            egui::ComboBox::from_id_salt("SUCCESS_MODEL")
+                .selected_text(success_model_name(self.solver_config.success_model))
+                .show_ui(ui, |ui| {
+                    for success_model in [
+                        SuccessModel::AssumeFail,
+                        SuccessModel::AssumeSuccess,
+                        SuccessModel::ExpectedValue,
+                    ] {
+                        ui.selectable_value(
+                            &mut self.solver_config.success_model,
+                            success_model,
+                            success_model_name(success_model),
+                        );
+                    }
+                });
+            ui.add(HelpText::new(
+                "How actions with a success rate below 100% (Rapid Synthesis, Hasty Touch, Daring Touch) are treated.\n  - Never use: the actions are never used.\n  - Assume success: the actions always succeed. The macro may fail in-game.\n  - Expected value: the actions increase Progress and Quality by their average increase.",
+            ));
+        });
+
+        self.draw_initial_effects(ui);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.draw_thread_pool_settings(ui);
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.remote_solve_config.enabled, "Solve remotely");
+            ui.add_enabled(
+                self.remote_solve_config.enabled,
+                egui::TextEdit::singleline(&mut self.remote_solve_config.url)
+                    .hint_text("ws://host:port/solve"),
+            );
+            ui.add(HelpText::new(
+                "Send solves to a raphael-server instance, e.g. on a desktop, instead of solving on this device.",
+            ));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("When done:");
+            ui.checkbox(&mut self.solve_notification_config.sound, "Sound");
+            if ui
+                .checkbox(
+                    &mut self.solve_notification_config.notification,
+                    "Notification",
+                )
+                .changed()
+                && self.solve_notification_config.notification
+            {
+                request_notification_permission();
+            }
+            ui.add(HelpText::new(
+                "Notify when a solve finishes or fails, e.g. while the app is in the background.",
+            ));
+        });
+    }
+
+    fn draw_initial_effects(&mut self, ui: &mut egui::Ui) {
+        let initial_effects = &mut self.solver_config.initial_effects;
+        let active = *initial_effects != InitialEffects::default();
+        let header = match active {
+            true => {
+                egui::RichText::new("Initial effects (active)").color(ui.visuals().warn_fg_color)
+            }
+            false => egui::RichText::new("Initial effects"),
+        };
+        egui::CollapsingHeader::new(header)
+            .id_salt("INITIAL_EFFECTS")
+            .show(ui, |ui| {
+                ui.label("Effects that are already active when the craft starts, e.g. for theorycrafting the rest of a craft. Only the solver and the simulator take these into account.");
+                egui::Grid::new("initial_effects_grid").show(ui, |ui| {
+                    let stacks = |ui: &mut egui::Ui, label: &str, value: &mut u8, max: u8| {
+                        ui.label(label);
+                        ui.add(egui::DragValue::new(value).range(0..=max));
+                        ui.end_row();
+                    };
+                    stacks(ui, "Inner Quiet", &mut initial_effects.inner_quiet, 10);
+                    stacks(ui, "Waste Not", &mut initial_effects.waste_not, 8);
+                    stacks(ui, "Innovation", &mut initial_effects.innovation, 4);
+                    stacks(ui, "Veneration", &mut initial_effects.veneration, 4);
+                    stacks(ui, "Great Strides", &mut initial_effects.great_strides, 3);
+                    stacks(ui, "Muscle Memory", &mut initial_effects.muscle_memory, 5);
+                    stacks(ui, "Manipulation", &mut initial_effects.manipulation, 8);
+                });
+                ui.checkbox(&mut initial_effects.heart_and_soul, "Heart and Soul active");
+                ui.checkbox(
+                    &mut initial_effects.trained_perfection,
+                    "Trained Perfection active",
+                );
+                if ui.add_enabled(active, egui::Button::new("Reset")).clicked() {
+                    *initial_effects = InitialEffects::default();
+                }
+            });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_thread_pool_settings(&mut self, ui: &mut egui::Ui) {
+        let max_threads = std::thread::available_parallelism().map_or(1, usize::from);
+        ui.horizontal(|ui| {
+            ui.label("Solver threads:");
+            ui.add(
+                egui::DragValue::new(&mut self.thread_pool_settings.num_threads)
+                    .range(0..=max_threads)
+                    .custom_formatter(|value, _| match value as usize {
+                        0 => "Auto".to_string(),
+                        value => value.to_string(),
+                    }),
+            );
+            ui.add(HelpText::new(
+                "Number of threads used by the solver.\n  - Auto uses all available cores.\n  - Fewer threads leave more room for other applications but increase solve time.",
+            ));
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.thread_pool_settings.background_priority,
+                "Background priority",
+            );
+            ui.add(HelpText::new(
+                "Run the solver below normal priority so that other applications (e.g. the game) stay responsive during long solves.",
+            ));
+        });
     }
 
     fn on_solve_button_clicked(&mut self, ctx: &egui::Context) {
-        self.actions = Vec::new();
+        self.start_solver(ctx, Vec::new());
+    }
+
+    /// Starts the solver for the actions after `prefix`, keeping the prefix as the start of the rotation.
+    fn start_solver(&mut self, ctx: &egui::Context, prefix: Vec<Action>) {
+        let initial_quality = self.initial_quality();
+        let request = self.current_solve_request();
+        self.start_solver_with_settings(
+            ctx,
+            request.simulator_settings(),
+            (request.game_settings(), initial_quality, self.solver_config),
+            prefix,
+        );
+    }
+
+    fn current_solve_request(&self) -> SolveRequest {
+        self.solver_config.solve_request(
+            self.recipe_config.recipe,
+            self.crafter_config.active_stats(),
+            self.selected_food,
+            self.selected_potion,
+            self.initial_quality(),
+        )
+    }
+
+    /// Starts the solver with the given simulator settings, without deriving them from the
+    /// selected recipe and crafter stats.
+    fn start_solver_with_settings(
+        &mut self,
+        ctx: &egui::Context,
+        solver_settings: Settings,
+        last_solve_params: SolveParams,
+        prefix: Vec<Action>,
+    ) {
+        let worker_config = self.prepare_solve(ctx, &solver_settings, last_solve_params, &prefix);
+        match self.remote_solve_config.enabled {
+            true => match RemoteBridge::connect(ctx.clone(), &self.remote_solve_config.url) {
+                Ok(mut remote_bridge) => {
+                    // the memory limit of this device doesn't apply to the server
+                    let worker_config = WorkerConfig {
+                        memory_limit: None,
+                        ..worker_config
+                    };
+                    remote_bridge.send(&SolverInput::Start(
+                        solver_settings,
+                        self.solver_config,
+                        worker_config,
+                        prefix,
+                    ));
+                    self.remote_bridge = Some(remote_bridge);
+                }
+                Err(error) => self.on_solver_event(
+                    ctx,
+                    SolverEvent::Error(SolverException::InternalError(format!(
+                        "Failed to connect to the remote server: {error}"
+                    ))),
+                ),
+            },
+            false => self.bridge.send(SolverInput::Start(
+                solver_settings,
+                self.solver_config,
+                worker_config,
+                prefix,
+            )),
+        }
+        log::debug!("{solver_settings:?}");
+    }
+
+    /// Resets the solve state of the app for a new solve and returns the config of the worker.
+    fn prepare_solve(
+        &mut self,
+        ctx: &egui::Context,
+        solver_settings: &Settings,
+        last_solve_params: SolveParams,
+        prefix: &[Action],
+    ) -> WorkerConfig {
+        self.actions = prefix.to_vec();
+        self.step_selection = StepSelection {
+            executed: prefix.len(),
+            ..StepSelection::default()
+        };
         self.solver_pending = true;
         self.solver_interrupt_pending = false;
+        self.solver_save_pending = false;
         self.solver_progress = 0;
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.last_worker_event = web_time::Instant::now();
+        }
+        self.solver_stats = None;
+        self.progress_only_reason = self.progress_only_reason(solver_settings, prefix);
+        self.similar_rotations.clear();
+        self.pareto_front.clear();
         self.start_time = web_time::Instant::now();
-        let mut game_settings = raphael_data::get_game_settings(
-            self.recipe_config.recipe,
-            self.crafter_config.crafter_stats[self.crafter_config.selected_job as usize],
-            self.selected_food,
-            self.selected_potion,
-            self.solver_config.adversarial,
+
+        self.last_solve_params = Some(last_solve_params);
+        ctx.data_mut(|data| {
+            data.insert_temp(Id::new("LAST_SOLVE_PARAMS"), last_solve_params);
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.bridge.thread_pool_settings = self.thread_pool_settings;
+        }
+        self.remote_bridge = None;
+        match low_spec_mode(ctx) {
+            true => WorkerConfig {
+                max_progress_events_per_second: 1,
+                ..WorkerConfig::default()
+            },
+            false => WorkerConfig::default(),
+        }
+    }
+
+    /// Stops the local solve and saves its progress to a file picked by the user.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn on_save_progress_clicked(&mut self) {
+        let file = self.current_rotation_file();
+        let file_name = format!(
+            "{}.{}",
+            file.file_name()
+                .trim_end_matches(&format!(".{FILE_EXTENSION}")),
+            solve_progress::FILE_EXTENSION
         );
-        let target_quality = self
-            .solver_config
-            .quality_target
-            .get_target(game_settings.max_quality);
-        let initial_quality = match self.recipe_config.quality_source {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Raphael solve progress", &[solve_progress::FILE_EXTENSION])
+            .set_file_name(file_name)
+            .save_file()
+        else {
+            return;
+        };
+        self.bridge
+            .send(SolverInput::SaveProgress(path, file.to_ron()));
+        self.solver_interrupt_pending = true;
+        self.solver_save_pending = true;
+    }
+
+    /// Restores the rotation of a progress file picked by the user and continues its solve.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn on_resume_solve_clicked(&mut self, ctx: &egui::Context) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Raphael solve progress", &[solve_progress::FILE_EXTENSION])
+            .pick_file()
+        else {
+            return;
+        };
+        let result = solve_progress::read_header(&path).and_then(|header| {
+            let file = RotationFile::from_bytes(header.rotation.as_bytes())?;
+            Ok((header, file))
+        });
+        let (header, file) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                self.file_error = Some(error);
+                return;
+            }
+        };
+        self.on_file_opened(Ok(file));
+        self.solver_config = header.config;
+        let request = self.current_solve_request();
+        let last_solve_params = (
+            request.game_settings(),
+            self.initial_quality(),
+            self.solver_config,
+        );
+        let worker_config =
+            self.prepare_solve(ctx, &header.settings, last_solve_params, &header.prefix);
+        // the progress is only saved by local solves, so it is always resumed locally
+        self.bridge.send(SolverInput::Resume(path, worker_config));
+    }
+
+    /// Mirrors the check of the macro solver, so that the solving dialog can tell that the
+    /// solve skips the Quality search.
+    fn progress_only_reason(
+        &self,
+        settings: &Settings,
+        prefix: &[Action],
+    ) -> Option<ProgressOnlyReason> {
+        if self.solver_config.expected_quality || self.solver_config.minimize_cp {
+            return None;
+        }
+        let initial_state = prefix
+            .iter()
+            .try_fold(
+                self.solver_config.initial_effects.initial_state(settings),
+                |state, action| state.use_action(*action, Condition::Normal, settings),
+            )
+            .ok()?;
+        progress_only_reason(settings, &initial_state)
+    }
+
+    fn initial_quality(&self) -> u16 {
+        match self.recipe_config.quality_source {
             QualitySource::HqMaterialList(hq_materials) => {
                 get_initial_quality(self.recipe_config.recipe, hq_materials)
             }
             QualitySource::Value(quality) => quality,
-        };
-
-        ctx.data_mut(|data| {
-            data.insert_temp(
-                Id::new("LAST_SOLVE_PARAMS"),
-                (game_settings, initial_quality, self.solver_config),
-            );
-        });
+        }
+    }
 
-        game_settings.max_quality = target_quality.saturating_sub(initial_quality);
-        self.bridge
-            .send(SolverInput::Start(game_settings, self.solver_config));
-        log::debug!("{game_settings:?}");
+    #[cfg(not(target_arch = "wasm32"))]
+    fn craft_params(&self) -> CraftParams {
+        CraftParams {
+            recipe: self.recipe_config.recipe,
+            crafter_stats: self.crafter_config.active_stats(),
+            food: self.selected_food,
+            potion: self.selected_potion,
+            solver_config: self.solver_config,
+            initial_quality: self.initial_quality(),
+            thread_pool_settings: self.thread_pool_settings,
+        }
     }
 
     fn draw_macro_output_widget(&mut self, ui: &mut egui::Ui) {
+        let variables = MacroTemplateVariables::new(
+            self.recipe_config.recipe.item_id,
+            self.recipe_config.recipe.job_id,
+            self.locale,
+        );
+        let craft = PluginCraft {
+            item_id: self.recipe_config.recipe.item_id,
+            food: self.selected_food,
+            potion: self.selected_potion,
+        };
         ui.add(MacroView::new(
             &mut self.actions,
             &mut self.macro_view_config,
+            variables,
+            craft,
             self.locale,
         ));
     }
 
+    /// Warns if the estimated memory usage of an adversarial or minimize-steps solve exceeds the
+    /// available memory, and offers to switch to the Fast effort, whose pruned search needs less
+    /// memory. Other solves stay well below the available memory.
+    fn draw_memory_estimate(&mut self, ui: &mut egui::Ui) {
+        if !self.solver_config.adversarial && !self.solver_config.minimize_steps {
+            return;
+        }
+        let Some(available_memory) = available_memory() else {
+            return;
+        };
+        let estimate = raphael_solver::estimate_peak_memory(
+            &self.current_solve_request().solver_settings(),
+            self.solver_config.minimize_steps,
+        );
+        if estimate <= available_memory {
+            return;
+        }
+        const GIGABYTE: f32 = (1 << 30) as f32;
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(format!(
+                    "⚠ Estimated memory usage: {:.1} GB ({:.1} GB available)",
+                    estimate as f32 / GIGABYTE,
+                    available_memory as f32 / GIGABYTE,
+                ))
+                .small()
+                .color(ui.visuals().warn_fg_color),
+            );
+            if self.solver_config.effort != SolverEffort::Fast
+                && ui
+                    .small_button("Use Fast effort")
+                    .on_hover_text("Prune the search more aggressively to use less memory. The rotation may not reach the best possible quality.")
+                    .clicked()
+            {
+                self.solver_config.effort = SolverEffort::Fast;
+            }
+        });
+    }
+
     fn experimental_warning_text() -> &'static str {
         #[cfg(not(target_arch = "wasm32"))]
         return "⚠ EXPERIMENTAL FEATURE\n This option may use a lot of memory (sometimes well above 4GB) which may cause your system to run out of memory.";
         #[cfg(target_arch = "wasm32")]
-        return "⚠ EXPERIMENTAL FEATURE\nMay crash the solver due to reaching the 4GB memory limit of 32-bit web assembly, causing the UI to get stuck in the \"solving\" state indefinitely.";
+        return "⚠ EXPERIMENTAL FEATURE\nMay exceed the 4GB memory limit of 32-bit web assembly, in which case the solve is aborted.";
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -882,6 +3361,77 @@ impl MacroSolverApp {
     }
 }
 
+fn success_model_name(success_model: SuccessModel) -> &'static str {
+    match success_model {
+        SuccessModel::AssumeFail => "Never use",
+        SuccessModel::AssumeSuccess => "Assume success",
+        SuccessModel::ExpectedValue => "Expected value",
+    }
+}
+
+/// Counters of the search that found the result, e.g. to compare the solver's speed across
+/// platforms.
+fn draw_performance_details(ui: &mut egui::Ui, stats: &SolverStats) {
+    const MEGABYTE: f32 = (1 << 20) as f32;
+    let hit_rate = |cache: CacheStats| {
+        cache.hit_rate().map_or_else(
+            || "-".to_owned(),
+            |rate| {
+                format!(
+                    "{:.1}% of {} lookups",
+                    rate * 100.0,
+                    cache.hits + cache.misses
+                )
+            },
+        )
+    };
+    egui::CollapsingHeader::new("Performance details")
+        .id_salt("PERFORMANCE_DETAILS")
+        .show(ui, |ui| {
+            egui::Grid::new("performance_details_grid").show(ui, |ui| {
+                ui.label("Searched nodes");
+                ui.label(stats.nodes.to_string());
+                ui.end_row();
+                ui.label("Nodes per second");
+                ui.label(stats.nodes_per_second().map_or_else(
+                    || "-".to_owned(),
+                    |nodes_per_second| format!("{nodes_per_second:.0}"),
+                ));
+                ui.end_row();
+                ui.label("Peak memory");
+                ui.label(format!("{:.0} MB", stats.peak_memory as f32 / MEGABYTE));
+                ui.end_row();
+                ui.label("Quality UB cache hits");
+                ui.label(hit_rate(stats.quality_ub_cache));
+                ui.end_row();
+                ui.label("Step LB cache hits");
+                ui.label(hit_rate(stats.step_lb_cache));
+                ui.end_row();
+                ui.label("Threads");
+                ui.label(stats.threads.to_string());
+                ui.end_row();
+            });
+        });
+}
+
+#[cfg(target_arch = "wasm32")]
+fn download_file(file_name: &str, contents: &[u8]) -> Option<()> {
+    use web_sys::wasm_bindgen::JsCast;
+    let parts = web_sys::js_sys::Array::of1(&web_sys::js_sys::Uint8Array::from(contents));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).ok()?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).ok()?;
+    let anchor = web_sys::window()?
+        .document()?
+        .create_element("a")
+        .ok()?
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .ok()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    web_sys::Url::revoke_object_url(&url).ok()
+}
+
 #[cfg(target_arch = "wasm32")]
 fn load_font_dyn(ctx: &egui::Context, font_name: &str, uri: &str) {
     use egui::epaint::text::{FontInsert, FontPriority, InsertFontFamily};
@@ -945,3 +3495,6 @@ fn load_fonts(ctx: &egui::Context) {
         ],
     ));
 }
+
+#[cfg(test)]
+mod tests;
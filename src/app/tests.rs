@@ -0,0 +1,49 @@
+//! Tests that drive the app through `eframe::App::update` without a window.
+
+use eframe::App;
+use raphael_data::game_data;
+use raphael_sim::Action;
+
+use super::MacroSolverApp;
+use crate::config::{QualitySource, RecipeConfiguration};
+use crate::widgets::Rotation;
+
+fn new_app(ctx: &egui::Context) -> MacroSolverApp {
+    MacroSolverApp::new(&eframe::CreationContext::_new_kittest(ctx.clone()))
+}
+
+fn run_frame(ctx: &egui::Context, app: &mut MacroSolverApp) {
+    let mut frame = eframe::Frame::_new_kittest();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| app.update(ctx, &mut frame));
+}
+
+#[test]
+fn opened_file_keeps_actions_of_recipe_with_default() {
+    let ctx = egui::Context::default();
+    let mut app = new_app(&ctx);
+    let recipes = game_data().recipes();
+    app.recipe_config = RecipeConfiguration {
+        recipe: recipes[0],
+        quality_source: QualitySource::HqMaterialList([0; 6]),
+    };
+    run_frame(&ctx, &mut app);
+
+    let mut file = app.current_rotation_file();
+    file.recipe_config.recipe = recipes[1];
+    file.actions = vec![Action::Reflect, Action::BasicSynthesis];
+    app.saved_rotations_data.pin_recipe_default(Rotation::new(
+        "Default",
+        vec![Action::MuscleMemory, Action::BasicSynthesis],
+        &file.recipe_config.recipe,
+        file.selected_food,
+        file.selected_potion,
+        &file.crafter_config,
+        &file.solver_config,
+        0,
+    ));
+
+    app.on_file_opened(Ok(file.clone()));
+    run_frame(&ctx, &mut app);
+    assert_eq!(app.recipe_config.recipe.item_id, recipes[1].item_id);
+    assert_eq!(app.actions, file.actions);
+}
@@ -1,4 +1,4 @@
-use raphael_data::{CrafterStats, Recipe};
+use raphael_data::{Consumable, CrafterStats, Locale, Recipe, get_job_name};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -22,20 +22,223 @@ impl Default for RecipeConfiguration {
     }
 }
 
+/// Recipe, consumables and Quality target that were last used with a job.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct JobContext {
+    pub recipe_config: RecipeConfiguration,
+    pub food: Option<Consumable>,
+    pub potion: Option<Consumable>,
+    pub quality_target: QualityTarget,
+}
+
+/// Last used [`JobContext`] of each job, in the order of [`CrafterConfig::crafter_stats`], so
+/// that selecting a job restores what it was last used for. Kept separate from
+/// [`CrafterConfig`], which is also stored in rotation files.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct JobContexts {
+    contexts: [Option<JobContext>; 8],
+}
+
+impl JobContexts {
+    pub fn get(&self, job_id: u8) -> Option<JobContext> {
+        self.contexts[job_id as usize]
+    }
+
+    pub fn set(&mut self, job_id: u8, context: JobContext) {
+        self.contexts[job_id as usize] = Some(context);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub struct CrafterConfig {
     pub selected_job: u8,
     pub crafter_stats: [CrafterStats; 8],
+    /// Level that all jobs are treated as, e.g. to plan the leveling crafts of an alt.
+    /// Only the actions and traits of that level are available to the simulator and solver.
+    #[serde(default)]
+    pub planning_level: Option<u8>,
 }
 
 impl CrafterConfig {
-    pub fn active_stats(&self) -> &CrafterStats {
-        &self.crafter_stats[self.selected_job as usize]
+    /// Stats of the job, with the planning level applied.
+    pub fn job_stats(&self, job_id: u8) -> CrafterStats {
+        let stats = self.crafter_stats[job_id as usize];
+        CrafterStats {
+            level: self.planning_level.unwrap_or(stats.level),
+            ..stats
+        }
+    }
+
+    /// Stats of the selected job, with the planning level applied.
+    pub fn active_stats(&self) -> CrafterStats {
+        self.job_stats(self.selected_job)
     }
 
     pub fn active_stats_mut(&mut self) -> &mut CrafterStats {
         &mut self.crafter_stats[self.selected_job as usize]
     }
+
+    /// Text to copy the stats of all jobs, see [`CrafterConfigExport`].
+    pub fn export(&self) -> String {
+        let export = CrafterConfigExport {
+            version: CrafterConfigExport::VERSION,
+            jobs: self
+                .crafter_stats
+                .iter()
+                .enumerate()
+                .map(|(job_id, stats)| JobStatsExport {
+                    job: get_job_name(job_id as u8, Locale::EN).to_string(),
+                    craftsmanship: stats.craftsmanship,
+                    control: stats.control,
+                    cp: stats.cp,
+                    level: stats.level,
+                    manipulation: stats.manipulation,
+                    heart_and_soul: stats.heart_and_soul,
+                    quick_innovation: stats.quick_innovation,
+                })
+                .collect(),
+        };
+        ron::to_string(&export).unwrap()
+    }
+
+    /// Loads the stats of the jobs contained in a copied crafter config, keeping the stats of
+    /// all other jobs. Nothing is changed if the config is invalid.
+    /// Also accepts configs copied before the format was versioned.
+    pub fn import(&mut self, text: &str) -> Result<(), String> {
+        let text = text.trim();
+        if let Ok(legacy_config) = ron::from_str::<Self>(text) {
+            self.crafter_stats = legacy_config.crafter_stats;
+            return Ok(());
+        }
+        let export: CrafterConfigExport =
+            ron::from_str(text).map_err(|error| format!("Not a crafter config: {error}"))?;
+        if export.version > CrafterConfigExport::VERSION {
+            return Err(format!(
+                "Crafter config has format version {}, which requires a newer version of Raphael",
+                export.version
+            ));
+        }
+        let mut crafter_stats = self.crafter_stats;
+        for job in export.jobs {
+            let job_id = (0..8)
+                .find(|job_id| get_job_name(*job_id, Locale::EN).eq_ignore_ascii_case(&job.job))
+                .ok_or_else(|| format!("Unknown job \"{}\"", job.job))?;
+            if !(1..=100).contains(&job.level) {
+                return Err(format!(
+                    "{}: Level {} is not between 1 and 100",
+                    job.job, job.level
+                ));
+            }
+            crafter_stats[job_id as usize] = CrafterStats {
+                craftsmanship: job.craftsmanship,
+                control: job.control,
+                cp: job.cp,
+                level: job.level,
+                manipulation: job.manipulation,
+                heart_and_soul: job.heart_and_soul,
+                quick_innovation: job.quick_innovation,
+            };
+        }
+        self.crafter_stats = crafter_stats;
+        Ok(())
+    }
+    /// Stats of all jobs after loading CSV lines of `job,craftsmanship,control,cp,level,specialist`,
+    /// e.g. `CRP,4000,3900,600,100,true`, without changing the config so that the changes can be
+    /// previewed. Jobs are given by their English abbreviation, a header line is optional and
+    /// jobs that aren't listed keep their stats. Manipulation isn't part of the CSV and is kept.
+    pub fn parse_csv(&self, text: &str) -> Result<[CrafterStats; 8], String> {
+        let mut crafter_stats = self.crafter_stats;
+        let mut listed_jobs = [false; 8];
+        let lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        for (index, line) in lines {
+            let error = |message: String| format!("Line {}: {message}", index + 1);
+            let values: Vec<&str> = line.split(',').map(str::trim).collect();
+            if values[0].eq_ignore_ascii_case("job") {
+                continue;
+            }
+            let [job, craftsmanship, control, cp, level, specialist] = values[..] else {
+                return Err(error(format!(
+                    "Expected 6 values (job, craftsmanship, control, cp, level, specialist), found {}",
+                    values.len()
+                )));
+            };
+            let job_id = (0..8)
+                .find(|job_id| get_job_name(*job_id, Locale::EN).eq_ignore_ascii_case(job))
+                .ok_or_else(|| error(format!("Unknown job \"{job}\"")))?;
+            if std::mem::replace(&mut listed_jobs[job_id as usize], true) {
+                return Err(error(format!("{job} is listed more than once")));
+            }
+            let parse = |name: &str, value: &str, max: u16| match value.parse::<u16>() {
+                Ok(value) if (1..=max).contains(&value) => Ok(value),
+                _ => Err(error(format!(
+                    "{name} \"{value}\" is not between 1 and {max}"
+                ))),
+            };
+            let specialist = match specialist.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" => true,
+                "false" | "no" | "0" | "" => false,
+                _ => {
+                    return Err(error(format!(
+                        "Specialist \"{specialist}\" is not true or false"
+                    )));
+                }
+            };
+            crafter_stats[job_id as usize] = CrafterStats {
+                craftsmanship: parse("Craftsmanship", craftsmanship, 9999)?,
+                control: parse("Control", control, 9999)?,
+                cp: parse("CP", cp, 999)?,
+                level: parse("Level", level, 100)? as u8,
+                heart_and_soul: specialist,
+                quick_innovation: specialist,
+                ..crafter_stats[job_id as usize]
+            };
+        }
+        Ok(crafter_stats)
+    }
+}
+
+/// Copy/paste format of [`CrafterConfig`], kept stable independently of the internal struct:
+/// - `version` is only increased for changes that older versions can't read.
+/// - New fields are added with a default value, so configs copied by older versions keep working.
+/// - Unknown fields are ignored, so configs copied by newer versions with the same `version` can be loaded.
+///
+/// Example: `(version:1,jobs:[(job:"CRP",craftsmanship:4000,control:3900,cp:600,level:100,manipulation:true)])`
+#[derive(Debug, Serialize, Deserialize)]
+struct CrafterConfigExport {
+    version: u32,
+    #[serde(default)]
+    jobs: Vec<JobStatsExport>,
+}
+
+impl CrafterConfigExport {
+    const VERSION: u32 = 1;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JobStatsExport {
+    /// English job abbreviation, e.g. "CRP".
+    job: String,
+    #[serde(default)]
+    craftsmanship: u16,
+    #[serde(default)]
+    control: u16,
+    #[serde(default)]
+    cp: u16,
+    #[serde(default = "default_level")]
+    level: u8,
+    #[serde(default)]
+    manipulation: bool,
+    #[serde(default)]
+    heart_and_soul: bool,
+    #[serde(default)]
+    quick_innovation: bool,
+}
+
+fn default_level() -> u8 {
+    100
 }
 
 impl Default for CrafterConfig {
@@ -43,6 +246,7 @@ impl Default for CrafterConfig {
         Self {
             selected_job: 1,
             crafter_stats: Default::default(),
+            planning_level: None,
         }
     }
 }
@@ -0,0 +1,35 @@
+//! Downloads newer game data, so that the recipes of a new patch can be used before the next
+//! release. Only used by the native app, as the web app is deployed with the latest data.
+
+use std::sync::mpsc::{self, Receiver};
+
+use raphael_data::GameDataset;
+
+/// Exported with `raphael-cli export-data` on every deployment of the web app.
+const GAME_DATA_URL: &str = "https://www.raphael-xiv.com/game-data.json";
+
+/// Downloads the latest game data in the background.
+/// The receiver yields a value only if the download succeeded.
+pub fn spawn_game_data_update(ctx: egui::Context) -> Receiver<GameDataset> {
+    let (tx, rx) = mpsc::channel();
+    let request = ehttp::Request {
+        headers: ehttp::Headers::new(&[("User-Agent", "raphael-xiv")]),
+        ..ehttp::Request::get(GAME_DATA_URL)
+    };
+    ehttp::fetch(request, move |result| {
+        let dataset = result.and_then(|response| match response.ok {
+            true => serde_json::from_slice::<GameDataset>(&response.bytes)
+                .map_err(|error| error.to_string()),
+            false => Err(format!("{} {}", response.status, response.status_text)),
+        });
+        match dataset {
+            Ok(dataset) => {
+                if tx.send(dataset).is_ok() {
+                    ctx.request_repaint();
+                }
+            }
+            Err(error) => log::warn!("Game data update failed: {error}"),
+        }
+    });
+    rx
+}
@@ -0,0 +1,81 @@
+use raphael_sim::{Action, Settings};
+use raphael_solver::{SolveRequest, SolverException};
+use serde::{Deserialize, Serialize};
+
+use crate::app::SolverConfig;
+
+pub const FILE_NAME: &str = "raphael-debug-bundle.json";
+
+/// Everything a maintainer needs to look into a bug report: the solver input, the rotation
+/// that was shown when the bundle was exported and the recent log messages.
+/// Stored as JSON, so that it can be attached to a GitHub issue as is.
+///
+/// See `examples/load_debug_bundle.rs` for loading a bundle outside of the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugBundle {
+    pub version: u32,
+    pub app_version: String,
+    pub platform: String,
+    /// Simulator settings of the last solve, which don't match the solve request if the solve
+    /// was started from raw settings.
+    pub settings: Settings,
+    pub solve_request: SolveRequest,
+    pub solver_config: SolverConfig,
+    pub actions: Vec<Action>,
+    /// Error of the last solve, if it failed.
+    pub solver_error: Option<SolverException>,
+    pub logs: Vec<String>,
+}
+
+impl DebugBundle {
+    pub const VERSION: u32 = 1;
+
+    pub fn new(
+        settings: Settings,
+        solve_request: SolveRequest,
+        solver_config: SolverConfig,
+        actions: Vec<Action>,
+        solver_error: Option<SolverException>,
+    ) -> Self {
+        Self {
+            version: Self::VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: platform(),
+            settings,
+            solve_request,
+            solver_config,
+            actions,
+            solver_error,
+            logs: crate::log_capture::recent_logs(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("DebugBundle is always serializable")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let bundle: Self = serde_json::from_slice(bytes).map_err(|error| error.to_string())?;
+        if bundle.version > Self::VERSION {
+            return Err(format!(
+                "Bundle was created by a newer version of Raphael (format version {})",
+                bundle.version
+            ));
+        }
+        Ok(bundle)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn platform() -> String {
+    format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn platform() -> String {
+    let user_agent = web_sys::window().and_then(|window| window.navigator().user_agent().ok());
+    format!(
+        "web ({})",
+        user_agent.as_deref().unwrap_or("unknown browser")
+    )
+}
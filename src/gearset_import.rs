@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::config::CrafterConfig;
+
+/// `ClassID` of Carpenter in the game's `ClassJob` sheet. The other crafters follow in the same
+/// order as the jobs of [`CrafterConfig`].
+const CARPENTER_CLASS_ID: u8 = 8;
+
+/// `BaseParam` IDs of the crafting stats.
+const CRAFTSMANSHIP_PARAM_ID: &str = "70";
+const CONTROL_PARAM_ID: &str = "71";
+const CP_PARAM_ID: &str = "11";
+
+/// XIVAPI page with the Lodestone profile of the character, including the class levels and the
+/// equipped gearset.
+pub fn character_url(lodestone_id: u64) -> String {
+    format!("https://xivapi.com/character/{lodestone_id}?data=CJ")
+}
+
+/// Crafter levels and stats of a character, as shown on its Lodestone profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterStats {
+    pub name: String,
+    /// Levels of the crafter jobs in the order of [`CrafterConfig`], `None` if not unlocked.
+    pub levels: [Option<u8>; 8],
+    /// Crafter job that the character has equipped, if any.
+    pub equipped_job: Option<u8>,
+    pub craftsmanship: u16,
+    pub control: u16,
+    pub cp: u16,
+    /// Whether a soul crystal is equipped, i.e. the equipped job is a specialist.
+    pub specialist: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CharacterResponse {
+    character: Character,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Character {
+    name: String,
+    #[serde(default)]
+    class_jobs: Vec<ClassJob>,
+    gear_set: GearSet,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ClassJob {
+    #[serde(rename = "ClassID")]
+    class_id: u8,
+    level: u8,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GearSet {
+    #[serde(rename = "ClassID")]
+    class_id: u8,
+    #[serde(default)]
+    attributes: HashMap<String, u16>,
+    #[serde(default)]
+    gear: HashMap<String, serde_json::Value>,
+}
+
+fn crafter_job_id(class_id: u8) -> Option<u8> {
+    class_id
+        .checked_sub(CARPENTER_CLASS_ID)
+        .filter(|job_id| *job_id < 8)
+}
+
+/// Parses the XIVAPI response for [`character_url`].
+pub fn parse_character(json: &str) -> Result<CharacterStats, String> {
+    let response: CharacterResponse = serde_json::from_str(json.trim())
+        .map_err(|error| format!("Not an XIVAPI character: {error}"))?;
+    let character = response.character;
+    let mut levels = [None; 8];
+    for class_job in &character.class_jobs {
+        // classes that aren't unlocked are listed with level 0
+        if let Some(job_id) = crafter_job_id(class_job.class_id).filter(|_| class_job.level != 0) {
+            levels[job_id as usize] = Some(class_job.level);
+        }
+    }
+    let equipped_job = crafter_job_id(character.gear_set.class_id);
+    if equipped_job.is_none() {
+        return Err(format!(
+            "{} doesn't have a crafter equipped on the Lodestone. Log in with a crafter to update the profile.",
+            character.name
+        ));
+    }
+    let attribute = |param_id: &str| {
+        character
+            .gear_set
+            .attributes
+            .get(param_id)
+            .copied()
+            .ok_or_else(|| format!("The gearset of {} has no stat {param_id}", character.name))
+    };
+    Ok(CharacterStats {
+        craftsmanship: attribute(CRAFTSMANSHIP_PARAM_ID)?,
+        control: attribute(CONTROL_PARAM_ID)?,
+        cp: attribute(CP_PARAM_ID)?,
+        specialist: character.gear_set.gear.contains_key("SoulCrystal"),
+        name: character.name,
+        levels,
+        equipped_job,
+    })
+}
+
+impl CrafterConfig {
+    /// Loads the stats of a character. The Lodestone only shows the equipped gearset, so its
+    /// stats are used for all jobs, which usually share the same gear. The levels are set per job.
+    /// Heart and Soul and Quick Innovation are only changed for the equipped job.
+    pub fn import_character(&mut self, character: &CharacterStats) {
+        for (job_id, stats) in self.crafter_stats.iter_mut().enumerate() {
+            stats.craftsmanship = character.craftsmanship;
+            stats.control = character.control;
+            stats.cp = character.cp;
+            if let Some(level) = character.levels[job_id] {
+                stats.level = level;
+            }
+            if character.equipped_job == Some(job_id as u8) {
+                stats.heart_and_soul = character.specialist;
+                stats.quick_innovation = character.specialist && stats.level >= 96;
+            }
+        }
+    }
+}
+
+/// Fetches the character in the background. The response body is stored in the temporary data
+/// of `ctx` under `result_id`, as a `Result<String, String>`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn fetch_character(ctx: egui::Context, lodestone_id: u64, result_id: egui::Id) {
+    let request = ehttp::Request {
+        headers: ehttp::Headers::new(&[("User-Agent", "raphael-xiv")]),
+        ..ehttp::Request::get(character_url(lodestone_id))
+    };
+    ehttp::fetch(request, move |result| {
+        let body = result.and_then(|response| match response.ok {
+            true => String::from_utf8(response.bytes).map_err(|error| error.to_string()),
+            false => Err(format!("{} {}", response.status, response.status_text)),
+        });
+        ctx.data_mut(|data| data.insert_temp(result_id, body));
+        ctx.request_repaint();
+    });
+}
@@ -0,0 +1,128 @@
+use serde::Deserialize;
+
+use crate::rotation_file::{FILE_EXTENSION, RotationFile};
+
+const GIST_API_URL: &str = "https://api.github.com/gists";
+
+/// Page where GitHub users create the token needed to export gists.
+pub const TOKEN_URL: &str =
+    "https://github.com/settings/tokens/new?scopes=gist&description=Raphael";
+
+#[derive(Deserialize)]
+struct Gist {
+    html_url: String,
+    files: std::collections::HashMap<String, GistFile>,
+}
+
+#[derive(Deserialize)]
+struct GistFile {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    truncated: bool,
+}
+
+/// Extracts the gist ID from a gist URL, e.g. `https://gist.github.com/user/0123abcd`, or from
+/// the bare ID.
+pub fn parse_gist_id(url: &str) -> Option<&str> {
+    let url = url.trim();
+    let url = url.split(['#', '?']).next().unwrap_or(url);
+    let id = url.trim_end_matches('/').rsplit('/').next()?;
+    match !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        true => Some(id),
+        false => None,
+    }
+}
+
+fn request_headers(token: Option<&str>) -> ehttp::Headers {
+    let mut headers = ehttp::Headers::new(&[
+        ("Accept", "application/vnd.github+json"),
+        ("User-Agent", "raphael-xiv"),
+    ]);
+    if let Some(token) = token {
+        headers.insert("Authorization", format!("Bearer {}", token.trim()));
+    }
+    headers
+}
+
+fn response_body(result: ehttp::Result<ehttp::Response>) -> Result<Vec<u8>, String> {
+    result.and_then(|response| match response.ok {
+        true => Ok(response.bytes),
+        false => Err(format!("{} {}", response.status, response.status_text)),
+    })
+}
+
+/// Creates a secret gist with the macro text and the `.raphael` file of the rotation, using the
+/// personal access token of the user. The URL of the gist is stored in the temporary data of
+/// `ctx` under `result_id`, as a `Result<String, String>`.
+pub fn export_gist(
+    ctx: egui::Context,
+    token: &str,
+    file: &RotationFile,
+    macro_text: String,
+    result_id: egui::Id,
+) {
+    let file_name = file.file_name();
+    let macro_file_name = format!(
+        "{}.txt",
+        file_name.trim_end_matches(&format!(".{FILE_EXTENSION}"))
+    );
+    let description = match file.notes.lines().next() {
+        Some(line) if !line.trim().is_empty() => format!("Raphael rotation: {}", line.trim()),
+        _ => "Raphael rotation".to_owned(),
+    };
+    let body = serde_json::json!({
+        "description": description,
+        "public": false,
+        "files": {
+            macro_file_name: { "content": macro_text },
+            file_name: { "content": file.to_ron() },
+        },
+    });
+    let mut headers = request_headers(Some(token));
+    headers.insert("Content-Type", "application/json");
+    let request = ehttp::Request {
+        headers,
+        ..ehttp::Request::post(GIST_API_URL, body.to_string().into_bytes())
+    };
+    ehttp::fetch(request, move |result| {
+        let url = response_body(result).and_then(|bytes| {
+            serde_json::from_slice::<Gist>(&bytes)
+                .map(|gist| gist.html_url)
+                .map_err(|error| error.to_string())
+        });
+        ctx.data_mut(|data| data.insert_temp(result_id, url));
+        ctx.request_repaint();
+    });
+}
+
+/// Parses the `.raphael` file of a gist from the response of the GitHub API.
+fn parse_gist(json: &[u8]) -> Result<RotationFile, String> {
+    let gist: Gist = serde_json::from_slice(json).map_err(|error| error.to_string())?;
+    let file = gist
+        .files
+        .iter()
+        .find(|(name, _)| name.ends_with(&format!(".{FILE_EXTENSION}")))
+        .map(|(_, file)| file)
+        .ok_or_else(|| format!("The gist doesn't contain a .{FILE_EXTENSION} file"))?;
+    if file.truncated {
+        return Err(format!(
+            "The .{FILE_EXTENSION} file of the gist is too large"
+        ));
+    }
+    RotationFile::from_bytes(file.content.as_bytes())
+}
+
+/// Loads the `.raphael` file of the gist in the background. The file is stored in the temporary
+/// data of `ctx` under `result_id`, as a `Result<RotationFile, String>`.
+pub fn import_gist(ctx: egui::Context, gist_id: &str, result_id: egui::Id) {
+    let request = ehttp::Request {
+        headers: request_headers(None),
+        ..ehttp::Request::get(format!("{GIST_API_URL}/{gist_id}"))
+    };
+    ehttp::fetch(request, move |result| {
+        let file = response_body(result).and_then(|bytes| parse_gist(&bytes));
+        ctx.data_mut(|data| data.insert_temp(result_id, file));
+        ctx.request_repaint();
+    });
+}
@@ -1,7 +1,43 @@
 mod app;
 pub use app::MacroSolverApp;
+pub use debug_bundle::DebugBundle;
+pub use log_capture::CapturingLogger;
+#[cfg(not(target_arch = "wasm32"))]
+pub use storage::StorageLocation;
+pub use widgets::AnalysisPlugin;
 pub use worker::Worker;
+#[cfg(target_arch = "wasm32")]
+pub use worker::web::register as register_worker;
 
+mod action_history;
 mod config;
+#[cfg(not(target_arch = "wasm32"))]
+mod data_update;
+mod debug_bundle;
+mod gearset_import;
+#[cfg(not(target_arch = "wasm32"))]
+mod gist_share;
+mod log_capture;
+mod macro_export;
+mod macro_import;
+mod planner;
+mod plugin_export;
+#[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+mod previous_solver;
+mod recipe_book;
+mod recipe_difficulty;
+mod reset_schedule;
+mod rotation_file;
+#[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+mod scripting;
+mod settings_import;
+mod share_code;
+mod share_link;
+mod solve_notification;
+#[cfg(not(target_arch = "wasm32"))]
+mod solve_progress;
+mod storage;
+#[cfg(not(target_arch = "wasm32"))]
+mod update_check;
 mod widgets;
 mod worker;
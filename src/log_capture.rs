@@ -0,0 +1,62 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Number of log messages that are kept, oldest first.
+const CAPACITY: usize = 200;
+
+/// Messages at this level and above are kept regardless of the filter of the wrapped logger.
+const CAPTURE_LEVEL: log::LevelFilter = log::LevelFilter::Info;
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Forwards log messages to another logger and keeps the most recent messages of the Raphael
+/// crates, so that they can be attached to a debug bundle.
+pub struct CapturingLogger<L> {
+    inner: L,
+}
+
+impl<L: log::Log + 'static> CapturingLogger<L> {
+    /// Installs the global logger. `max_level` is the filter of `inner`.
+    pub fn init(inner: L, max_level: log::LevelFilter) -> Result<(), log::SetLoggerError> {
+        log::set_boxed_logger(Box::new(Self { inner }))?;
+        log::set_max_level(std::cmp::max(max_level, CAPTURE_LEVEL));
+        Ok(())
+    }
+}
+
+impl<L: log::Log> log::Log for CapturingLogger<L> {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        is_captured(metadata) || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if is_captured(record.metadata()) {
+            let mut logs = RECENT_LOGS.lock().unwrap();
+            if logs.len() == CAPACITY {
+                logs.pop_front();
+            }
+            logs.push_back(format!(
+                "[{} {}] {}",
+                record.level(),
+                record.target(),
+                record.args()
+            ));
+        }
+        if self.inner.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+fn is_captured(metadata: &log::Metadata) -> bool {
+    metadata.level() <= CAPTURE_LEVEL && metadata.target().starts_with("raphael")
+}
+
+/// The most recent log messages, oldest first. Empty if no [`CapturingLogger`] is installed.
+pub fn recent_logs() -> Vec<String> {
+    RECENT_LOGS.lock().unwrap().iter().cloned().collect()
+}
@@ -0,0 +1,102 @@
+use std::io::Write;
+
+use raphael_data::{Locale, get_item_name, get_job_name};
+use serde::{Deserialize, Serialize};
+
+use crate::widgets::{MacroTemplateVariables, MacroViewConfig, Rotation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MacroExportFormat {
+    /// All macros in a single text file.
+    SingleFile,
+    /// A zip archive with one text file per rotation.
+    Zip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MacroExportConfig {
+    pub name_template: String,
+    pub format: MacroExportFormat,
+}
+
+impl Default for MacroExportConfig {
+    fn default() -> Self {
+        Self {
+            name_template: "{index} {item} ({job})".to_string(),
+            format: MacroExportFormat::SingleFile,
+        }
+    }
+}
+
+impl MacroExportConfig {
+    pub const TEMPLATE_HELP: &str = "Available placeholders:\n{index}: position in the list\n{item}: name of the crafted item\n{job}: crafting job\n{name}: name of the saved macro\n{steps}: number of steps";
+
+    /// Expands the name template for the rotation at `index` (starting from 1).
+    pub fn rotation_name(&self, index: usize, rotation: &Rotation, locale: Locale) -> String {
+        let item_name = get_item_name(rotation.item, false, locale);
+        self.name_template
+            .replace("{index}", &index.to_string())
+            .replace(
+                "{item}",
+                item_name.trim_end_matches([' ', raphael_data::CL_ICON_CHAR]),
+            )
+            .replace("{job}", get_job_name(rotation.job_id, locale))
+            .replace("{name}", &rotation.name)
+            .replace("{steps}", &rotation.actions.len().to_string())
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self.format {
+            MacroExportFormat::SingleFile => "macros.txt",
+            MacroExportFormat::Zip => "macros.zip",
+        }
+    }
+
+    /// Exports the macros of all rotations in the configured format.
+    pub fn export(
+        &self,
+        rotations: &[Rotation],
+        macro_config: &MacroViewConfig,
+        newline: &str,
+        locale: Locale,
+    ) -> Result<Vec<u8>, String> {
+        let entries = rotations.iter().enumerate().map(|(index, rotation)| {
+            let name = self.rotation_name(index + 1, rotation, locale);
+            let variables = MacroTemplateVariables::new(rotation.item, rotation.job_id, locale);
+            let macros = macro_config.macro_texts(&rotation.actions, &variables, newline, locale);
+            let separator = format!("{newline}{newline}");
+            (name, macros.join(&separator))
+        });
+        match self.format {
+            MacroExportFormat::SingleFile => {
+                let text = entries
+                    .map(|(name, macros)| format!("# {name}{newline}{newline}{macros}"))
+                    .collect::<Vec<_>>()
+                    .join(&format!("{newline}{newline}{newline}"));
+                Ok(text.into_bytes())
+            }
+            MacroExportFormat::Zip => {
+                let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+                let options = zip::write::SimpleFileOptions::default()
+                    .compression_method(zip::CompressionMethod::Stored);
+                let mut file_names = std::collections::HashSet::new();
+                for (name, macros) in entries {
+                    let name = name.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_");
+                    // two rotations may expand to the same name
+                    let mut file_name = format!("{name}.txt");
+                    let mut duplicate = 1;
+                    while !file_names.insert(file_name.clone()) {
+                        duplicate += 1;
+                        file_name = format!("{name} ({duplicate}).txt");
+                    }
+                    zip.start_file(file_name, options)
+                        .map_err(|error| error.to_string())?;
+                    zip.write_all(macros.as_bytes())
+                        .map_err(|error| error.to_string())?;
+                }
+                let cursor = zip.finish().map_err(|error| error.to_string())?;
+                Ok(cursor.into_inner())
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use raphael_data::action_from_name_any_locale;
+use raphael_sim::Action;
+
+/// Commands that use an action. `/ac` works in all languages of the game.
+const ACTION_COMMANDS: &[&str] = &["/ac", "/action", "/aktion"];
+
+/// Reads the actions of macros copied from the game, e.g. `/ac "Basic Touch" <wait.3>`, in any
+/// language of the game. Lines with other commands (e.g. `/echo` or `/mlock`) and lines without
+/// a command are skipped, so that several macros and their names can be pasted at once.
+/// Also reads the numbered action lists of the share window, e.g. `1. Basic Touch`.
+pub fn parse_macro_text(text: &str) -> Result<Vec<Action>, String> {
+    let mut actions = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let Some(name) = action_name_of_line(line.trim()) else {
+            continue;
+        };
+        match action_from_name_any_locale(name) {
+            Some((action, _locale)) => actions.push(action),
+            None => return Err(format!("Line {}: unknown action \"{}\"", index + 1, name)),
+        }
+    }
+    match actions.is_empty() {
+        true => Err("The text doesn't contain any actions".to_string()),
+        false => Ok(actions),
+    }
+}
+
+/// Name of the action that `line` uses, `None` if the line doesn't use an action.
+fn action_name_of_line(line: &str) -> Option<&str> {
+    let arguments = match line.split_once(char::is_whitespace) {
+        Some((command, arguments))
+            if ACTION_COMMANDS
+                .iter()
+                .any(|action_command| command.eq_ignore_ascii_case(action_command)) =>
+        {
+            arguments.trim_start()
+        }
+        _ => numbered_entry(line)?,
+    };
+    let name = arguments.strip_prefix('"').map_or_else(
+        // unquoted names end at the first placeholder, e.g. `<wait.3>`
+        || arguments.split('<').next().unwrap_or_default(),
+        |quoted| quoted.split('"').next().unwrap_or_default(),
+    );
+    match name.trim().is_empty() {
+        true => None,
+        false => Some(name.trim()),
+    }
+}
+
+/// Text after the number of a line like `12. Basic Touch`.
+fn numbered_entry(line: &str) -> Option<&str> {
+    let (number, rest) = line.split_once('.')?;
+    match !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+        true => Some(rest.trim_start()),
+        false => None,
+    }
+}
@@ -2,17 +2,54 @@
 // This attribute is ignored for all other platforms
 #![windows_subsystem = "windows"]
 
+#[cfg(not(target_arch = "wasm32"))]
+const USAGE: &str =
+    "Usage: raphael-xiv [--portable | --data-dir <DIR>] [--share-link <URL>] [FILE]";
+
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
-    env_logger::builder()
+    let logger = env_logger::builder()
         .format_timestamp(None)
         .format_target(false)
-        .init();
+        .build();
+    let max_level = logger.filter();
+    raphael_xiv::CapturingLogger::init(logger, max_level).unwrap();
+
+    let mut args: Vec<_> = std::env::args_os().skip(1).collect();
+    let persistence_path = raphael_xiv::StorageLocation::from_env(&mut args)
+        .and_then(|location| location.persistence_path())
+        .unwrap_or_else(|error| {
+            log::error!("{error}");
+            std::process::exit(1);
+        });
+
+    let share_link = match args.iter().position(|arg| arg == "--share-link") {
+        Some(index) if index + 1 < args.len() => {
+            args.remove(index);
+            Some(args.remove(index).to_string_lossy().into_owned())
+        }
+        Some(_) => {
+            log::error!("--share-link requires a URL");
+            std::process::exit(1);
+        }
+        None => None,
+    };
+
+    // the only positional argument is the file to open, so anything else that looks like an
+    // option is a typo or an option of a newer version
+    if let Some(arg) = args
+        .iter()
+        .find(|arg| arg.to_string_lossy().starts_with('-'))
+    {
+        log::error!("Unknown option {}\n{USAGE}", arg.to_string_lossy());
+        std::process::exit(1);
+    }
 
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([400.0, 300.0])
             .with_min_inner_size([300.0, 220.0]),
+        persistence_path,
         ..Default::default()
     };
     eframe::run_native(
@@ -20,7 +57,16 @@ fn main() -> eframe::Result<()> {
         native_options,
         Box::new(|cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Ok(Box::new(raphael_xiv::MacroSolverApp::new(cc)))
+            let mut app = raphael_xiv::MacroSolverApp::new(cc);
+            // Launched through a file association, e.g. double-clicking a `.raphael` file
+            if let Some(path) = args.first() {
+                app.open_file_path(std::path::Path::new(path));
+            }
+            // Opened from a share link of the web app
+            if let Some(url) = share_link {
+                app.open_share_link(&url);
+            }
+            Ok(Box::new(app))
         }),
     )
 }
@@ -28,7 +74,11 @@ fn main() -> eframe::Result<()> {
 #[cfg(target_arch = "wasm32")]
 fn main() {
     // Redirect `log` message to `console.log` and friends:
-    eframe::WebLogger::init(log::LevelFilter::Debug).ok();
+    raphael_xiv::CapturingLogger::init(
+        eframe::WebLogger::new(log::LevelFilter::Debug),
+        log::LevelFilter::Debug,
+    )
+    .ok();
 
     fn get_canvas() -> Option<web_sys::HtmlCanvasElement> {
         use web_sys::wasm_bindgen::JsCast;
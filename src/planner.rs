@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use raphael_data::{Locale, Recipe, game_data, get_item_name};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{QualitySource, RecipeConfiguration};
+
+/// A recipe to craft several times, using the same HQ ingredients for every craft.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PlanEntry {
+    pub recipe: Recipe,
+    /// Number of crafts.
+    pub quantity: u32,
+    /// Number of HQ ingredients used per craft, for each ingredient of the recipe.
+    pub hq_ingredients: [u8; 6],
+}
+
+impl PlanEntry {
+    pub fn new(recipe_config: &RecipeConfiguration) -> Self {
+        Self {
+            recipe: recipe_config.recipe,
+            quantity: 1,
+            hq_ingredients: match recipe_config.quality_source {
+                QualitySource::HqMaterialList(hq_ingredients) => hq_ingredients,
+                QualitySource::Value(_) => [0; 6],
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShoppingListItem {
+    pub item_id: u32,
+    pub nq: u32,
+    pub hq: u32,
+}
+
+/// List of recipes to craft in one session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchPlan {
+    pub entries: Vec<PlanEntry>,
+}
+
+impl BatchPlan {
+    /// Adds a craft of the recipe, merging it into an existing entry with the same recipe
+    /// and HQ ingredients.
+    pub fn add(&mut self, recipe_config: &RecipeConfiguration) {
+        self.add_entry(PlanEntry::new(recipe_config));
+    }
+
+    fn add_entry(&mut self, new_entry: PlanEntry) {
+        let existing_entry = self.entries.iter_mut().find(|entry| {
+            entry.recipe == new_entry.recipe && entry.hq_ingredients == new_entry.hq_ingredients
+        });
+        match existing_entry {
+            Some(entry) => entry.quantity += new_entry.quantity,
+            None => self.entries.push(new_entry),
+        }
+    }
+
+    /// Ingredients needed for all crafts of the plan, sorted by item ID.
+    pub fn shopping_list(&self) -> Vec<ShoppingListItem> {
+        let mut items: BTreeMap<u32, ShoppingListItem> = BTreeMap::new();
+        for entry in &self.entries {
+            for (ingredient, hq_amount) in entry.recipe.ingredients.iter().zip(entry.hq_ingredients)
+            {
+                if ingredient.item_id == 0 {
+                    continue;
+                }
+                let hq = u32::from(hq_amount).min(ingredient.amount) * entry.quantity;
+                let nq = ingredient.amount * entry.quantity - hq;
+                let item = items.entry(ingredient.item_id).or_insert(ShoppingListItem {
+                    item_id: ingredient.item_id,
+                    nq: 0,
+                    hq: 0,
+                });
+                item.nq += nq;
+                item.hq += hq;
+            }
+        }
+        items.into_values().collect()
+    }
+
+    /// Shopping list with one item per line, e.g. "6x Iron Ore (2 HQ, 4 NQ)".
+    pub fn shopping_list_text(&self, locale: Locale) -> String {
+        self.shopping_list()
+            .iter()
+            .map(|item| {
+                let name = get_item_name(item.item_id, false, locale);
+                let name = name.trim_end_matches([
+                    ' ',
+                    raphael_data::HQ_ICON_CHAR,
+                    raphael_data::CL_ICON_CHAR,
+                ]);
+                match item.hq {
+                    0 => format!("{}x {name}", item.nq),
+                    hq => format!("{}x {name} ({hq} HQ, {} NQ)", item.nq + hq, item.nq),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Adds the items of a Teamcraft list as crafts with NQ ingredients. Accepts the import
+    /// link of a list, its decoded content (e.g. `44104,null,3;44105,null,1`) or the JSON
+    /// export of a list. Items that can't be crafted are skipped and returned.
+    pub fn import_teamcraft(&mut self, text: &str) -> Result<Vec<u32>, String> {
+        let items = parse_teamcraft_items(text)?;
+        if items.is_empty() {
+            return Err("The Teamcraft list is empty".to_string());
+        }
+        let mut skipped_items = Vec::new();
+        for item in items {
+            // items with recipes for several jobs use the first job
+            match game_data()
+                .recipes()
+                .iter()
+                .find(|recipe| recipe.item_id == item.id)
+            {
+                Some(recipe) => self.add_entry(PlanEntry {
+                    recipe: *recipe,
+                    quantity: item.amount.max(1),
+                    hq_ingredients: [0; 6],
+                }),
+                None => skipped_items.push(item.id),
+            }
+        }
+        Ok(skipped_items)
+    }
+
+    /// Link that imports the crafts of the plan as a new list in FFXIV Teamcraft,
+    /// which then works out the ingredients itself.
+    pub fn teamcraft_import_link(&self) -> String {
+        let items = self
+            .entries
+            .iter()
+            .map(|entry| format!("{},null,{}", entry.recipe.item_id, entry.quantity))
+            .collect::<Vec<_>>()
+            .join(";");
+        format!(
+            "https://ffxivteamcraft.com/import/{}",
+            base64_encode(items.as_bytes())
+        )
+    }
+}
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, byte)| {
+            bits | u32::from(*byte) << (16 - 8 * index)
+        });
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => encoded.push(char::from(
+                    BASE64_ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize],
+                )),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut decoded = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut bits = 0u32;
+        for (index, char) in chunk.iter().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|c| c == char)?;
+            bits |= (value as u32) << (18 - 6 * index);
+        }
+        for index in 0..chunk.len() - 1 {
+            decoded.push((bits >> (16 - 8 * index)) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+/// Item and amount of a Teamcraft list.
+#[derive(Deserialize)]
+struct TeamcraftItem {
+    id: u32,
+    amount: u32,
+}
+
+/// Only the fields of a Teamcraft list export that are needed to import it.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TeamcraftList {
+    final_items: Vec<TeamcraftItem>,
+}
+
+/// Items of a Teamcraft list in any of the formats that [`BatchPlan::import_teamcraft`] accepts.
+fn parse_teamcraft_items(text: &str) -> Result<Vec<TeamcraftItem>, String> {
+    let text = text.trim();
+    if text.starts_with('{') {
+        let list: TeamcraftList = serde_json::from_str(text)
+            .map_err(|error| format!("Not a Teamcraft list export: {error}"))?;
+        return Ok(list.final_items);
+    }
+    if text.starts_with('[') {
+        return serde_json::from_str(text)
+            .map_err(|error| format!("Not a Teamcraft item list: {error}"));
+    }
+    // import link, e.g. `https://ffxivteamcraft.com/import/<base64>`, or its decoded content
+    let content = match text.rsplit_once("/import/") {
+        Some((_, encoded)) => {
+            let encoded = encoded.split(['?', '#']).next().unwrap_or_default();
+            base64_decode(encoded)
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .ok_or_else(|| "Invalid Teamcraft import link".to_string())?
+        }
+        None => text.to_string(),
+    };
+    content
+        .split(';')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            // item ID, recipe ID (or null) and amount
+            let fields: Vec<&str> = entry.trim().split(',').collect();
+            let [item_id, _, amount] = fields.as_slice() else {
+                return Err(format!("Invalid Teamcraft list entry \"{entry}\""));
+            };
+            match (item_id.parse(), amount.parse()) {
+                (Ok(id), Ok(amount)) => Ok(TeamcraftItem { id, amount }),
+                _ => Err(format!("Invalid Teamcraft list entry \"{entry}\"")),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,101 @@
+use raphael_data::{CL_ICON_CHAR, Consumable, Locale, get_item_name};
+use raphael_sim::Action;
+use serde::Serialize;
+
+/// Item and consumables of the craft that a rotation is exported for.
+#[derive(Debug, Clone, Copy)]
+pub struct PluginCraft {
+    pub item_id: u32,
+    pub food: Option<Consumable>,
+    pub potion: Option<Consumable>,
+}
+
+/// Entry of the JSON macro list that Dalamud crafting plugins (e.g. Artisan) import.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PluginMacro {
+    pub name: String,
+    pub item_id: u32,
+    pub food: Option<PluginConsumable>,
+    pub potion: Option<PluginConsumable>,
+    /// Game action IDs of the Carpenter versions of the actions, which the plugins translate
+    /// to the actions of the job that crafts the item.
+    pub actions: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PluginConsumable {
+    pub item_id: u32,
+    pub hq: bool,
+}
+
+impl From<Consumable> for PluginConsumable {
+    fn from(consumable: Consumable) -> Self {
+        Self {
+            item_id: consumable.item_id,
+            hq: consumable.hq,
+        }
+    }
+}
+
+impl PluginMacro {
+    pub fn new(craft: PluginCraft, actions: &[Action], locale: Locale) -> Self {
+        let item_name = get_item_name(craft.item_id, false, locale);
+        Self {
+            name: format!(
+                "{} (Raphael)",
+                item_name.trim_end_matches([' ', CL_ICON_CHAR])
+            ),
+            item_id: craft.item_id,
+            food: craft.food.map(PluginConsumable::from),
+            potion: craft.potion.map(PluginConsumable::from),
+            actions: actions.iter().copied().map(action_id).collect(),
+        }
+    }
+}
+
+/// The macros as the JSON list that the plugins import from the clipboard.
+pub fn to_json(macros: &[PluginMacro]) -> String {
+    serde_json::to_string_pretty(macros).expect("PluginMacro is always serializable")
+}
+
+/// ID of the Carpenter version of the action in the game's `Action` and `CraftAction` sheets.
+const fn action_id(action: Action) -> u32 {
+    match action {
+        Action::BasicSynthesis => 100001,
+        Action::BasicTouch => 100002,
+        Action::MasterMend => 100003,
+        Action::StandardTouch => 100004,
+        Action::Observe => 100010,
+        Action::PreciseTouch => 100128,
+        Action::CarefulSynthesis => 100203,
+        Action::PrudentTouch => 100227,
+        Action::TrainedEye => 100283,
+        Action::PreparatoryTouch => 100299,
+        Action::IntensiveSynthesis => 100315,
+        Action::DelicateSynthesis => 100323,
+        Action::ByregotsBlessing => 100339,
+        Action::HastyTouch => 100355,
+        Action::RapidSynthesis => 100363,
+        Action::TricksOfTheTrade => 100371,
+        Action::MuscleMemory => 100379,
+        Action::Reflect => 100387,
+        Action::Groundwork => 100403,
+        Action::AdvancedTouch => 100411,
+        Action::HeartAndSoul => 100419,
+        Action::PrudentSynthesis => 100427,
+        Action::TrainedFinesse => 100435,
+        Action::RefinedTouch => 100443,
+        Action::DaringTouch => 100451,
+        Action::QuickInnovation => 100459,
+        Action::ImmaculateMend => 100467,
+        Action::TrainedPerfection => 100475,
+        Action::GreatStrides => 260,
+        Action::Manipulation => 4574,
+        Action::WasteNot => 4631,
+        Action::WasteNot2 => 4639,
+        Action::Innovation => 19004,
+        Action::Veneration => 19297,
+    }
+}
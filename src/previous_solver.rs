@@ -0,0 +1,121 @@
+//! Runs the solver of a previous release through the C API of its `raphael_bindings` library,
+//! so that its output can be compared against the current solver.
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use raphael_sim::{Action, ActionMask};
+use raphael_solver::SolverSettings;
+
+/// Must have the same layout as `SolveArgs` in `raphael-bindings`.
+#[repr(C)]
+struct SolveArgs {
+    on_start: extern "C" fn(*mut bool),
+    on_finish: extern "C" fn(*const u8, usize),
+    on_suggest_solution: Option<extern "C" fn(*const u8, usize)>,
+    on_progress: Option<extern "C" fn(usize)>,
+    action_mask: u64,
+    progress: u16,
+    quality: u16,
+    base_progress: u16,
+    base_quality: u16,
+    cp: i16,
+    durability: i8,
+    job_level: u8,
+    adversarial: bool,
+    backload_progress: bool,
+    unsound_branch_pruning: bool,
+}
+
+type SolveFn = unsafe extern "C" fn(&SolveArgs);
+
+/// The callbacks of the C API don't take a user pointer, so only one solve can run at a time.
+static SOLVE_LOCK: Mutex<()> = Mutex::new(());
+/// Address of the interrupt flag of the running solve, or 0 if no solve is running.
+static INTERRUPT_FLAG: Mutex<usize> = Mutex::new(0);
+static SOLUTION: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+
+extern "C" fn on_start(flag: *mut bool) {
+    *INTERRUPT_FLAG.lock().unwrap() = flag as usize;
+}
+
+extern "C" fn on_finish(actions: *const u8, len: usize) {
+    // SAFETY: the solver passes a pointer to `len` actions that is valid during the callback.
+    let actions = unsafe { std::slice::from_raw_parts(actions, len) };
+    *SOLUTION.lock().unwrap() = Some(actions.to_vec());
+}
+
+fn action_from_u8(value: u8) -> Option<Action> {
+    ActionMask::all()
+        .actions_iter()
+        .find(|action| *action as u8 == value)
+}
+
+pub struct PreviousSolver {
+    solve: libloading::Symbol<'static, SolveFn>,
+}
+
+impl PreviousSolver {
+    /// Loads the `raphael_bindings` dynamic library of a previous release.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        // SAFETY: the library is expected to be a build of `raphael-bindings`, whose
+        // initialization routines have no side effects.
+        let library =
+            unsafe { libloading::Library::new(path) }.map_err(|error| error.to_string())?;
+        // The library is never unloaded, because the threads of its thread pool keep running
+        // after the solve has finished.
+        let library: &'static libloading::Library = Box::leak(Box::new(library));
+        // SAFETY: `solve` has the signature of `SolveFn` in all releases of `raphael-bindings`.
+        let solve = unsafe { library.get::<SolveFn>(b"solve") }
+            .map_err(|error| format!("Not a raphael-bindings library: {error}"))?;
+        Ok(Self { solve })
+    }
+
+    /// Solves on the calling thread. Returns an empty macro if the previous solver didn't find
+    /// a solution or was interrupted.
+    pub fn solve(&self, settings: &SolverSettings) -> Result<Vec<Action>, String> {
+        let _lock = SOLVE_LOCK.lock().unwrap();
+        let simulator_settings = &settings.simulator_settings;
+        let args = SolveArgs {
+            on_start,
+            on_finish,
+            on_suggest_solution: None,
+            on_progress: None,
+            action_mask: simulator_settings.allowed_actions.to_bits(),
+            progress: simulator_settings.max_progress,
+            quality: simulator_settings.max_quality,
+            base_progress: simulator_settings.base_progress,
+            base_quality: simulator_settings.base_quality,
+            cp: simulator_settings.max_cp,
+            durability: simulator_settings.max_durability,
+            job_level: simulator_settings.job_level,
+            adversarial: simulator_settings.adversarial,
+            backload_progress: settings.backload_progress,
+            unsound_branch_pruning: settings.allow_unsound_branch_pruning,
+        };
+        // SAFETY: `args` and the callbacks outlive the call.
+        unsafe { (self.solve)(&args) };
+        *INTERRUPT_FLAG.lock().unwrap() = 0;
+
+        let solution = SOLUTION
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or("The previous solver didn't report a solution")?;
+        solution
+            .into_iter()
+            .map(|value| action_from_u8(value).ok_or(format!("Unknown action ID {value}")))
+            .collect()
+    }
+}
+
+/// Interrupts the running solve of the previous solver, if any.
+pub fn interrupt() {
+    let flag = INTERRUPT_FLAG.lock().unwrap();
+    if *flag != 0 {
+        // SAFETY: the flag is an `AtomicBool` of the previous solver that stays alive until the
+        // solve returns, after which the address is reset while holding the lock.
+        unsafe { AtomicBool::from_ptr(*flag as *mut bool) }.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::BTreeSet;
+
+use raphael_data::game_data;
+use serde::{Deserialize, Serialize};
+
+/// Recipe level ranges of each expansion, used to summarize the completion.
+pub const LEVEL_BRACKETS: [(&str, u8, u8); 6] = [
+    ("A Realm Reborn", 1, 50),
+    ("Heavensward", 51, 60),
+    ("Stormblood", 61, 70),
+    ("Shadowbringers", 71, 80),
+    ("Endwalker", 81, 90),
+    ("Dawntrail", 91, 100),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BracketProgress {
+    pub name: &'static str,
+    pub mastered: usize,
+    pub total: usize,
+}
+
+/// Recipes the user has marked as mastered, i.e. recipes for which they have a macro that
+/// reaches their Quality target.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecipeBook {
+    /// Item IDs of the mastered recipes of each job.
+    mastered: [BTreeSet<u32>; 8],
+}
+
+impl RecipeBook {
+    pub fn is_mastered(&self, job_id: u8, item_id: u32) -> bool {
+        self.mastered[job_id as usize].contains(&item_id)
+    }
+
+    pub fn set_mastered(&mut self, job_id: u8, item_id: u32, mastered: bool) {
+        match mastered {
+            true => self.mastered[job_id as usize].insert(item_id),
+            false => self.mastered[job_id as usize].remove(&item_id),
+        };
+    }
+
+    /// Number of mastered and total recipes of the job in each level bracket.
+    pub fn progress(&self, job_id: u8) -> Vec<BracketProgress> {
+        LEVEL_BRACKETS
+            .iter()
+            .map(|(name, min_level, max_level)| {
+                let recipes = game_data().recipes().iter().filter(|recipe| {
+                    recipe.job_id == job_id && (*min_level..=*max_level).contains(&recipe.level)
+                });
+                let (mastered, total) =
+                    recipes.fold((0, 0), |(mastered, total), recipe| {
+                        match self.is_mastered(job_id, recipe.item_id) {
+                            true => (mastered + 1, total + 1),
+                            false => (mastered, total + 1),
+                        }
+                    });
+                BracketProgress {
+                    name,
+                    mastered,
+                    total,
+                }
+            })
+            .collect()
+    }
+}
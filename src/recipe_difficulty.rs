@@ -0,0 +1,123 @@
+use egui::util::cache::{ComputerMut, FrameCache};
+use raphael_sim::*;
+use raphael_solver::{SolverHeuristics, SolverSettings, progress_only_reason};
+
+/// Share of the CP and durability budget up to which a recipe counts as easy.
+const EASY_BUDGET_SHARE: f32 = 0.8;
+/// Share of the CP and durability budget above which a recipe is likely impossible to max out.
+const IMPOSSIBLE_BUDGET_SHARE: f32 = 1.3;
+
+/// Rough estimate of how hard it is to max out the Quality of a recipe, to triage recipes
+/// without solving them. Compares the CP and durability that a typical rotation spends on
+/// Progress and Quality with the CP and durability available, so recipes close to the limits
+/// can be misjudged in either direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipeDifficulty {
+    /// Max Quality is reached with plenty of CP to spare.
+    Easy,
+    /// Max Quality might be reachable, but only the solver can tell.
+    NeedsSolve,
+    /// Neither Progress nor Quality can be maxed out with a typical rotation.
+    LikelyImpossible,
+}
+
+impl RecipeDifficulty {
+    pub fn estimate(settings: &Settings) -> Self {
+        let initial_state = SimulationState::new(settings);
+        if progress_only_reason(settings, &initial_state).is_some() {
+            return Self::Easy;
+        }
+        let heuristics = SolverHeuristics::new(&SolverSettings {
+            simulator_settings: *settings,
+            backload_progress: false,
+            allow_unsound_branch_pruning: false,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
+        });
+        // CP that 5 durability are worth
+        let durability_cost = f32::from(heuristics.durability_cost);
+        let budget =
+            f32::from(settings.max_cp) + f32::from(settings.max_durability / 5) * durability_cost;
+
+        // CP and durability spent per step of a typical rotation, including the buff that is
+        // active for the step, and the Progress or Quality that the step increases
+        let step_cost = |action: Action, buff_cp_cost: f32| {
+            let effect = initial_state.action_effect(action, Condition::Normal, settings);
+            f32::from(effect.cp_cost)
+                + f32::from(effect.durability_cost / 5) * durability_cost
+                + buff_cp_cost / 4.0
+        };
+        let progress_action = match settings.is_action_allowed::<CarefulSynthesis>() {
+            true => Action::CarefulSynthesis,
+            false => Action::BasicSynthesis,
+        };
+        let (veneration_multiplier, veneration_cost) =
+            match settings.is_action_allowed::<Veneration>() {
+                true => (1.5, 18.0),
+                false => (1.0, 0.0),
+            };
+        let progress_per_step = f32::from(
+            initial_state
+                .action_effect(progress_action, Condition::Normal, settings)
+                .progress_increase,
+        ) * veneration_multiplier;
+        let progress_cost = (f32::from(settings.max_progress) / progress_per_step.max(1.0)).ceil()
+            * step_cost(progress_action, veneration_cost);
+        if progress_cost > budget {
+            return Self::LikelyImpossible;
+        }
+
+        let (innovation_multiplier, innovation_cost) =
+            match settings.is_action_allowed::<Innovation>() {
+                true => (1.5, 18.0),
+                false => (1.0, 0.0),
+            };
+        // Basic Touch combos average 125% efficiency, Inner Quiet averages about 5 stacks
+        let quality_per_step = f32::from(
+            initial_state
+                .action_effect(Action::BasicTouch, Condition::Normal, settings)
+                .quality_increase,
+        ) * 1.25
+            * 1.5
+            * innovation_multiplier;
+        let quality_cost = (f32::from(settings.max_quality) / quality_per_step.max(1.0)).ceil()
+            * step_cost(Action::BasicTouch, innovation_cost);
+
+        let budget_share = (progress_cost + quality_cost) / budget;
+        if budget_share <= EASY_BUDGET_SHARE {
+            Self::Easy
+        } else if budget_share <= IMPOSSIBLE_BUDGET_SHARE {
+            Self::NeedsSolve
+        } else {
+            Self::LikelyImpossible
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Easy => "Estimate: 100% HQ with CP to spare",
+            Self::NeedsSolve => "Estimate: 100% HQ might be possible, needs a full solve",
+            Self::LikelyImpossible => "Estimate: 100% HQ is likely impossible with these stats",
+        }
+    }
+
+    pub fn color(self) -> egui::Color32 {
+        match self {
+            Self::Easy => egui::Color32::from_rgb(0x4c, 0xaf, 0x50),
+            Self::NeedsSolve => egui::Color32::from_rgb(0xff, 0xb3, 0x00),
+            Self::LikelyImpossible => egui::Color32::from_rgb(0xe5, 0x39, 0x35),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DifficultyEstimator {}
+
+impl ComputerMut<&Settings, RecipeDifficulty> for DifficultyEstimator {
+    fn compute(&mut self, settings: &Settings) -> RecipeDifficulty {
+        RecipeDifficulty::estimate(settings)
+    }
+}
+
+/// Estimates of the recipes that are visible in the recipe list, keyed by their settings.
+pub type DifficultyCache = FrameCache<RecipeDifficulty, DifficultyEstimator>;
@@ -0,0 +1,67 @@
+//! Reset times of the in-game daily and weekly activities, computed in seconds since the Unix epoch.
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Daily reset of the Grand Company supply and provisioning missions, 20:00 UTC.
+const DAILY_RESET_OFFSET: u64 = 20 * HOUR;
+/// Weekly reset of the custom delivery allowances, Tuesday 08:00 UTC.
+/// The Unix epoch was a Thursday, so the first Tuesday was 5 days later.
+const WEEKLY_RESET_OFFSET: u64 = 5 * DAY + 8 * HOUR;
+
+pub fn now() -> u64 {
+    web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn last_reset(time: u64, period: u64, offset: u64) -> u64 {
+    // shift by a whole period so that times before the first reset don't underflow
+    ((time + period - offset) / period * period + offset).saturating_sub(period)
+}
+
+pub fn last_daily_reset(time: u64) -> u64 {
+    last_reset(time, DAY, DAILY_RESET_OFFSET)
+}
+
+pub fn next_daily_reset(time: u64) -> u64 {
+    last_daily_reset(time) + DAY
+}
+
+pub fn last_weekly_reset(time: u64) -> u64 {
+    last_reset(time, WEEK, WEEKLY_RESET_OFFSET)
+}
+
+pub fn next_weekly_reset(time: u64) -> u64 {
+    last_weekly_reset(time) + WEEK
+}
+
+/// Formats a duration as the two most significant units, e.g. "2d 5h" or "3h 12m".
+pub fn format_duration(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / DAY, seconds % DAY / HOUR, seconds % HOUR / 60);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+/// Formats the UTC date of a time as "YYYY-MM-DD".
+pub fn format_date(time: u64) -> String {
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = time / DAY + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = match month_index < 10 {
+        true => month_index + 3,
+        false => month_index - 9,
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,57 @@
+use raphael_data::Consumable;
+use raphael_sim::Action;
+use serde::{Deserialize, Serialize};
+
+use crate::app::SolverConfig;
+use crate::config::{CrafterConfig, RecipeConfiguration};
+
+pub const FILE_EXTENSION: &str = "raphael";
+
+/// Everything needed to reproduce a solve: the recipe and crafter setup, the solver
+/// configuration, the resulting rotation and free-form notes.
+/// Stored on disk as RON in a `.raphael` file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationFile {
+    pub version: u32,
+    pub recipe_config: RecipeConfiguration,
+    pub selected_food: Option<Consumable>,
+    pub selected_potion: Option<Consumable>,
+    pub crafter_config: CrafterConfig,
+    pub solver_config: SolverConfig,
+    pub actions: Vec<Action>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+impl RotationFile {
+    pub const VERSION: u32 = 1;
+
+    pub fn file_name(&self) -> String {
+        let item_name = raphael_data::get_item_name(
+            self.recipe_config.recipe.item_id,
+            false,
+            raphael_data::Locale::EN,
+        );
+        let item_name = item_name
+            .trim_end_matches([' ', raphael_data::CL_ICON_CHAR])
+            .replace(|c: char| !c.is_alphanumeric(), "_");
+        format!("{}.{}", item_name, FILE_EXTENSION)
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .expect("RotationFile is always serializable")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let text = std::str::from_utf8(bytes).map_err(|_| "File is not valid UTF-8".to_string())?;
+        let file: Self = ron::from_str(text).map_err(|error| error.to_string())?;
+        if file.version > Self::VERSION {
+            return Err(format!(
+                "File was created by a newer version of Raphael (format version {})",
+                file.version
+            ));
+        }
+        Ok(file)
+    }
+}
@@ -0,0 +1,270 @@
+//! Embedded [Rhai](https://rhai.rs) scripting for automating repetitive solves, e.g. stat sweeps.
+//!
+//! Scripts start from a snapshot of the current recipe, crafter stats, consumables and solver
+//! configuration and run on a background thread, so the UI stays responsive.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use raphael_data::{Consumable, CrafterStats, Locale, Recipe, action_name, get_item_name};
+use raphael_sim::{Action, ActionMask, SimulationState};
+use raphael_solver::AtomicFlag;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map};
+
+use crate::app::SolverConfig;
+
+pub const HELP_TEXT: &str = "Available functions:
+solve() / solve(#{craftsmanship: .., control: .., cp: ..}): solve the recipe, optionally with different stats. Returns #{quality, progress, steps, duration, actions}.
+simulate([\"Muscle Memory\", ..]): simulate a list of actions. Returns #{progress, quality, durability, cp, errors}.
+stats(): current crafter stats.
+find_recipes(\"name\"): names of matching recipes.
+select_recipe(\"name\"): use the first matching recipe for subsequent calls, starting without HQ materials.
+print(..): write to the output.";
+
+pub const EXAMPLE_SCRIPT: &str = "for control in range(4700, 4810, 10) {
+    let result = solve(#{control: control});
+    print(`${control}: quality ${result.quality}, ${result.steps} steps`);
+}";
+
+/// Snapshot of the app state that a script starts from.
+#[derive(Debug, Clone, Copy)]
+pub struct ScriptContext {
+    pub recipe: Recipe,
+    pub crafter_stats: CrafterStats,
+    pub food: Option<Consumable>,
+    pub potion: Option<Consumable>,
+    pub solver_config: SolverConfig,
+    pub initial_quality: u16,
+    pub locale: Locale,
+}
+
+pub enum ScriptEvent {
+    Output(String),
+    Finished(Result<(), String>),
+}
+
+pub struct ScriptRun {
+    pub events: Receiver<ScriptEvent>,
+    interrupt_signal: AtomicFlag,
+}
+
+impl ScriptRun {
+    pub fn cancel(&self) {
+        self.interrupt_signal.set();
+    }
+}
+
+pub fn spawn_script(script: String, context: ScriptContext) -> ScriptRun {
+    let (tx, rx) = mpsc::channel();
+    let interrupt_signal = AtomicFlag::new();
+    let run = ScriptRun {
+        events: rx,
+        interrupt_signal: interrupt_signal.clone(),
+    };
+    std::thread::spawn(move || {
+        let result = run_script(&script, context, tx.clone(), interrupt_signal);
+        _ = tx.send(ScriptEvent::Finished(result));
+    });
+    run
+}
+
+fn run_script(
+    script: &str,
+    context: ScriptContext,
+    tx: Sender<ScriptEvent>,
+    interrupt_signal: AtomicFlag,
+) -> Result<(), String> {
+    let context = Rc::new(RefCell::new(context));
+    let mut engine = Engine::new();
+
+    engine.on_print(move |text| _ = tx.send(ScriptEvent::Output(text.to_string())));
+    let signal = interrupt_signal.clone();
+    engine.on_progress(move |_| signal.is_set().then_some(Dynamic::UNIT));
+
+    let (ctx, signal) = (context.clone(), interrupt_signal.clone());
+    engine.register_fn("solve", move || {
+        solve(&ctx.borrow(), ctx.borrow().crafter_stats, &signal)
+    });
+    let (ctx, signal) = (context.clone(), interrupt_signal);
+    engine.register_fn("solve", move |overrides: Map| {
+        let crafter_stats = with_overrides(ctx.borrow().crafter_stats, &overrides)?;
+        solve(&ctx.borrow(), crafter_stats, &signal)
+    });
+    let ctx = context.clone();
+    engine.register_fn("simulate", move |actions: Array| {
+        simulate(&ctx.borrow(), &actions)
+    });
+    let ctx = context.clone();
+    engine.register_fn("stats", move || stats_map(ctx.borrow().crafter_stats));
+    let ctx = context.clone();
+    engine.register_fn("find_recipes", move |query: &str| -> Array {
+        let locale = ctx.borrow().locale;
+        raphael_data::find_recipes(query, locale)
+            .into_iter()
+            .map(|index| recipe_name(&raphael_data::game_data().recipes()[index], locale).into())
+            .collect()
+    });
+    let ctx = context;
+    engine.register_fn(
+        "select_recipe",
+        move |query: &str| -> Result<String, Box<EvalAltResult>> {
+            let locale = ctx.borrow().locale;
+            let Some(&index) = raphael_data::find_recipes(query, locale).first() else {
+                return Err(format!("No recipe found for \"{query}\"").into());
+            };
+            let recipe = raphael_data::game_data().recipes()[index];
+            let mut context = ctx.borrow_mut();
+            context.recipe = recipe;
+            // the initial quality of the app comes from the HQ materials of its own recipe
+            context.initial_quality = 0;
+            Ok(recipe_name(&recipe, locale))
+        },
+    );
+
+    engine.run(script).map_err(|error| match *error {
+        EvalAltResult::ErrorTerminated(..) => "Script cancelled".to_string(),
+        error => error.to_string(),
+    })
+}
+
+fn recipe_name(recipe: &Recipe, locale: Locale) -> String {
+    get_item_name(recipe.item_id, false, locale)
+}
+
+fn parse_action(name: &str, locale: Locale) -> Option<Action> {
+    ActionMask::all().actions_iter().find(|action| {
+        action_name(*action, locale).eq_ignore_ascii_case(name)
+            || format!("{action:?}").eq_ignore_ascii_case(name)
+    })
+}
+
+fn stats_map(crafter_stats: CrafterStats) -> Map {
+    let mut map = Map::new();
+    map.insert(
+        "craftsmanship".into(),
+        i64::from(crafter_stats.craftsmanship).into(),
+    );
+    map.insert("control".into(), i64::from(crafter_stats.control).into());
+    map.insert("cp".into(), i64::from(crafter_stats.cp).into());
+    map.insert("level".into(), i64::from(crafter_stats.level).into());
+    map.insert("manipulation".into(), crafter_stats.manipulation.into());
+    map.insert("heart_and_soul".into(), crafter_stats.heart_and_soul.into());
+    map.insert(
+        "quick_innovation".into(),
+        crafter_stats.quick_innovation.into(),
+    );
+    map
+}
+
+fn with_overrides(
+    mut crafter_stats: CrafterStats,
+    overrides: &Map,
+) -> Result<CrafterStats, Box<EvalAltResult>> {
+    fn int<T: TryFrom<i64>>(key: &str, value: &Dynamic) -> Result<T, Box<EvalAltResult>> {
+        value
+            .as_int()
+            .ok()
+            .and_then(|value| T::try_from(value).ok())
+            .ok_or_else(|| format!("Invalid value for \"{key}\": {value}").into())
+    }
+    fn bool(key: &str, value: &Dynamic) -> Result<bool, Box<EvalAltResult>> {
+        value
+            .as_bool()
+            .map_err(|_| format!("Invalid value for \"{key}\": {value}").into())
+    }
+    for (key, value) in overrides {
+        match key.as_str() {
+            "craftsmanship" => crafter_stats.craftsmanship = int(key, value)?,
+            "control" => crafter_stats.control = int(key, value)?,
+            "cp" => crafter_stats.cp = int(key, value)?,
+            "level" => crafter_stats.level = int(key, value)?,
+            "manipulation" => crafter_stats.manipulation = bool(key, value)?,
+            "heart_and_soul" => crafter_stats.heart_and_soul = bool(key, value)?,
+            "quick_innovation" => crafter_stats.quick_innovation = bool(key, value)?,
+            _ => return Err(format!("Unknown stat \"{key}\"").into()),
+        }
+    }
+    Ok(crafter_stats)
+}
+
+fn solve(
+    context: &ScriptContext,
+    crafter_stats: CrafterStats,
+    interrupt_signal: &AtomicFlag,
+) -> Result<Map, Box<EvalAltResult>> {
+    let request = context.solver_config.solve_request(
+        context.recipe,
+        crafter_stats,
+        context.food,
+        context.potion,
+        context.initial_quality,
+    );
+    let solver_settings = request.simulator_settings();
+    let actions = request
+        .solve(interrupt_signal.clone())
+        .map_err(|error| format!("{error:?}"))?;
+
+    let (state, _) = SimulationState::from_macro_continue_on_error(&solver_settings, &actions);
+    let mut result = Map::new();
+    result.insert(
+        "quality".into(),
+        (i64::from(context.initial_quality) + i64::from(state.quality)).into(),
+    );
+    result.insert("progress".into(), i64::from(state.progress).into());
+    result.insert("steps".into(), (actions.len() as i64).into());
+    result.insert(
+        "duration".into(),
+        actions
+            .iter()
+            .map(|action| i64::from(action.time_cost()))
+            .sum::<i64>()
+            .into(),
+    );
+    result.insert(
+        "actions".into(),
+        actions
+            .iter()
+            .map(|action| Dynamic::from(action_name(*action, context.locale).to_string()))
+            .collect::<Array>()
+            .into(),
+    );
+    Ok(result)
+}
+
+fn simulate(context: &ScriptContext, actions: &Array) -> Result<Map, Box<EvalAltResult>> {
+    let actions = actions
+        .iter()
+        .map(|name| {
+            let name = name.to_string();
+            parse_action(&name, context.locale)
+                .ok_or_else(|| format!("Unknown action \"{name}\"").into())
+        })
+        .collect::<Result<Vec<_>, Box<EvalAltResult>>>()?;
+    let settings = raphael_data::get_game_settings(
+        context.recipe,
+        context.crafter_stats,
+        context.food,
+        context.potion,
+        context.solver_config.adversarial,
+    );
+    let (state, errors) = SimulationState::from_macro_continue_on_error(&settings, &actions);
+    let mut result = Map::new();
+    result.insert("progress".into(), i64::from(state.progress).into());
+    result.insert(
+        "quality".into(),
+        (i64::from(context.initial_quality) + i64::from(state.quality)).into(),
+    );
+    result.insert("durability".into(), i64::from(state.durability).into());
+    result.insert("cp".into(), i64::from(state.cp).into());
+    result.insert(
+        "errors".into(),
+        errors
+            .into_iter()
+            .filter_map(Result::err)
+            .map(|error| Dynamic::from(error.to_string()))
+            .collect::<Array>()
+            .into(),
+    );
+    Ok(result)
+}
@@ -0,0 +1,37 @@
+use raphael_sim::Settings;
+
+/// Parses simulator settings pasted from a bug report, either as JSON or in the format the
+/// settings are logged in when starting the solver, e.g.
+/// `Settings { max_cp: 627, max_durability: 70, ..., adversarial: false }`.
+/// Anything in front of the logged settings, such as the log prefix, is ignored.
+/// The settings of a debug bundle are used if a whole bundle is pasted.
+/// Settings the solver can't handle, e.g. a durability that isn't a multiple of 5, are rejected.
+pub fn parse_settings(text: &str) -> Result<Settings, String> {
+    let settings = parse_unchecked_settings(text)?;
+    settings
+        .validate()
+        .map_err(|error| format!("Invalid settings: {error}"))?;
+    Ok(settings)
+}
+
+fn parse_unchecked_settings(text: &str) -> Result<Settings, String> {
+    let text = text.trim();
+    if text.starts_with('{') {
+        let mut value: serde_json::Value =
+            serde_json::from_str(text).map_err(|error| format!("Invalid JSON: {error}"))?;
+        if let Some(settings) = value.get_mut("settings") {
+            value = settings.take();
+        }
+        return serde_json::from_value(value).map_err(|error| format!("Invalid settings: {error}"));
+    }
+    let start = text
+        .find("Settings")
+        .ok_or_else(|| "Expected JSON or logged settings".to_string())?;
+    // the debug output is valid RON once the struct braces are replaced by parentheses
+    let ron_text = text[start..].replace('{', "(").replace('}', ")");
+    ron::from_str(&ron_text).map_err(|error| format!("Invalid settings: {error}"))
+}
+
+pub fn settings_to_json(settings: &Settings) -> String {
+    serde_json::to_string_pretty(settings).unwrap()
+}
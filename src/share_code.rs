@@ -0,0 +1,141 @@
+use raphael_data::{Consumable, CrafterStats, Recipe, game_data};
+use raphael_sim::{Action, ActionMask};
+
+const PREFIX: &str = "RPH1";
+const SEPARATOR: char = '.';
+/// One character per action, indexed by the action's discriminant.
+const ACTION_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Compact text encoding of a rotation together with the recipe and crafter setup it was made for,
+/// short enough to fit in a QR code.
+///
+/// Only references the recipe and consumables by item ID, so the receiving side must have
+/// the same game data.
+#[derive(Debug, Clone)]
+pub struct ShareCode {
+    pub recipe: Recipe,
+    pub crafter_stats: CrafterStats,
+    pub food: Option<Consumable>,
+    pub potion: Option<Consumable>,
+    pub initial_quality: u16,
+    pub actions: Vec<Action>,
+}
+
+impl ShareCode {
+    pub fn encode(&self) -> String {
+        let consumable = |consumable: Option<Consumable>| match consumable {
+            Some(Consumable {
+                item_id, hq: true, ..
+            }) => format!("{item_id}H"),
+            Some(Consumable { item_id, .. }) => item_id.to_string(),
+            None => "-".to_string(),
+        };
+        let flags = u8::from(self.crafter_stats.manipulation)
+            | (u8::from(self.crafter_stats.heart_and_soul) << 1)
+            | (u8::from(self.crafter_stats.quick_innovation) << 2);
+        let actions: String = self
+            .actions
+            .iter()
+            .map(|action| char::from(ACTION_CHARS[*action as usize]))
+            .collect();
+        [
+            PREFIX.to_string(),
+            self.recipe.job_id.to_string(),
+            self.recipe.item_id.to_string(),
+            self.crafter_stats.craftsmanship.to_string(),
+            self.crafter_stats.control.to_string(),
+            self.crafter_stats.cp.to_string(),
+            self.crafter_stats.level.to_string(),
+            flags.to_string(),
+            consumable(self.food),
+            consumable(self.potion),
+            self.initial_quality.to_string(),
+            actions,
+        ]
+        .join(&SEPARATOR.to_string())
+    }
+
+    pub fn decode(code: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = code.trim().split(SEPARATOR).collect();
+        let [
+            prefix,
+            job_id,
+            item_id,
+            craftsmanship,
+            control,
+            cp,
+            level,
+            flags,
+            food,
+            potion,
+            initial_quality,
+            actions,
+        ] = fields.as_slice()
+        else {
+            return Err("Share code has the wrong number of fields".to_string());
+        };
+        if *prefix != PREFIX {
+            return Err("Not a Raphael share code".to_string());
+        }
+
+        let job_id: u8 = parse_number(job_id)?;
+        let item_id: u32 = parse_number(item_id)?;
+        let recipe = *game_data()
+            .recipes()
+            .iter()
+            .find(|recipe| recipe.job_id == job_id && recipe.item_id == item_id)
+            .ok_or_else(|| format!("Unknown recipe for item {item_id}"))?;
+
+        let flags: u8 = parse_number(flags)?;
+        let crafter_stats = CrafterStats {
+            craftsmanship: parse_number(craftsmanship)?,
+            control: parse_number(control)?,
+            cp: parse_number(cp)?,
+            level: parse_number(level)?,
+            manipulation: flags & 1 != 0,
+            heart_and_soul: flags & 2 != 0,
+            quick_innovation: flags & 4 != 0,
+        };
+
+        let actions = actions
+            .bytes()
+            .map(|byte| {
+                ActionMask::all()
+                    .actions_iter()
+                    .find(|action| ACTION_CHARS.get(*action as usize) == Some(&byte))
+                    .ok_or_else(|| format!("Unknown action '{}'", char::from(byte)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            recipe,
+            crafter_stats,
+            food: parse_consumable(food, game_data().meals())?,
+            potion: parse_consumable(potion, game_data().potions())?,
+            initial_quality: parse_number(initial_quality)?,
+            actions,
+        })
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(field: &str) -> Result<T, String> {
+    field
+        .parse()
+        .map_err(|_| format!("Invalid number \"{field}\""))
+}
+
+fn parse_consumable(field: &str, consumables: &[Consumable]) -> Result<Option<Consumable>, String> {
+    if field == "-" {
+        return Ok(None);
+    }
+    let (item_id, hq) = field
+        .strip_suffix('H')
+        .map_or((field, false), |item_id| (item_id, true));
+    let item_id: u32 = parse_number(item_id)?;
+    consumables
+        .iter()
+        .find(|consumable| consumable.item_id == item_id && consumable.hq == hq)
+        .copied()
+        .map(Some)
+        .ok_or_else(|| format!("Unknown consumable {item_id}"))
+}
@@ -0,0 +1,107 @@
+use crate::app::SolverConfig;
+use crate::share_code::ShareCode;
+
+/// Address of the web app that share links open.
+#[cfg(target_arch = "wasm32")]
+const APP_URL: &str = env!("BASE_URL");
+#[cfg(not(target_arch = "wasm32"))]
+const APP_URL: &str = "https://www.raphael-xiv.com";
+
+/// Query parameter with the [`ShareCode`], which only uses URL-safe characters.
+const SHARE_CODE_PARAM: &str = "s";
+/// Query parameter with the JSON of the [`SolverConfig`], encoded as URL-safe base64.
+const SOLVER_CONFIG_PARAM: &str = "c";
+
+/// Link that opens the web app with a rotation and everything needed to solve it again,
+/// e.g. `https://www.raphael-xiv.com/?s=RPH1.1.44104...&c=eyJxdWFsaXR5...`.
+#[derive(Debug, Clone)]
+pub struct ShareLink {
+    pub share_code: ShareCode,
+    /// `None` if the link doesn't contain a solver config, e.g. because it was the default.
+    pub solver_config: Option<SolverConfig>,
+}
+
+impl ShareLink {
+    pub fn to_url(&self) -> String {
+        let mut url = format!("{APP_URL}/?{SHARE_CODE_PARAM}={}", self.share_code.encode());
+        if let Some(solver_config) = self
+            .solver_config
+            .filter(|solver_config| *solver_config != SolverConfig::default())
+        {
+            let json = serde_json::to_string(&solver_config).unwrap();
+            url.push('&');
+            url.push_str(SOLVER_CONFIG_PARAM);
+            url.push('=');
+            url.push_str(&base64_url_encode(json.as_bytes()));
+        }
+        url
+    }
+
+    /// Reads the link from a full URL or just its query string, e.g. `?s=RPH1...`.
+    pub fn from_url(url: &str) -> Result<Self, String> {
+        let query = url
+            .trim()
+            .split_once('?')
+            .map_or(url.trim(), |(_, query)| query);
+        // the fragment isn't part of the query
+        let query = query.split('#').next().unwrap_or_default();
+        let mut share_code = None;
+        let mut solver_config = None;
+        for (key, value) in query.split('&').filter_map(|param| param.split_once('=')) {
+            match key {
+                SHARE_CODE_PARAM => share_code = Some(ShareCode::decode(value)?),
+                SOLVER_CONFIG_PARAM => {
+                    let json = base64_url_decode(value)
+                        .ok_or_else(|| "Invalid solver config in link".to_string())?;
+                    solver_config = Some(
+                        serde_json::from_slice(&json)
+                            .map_err(|error| format!("Invalid solver config in link: {error}"))?,
+                    );
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            share_code: share_code
+                .ok_or_else(|| "The link doesn't contain a rotation".to_string())?,
+            solver_config,
+        })
+    }
+}
+
+const BASE64_URL_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Base64 with the URL-safe alphabet and without padding.
+pub fn base64_url_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, byte)| {
+            bits | u32::from(*byte) << (16 - 8 * index)
+        });
+        for index in 0..=chunk.len() {
+            encoded.push(char::from(
+                BASE64_URL_ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize],
+            ));
+        }
+    }
+    encoded
+}
+
+pub fn base64_url_decode(text: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut bits = 0u32;
+        for (index, char) in chunk.iter().enumerate() {
+            let value = BASE64_URL_ALPHABET.iter().position(|c| c == char)?;
+            bits |= (value as u32) << (18 - 6 * index);
+        }
+        for index in 0..chunk.len() - 1 {
+            decoded.push((bits >> (16 - 8 * index)) as u8);
+        }
+    }
+    Some(decoded)
+}
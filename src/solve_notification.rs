@@ -0,0 +1,97 @@
+//! Notifies the user when a solve finishes or fails, as long solves are often left running
+//! in the background.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SolveNotificationConfig {
+    /// Play a short beep.
+    pub sound: bool,
+    /// Show a browser notification on web, flash the taskbar entry or bounce the dock icon
+    /// on native.
+    pub notification: bool,
+}
+
+impl SolveNotificationConfig {
+    pub fn notify(&self, ctx: &egui::Context, message: &str) {
+        if self.sound {
+            play_sound();
+        }
+        if self.notification {
+            show_notification(ctx, message);
+        }
+    }
+}
+
+const BEEP_FREQUENCY: f32 = 880.0;
+const BEEP_VOLUME: f32 = 0.2;
+const BEEP_DURATION: std::time::Duration = std::time::Duration::from_millis(200);
+
+#[cfg(not(target_arch = "wasm32"))]
+fn play_sound() {
+    use rodio::Source;
+    std::thread::spawn(|| {
+        let Ok((_stream, handle)) = rodio::OutputStream::try_default() else {
+            log::warn!("No audio output device available");
+            return;
+        };
+        match rodio::Sink::try_new(&handle) {
+            Ok(sink) => {
+                sink.append(
+                    rodio::source::SineWave::new(BEEP_FREQUENCY)
+                        .take_duration(BEEP_DURATION)
+                        .amplify(BEEP_VOLUME),
+                );
+                sink.sleep_until_end();
+            }
+            Err(error) => log::warn!("Failed to play sound: {error}"),
+        }
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+fn play_sound() {
+    let play = || -> Result<(), web_sys::wasm_bindgen::JsValue> {
+        let audio_context = web_sys::AudioContext::new()?;
+        let oscillator = audio_context.create_oscillator()?;
+        oscillator.frequency().set_value(BEEP_FREQUENCY);
+        let gain = audio_context.create_gain()?;
+        gain.gain().set_value(BEEP_VOLUME);
+        oscillator.connect_with_audio_node(&gain)?;
+        gain.connect_with_audio_node(&audio_context.destination())?;
+        oscillator.start()?;
+        oscillator.stop_with_when(audio_context.current_time() + BEEP_DURATION.as_secs_f64())?;
+        Ok(())
+    };
+    if let Err(error) = play() {
+        log::warn!("Failed to play sound: {error:?}");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn show_notification(ctx: &egui::Context, _message: &str) {
+    ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+        egui::UserAttentionType::Informational,
+    ));
+}
+
+#[cfg(target_arch = "wasm32")]
+fn show_notification(_ctx: &egui::Context, message: &str) {
+    if web_sys::Notification::permission() != web_sys::NotificationPermission::Granted {
+        return;
+    }
+    let options = web_sys::NotificationOptions::new();
+    options.set_body(message);
+    if let Err(error) = web_sys::Notification::new_with_options("Raphael", &options) {
+        log::warn!("Failed to show notification: {error:?}");
+    }
+}
+
+/// Asks the browser for permission to show notifications. Does nothing on native.
+pub fn request_notification_permission() {
+    #[cfg(target_arch = "wasm32")]
+    if web_sys::Notification::permission() == web_sys::NotificationPermission::Default {
+        _ = web_sys::Notification::request_permission();
+    }
+}
@@ -0,0 +1,78 @@
+//! Progress of a stopped solve, saved to a file so that the solve can continue after the app
+//! was closed. The file contains a bincode-encoded [`ProgressHeader`] followed by the
+//! bincode-encoded [`SolverCheckpoint`], so the header can be read without the checkpoint.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use raphael_sim::{Action, Settings};
+use raphael_solver::SolverCheckpoint;
+use serde::{Deserialize, Serialize};
+
+use crate::app::SolverConfig;
+
+pub const FILE_EXTENSION: &str = "raphael-progress";
+
+/// What the solve was started with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressHeader {
+    pub version: u32,
+    /// `.raphael` file of the app when the progress was saved, for restoring the recipe and
+    /// crafter setup.
+    pub rotation: String,
+    pub settings: Settings,
+    pub config: SolverConfig,
+    pub prefix: Vec<Action>,
+}
+
+impl ProgressHeader {
+    pub const VERSION: u32 = 1;
+}
+
+fn read_error(path: &Path, error: impl std::fmt::Display) -> String {
+    format!("Failed to read {}: {}", path.display(), error)
+}
+
+fn write_error(path: &Path, error: impl std::fmt::Display) -> String {
+    format!("Failed to write {}: {}", path.display(), error)
+}
+
+pub fn write_progress_file(
+    path: &Path,
+    header: &ProgressHeader,
+    checkpoint: &SolverCheckpoint,
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|error| write_error(path, error))?;
+    let mut writer = BufWriter::new(file);
+    bincode::serialize_into(&mut writer, header).map_err(|error| write_error(path, error))?;
+    bincode::serialize_into(&mut writer, checkpoint).map_err(|error| write_error(path, error))?;
+    writer.flush().map_err(|error| write_error(path, error))
+}
+
+fn read_header_from(path: &Path, reader: &mut BufReader<File>) -> Result<ProgressHeader, String> {
+    let header: ProgressHeader =
+        bincode::deserialize_from(reader).map_err(|error| read_error(path, error))?;
+    if header.version != ProgressHeader::VERSION {
+        return Err(format!(
+            "{} was saved by a different version of Raphael",
+            path.display()
+        ));
+    }
+    Ok(header)
+}
+
+/// Reads only the header, which is small compared to the checkpoint.
+pub fn read_header(path: &Path) -> Result<ProgressHeader, String> {
+    let file = File::open(path).map_err(|error| read_error(path, error))?;
+    read_header_from(path, &mut BufReader::new(file))
+}
+
+pub fn read_progress_file(path: &Path) -> Result<(ProgressHeader, SolverCheckpoint), String> {
+    let file = File::open(path).map_err(|error| read_error(path, error))?;
+    let mut reader = BufReader::new(file);
+    let header = read_header_from(path, &mut reader)?;
+    let checkpoint =
+        bincode::deserialize_from(&mut reader).map_err(|error| read_error(path, error))?;
+    Ok((header, checkpoint))
+}
@@ -0,0 +1,308 @@
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::share_link::{base64_url_decode, base64_url_encode};
+
+/// Values whose RON is longer than this are stored compressed, e.g. the saved rotations, which
+/// would otherwise take up a large part of the browser's local storage quota.
+const COMPRESSION_THRESHOLD: usize = 16 * 1024;
+/// Longest string that a compressed value stores under a single key. Longer values are split
+/// into chunks stored under `<key>#<index>`.
+const CHUNK_LEN: usize = 512 * 1024;
+/// The key of a compressed value holds this header followed by the number of chunks.
+const COMPRESSED_HEADER: &str = "RAPHAEL_DEFLATE1 ";
+/// Compression level of `miniz_oxide`, from 0 (fastest) to 10 (smallest).
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// Thin wrapper around the eframe key-value store so that the app doesn't depend on
+/// where (or whether) its state is persisted.
+pub struct Storage<'a> {
+    inner: Option<&'a dyn eframe::Storage>,
+}
+
+impl<'a> Storage<'a> {
+    pub fn new(inner: Option<&'a dyn eframe::Storage>) -> Self {
+        Self { inner }
+    }
+
+    /// Loads the value stored by [`store`]. Values stored uncompressed by older versions are
+    /// still loaded, and compressed the next time they are stored.
+    pub fn load<T: DeserializeOwned>(&self, key: &'static str, default: T) -> T {
+        let Some(storage) = self.inner else {
+            return default;
+        };
+        if compressed_chunk_count(storage, key).is_none() {
+            return eframe::get_value(storage, key).unwrap_or(default);
+        }
+        match load_compressed(storage, key) {
+            Ok(value) => value,
+            Err(error) => {
+                log::warn!("Failed to load {key}: {error}");
+                default
+            }
+        }
+    }
+}
+
+/// Stores `value` as RON, compressed and split into chunks if it is long.
+pub fn store<T: Serialize>(storage: &mut dyn eframe::Storage, key: &'static str, value: &T) {
+    let previous_chunk_count = compressed_chunk_count(storage, key).unwrap_or(0);
+    let chunk_count = match ron::ser::to_string(value) {
+        Ok(ron) if ron.len() > COMPRESSION_THRESHOLD => {
+            let compressed =
+                miniz_oxide::deflate::compress_to_vec(ron.as_bytes(), COMPRESSION_LEVEL);
+            let encoded = base64_url_encode(&compressed);
+            let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(CHUNK_LEN).collect();
+            for (index, chunk) in chunks.iter().enumerate() {
+                let chunk = String::from_utf8_lossy(chunk).into_owned();
+                storage.set_string(&chunk_key(key, index), chunk);
+            }
+            storage.set_string(key, format!("{COMPRESSED_HEADER}{}", chunks.len()));
+            chunks.len()
+        }
+        _ => {
+            eframe::set_value(storage, key, value);
+            0
+        }
+    };
+    // the storage can't remove keys, so the chunks left over from a longer value are emptied
+    for index in chunk_count..previous_chunk_count {
+        storage.set_string(&chunk_key(key, index), String::new());
+    }
+}
+
+fn chunk_key(key: &str, index: usize) -> String {
+    format!("{key}#{index}")
+}
+
+/// Number of chunks of the value stored under `key`, `None` if the value isn't compressed.
+fn compressed_chunk_count(storage: &dyn eframe::Storage, key: &str) -> Option<usize> {
+    storage
+        .get_string(key)?
+        .strip_prefix(COMPRESSED_HEADER)?
+        .parse()
+        .ok()
+}
+
+fn load_compressed<T: DeserializeOwned>(
+    storage: &dyn eframe::Storage,
+    key: &str,
+) -> Result<T, String> {
+    let chunk_count = compressed_chunk_count(storage, key).ok_or("missing header")?;
+    let encoded = (0..chunk_count)
+        .map(|index| storage.get_string(&chunk_key(key, index)))
+        .collect::<Option<String>>()
+        .ok_or("missing chunk")?;
+    let compressed = base64_url_decode(&encoded).ok_or("invalid base64")?;
+    let ron = miniz_oxide::inflate::decompress_to_vec(&compressed)
+        .map_err(|error| format!("invalid compressed data: {error}"))?;
+    let ron = String::from_utf8(ron).map_err(|error| error.to_string())?;
+    ron::from_str(&ron).map_err(|error| error.to_string())
+}
+
+/// Where the native app persists its state.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StorageLocation {
+    /// The platform's default data directory, as chosen by eframe.
+    #[default]
+    Default,
+    /// A `data` folder next to the executable.
+    Portable,
+    /// A user-specified data directory.
+    Custom(std::path::PathBuf),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StorageLocation {
+    pub const DATA_DIR_ENV_VAR: &str = "RAPHAEL_DATA_DIR";
+    /// Placing a file with this name next to the executable enables portable mode.
+    pub const PORTABLE_MARKER_FILE: &str = "portable.txt";
+
+    /// Determines the storage location from the command line arguments (`--portable` or
+    /// `--data-dir <DIR>`), the `RAPHAEL_DATA_DIR` environment variable or the presence
+    /// of a portable marker file, in that order. Recognized arguments are removed from `args`.
+    pub fn from_env(args: &mut Vec<std::ffi::OsString>) -> Result<Self, String> {
+        if let Some(index) = args.iter().position(|arg| arg == "--portable") {
+            args.remove(index);
+            return Ok(Self::Portable);
+        }
+        if let Some(index) = args.iter().position(|arg| arg == "--data-dir") {
+            args.remove(index);
+            if index >= args.len() {
+                return Err("Missing directory after `--data-dir`".to_string());
+            }
+            return Ok(Self::Custom(args.remove(index).into()));
+        }
+        if let Some(dir) = std::env::var_os(Self::DATA_DIR_ENV_VAR) {
+            return Ok(Self::Custom(dir.into()));
+        }
+        match executable_dir() {
+            Some(dir) if dir.join(Self::PORTABLE_MARKER_FILE).exists() => Ok(Self::Portable),
+            _ => Ok(Self::Default),
+        }
+    }
+
+    /// Path of the file that eframe should persist to, or `None` to use eframe's default.
+    pub fn persistence_path(&self) -> Result<Option<std::path::PathBuf>, String> {
+        let dir = match self {
+            Self::Default => return Ok(None),
+            Self::Portable => executable_dir()
+                .ok_or("Unable to locate the executable directory")?
+                .join("data"),
+            Self::Custom(dir) => dir.clone(),
+        };
+        std::fs::create_dir_all(&dir)
+            .map_err(|error| format!("Unable to create {}: {}", dir.display(), error))?;
+        Ok(Some(dir.join("app.ron")))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn executable_dir() -> Option<std::path::PathBuf> {
+    Some(std::env::current_exe().ok()?.parent()?.to_path_buf())
+}
+
+/// Coordinates persistence between multiple tabs sharing the same local storage.
+///
+/// Only one tab (the writer) persists its state. The writer refreshes a heartbeat in local storage;
+/// other tabs may take over once the heartbeat expires (e.g. the writer tab was closed) or when
+/// the user explicitly requests it.
+#[cfg(target_arch = "wasm32")]
+pub struct TabLock {
+    tab_id: String,
+    last_heartbeat: f64,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl TabLock {
+    const KEY: &str = "RAPHAEL_TAB_LOCK";
+    const HEARTBEAT_INTERVAL_MS: f64 = 1000.0;
+    const TIMEOUT_MS: f64 = 5000.0;
+
+    pub fn new() -> Self {
+        Self {
+            tab_id: format!("{:016x}", (js_sys::Math::random() * u64::MAX as f64) as u64),
+            last_heartbeat: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Returns `true` if this tab is the writer, acquiring the lock if it is not held by another live tab.
+    pub fn try_acquire(&mut self) -> bool {
+        let now = js_sys::Date::now();
+        let lock = eframe::web::storage::local_storage_get(Self::KEY);
+        let is_free = match lock.as_deref().and_then(|lock| lock.split_once(' ')) {
+            Some((tab_id, _)) if tab_id == self.tab_id => true,
+            Some((_, heartbeat)) => heartbeat
+                .parse::<f64>()
+                .map_or(true, |heartbeat| now - heartbeat > Self::TIMEOUT_MS),
+            None => true,
+        };
+        if is_free && now - self.last_heartbeat >= Self::HEARTBEAT_INTERVAL_MS {
+            self.write_heartbeat(now);
+        }
+        is_free
+    }
+
+    /// Takes over the lock from another tab.
+    pub fn force_acquire(&mut self) {
+        self.write_heartbeat(js_sys::Date::now());
+    }
+
+    fn write_heartbeat(&mut self, now: f64) {
+        eframe::web::storage::local_storage_set(Self::KEY, &format!("{} {}", self.tab_id, now));
+        self.last_heartbeat = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MemoryStorage(HashMap<String, String>);
+
+    impl eframe::Storage for MemoryStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_owned(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    const KEY: &str = "VALUES";
+
+    /// Pseudo-random numbers, which barely compress.
+    fn random_values(len: usize) -> Vec<u64> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            })
+            .collect()
+    }
+
+    fn load(storage: &MemoryStorage) -> Vec<u64> {
+        Storage::new(Some(storage)).load(KEY, Vec::new())
+    }
+
+    #[test]
+    fn compressed_round_trip() {
+        let values = random_values(2_000);
+        assert!(ron::ser::to_string(&values).unwrap().len() > COMPRESSION_THRESHOLD);
+        let mut storage = MemoryStorage::default();
+        store(&mut storage, KEY, &values);
+        assert_eq!(compressed_chunk_count(&storage, KEY), Some(1));
+        assert_eq!(load(&storage), values);
+    }
+
+    #[test]
+    fn chunked_round_trip() {
+        let values = random_values(200_000);
+        let mut storage = MemoryStorage::default();
+        store(&mut storage, KEY, &values);
+        let chunk_count = compressed_chunk_count(&storage, KEY).unwrap();
+        assert!(chunk_count > 1);
+        for index in 0..chunk_count {
+            assert!(storage.0[&chunk_key(KEY, index)].len() <= CHUNK_LEN);
+        }
+        assert_eq!(load(&storage), values);
+    }
+
+    #[test]
+    fn shrink_to_fewer_chunks() {
+        let mut storage = MemoryStorage::default();
+        store(&mut storage, KEY, &random_values(200_000));
+        let chunk_count = compressed_chunk_count(&storage, KEY).unwrap();
+
+        let values = random_values(2_000);
+        store(&mut storage, KEY, &values);
+        assert_eq!(compressed_chunk_count(&storage, KEY), Some(1));
+        for index in 1..chunk_count {
+            assert_eq!(storage.0[&chunk_key(KEY, index)], "");
+        }
+        assert_eq!(load(&storage), values);
+
+        let values = vec![1, 2, 3];
+        store(&mut storage, KEY, &values);
+        assert_eq!(compressed_chunk_count(&storage, KEY), None);
+        assert_eq!(storage.0[&chunk_key(KEY, 0)], "");
+        assert_eq!(load(&storage), values);
+    }
+
+    #[test]
+    fn load_uncompressed_value() {
+        let values = random_values(2_000);
+        let mut storage = MemoryStorage::default();
+        eframe::set_value(&mut storage, KEY, &values);
+        assert_eq!(load(&storage), values);
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::mpsc::{self, Receiver};
+
+use serde::Deserialize;
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/KonaeAkira/raphael-rs/releases/latest";
+
+#[derive(Debug, Clone)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+    html_url: String,
+}
+
+/// Queries the latest GitHub release in the background.
+/// The receiver yields a value only if the release is newer than the running build.
+pub fn spawn_update_check(ctx: egui::Context) -> Receiver<UpdateInfo> {
+    let (tx, rx) = mpsc::channel();
+    let request = ehttp::Request {
+        headers: ehttp::Headers::new(&[
+            ("Accept", "application/vnd.github+json"),
+            ("User-Agent", "raphael-xiv"),
+        ]),
+        ..ehttp::Request::get(LATEST_RELEASE_URL)
+    };
+    ehttp::fetch(request, move |result| {
+        let release = result.and_then(|response| match response.ok {
+            true => serde_json::from_slice::<Release>(&response.bytes)
+                .map_err(|error| error.to_string()),
+            false => Err(format!("{} {}", response.status, response.status_text)),
+        });
+        match release {
+            Ok(release) if is_newer(&release.tag_name, env!("CARGO_PKG_VERSION")) => {
+                let update = UpdateInfo {
+                    version: release.tag_name,
+                    url: release.html_url,
+                };
+                if tx.send(update).is_ok() {
+                    ctx.request_repaint();
+                }
+            }
+            Ok(_) => log::info!("Raphael is up to date"),
+            Err(error) => log::warn!("Update check failed: {error}"),
+        }
+    });
+    rx
+}
+
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.trim_start_matches('v').splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.split(['-', '+']).next()?.parse().ok()?;
+    Some((major, minor, patch))
+}
+
+fn is_newer(release: &str, current: &str) -> bool {
+    match (parse_version(release), parse_version(current)) {
+        (Some(release), Some(current)) => release > current,
+        _ => false,
+    }
+}
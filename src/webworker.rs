@@ -1,9 +1,6 @@
-#[cfg(target_arch = "wasm32")]
-use gloo_worker::Registrable;
-
 #[cfg(target_arch = "wasm32")]
 fn main() {
-    raphael_xiv::Worker::registrar().register();
+    raphael_xiv::register_worker();
     // Redirect `log` message to `console.log` and friends:
     eframe::WebLogger::init(log::LevelFilter::Debug).ok();
 }
@@ -1,11 +1,116 @@
-use egui_plot::PlotPoints;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver};
+
 use raphael_sim::*;
 
 use crate::config::QualityTarget;
 
 use super::util;
 
+/// Seed of the simulated crafts, fixed so that the analysis of a rotation doesn't change between
+/// runs.
+const SEED: u64 = 0;
+const HISTOGRAM_BINS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+struct AnalysisInput {
+    settings: Settings,
+    actions: Vec<Action>,
+    rollout_count: usize,
+}
+
+impl AnalysisInput {
+    fn run(&self) -> Vec<RolloutResult> {
+        rollout_batch(
+            &self.settings,
+            SimulationState::new(&self.settings),
+            &self.actions,
+            SEED,
+            self.rollout_count,
+        )
+    }
+}
+
+/// Results of simulating the current rotation many times with random conditions.
+pub struct SolutionAnalysisData {
+    rollout_count: usize,
+    /// Input of the results, or of the pending run if there is one.
+    input: Option<AnalysisInput>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pending: Option<Receiver<Vec<RolloutResult>>>,
+    results: Vec<RolloutResult>,
+}
+
+impl Default for SolutionAnalysisData {
+    fn default() -> Self {
+        Self {
+            rollout_count: 10_000,
+            input: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending: None,
+            results: Vec::new(),
+        }
+    }
+}
+
+impl SolutionAnalysisData {
+    fn is_pending(&self) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.pending.is_some();
+        #[cfg(target_arch = "wasm32")]
+        return false;
+    }
+
+    /// Starts a new run if the input changed since the last one.
+    fn update(&mut self, ctx: &egui::Context, input: AnalysisInput) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(pending) = self.pending.as_ref() {
+            match pending.try_recv() {
+                Ok(results) => {
+                    self.results = results;
+                    self.pending = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.pending = None,
+                Err(mpsc::TryRecvError::Empty) => return,
+            }
+        }
+        if self.input.as_ref() == Some(&input) {
+            return;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let (tx, rx) = mpsc::channel();
+            let run_input = input.clone();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                _ = tx.send(run_input.run());
+                ctx.request_repaint();
+            });
+            self.pending = Some(rx);
+        }
+        // the simulation is cheap enough to run within a frame on the web
+        #[cfg(target_arch = "wasm32")]
+        {
+            _ = ctx;
+            self.results = input.run();
+        }
+        self.input = Some(input);
+    }
+
+    /// Fraction of the simulated crafts that finish with at least `quality`.
+    fn chance_at_least(&self, initial_quality: u16, quality: u16) -> f32 {
+        let count = self
+            .results
+            .iter()
+            .filter(|result| result.finished)
+            .filter(|result| result.quality.saturating_add(initial_quality) >= quality)
+            .count();
+        count as f32 / self.results.len().max(1) as f32
+    }
+}
+
 pub struct SolutionAnalysis<'a> {
+    data: &'a mut SolutionAnalysisData,
     settings: Settings,
     initial_quality: u16,
     target_quality: u16,
@@ -15,6 +120,7 @@ pub struct SolutionAnalysis<'a> {
 
 impl<'a> SolutionAnalysis<'a> {
     pub fn new(
+        data: &'a mut SolutionAnalysisData,
         settings: Settings,
         initial_quality: u16,
         target_quality: u16,
@@ -22,6 +128,7 @@ impl<'a> SolutionAnalysis<'a> {
         is_expert: bool,
     ) -> Self {
         Self {
+            data,
             settings: Settings {
                 adversarial: false,
                 ..settings
@@ -32,6 +139,112 @@ impl<'a> SolutionAnalysis<'a> {
             is_expert,
         }
     }
+
+    fn show_histogram(&self, ui: &mut egui::Ui) {
+        let max_quality = self.settings.max_quality.max(1);
+        let mut bins = [0usize; HISTOGRAM_BINS];
+        let mut failed = 0;
+        for result in &self.data.results {
+            match result.finished {
+                true => {
+                    let quality = result
+                        .quality
+                        .saturating_add(self.initial_quality)
+                        .min(max_quality);
+                    let bin = usize::from(quality) * HISTOGRAM_BINS / usize::from(max_quality);
+                    bins[bin.min(HISTOGRAM_BINS - 1)] += 1;
+                }
+                false => failed += 1,
+            }
+        }
+        let max_count = bins.iter().copied().max().unwrap_or_default().max(1);
+
+        let label_height = 16.0;
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(ui.available_width(), 160.0),
+            egui::Sense::hover(),
+        );
+        let plot_rect = egui::Rect::from_min_max(
+            response.rect.min,
+            response.rect.max - egui::vec2(0.0, label_height),
+        );
+        let to_x = |quality: u16| {
+            egui::lerp(
+                plot_rect.x_range(),
+                f32::from(quality) / f32::from(max_quality),
+            )
+        };
+
+        let visuals = ui.visuals();
+        let text_color = visuals.text_color();
+        let font = egui::FontId::monospace(10.0);
+        painter.rect_stroke(
+            plot_rect,
+            0.0,
+            visuals.widgets.noninteractive.bg_stroke,
+            egui::StrokeKind::Inside,
+        );
+        let bin_width = plot_rect.width() / HISTOGRAM_BINS as f32;
+        for (index, count) in bins.iter().enumerate() {
+            let height = plot_rect.height() * *count as f32 / max_count as f32;
+            let left = plot_rect.left() + bin_width * index as f32;
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(left + 1.0, plot_rect.bottom() - height),
+                    egui::pos2(left + bin_width - 1.0, plot_rect.bottom()),
+                ),
+                0.0,
+                visuals.hyperlink_color,
+            );
+        }
+        let target_x = to_x(self.target_quality.min(max_quality));
+        painter.line_segment(
+            [
+                egui::pos2(target_x, plot_rect.top()),
+                egui::pos2(target_x, plot_rect.bottom()),
+            ],
+            egui::Stroke::new(1.5, visuals.warn_fg_color),
+        );
+        painter.text(
+            plot_rect.left_bottom() + egui::vec2(0.0, 2.0),
+            egui::Align2::LEFT_TOP,
+            "0",
+            font.clone(),
+            text_color,
+        );
+        painter.text(
+            plot_rect.right_bottom() + egui::vec2(0.0, 2.0),
+            egui::Align2::RIGHT_TOP,
+            format!("{max_quality} Quality"),
+            font,
+            text_color,
+        );
+
+        if let Some(position) = response.hover_pos() {
+            let index = ((position.x - plot_rect.left()) / bin_width).floor() as usize;
+            if let Some(count) = bins.get(index) {
+                let bin_quality =
+                    |index: usize| (usize::from(max_quality) * index / HISTOGRAM_BINS) as u16;
+                response.on_hover_ui_at_pointer(|ui| {
+                    ui.label(format!(
+                        "Quality {}–{}: {:.1}%",
+                        bin_quality(index),
+                        bin_quality(index + 1),
+                        *count as f32 * 100.0 / self.data.results.len() as f32
+                    ));
+                });
+            }
+        }
+        if failed != 0 {
+            ui.label(
+                egui::RichText::new(format!(
+                    "⚠ {:.1}% of the simulated crafts don't reach the max Progress",
+                    failed as f32 * 100.0 / self.data.results.len() as f32
+                ))
+                .color(ui.visuals().warn_fg_color),
+            );
+        }
+    }
 }
 
 impl egui::Widget for SolutionAnalysis<'_> {
@@ -40,13 +253,19 @@ impl egui::Widget for SolutionAnalysis<'_> {
             ui.set_width(ui.available_width());
             ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
 
-            let mut collapsed = true;
-            let distribution = raphael_sim::quality_probability_distribution(
-                self.settings,
-                self.actions,
-                self.initial_quality,
-            );
+            let is_available = !self.is_expert && !self.actions.is_empty();
+            if is_available {
+                self.data.update(
+                    ui.ctx(),
+                    AnalysisInput {
+                        settings: self.settings,
+                        actions: self.actions.to_vec(),
+                        rollout_count: self.data.rollout_count,
+                    },
+                );
+            }
 
+            let mut collapsed = true;
             ui.vertical(|ui| {
                 ui.horizontal(|ui| {
                     util::collapse_persisted(
@@ -59,77 +278,63 @@ impl egui::Widget for SolutionAnalysis<'_> {
                         ui.label("N/A (Expert recipes not supported)");
                     } else if self.actions.is_empty() {
                         ui.label("N/A (No macro to analyze)");
+                    } else if self.data.is_pending() {
+                        util::busy_indicator(ui);
+                        ui.ctx()
+                            .request_repaint_after(util::background_repaint_interval(ui.ctx()));
                     } else {
                         ui.label(format!(
-                            "{:.2}% chance to reach target Quality ({})",
-                            distribution.at_least(self.target_quality) * 100.0,
+                            "{:.1}% chance to reach target Quality ({})",
+                            self.data
+                                .chance_at_least(self.initial_quality, self.target_quality)
+                                * 100.0,
                             self.target_quality
                         ));
                     }
                 });
-                if collapsed || self.is_expert {
+                if collapsed || !is_available {
                     return;
                 }
                 ui.separator();
 
-                let plot_max_quality =
-                    std::cmp::max(self.settings.max_quality, self.target_quality);
-                let mut prob_acc = distribution.at_least(plot_max_quality) as f64;
-
-                let mut plot_points = vec![
-                    [plot_max_quality as f64, 0.0],
-                    [plot_max_quality as f64, prob_acc],
-                ];
-                for value in distribution
-                    .into_iter()
-                    .rev()
-                    .filter(|value| value.quality < plot_max_quality)
-                {
-                    plot_points.push([value.quality as f64, prob_acc]);
-                    prob_acc = (prob_acc + value.probability as f64).clamp(0.0, 1.0);
-                    plot_points.push([value.quality as f64, prob_acc]);
+                ui.horizontal(|ui| {
+                    ui.label("Simulated crafts:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.data.rollout_count)
+                            .range(100..=100_000)
+                            .speed(100),
+                    );
+                    ui.add(super::HelpText::new("The rotation is simulated with random conditions and random success of actions that can fail. Conditions follow the probabilities of regular recipes."));
+                });
+                if self.data.is_pending() || self.data.results.is_empty() {
+                    return;
                 }
-                plot_points.push([0.0, prob_acc]);
-                plot_points.push([0.0, 1.0]);
-                let line_chart = egui_plot::Line::new(PlotPoints::from(plot_points));
-
-                let grid_marks = vec![
-                    egui_plot::GridMark {
-                        value: 0.0,
-                        step_size: self.settings.max_quality as f64,
-                    },
-                    egui_plot::GridMark {
-                        value: QualityTarget::CollectableT1.get_target(self.settings.max_quality)
-                            as f64,
-                        step_size: self.settings.max_quality as f64,
-                    },
-                    egui_plot::GridMark {
-                        value: QualityTarget::CollectableT2.get_target(self.settings.max_quality)
-                            as f64,
-                        step_size: self.settings.max_quality as f64,
-                    },
-                    egui_plot::GridMark {
-                        value: QualityTarget::CollectableT3.get_target(self.settings.max_quality)
-                            as f64,
-                        step_size: self.settings.max_quality as f64,
-                    },
-                    egui_plot::GridMark {
-                        value: self.settings.max_quality as f64,
-                        step_size: self.settings.max_quality as f64,
-                    },
-                ];
-
-                egui_plot::Plot::new("quality_probability_distribution")
-                    .x_grid_spacer(|_| grid_marks.clone())
-                    .height(240.0)
-                    .clamp_grid(true)
-                    .allow_drag(false)
-                    .allow_scroll(false)
-                    .allow_boxed_zoom(false)
-                    .allow_zoom(false)
-                    .show(ui, |plot_ui| {
-                        plot_ui.line(line_chart);
+
+                egui::Grid::new("analysis_tiers")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Target");
+                        ui.label("Quality");
+                        ui.label("Chance");
+                        ui.end_row();
+                        for target in [
+                            QualityTarget::CollectableT1,
+                            QualityTarget::CollectableT2,
+                            QualityTarget::CollectableT3,
+                            QualityTarget::Full,
+                        ] {
+                            let quality = target.get_target(self.settings.max_quality);
+                            ui.label(target.to_string());
+                            ui.label(quality.to_string());
+                            ui.label(format!(
+                                "{:.1}%",
+                                self.data.chance_at_least(self.initial_quality, quality) * 100.0
+                            ));
+                            ui.end_row();
+                        }
                     });
+                ui.separator();
+                self.show_histogram(ui);
             });
         })
         .response
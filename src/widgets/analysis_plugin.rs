@@ -0,0 +1,126 @@
+use raphael_data::{Locale, action_name};
+use raphael_sim::{Action, Settings, SimulationState};
+
+use super::util;
+
+/// Extension point for additional analysis panels.
+///
+/// Registered plugins are notified after each successful solve and get their own
+/// collapsible panel below the simulator.
+pub trait AnalysisPlugin {
+    /// Title of the panel. Also used to persist whether the panel is collapsed.
+    fn name(&self) -> &str;
+
+    /// Called after each successful solve with the simulator settings used by the solver,
+    /// the resulting actions and the final state of the simulation.
+    fn on_solve(&mut self, settings: &Settings, actions: &[Action], state: &SimulationState);
+
+    /// Draws the contents of the panel, with action names in the given locale.
+    fn ui(&mut self, ui: &mut egui::Ui, locale: Locale);
+}
+
+pub struct AnalysisPlugins {
+    plugins: Vec<Box<dyn AnalysisPlugin>>,
+}
+
+impl Default for AnalysisPlugins {
+    fn default() -> Self {
+        Self {
+            plugins: vec![
+                Box::new(ActionUsagePlugin::default()),
+                Box::new(super::DurabilityRestorationPlugin::default()),
+                #[cfg(not(target_arch = "wasm32"))]
+                Box::new(super::TradeoffCurvePlugin::default()),
+            ],
+        }
+    }
+}
+
+impl AnalysisPlugins {
+    pub fn register(&mut self, plugin: Box<dyn AnalysisPlugin>) {
+        self.plugins.push(plugin);
+    }
+
+    pub fn on_solve(&mut self, settings: &Settings, actions: &[Action]) {
+        let (state, _errors) = SimulationState::from_macro_continue_on_error(settings, actions);
+        for plugin in &mut self.plugins {
+            plugin.on_solve(settings, actions, &state);
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, locale: Locale) {
+        for plugin in &mut self.plugins {
+            ui.group(|ui| {
+                ui.set_width(ui.available_width());
+                ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+                let mut collapsed = true;
+                ui.horizontal(|ui| {
+                    util::collapse_persisted(
+                        ui,
+                        egui::Id::new("analysis_plugin_collapsed").with(plugin.name()),
+                        &mut collapsed,
+                    );
+                    ui.label(egui::RichText::new(plugin.name()).strong());
+                });
+                if !collapsed {
+                    ui.separator();
+                    plugin.ui(ui, locale);
+                }
+            });
+        }
+    }
+}
+
+/// Shows how often each action is used in the last solution and how much CP it costs in total.
+#[derive(Default)]
+pub struct ActionUsagePlugin {
+    usage: Vec<(Action, usize, i16)>,
+}
+
+impl AnalysisPlugin for ActionUsagePlugin {
+    fn name(&self) -> &str {
+        "Action usage"
+    }
+
+    fn on_solve(&mut self, settings: &Settings, actions: &[Action], _state: &SimulationState) {
+        self.usage.clear();
+        let mut state = SimulationState::new(settings);
+        for action in actions {
+            let cp_before = state.cp;
+            if let Ok(new_state) =
+                state.use_action(*action, raphael_sim::Condition::Normal, settings)
+            {
+                state = new_state;
+            }
+            let cp_cost = cp_before - state.cp;
+            match self.usage.iter_mut().find(|(used, _, _)| used == action) {
+                Some((_, count, total_cp)) => {
+                    *count += 1;
+                    *total_cp += cp_cost;
+                }
+                None => self.usage.push((*action, 1, cp_cost)),
+            }
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, locale: Locale) {
+        if self.usage.is_empty() {
+            ui.label("Solve a recipe to see the action usage");
+            return;
+        }
+        egui::Grid::new("action_usage")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Action");
+                ui.label("Uses");
+                ui.label("CP");
+                ui.end_row();
+                for (action, count, total_cp) in &self.usage {
+                    ui.label(action_name(*action, locale));
+                    ui.label(count.to_string());
+                    ui.label(total_cp.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,161 @@
+use raphael_data::{Locale, get_job_name};
+
+use crate::config::RecipeConfiguration;
+use crate::planner::BatchPlan;
+
+use super::{HelpText, ItemNameLabel, util};
+
+/// Edits the batch plan and shows the ingredients needed for it.
+pub struct BatchPlannerWidget<'a> {
+    plan: &'a mut BatchPlan,
+    recipe_config: &'a RecipeConfiguration,
+    locale: Locale,
+}
+
+impl<'a> BatchPlannerWidget<'a> {
+    pub fn new(
+        plan: &'a mut BatchPlan,
+        recipe_config: &'a RecipeConfiguration,
+        locale: Locale,
+    ) -> Self {
+        Self {
+            plan,
+            recipe_config,
+            locale,
+        }
+    }
+
+    fn draw_entries(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Crafts").strong());
+            if ui.button("Add selected recipe").clicked() {
+                self.plan.add(self.recipe_config);
+            }
+            if ui
+                .add_enabled(!self.plan.entries.is_empty(), egui::Button::new("Clear"))
+                .clicked()
+            {
+                self.plan.entries.clear();
+            }
+        });
+        if self.plan.entries.is_empty() {
+            ui.label("No crafts planned");
+            return;
+        }
+        let mut removed_entry = None;
+        egui::Grid::new("batch_plan_entries")
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, entry) in self.plan.entries.iter_mut().enumerate() {
+                    ui.label(get_job_name(entry.recipe.job_id, self.locale));
+                    ui.add(ItemNameLabel::new(entry.recipe.item_id, false, self.locale));
+                    ui.add(egui::DragValue::new(&mut entry.quantity).range(1..=999));
+                    if ui.small_button("🗑").clicked() {
+                        removed_entry = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = removed_entry {
+            self.plan.entries.remove(index);
+        }
+    }
+
+    fn draw_teamcraft_import(&mut self, ui: &mut egui::Ui) {
+        let input_id = egui::Id::new("teamcraft_import_input");
+        let message_id = egui::Id::new("teamcraft_import_message");
+        let mut input = ui
+            .ctx()
+            .data(|data| data.get_temp::<String>(input_id))
+            .unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut input)
+                    .hint_text("📋 Teamcraft list link or export")
+                    .desired_width(260.0),
+            );
+            if ui
+                .add_enabled(!input.trim().is_empty(), egui::Button::new("Import"))
+                .clicked()
+            {
+                let message = match self.plan.import_teamcraft(&input) {
+                    Ok(skipped_items) if skipped_items.is_empty() => Ok(String::new()),
+                    Ok(skipped_items) => Ok(format!(
+                        "Skipped {} items that can't be crafted",
+                        skipped_items.len()
+                    )),
+                    Err(error) => Err(error),
+                };
+                if message.is_ok() {
+                    input.clear();
+                }
+                ui.ctx()
+                    .data_mut(|data| data.insert_temp(message_id, message));
+            }
+            ui.add(HelpText::new(
+                "Adds the items of a Teamcraft list, pasted as its import link or as the JSON export of the list. Items that are crafted by several jobs are added for the first job.",
+            ));
+        });
+        ui.ctx().data_mut(|data| data.insert_temp(input_id, input));
+        match ui
+            .ctx()
+            .data(|data| data.get_temp::<Result<String, String>>(message_id))
+        {
+            Some(Ok(message)) if !message.is_empty() => {
+                ui.label(message);
+            }
+            Some(Err(error)) => {
+                ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+            _ => {}
+        }
+    }
+
+    fn draw_shopping_list(&self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Shopping list").strong());
+            ui.add(HelpText::new(
+                "Ingredients for all planned crafts. HQ amounts are taken from the HQ ingredients selected when the recipe was added.",
+            ));
+        });
+        let shopping_list = self.plan.shopping_list();
+        if shopping_list.is_empty() {
+            ui.label("Nothing to buy");
+            return;
+        }
+        egui::Grid::new("batch_plan_shopping_list")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Item");
+                ui.label("NQ");
+                ui.label("HQ");
+                ui.end_row();
+                for item in &shopping_list {
+                    ui.add(ItemNameLabel::new(item.item_id, false, self.locale));
+                    ui.label(item.nq.to_string());
+                    ui.label(item.hq.to_string());
+                    ui.end_row();
+                }
+            });
+        ui.horizontal(|ui| {
+            if ui.button("Copy as text").clicked() {
+                util::copy_to_clipboard(ui.ctx(), self.plan.shopping_list_text(self.locale));
+            }
+            if ui.button("Copy Teamcraft import link").clicked() {
+                util::copy_to_clipboard(ui.ctx(), self.plan.teamcraft_import_link());
+            }
+        });
+    }
+}
+
+impl egui::Widget for BatchPlannerWidget<'_> {
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            self.draw_entries(ui);
+            self.draw_teamcraft_import(ui);
+            ui.separator();
+            self.draw_shopping_list(ui);
+        })
+        .response
+    }
+}
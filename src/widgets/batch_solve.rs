@@ -0,0 +1,243 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+
+use raphael_data::{CrafterStats, Locale, Recipe, get_initial_quality, get_job_name};
+use raphael_sim::Action;
+use raphael_solver::{AtomicFlag, SolverException};
+
+use crate::app::CraftParams;
+use crate::planner::BatchPlan;
+
+use super::{ItemNameLabel, MacroTemplateVariables, MacroViewConfig, macro_newline, util};
+
+struct BatchSolveResult {
+    recipe: Recipe,
+    target_quality: u16,
+    /// Quality and actions of the macro, including the Quality of the HQ ingredients.
+    result: Result<(u16, Vec<Action>), SolverException>,
+}
+
+struct BatchSolveRun {
+    result: Receiver<Vec<BatchSolveResult>>,
+    completed_recipes: Arc<AtomicUsize>,
+    total_recipes: usize,
+    interrupt_signal: AtomicFlag,
+}
+
+/// Solves every recipe of the batch plan, one after another in the background.
+#[derive(Default)]
+pub struct BatchSolveData {
+    run: Option<BatchSolveRun>,
+    results: Vec<BatchSolveResult>,
+}
+
+impl BatchSolveData {
+    fn start(
+        &mut self,
+        ctx: egui::Context,
+        plan: &BatchPlan,
+        crafter_stats: [CrafterStats; 8],
+        params: CraftParams,
+    ) {
+        let recipes: Vec<CraftParams> = plan
+            .entries
+            .iter()
+            .map(|entry| CraftParams {
+                recipe: entry.recipe,
+                crafter_stats: crafter_stats[entry.recipe.job_id as usize],
+                initial_quality: get_initial_quality(entry.recipe, entry.hq_ingredients),
+                ..params
+            })
+            .collect();
+
+        let (tx, rx) = mpsc::channel();
+        let completed_recipes = Arc::new(AtomicUsize::new(0));
+        let interrupt_signal = AtomicFlag::new();
+        self.run = Some(BatchSolveRun {
+            result: rx,
+            completed_recipes: completed_recipes.clone(),
+            total_recipes: recipes.len(),
+            interrupt_signal: interrupt_signal.clone(),
+        });
+
+        std::thread::spawn(move || {
+            let thread_pool = match params.thread_pool_settings.build() {
+                Ok(thread_pool) => thread_pool,
+                Err(error) => {
+                    log::error!("Failed to build thread pool: {error}");
+                    return;
+                }
+            };
+            let mut results = Vec::new();
+            for recipe_params in recipes {
+                if interrupt_signal.is_set() {
+                    return;
+                }
+                let request = recipe_params.solve_request();
+                let result = thread_pool
+                    .install(|| request.solve(interrupt_signal.clone()))
+                    .map(|actions| {
+                        let quality = raphael_solver::test_utils::get_quality(
+                            &request.simulator_settings(),
+                            &actions,
+                        );
+                        (
+                            recipe_params.initial_quality.saturating_add(quality),
+                            actions,
+                        )
+                    });
+                results.push(BatchSolveResult {
+                    recipe: recipe_params.recipe,
+                    target_quality: recipe_params.target_quality(),
+                    result,
+                });
+                completed_recipes.fetch_add(1, Ordering::Relaxed);
+                ctx.request_repaint();
+            }
+            _ = tx.send(results);
+            ctx.request_repaint();
+        });
+    }
+
+    fn update(&mut self) {
+        if let Some(run) = self.run.as_ref() {
+            match run.result.try_recv() {
+                Ok(results) => {
+                    self.results = results;
+                    self.run = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.run = None,
+                Err(mpsc::TryRecvError::Empty) => (),
+            }
+        }
+    }
+}
+
+pub struct BatchSolveWidget<'a> {
+    data: &'a mut BatchSolveData,
+    plan: &'a BatchPlan,
+    crafter_stats: [CrafterStats; 8],
+    params: CraftParams,
+    macro_view_config: &'a MacroViewConfig,
+    locale: Locale,
+}
+
+impl<'a> BatchSolveWidget<'a> {
+    pub fn new(
+        data: &'a mut BatchSolveData,
+        plan: &'a BatchPlan,
+        crafter_stats: [CrafterStats; 8],
+        params: CraftParams,
+        macro_view_config: &'a MacroViewConfig,
+        locale: Locale,
+    ) -> Self {
+        Self {
+            data,
+            plan,
+            crafter_stats,
+            params,
+            macro_view_config,
+            locale,
+        }
+    }
+
+    fn macro_text(&self, ctx: &egui::Context, recipe: &Recipe, actions: &[Action]) -> String {
+        let variables = MacroTemplateVariables::new(recipe.item_id, recipe.job_id, self.locale);
+        let newline = macro_newline(ctx);
+        self.macro_view_config
+            .macro_texts(actions, &variables, newline, self.locale)
+            .join(&format!("{newline}{newline}"))
+    }
+
+    fn show_controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| match self.data.run.as_ref() {
+            Some(run) => {
+                util::busy_indicator(ui);
+                ui.label(format!(
+                    "{} / {} solved",
+                    run.completed_recipes.load(Ordering::Relaxed),
+                    run.total_recipes
+                ));
+                if ui.button("Cancel").clicked() {
+                    run.interrupt_signal.set();
+                }
+            }
+            None => {
+                if ui
+                    .add_enabled(
+                        !self.plan.entries.is_empty(),
+                        egui::Button::new("Solve all crafts"),
+                    )
+                    .clicked()
+                {
+                    self.data
+                        .start(ui.ctx().clone(), self.plan, self.crafter_stats, self.params);
+                }
+                ui.label("Uses the stats of each job and the selected consumables");
+            }
+        });
+    }
+
+    fn show_results(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("batch_solve_results")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Job");
+                ui.label("Item");
+                ui.label("Quality");
+                ui.label("Steps");
+                ui.label("Duration");
+                ui.end_row();
+                for result in &self.data.results {
+                    ui.label(get_job_name(result.recipe.job_id, self.locale));
+                    ui.add(ItemNameLabel::new(
+                        result.recipe.item_id,
+                        false,
+                        self.locale,
+                    ));
+                    match &result.result {
+                        Ok((quality, actions)) => {
+                            ui.label(format!("{} / {}", quality, result.target_quality));
+                            ui.label(actions.len().to_string());
+                            let duration: u32 = actions
+                                .iter()
+                                .map(|action| u32::from(action.time_cost()))
+                                .sum();
+                            ui.label(format!("{duration}s"));
+                            if ui.small_button("Copy macro").clicked() {
+                                let text = self.macro_text(ui.ctx(), &result.recipe, actions);
+                                util::copy_to_clipboard(ui.ctx(), text);
+                            }
+                        }
+                        Err(error) => {
+                            let message = match error {
+                                SolverException::NoSolution => "No solution",
+                                SolverException::MemoryLimitReached => "Out of memory",
+                                SolverException::Interrupted => "Cancelled",
+                                SolverException::InternalError(_) => "Internal error",
+                            };
+                            ui.label(
+                                egui::RichText::new(message).color(ui.visuals().error_fg_color),
+                            );
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+impl egui::Widget for BatchSolveWidget<'_> {
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        self.data.update();
+        ui.vertical(|ui| {
+            ui.label(egui::RichText::new("Macros").strong());
+            self.show_controls(ui);
+            if !self.data.results.is_empty() {
+                self.show_results(ui);
+            }
+        })
+        .response
+    }
+}
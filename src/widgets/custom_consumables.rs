@@ -0,0 +1,110 @@
+use egui::Widget;
+use raphael_data::{Consumable, CustomConsumable};
+use serde::{Deserialize, Serialize};
+
+/// Food and potions defined by the user, e.g. items of a new patch that aren't in the bundled
+/// data yet.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomConsumables {
+    pub meals: Vec<CustomConsumable>,
+    pub potions: Vec<CustomConsumable>,
+}
+
+impl CustomConsumables {
+    /// Keeps a selected custom consumable in sync with its definition, as the selection is a copy.
+    /// The selection is cleared if the custom consumable was deleted.
+    pub fn update_selected(consumables: &[CustomConsumable], selected: &mut Option<Consumable>) {
+        if let Some(consumable) = selected.filter(|consumable| consumable.is_custom()) {
+            *selected = consumables
+                .iter()
+                .find(|custom| custom.consumable.item_id == consumable.item_id)
+                .map(|custom| custom.consumable);
+        }
+    }
+}
+
+pub struct CustomConsumablesWidget<'a> {
+    custom_consumables: &'a mut CustomConsumables,
+}
+
+impl<'a> CustomConsumablesWidget<'a> {
+    pub fn new(custom_consumables: &'a mut CustomConsumables) -> Self {
+        Self { custom_consumables }
+    }
+
+    fn draw_consumables(
+        ui: &mut egui::Ui,
+        id_salt: &str,
+        consumables: &mut Vec<CustomConsumable>,
+        other_consumables: &[CustomConsumable],
+    ) {
+        let mut deleted_index = None;
+        egui::Grid::new(id_salt).striped(true).show(ui, |ui| {
+            ui.label("Name");
+            ui.label("HQ");
+            ui.label("Item level");
+            ui.label("Crafts. %");
+            ui.label("max");
+            ui.label("Control %");
+            ui.label("max");
+            ui.label("CP %");
+            ui.label("max");
+            ui.end_row();
+            for (index, custom) in consumables.iter_mut().enumerate() {
+                egui::TextEdit::singleline(&mut custom.name)
+                    .desired_width(140.0)
+                    .ui(ui);
+                ui.checkbox(&mut custom.consumable.hq, "");
+                ui.add(egui::DragValue::new(&mut custom.consumable.item_level));
+                let consumable = &mut custom.consumable;
+                for value in [
+                    &mut consumable.craft_rel,
+                    &mut consumable.craft_max,
+                    &mut consumable.control_rel,
+                    &mut consumable.control_max,
+                    &mut consumable.cp_rel,
+                    &mut consumable.cp_max,
+                ] {
+                    ui.add(egui::DragValue::new(value));
+                }
+                if ui.button("Delete").clicked() {
+                    deleted_index = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = deleted_index {
+            consumables.remove(index);
+        }
+        if ui.button("Add").clicked() {
+            // item IDs are shared between food and potions
+            let all_consumables: Vec<CustomConsumable> = consumables
+                .iter()
+                .chain(other_consumables.iter())
+                .cloned()
+                .collect();
+            consumables.push(CustomConsumable::new(
+                "New item".to_string(),
+                true,
+                &all_consumables,
+            ));
+        }
+    }
+}
+
+impl egui::Widget for CustomConsumablesWidget<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            ui.label("Food and potions that aren't in the game data yet. The bonus of each stat is the percentage of the base stat, up to the max.");
+            ui.separator();
+            ui.label(egui::RichText::new("Food").strong());
+            let CustomConsumables { meals, potions } = self.custom_consumables;
+            Self::draw_consumables(ui, "CUSTOM_MEALS", meals, potions);
+            ui.separator();
+            ui.label(egui::RichText::new("Potions").strong());
+            Self::draw_consumables(ui, "CUSTOM_POTIONS", potions, meals);
+        })
+        .response
+    }
+}
@@ -0,0 +1,111 @@
+use raphael_data::{Locale, get_job_name};
+use serde::{Deserialize, Serialize};
+
+use crate::reset_schedule;
+
+/// Counters for repeatable crafting deliveries, reset automatically at the in-game reset times.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeliveryTracker {
+    /// Custom delivery allowances used since the last weekly reset.
+    custom_deliveries: u8,
+    /// Jobs that turned in their Grand Company supply mission since the last daily reset.
+    gc_turn_ins: [bool; 8],
+    /// Time of the last update in seconds since the Unix epoch.
+    updated_at: u64,
+}
+
+impl DeliveryTracker {
+    pub const CUSTOM_DELIVERY_ALLOWANCES: u8 = 12;
+
+    /// Clears the counters whose reset has passed since the last update.
+    pub fn update(&mut self, now: u64) {
+        if self.updated_at < reset_schedule::last_weekly_reset(now) {
+            self.custom_deliveries = 0;
+        }
+        if self.updated_at < reset_schedule::last_daily_reset(now) {
+            self.gc_turn_ins = [false; 8];
+        }
+        self.updated_at = now;
+    }
+
+    pub fn remaining_custom_deliveries(&self) -> u8 {
+        Self::CUSTOM_DELIVERY_ALLOWANCES.saturating_sub(self.custom_deliveries)
+    }
+
+    pub fn remaining_gc_turn_ins(&self) -> usize {
+        self.gc_turn_ins
+            .iter()
+            .filter(|turned_in| !**turned_in)
+            .count()
+    }
+}
+
+pub struct DeliveryTrackerWidget<'a> {
+    tracker: &'a mut DeliveryTracker,
+    locale: Locale,
+}
+
+impl<'a> DeliveryTrackerWidget<'a> {
+    pub fn new(tracker: &'a mut DeliveryTracker, locale: Locale) -> Self {
+        Self { tracker, locale }
+    }
+}
+
+impl egui::Widget for DeliveryTrackerWidget<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let now = reset_schedule::now();
+        self.tracker.update(now);
+        // keep the remaining time until the resets up to date
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_secs(60));
+
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Custom deliveries").strong());
+                ui.label(format!(
+                    "resets in {}",
+                    reset_schedule::format_duration(reset_schedule::next_weekly_reset(now) - now)
+                ));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Allowances used:");
+                ui.add(
+                    egui::DragValue::new(&mut self.tracker.custom_deliveries)
+                        .range(0..=DeliveryTracker::CUSTOM_DELIVERY_ALLOWANCES),
+                );
+                ui.label(format!("/ {}", DeliveryTracker::CUSTOM_DELIVERY_ALLOWANCES));
+                if ui
+                    .add_enabled(
+                        self.tracker.remaining_custom_deliveries() != 0,
+                        egui::Button::new("+1"),
+                    )
+                    .clicked()
+                {
+                    self.tracker.custom_deliveries += 1;
+                }
+            });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Grand Company supply missions").strong());
+                ui.label(format!(
+                    "resets in {}",
+                    reset_schedule::format_duration(reset_schedule::next_daily_reset(now) - now)
+                ));
+            });
+            ui.horizontal_wrapped(|ui| {
+                for (job_id, turned_in) in self.tracker.gc_turn_ins.iter_mut().enumerate() {
+                    ui.checkbox(turned_in, get_job_name(job_id as u8, self.locale));
+                }
+            });
+            ui.separator();
+
+            ui.label(format!(
+                "Crafts left before reset: {} custom deliveries, {} supply missions",
+                self.tracker.remaining_custom_deliveries(),
+                self.tracker.remaining_gc_turn_ins()
+            ));
+        })
+        .response
+    }
+}
@@ -0,0 +1,150 @@
+use raphael_data::{Locale, action_name};
+use raphael_sim::{
+    Action, Condition, ImmaculateMend, Manipulation, MasterMend, Settings, SimulationState,
+};
+
+use raphael_solver::{SolverHeuristics, SolverSettings};
+
+use super::AnalysisPlugin;
+
+struct Restoration {
+    action: Action,
+    /// Zero-based index of the step the action was used at.
+    step: usize,
+    cp_cost: i16,
+    /// Durability that was actually restored, e.g. less than 30 for Master's Mend if the
+    /// durability was already close to the max.
+    restored: i16,
+}
+
+impl Restoration {
+    /// CP spent per 5 durability, the unit of durability that actions consume.
+    fn cp_per_durability(&self) -> Option<f32> {
+        match self.restored {
+            0 => None,
+            restored => Some(f32::from(self.cp_cost) * 5.0 / f32::from(restored)),
+        }
+    }
+}
+
+/// CP per 5 durability of each restoration action if all of its durability is used, the same
+/// estimate that the Quality upper bound solver uses to convert durability into CP.
+fn best_cp_per_durability(action: Action, settings: &Settings) -> f32 {
+    let state = SimulationState::new(settings);
+    match action {
+        Action::MasterMend => f32::from(settings.base_cp_cost::<MasterMend>(&state)) / 6.0,
+        Action::Manipulation => f32::from(settings.base_cp_cost::<Manipulation>(&state)) / 8.0,
+        _ => {
+            let max_restored = f32::from(settings.max_durability / 5 - 1);
+            f32::from(settings.base_cp_cost::<ImmaculateMend>(&state)) / max_restored
+        }
+    }
+}
+
+/// Compares the durability each restoration action restored in the solved macro against the
+/// durability it can restore at best.
+#[derive(Default)]
+pub struct DurabilityRestorationPlugin {
+    restorations: Vec<Restoration>,
+    settings: Option<Settings>,
+}
+
+impl AnalysisPlugin for DurabilityRestorationPlugin {
+    fn name(&self) -> &str {
+        "Durability restoration"
+    }
+
+    fn on_solve(&mut self, settings: &Settings, actions: &[Action], _state: &SimulationState) {
+        self.restorations.clear();
+        self.settings = Some(*settings);
+        let mut state = SimulationState::new(settings);
+        for (step, action) in actions.iter().enumerate() {
+            let effect = state.action_effect(*action, Condition::Normal, settings);
+            let Ok(new_state) = state.use_action(*action, Condition::Normal, settings) else {
+                continue;
+            };
+            let durability = state.durability - effect.durability_cost;
+            let restored = i16::from(new_state.durability - durability);
+            // durability restored by the action itself, the rest is restored by Manipulation
+            let own_restored = match action {
+                Action::MasterMend => i16::from((settings.max_durability - durability).min(30)),
+                Action::ImmaculateMend => i16::from(settings.max_durability - durability),
+                _ => 0,
+            };
+            let manipulation = self
+                .restorations
+                .iter_mut()
+                .rfind(|restoration| restoration.action == Action::Manipulation);
+            if let Some(manipulation) = manipulation.filter(|_| restored > own_restored) {
+                manipulation.restored += restored - own_restored;
+            }
+            if matches!(
+                action,
+                Action::MasterMend | Action::ImmaculateMend | Action::Manipulation
+            ) {
+                self.restorations.push(Restoration {
+                    action: *action,
+                    step,
+                    cp_cost: effect.cp_cost,
+                    restored: own_restored,
+                });
+            }
+            state = new_state;
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, locale: Locale) {
+        let Some(settings) = self.settings.as_ref() else {
+            ui.label("Solve a recipe to see the durability restoration");
+            return;
+        };
+        if self.restorations.is_empty() {
+            ui.label("The macro doesn't restore any durability");
+            return;
+        }
+        egui::Grid::new("durability_restoration")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Step");
+                ui.label("Action");
+                ui.label("Restored");
+                ui.label("CP");
+                ui.label("CP / 5 durability");
+                ui.label("Best");
+                ui.end_row();
+                for restoration in &self.restorations {
+                    ui.label((restoration.step + 1).to_string());
+                    ui.label(action_name(restoration.action, locale));
+                    ui.label(restoration.restored.to_string());
+                    ui.label(restoration.cp_cost.to_string());
+                    ui.label(
+                        restoration
+                            .cp_per_durability()
+                            .map_or_else(|| "-".to_string(), |cost| format!("{cost:.1}")),
+                    );
+                    ui.label(format!(
+                        "{:.1}",
+                        best_cp_per_durability(restoration.action, settings)
+                    ));
+                    ui.end_row();
+                }
+            });
+        let heuristics = SolverHeuristics::new(&SolverSettings {
+            simulator_settings: *settings,
+            backload_progress: false,
+            allow_unsound_branch_pruning: false,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
+        });
+        ui.label(format!(
+            "The solver values 5 durability at {} CP.",
+            heuristics.durability_cost
+        ));
+        ui.label(
+            egui::RichText::new(
+                "Lower is better. Best assumes that all of the restored durability is used.",
+            )
+            .small(),
+        );
+    }
+}
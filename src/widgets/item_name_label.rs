@@ -1,5 +1,7 @@
 use raphael_data::{Locale, get_item_name};
 
+use super::util;
+
 pub struct ItemNameLabel {
     item_id: u32,
     text: String,
@@ -39,13 +41,13 @@ impl egui::Widget for ItemNameLabel {
                     .text
                     .trim_end_matches([' ', raphael_data::HQ_ICON_CHAR, raphael_data::CL_ICON_CHAR])
                     .to_string();
-                ui.ctx().copy_text(copy_item_name);
+                util::copy_to_clipboard(ui.ctx(), copy_item_name);
                 ui.close();
                 selection_made = true;
             }
             ui.separator();
             if ui.button("Copy item ID").clicked() {
-                ui.ctx().copy_text(self.item_id.to_string());
+                util::copy_to_clipboard(ui.ctx(), self.item_id.to_string());
                 ui.close();
                 selection_made = true;
             }
@@ -1,9 +1,12 @@
 use egui::{Align, Id, Layout, Widget};
-use raphael_data::{Locale, action_name};
+use raphael_data::{CL_ICON_CHAR, Locale, action_name, game_data, get_item_name};
 use raphael_sim::Action;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+use super::util;
+use crate::plugin_export::{self, PluginCraft, PluginMacro};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct MacroViewConfig {
     #[serde(default)]
     split_macro: bool,
@@ -15,6 +18,16 @@ pub struct MacroViewConfig {
     notification_sound: u8,
     #[serde(default)]
     macro_lock: bool,
+    #[serde(default)]
+    guided_copy: bool,
+    #[serde(default)]
+    header_enabled: bool,
+    #[serde(default = "default_header_template")]
+    header_template: String,
+}
+
+fn default_header_template() -> String {
+    "/echo Crafting {item} – part {n}/{total}".to_string()
 }
 
 impl Default for MacroViewConfig {
@@ -25,29 +38,109 @@ impl Default for MacroViewConfig {
             notification_enabled: false,
             notification_sound: 1,
             macro_lock: false,
+            guided_copy: false,
+            header_enabled: false,
+            header_template: default_header_template(),
         }
     }
 }
 
-struct MacroTextBox {
-    text: String,
+/// Values of the placeholders of the header template that don't depend on the macro block.
+#[derive(Debug, Clone)]
+pub struct MacroTemplateVariables {
+    item: String,
+    tier: String,
+    date: String,
 }
 
-impl MacroTextBox {
-    pub fn new(
+impl MacroTemplateVariables {
+    pub const TEMPLATE_HELP: &str = "Available placeholders:\n{item}: name of the crafted item\n{tier}: level of the recipe\n{date}: today's date\n{n}: number of the macro\n{total}: number of macros";
+
+    pub fn new(item_id: u32, job_id: u8, locale: Locale) -> Self {
+        let item_name = get_item_name(item_id, false, locale);
+        let recipe = game_data()
+            .recipes()
+            .iter()
+            .find(|recipe| recipe.item_id == item_id && recipe.job_id == job_id);
+        Self {
+            item: item_name.trim_end_matches([' ', CL_ICON_CHAR]).to_string(),
+            tier: recipe.map_or_else(String::new, |recipe| match recipe.is_expert {
+                true => format!("Lv. {} Expert", recipe.level),
+                false => format!("Lv. {}", recipe.level),
+            }),
+            date: crate::reset_schedule::format_date(crate::reset_schedule::now()),
+        }
+    }
+}
+
+impl MacroViewConfig {
+    /// Returns the text of each in-game macro, splitting the actions into several macros if enabled.
+    pub fn macro_texts(
+        &self,
+        actions: &[Action],
+        variables: &MacroTemplateVariables,
+        newline: &str,
+        locale: Locale,
+    ) -> Vec<String> {
+        let chunk_size = match self.split_macro {
+            true => {
+                let mut chunk_size = 15;
+                if self.notification_enabled {
+                    chunk_size -= 1;
+                }
+                if self.macro_lock {
+                    chunk_size -= 1;
+                }
+                if self.header_enabled {
+                    chunk_size -= 1;
+                }
+                chunk_size
+            }
+            false => usize::MAX,
+        };
+        let count = actions.chunks(chunk_size).count();
+        actions
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(index, actions)| {
+                self.macro_text(index + 1, count, actions, variables, newline, locale)
+            })
+            .collect()
+    }
+
+    /// Expands the header template for the macro at `index` (starting from 1).
+    fn header_line(
+        &self,
+        index: usize,
+        max_index: usize,
+        variables: &MacroTemplateVariables,
+    ) -> String {
+        self.header_template
+            .replace("{item}", &variables.item)
+            .replace("{tier}", &variables.tier)
+            .replace("{date}", &variables.date)
+            .replace("{n}", &index.to_string())
+            .replace("{total}", &max_index.to_string())
+    }
+
+    fn macro_text(
+        &self,
         index: usize,
         max_index: usize,
         actions: &[Action],
-        config: &MacroViewConfig,
-        newline: &'static str,
+        variables: &MacroTemplateVariables,
+        newline: &str,
         locale: Locale,
-    ) -> Self {
+    ) -> String {
         let mut lines: Vec<String> = Vec::new();
-        if config.macro_lock {
+        if self.header_enabled {
+            lines.push(self.header_line(index, max_index, variables));
+        }
+        if self.macro_lock {
             lines.push("/macrolock ".to_string());
         }
         lines.extend(actions.iter().map(|action| {
-            if config.include_delay {
+            if self.include_delay {
                 format!(
                     "/ac \"{}\" <wait.{}>",
                     action_name(*action, locale),
@@ -57,43 +150,89 @@ impl MacroTextBox {
                 format!("/ac \"{}\"", action_name(*action, locale))
             }
         }));
-        if config.notification_enabled {
+        if self.notification_enabled {
             lines.push(format!(
                 "/echo Macro finished ({}/{}) <se.{}>",
-                index, max_index, config.notification_sound
+                index, max_index, self.notification_sound
             ));
         }
-        Self {
-            text: lines.join(newline),
-        }
+        lines.join(newline)
+    }
+}
+
+/// Newline sequence expected by the game client on the current OS.
+pub fn macro_newline(ctx: &egui::Context) -> &'static str {
+    match ctx.os() {
+        egui::os::OperatingSystem::Mac => "\n",
+        _ => "\r\n",
     }
 }
 
-impl Widget for MacroTextBox {
+struct MacroTextBox<'a> {
+    text: String,
+    index: usize,
+    count: usize,
+    /// Index of the block that is copied by the "Copy next" button.
+    next_block: &'a mut usize,
+    /// Highlights the next block and only allows copying blocks up to the next one,
+    /// so that blocks can't be skipped by accident.
+    guided: bool,
+}
+
+impl Widget for MacroTextBox<'_> {
     fn ui(self, ui: &mut egui::Ui) -> egui::Response {
         let id = Id::new(&self.text);
-        ui.group(|ui| {
-            ui.horizontal_top(|ui| {
-                ui.monospace(&self.text);
-                ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
-                    if ui.ctx().animate_bool_with_time(id, false, 2.0) == 0.0 {
-                        if ui.button("Copy").clicked() {
-                            ui.ctx().copy_text(self.text);
-                            ui.ctx().animate_bool_with_time(id, true, 0.0);
+        let is_next = self.index == *self.next_block;
+        let mut frame = egui::Frame::group(ui.style());
+        if self.guided && is_next {
+            frame = frame.stroke(ui.visuals().selection.stroke);
+        }
+        frame
+            .show(ui, |ui| {
+                ui.horizontal_top(|ui| {
+                    ui.monospace(&self.text);
+                    ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
+                        if ui.ctx().animate_bool_with_time(id, false, 2.0) == 0.0 {
+                            let copy_enabled = !self.guided || self.index <= *self.next_block;
+                            if ui
+                                .add_enabled(copy_enabled, egui::Button::new("Copy"))
+                                .clicked()
+                            {
+                                util::copy_to_clipboard(ui.ctx(), self.text);
+                                ui.ctx().animate_bool_with_time(id, true, 0.0);
+                                *self.next_block = self.index + 1;
+                            }
+                        } else {
+                            ui.add_enabled(false, egui::Button::new("Copied"));
                         }
-                    } else {
-                        ui.add_enabled(false, egui::Button::new("Copied"));
-                    }
+                        if self.guided {
+                            let status = match self.index < *self.next_block {
+                                true => {
+                                    egui::RichText::new("✔").color(ui.visuals().weak_text_color())
+                                }
+                                false => egui::RichText::new(format!(
+                                    "{}/{}",
+                                    self.index + 1,
+                                    self.count
+                                )),
+                            };
+                            ui.label(match is_next {
+                                true => status.strong(),
+                                false => status,
+                            });
+                        }
+                    });
                 });
-            });
-        })
-        .response
+            })
+            .response
     }
 }
 
 pub struct MacroView<'a> {
     actions: &'a mut Vec<Action>,
     config: &'a mut MacroViewConfig,
+    variables: MacroTemplateVariables,
+    craft: PluginCraft,
     locale: Locale,
 }
 
@@ -101,11 +240,15 @@ impl<'a> MacroView<'a> {
     pub fn new(
         actions: &'a mut Vec<Action>,
         config: &'a mut MacroViewConfig,
+        variables: MacroTemplateVariables,
+        craft: PluginCraft,
         locale: Locale,
     ) -> Self {
         Self {
             actions,
             config,
+            variables,
+            craft,
             locale,
         }
     }
@@ -125,6 +268,19 @@ impl Widget for MacroView<'_> {
                         {
                             self.actions.clear();
                         }
+                        if ui
+                            .add_enabled(
+                                !self.actions.is_empty(),
+                                egui::Button::new("Copy for plugins"),
+                            )
+                            .on_hover_text("Copies the macro as JSON for Dalamud crafting plugins, e.g. Artisan, including the item and consumables")
+                            .clicked()
+                        {
+                            let plugin_macro =
+                                PluginMacro::new(self.craft, self.actions, self.locale);
+                            let json = plugin_export::to_json(&[plugin_macro]);
+                            util::copy_to_clipboard(ui.ctx(), json);
+                        }
                         let duration = self
                             .actions
                             .iter()
@@ -142,6 +298,8 @@ impl Widget for MacroView<'_> {
                     ui.checkbox(&mut self.config.include_delay, "Include delay");
                     ui.checkbox(&mut self.config.split_macro, "Split macro");
                     ui.checkbox(&mut self.config.macro_lock, "Macro lock");
+                    ui.checkbox(&mut self.config.guided_copy, "Guided copy")
+                        .on_hover_text("Walk through the macros one at a time, highlighting the macro to copy next");
                 });
                 ui.horizontal(|ui| {
                     ui.add(egui::Checkbox::new(
@@ -162,35 +320,68 @@ impl Widget for MacroView<'_> {
                             });
                     });
                 });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.config.header_enabled, "Header line");
+                    ui.add_enabled(
+                        self.config.header_enabled,
+                        egui::TextEdit::singleline(&mut self.config.header_template)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .on_hover_text(MacroTemplateVariables::TEMPLATE_HELP);
+                });
                 ui.separator();
-                let chunk_size = match self.config.split_macro {
-                    true => {
-                        let mut chunk_size = 15;
-                        if self.config.notification_enabled {
-                            chunk_size -= 1;
+                let newline = macro_newline(ui.ctx());
+                let texts =
+                    self.config
+                        .macro_texts(self.actions, &self.variables, newline, self.locale);
+                // progress of copying the macro block by block, reset whenever the actions change
+                let next_block_id = Id::new("MACRO_NEXT_BLOCK");
+                let mut next_block = ui
+                    .data(|data| data.get_temp::<(Vec<Action>, usize)>(next_block_id))
+                    .filter(|(actions, _)| actions == self.actions)
+                    .map_or(0, |(_, next_block)| next_block);
+                let count = texts.len();
+                let multiple_blocks = count > 1;
+                if multiple_blocks {
+                    ui.horizontal(|ui| match texts.get(next_block) {
+                        Some(text) => {
+                            let copy_clicked = ui
+                                .button(format!("Copy next ({}/{count})", next_block + 1))
+                                .clicked();
+                            if self.config.guided_copy {
+                                ui.label(match next_block {
+                                    0 => format!("Copy macro 1 of {count} into your first macro slot"),
+                                    _ => format!(
+                                        "Paste macro {next_block}, then copy macro {} of {count}",
+                                        next_block + 1
+                                    ),
+                                });
+                            }
+                            if copy_clicked {
+                                util::copy_to_clipboard(ui.ctx(), text.clone());
+                                next_block += 1;
+                            }
                         }
-                        if self.config.macro_lock {
-                            chunk_size -= 1;
+                        None => {
+                            ui.label(format!("✔ All {count} macros copied"));
+                            if ui.button("Start over").clicked() {
+                                next_block = 0;
+                            }
                         }
-                        chunk_size
-                    }
-                    false => usize::MAX,
-                };
-                let count = self.actions.chunks(chunk_size).count();
-                let newline = match ui.ctx().os() {
-                    egui::os::OperatingSystem::Mac => "\n",
-                    _ => "\r\n",
-                };
-                for (index, actions) in self.actions.chunks(chunk_size).enumerate() {
-                    ui.add(MacroTextBox::new(
-                        index + 1,
+                    });
+                }
+                for (index, text) in texts.into_iter().enumerate() {
+                    ui.add(MacroTextBox {
+                        text,
+                        index,
                         count,
-                        actions,
-                        self.config,
-                        newline,
-                        self.locale,
-                    ));
+                        next_block: &mut next_block,
+                        guided: multiple_blocks && self.config.guided_copy,
+                    });
                 }
+                ui.data_mut(|data| {
+                    data.insert_temp(next_block_id, (self.actions.clone(), next_block));
+                });
 
                 if self.actions.is_empty() {
                     ui.label("None");
@@ -1,18 +1,27 @@
 mod macro_view;
-pub use macro_view::{MacroView, MacroViewConfig};
+pub use macro_view::{MacroTemplateVariables, MacroView, MacroViewConfig, macro_newline};
 
 mod simulator;
-pub use simulator::Simulator;
+pub use simulator::{Simulator, StepSelection};
 
 mod recipe_select;
 pub use recipe_select::RecipeSelect;
 
+mod batch_planner;
+pub use batch_planner::BatchPlannerWidget;
+
+mod delivery_tracker;
+pub use delivery_tracker::{DeliveryTracker, DeliveryTrackerWidget};
+
 mod food_select;
 pub use food_select::FoodSelect;
 
 mod potion_select;
 pub use potion_select::PotionSelect;
 
+mod custom_consumables;
+pub use custom_consumables::{CustomConsumables, CustomConsumablesWidget};
+
 mod stats_edit;
 pub use stats_edit::StatsEdit;
 
@@ -22,10 +31,64 @@ pub use help_text::HelpText;
 mod item_name_label;
 pub use item_name_label::ItemNameLabel;
 
-// mod analysis;
-// pub use analysis::SolutionAnalysis;
+mod analysis_plugin;
+pub use analysis_plugin::{AnalysisPlugin, AnalysisPlugins};
+
+mod durability_restoration;
+pub use durability_restoration::DurabilityRestorationPlugin;
+
+mod analysis;
+pub use analysis::{SolutionAnalysis, SolutionAnalysisData};
+
+mod qr_code;
+pub use qr_code::QrCode;
+
+mod rotation_check;
+pub use rotation_check::{RotationCheck, rotation_error_text};
+
+mod rotation_lint;
+pub use rotation_lint::RotationLint;
+
+mod proc_cheat_sheet;
+pub use proc_cheat_sheet::ProcCheatSheet;
 
 mod saved_rotations;
-pub use saved_rotations::{Rotation, SavedRotationsData, SavedRotationsWidget};
+pub use saved_rotations::{
+    RecipeFingerprint, Rotation, SavedRotationsData, SavedRotationsWidget, SimilarRotation,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod stat_sweep;
+#[cfg(not(target_arch = "wasm32"))]
+pub use stat_sweep::{StatSweepConfig, StatSweepData, StatSweepWidget};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod batch_solve;
+#[cfg(not(target_arch = "wasm32"))]
+pub use batch_solve::{BatchSolveData, BatchSolveWidget};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod reliability_report;
+#[cfg(not(target_arch = "wasm32"))]
+pub use reliability_report::{ReliabilityReportData, ReliabilityReportWidget};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod step_advisor;
+#[cfg(not(target_arch = "wasm32"))]
+pub use step_advisor::{StepAdvisorData, StepAdvisorWidget};
+
+#[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+mod solver_comparison;
+#[cfg(all(feature = "solver-comparison", not(target_arch = "wasm32")))]
+pub use solver_comparison::{SolverComparisonData, SolverComparisonWidget};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod tradeoff_curve;
+#[cfg(not(target_arch = "wasm32"))]
+pub use tradeoff_curve::TradeoffCurvePlugin;
 
 mod util;
+pub use util::{
+    background_repaint_interval, busy_indicator, copy_to_clipboard, low_spec_mode,
+    set_low_spec_mode,
+};
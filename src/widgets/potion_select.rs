@@ -3,7 +3,9 @@ use egui::{
     util::cache::{ComputerMut, FrameCache},
 };
 use egui_extras::Column;
-use raphael_data::{Consumable, CrafterStats, Locale, find_potions};
+use raphael_data::{
+    Consumable, CrafterStats, CustomConsumable, Locale, find_custom_consumables, find_potions,
+};
 
 use super::{ItemNameLabel, util};
 
@@ -21,6 +23,7 @@ type PotionSearchCache<'a> = FrameCache<Vec<usize>, PotionFinder>;
 pub struct PotionSelect<'a> {
     crafter_stats: CrafterStats,
     selected_consumable: &'a mut Option<Consumable>,
+    custom_consumables: &'a [CustomConsumable],
     locale: Locale,
 }
 
@@ -28,11 +31,13 @@ impl<'a> PotionSelect<'a> {
     pub fn new(
         crafter_stats: CrafterStats,
         selected_consumable: &'a mut Option<Consumable>,
+        custom_consumables: &'a [CustomConsumable],
         locale: Locale,
     ) -> Self {
         Self {
             crafter_stats,
             selected_consumable,
+            custom_consumables,
             locale,
         }
     }
@@ -53,9 +58,14 @@ impl Widget for PotionSelect<'_> {
                     ui.label(egui::RichText::new("Potion").strong());
                     match self.selected_consumable {
                         None => ui.label("None"),
-                        Some(item) => {
-                            ui.add(ItemNameLabel::new(item.item_id, item.hq, self.locale))
-                        }
+                        Some(item) => match self
+                            .custom_consumables
+                            .iter()
+                            .find(|custom| custom.consumable.item_id == item.item_id)
+                        {
+                            Some(custom) => ui.label(custom.display_name()),
+                            None => ui.add(ItemNameLabel::new(item.item_id, item.hq, self.locale)),
+                        },
                     };
                     ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                         if ui
@@ -70,6 +80,14 @@ impl Widget for PotionSelect<'_> {
                     });
                 });
 
+                util::capped_consumable_warning(
+                    ui,
+                    self.selected_consumable,
+                    raphael_data::game_data().potions(),
+                    self.crafter_stats,
+                    self.locale,
+                );
+
                 if collapsed {
                     return;
                 }
@@ -100,6 +118,8 @@ impl Widget for PotionSelect<'_> {
                     let search_cache = mem.caches.cache::<PotionSearchCache<'_>>();
                     search_result = search_cache.get((&search_text, self.locale));
                 });
+                // custom consumables are listed first, as they are usually the newest ones
+                let custom_result = find_custom_consumables(&search_text, self.custom_consumables);
 
                 ui.ctx().data_mut(|data| {
                     data.insert_persisted(id, search_text);
@@ -125,15 +145,30 @@ impl Widget for PotionSelect<'_> {
                     .max_scroll_height(table_height);
 
                 table.body(|body| {
-                    body.rows(line_height, search_result.len(), |mut row| {
-                        let item = raphael_data::POTIONS[search_result[row.index()]];
+                    let row_count = custom_result.len() + search_result.len();
+                    body.rows(line_height, row_count, |mut row| {
+                        let (item, custom_name) = match custom_result.get(row.index()) {
+                            Some(index) => {
+                                let custom = &self.custom_consumables[*index];
+                                (custom.consumable, Some(custom.display_name()))
+                            }
+                            None => {
+                                let index = search_result[row.index() - custom_result.len()];
+                                (raphael_data::game_data().potions()[index], None)
+                            }
+                        };
                         row.col(|ui| {
                             if ui.button("Select").clicked() {
                                 *self.selected_consumable = Some(item);
                             }
                         });
-                        row.col(|ui| {
-                            ui.add(ItemNameLabel::new(item.item_id, item.hq, self.locale));
+                        row.col(|ui| match custom_name {
+                            Some(name) => {
+                                ui.label(name);
+                            }
+                            None => {
+                                ui.add(ItemNameLabel::new(item.item_id, item.hq, self.locale));
+                            }
                         });
                         row.col(|ui| {
                             ui.label(item.effect_string(
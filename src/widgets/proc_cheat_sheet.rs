@@ -0,0 +1,147 @@
+use raphael_data::{Locale, action_name};
+use raphael_sim::{Action, Condition, Settings, SimulationState};
+use raphael_solver::{ProcAdvice, ProcSuggestion, condition_cheat_sheet};
+
+use super::util;
+
+/// Lists what to do by hand when a step of the rotation gets a Good or Excellent condition, see
+/// [`raphael_solver::condition_cheat_sheet`]. Steps at which the rotation should just be
+/// continued are left out.
+pub struct ProcCheatSheet<'a> {
+    settings: &'a Settings,
+    initial_state: SimulationState,
+    initial_quality: u16,
+    actions: &'a [Action],
+    job_id: u8,
+    locale: Locale,
+}
+
+impl<'a> ProcCheatSheet<'a> {
+    pub fn new(
+        settings: &'a Settings,
+        initial_state: SimulationState,
+        initial_quality: u16,
+        actions: &'a [Action],
+        job_id: u8,
+        locale: Locale,
+    ) -> Self {
+        Self {
+            settings,
+            initial_state,
+            initial_quality,
+            actions,
+            job_id,
+            locale,
+        }
+    }
+
+    fn advice_text(&self, suggestion: &ProcSuggestion) -> String {
+        let step_action = action_name(self.actions[suggestion.step], self.locale);
+        match suggestion.advice {
+            ProcAdvice::Continue => "Continue the macro".to_owned(),
+            ProcAdvice::Replace(action) => format!(
+                "Use {} instead of {step_action}",
+                action_name(action, self.locale)
+            ),
+            ProcAdvice::Insert(action) => format!(
+                "Use {} before {step_action}",
+                action_name(action, self.locale)
+            ),
+        }
+    }
+
+    /// Plain text version of the cheat sheet, meant to be printed or kept next to the game.
+    fn to_text(&self, suggestions: &[ProcSuggestion]) -> String {
+        let lines = suggestions.iter().map(|suggestion| {
+            format!(
+                "Step {} ({}): {} (+{} Quality)",
+                suggestion.step + 1,
+                condition_name(suggestion.condition),
+                self.advice_text(suggestion),
+                suggestion.quality - suggestion.continue_quality,
+            )
+        });
+        std::iter::once("Condition cheat sheet".to_owned())
+            .chain(lines)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl egui::Widget for ProcCheatSheet<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let suggestions: Vec<ProcSuggestion> =
+            condition_cheat_sheet(self.settings, self.initial_state, self.actions)
+                .into_iter()
+                .filter(|suggestion| suggestion.advice != ProcAdvice::Continue)
+                .collect();
+        let mut collapsed = true;
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                util::collapse_persisted(
+                    ui,
+                    egui::Id::new("proc_cheat_sheet_collapsed"),
+                    &mut collapsed,
+                );
+                ui.label(egui::RichText::new("Condition cheat sheet").strong());
+                ui.add(super::HelpText::new("What to do by hand when a step gets a Good or Excellent condition, assuming all other steps are Normal. Steps at which the macro should just be continued aren't listed."));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add_enabled(!suggestions.is_empty(), egui::Button::new("Copy"))
+                        .clicked()
+                    {
+                        util::copy_to_clipboard(ui.ctx(), self.to_text(&suggestions));
+                    }
+                });
+            });
+            if collapsed {
+                return;
+            }
+            ui.separator();
+            if suggestions.is_empty() {
+                ui.label("Just continue the macro on any condition");
+                return;
+            }
+            egui::Grid::new("proc_cheat_sheet")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Step");
+                    ui.label("Condition");
+                    ui.label("Do");
+                    ui.label("Quality");
+                    ui.end_row();
+                    for suggestion in &suggestions {
+                        ui.label((suggestion.step + 1).to_string());
+                        ui.label(condition_name(suggestion.condition));
+                        ui.horizontal(|ui| {
+                            let (ProcAdvice::Replace(action) | ProcAdvice::Insert(action)) =
+                                suggestion.advice
+                            else {
+                                return;
+                            };
+                            ui.add(
+                                util::get_action_icon(action, self.job_id)
+                                    .fit_to_exact_size(egui::Vec2::new(20.0, 20.0))
+                                    .corner_radius(3.0),
+                            );
+                            ui.label(self.advice_text(suggestion));
+                        });
+                        ui.label(format!(
+                            "{} (+{})",
+                            self.initial_quality + suggestion.quality,
+                            suggestion.quality - suggestion.continue_quality
+                        ));
+                        ui.end_row();
+                    }
+                });
+        })
+        .response
+    }
+}
+
+fn condition_name(condition: Condition) -> &'static str {
+    match condition {
+        Condition::Excellent => "Excellent",
+        _ => "Good",
+    }
+}
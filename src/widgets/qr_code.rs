@@ -0,0 +1,43 @@
+/// Renders `data` as a QR code, e.g. to transfer a share code or macro to a phone.
+pub struct QrCode<'a> {
+    data: &'a str,
+    module_size: f32,
+}
+
+impl<'a> QrCode<'a> {
+    pub fn new(data: &'a str) -> Self {
+        Self {
+            data,
+            module_size: 4.0,
+        }
+    }
+}
+
+impl egui::Widget for QrCode<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let code = match qrcode::QrCode::new(self.data.as_bytes()) {
+            Ok(code) => code,
+            Err(error) => return ui.label(format!("Unable to create QR code: {error}")),
+        };
+        let width = code.width();
+        // the spec requires a light border of 4 modules around the code
+        let quiet_zone = 4;
+        let size = (width + 2 * quiet_zone) as f32 * self.module_size;
+        let (response, painter) = ui.allocate_painter(egui::vec2(size, size), egui::Sense::hover());
+
+        // always dark on light, some scanners can't read inverted codes
+        painter.rect_filled(response.rect, 0.0, egui::Color32::WHITE);
+        for (index, color) in code.to_colors().into_iter().enumerate() {
+            if color != qrcode::Color::Dark {
+                continue;
+            }
+            let (x, y) = (index % width + quiet_zone, index / width + quiet_zone);
+            let module = egui::Rect::from_min_size(
+                response.rect.min + egui::vec2(x as f32, y as f32) * self.module_size,
+                egui::Vec2::splat(self.module_size),
+            );
+            painter.rect_filled(module, 0.0, egui::Color32::BLACK);
+        }
+        response
+    }
+}
@@ -4,10 +4,13 @@ use egui::{
 };
 use egui_extras::Column;
 use raphael_data::{
-    Consumable, Ingredient, Locale, RLVLS, find_recipes, get_game_settings, get_job_name,
+    Consumable, GameSettingsBreakdown, GameSettingsOverrides, Ingredient, Locale, find_recipes,
+    game_data, get_game_settings, get_game_settings_breakdown, get_job_name,
 };
 
 use crate::config::{CrafterConfig, QualitySource, RecipeConfiguration};
+use crate::recipe_book::RecipeBook;
+use crate::recipe_difficulty::DifficultyCache;
 
 use super::{ItemNameLabel, util};
 
@@ -25,6 +28,7 @@ type SearchCache<'a> = FrameCache<Vec<usize>, RecipeFinder>;
 pub struct RecipeSelect<'a> {
     crafter_config: &'a mut CrafterConfig,
     recipe_config: &'a mut RecipeConfiguration,
+    recipe_book: &'a mut RecipeBook,
     selected_food: Option<Consumable>, // used for base prog/qual display
     selected_potion: Option<Consumable>, // used for base prog/qual display
     locale: Locale,
@@ -34,6 +38,7 @@ impl<'a> RecipeSelect<'a> {
     pub fn new(
         crafter_config: &'a mut CrafterConfig,
         recipe_config: &'a mut RecipeConfiguration,
+        recipe_book: &'a mut RecipeBook,
         selected_food: Option<Consumable>,
         selected_potion: Option<Consumable>,
         locale: Locale,
@@ -41,13 +46,31 @@ impl<'a> RecipeSelect<'a> {
         Self {
             crafter_config,
             recipe_config,
+            recipe_book,
             selected_food,
             selected_potion,
             locale,
         }
     }
 
-    fn draw_normal_recipe_select(self, ui: &mut egui::Ui) {
+    /// Planning level toggle, which also hides the recipes above the planning level.
+    fn draw_planning_level(&mut self, ui: &mut egui::Ui) {
+        if let Some(planning_level) = self.crafter_config.planning_level.as_mut() {
+            ui.add(egui::DragValue::new(planning_level).range(1..=100));
+        }
+        let mut planning = self.crafter_config.planning_level.is_some();
+        if ui
+            .checkbox(&mut planning, "Plan for level")
+            .on_hover_text("Treat all jobs as this level, e.g. to plan the leveling crafts of an alt. Only the actions and traits of this level are used.")
+            .changed()
+        {
+            self.crafter_config.planning_level =
+                planning.then(|| self.crafter_config.active_stats().level);
+        }
+    }
+
+    /// Returns whether a recipe was selected from the search results.
+    fn draw_normal_recipe_select(mut self, ui: &mut egui::Ui) -> bool {
         let mut search_text = String::new();
         ui.ctx().data_mut(|data| {
             if let Some(text) = data.get_persisted::<String>(Id::new("RECIPE_SEARCH_TEXT")) {
@@ -55,21 +78,43 @@ impl<'a> RecipeSelect<'a> {
             }
         });
 
-        if egui::TextEdit::singleline(&mut search_text)
-            .desired_width(f32::INFINITY)
-            .hint_text("🔍 Search")
-            .ui(ui)
-            .changed()
-        {
-            search_text = search_text.replace('\0', "");
-        }
+        let mut hide_mastered = ui.ctx().data_mut(|data| {
+            *data.get_persisted_mut_or_default::<bool>(Id::new("RECIPE_HIDE_MASTERED"))
+        });
+        ui.horizontal(|ui| {
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                ui.checkbox(&mut hide_mastered, "Hide mastered");
+                self.draw_planning_level(ui);
+                if egui::TextEdit::singleline(&mut search_text)
+                    .desired_width(f32::INFINITY)
+                    .hint_text("🔍 Search")
+                    .ui(ui)
+                    .changed()
+                {
+                    search_text = search_text.replace('\0', "");
+                }
+            });
+        });
+        ui.ctx().data_mut(|data| {
+            data.insert_persisted(Id::new("RECIPE_HIDE_MASTERED"), hide_mastered);
+        });
         ui.separator();
 
+        let mut selected = false;
         let mut search_result = Vec::new();
         ui.ctx().memory_mut(|mem| {
             let search_cache = mem.caches.cache::<SearchCache<'_>>();
             search_result = search_cache.get((&search_text, self.locale));
         });
+        if let Some(planning_level) = self.crafter_config.planning_level {
+            search_result.retain(|index| game_data().recipes()[*index].level <= planning_level);
+        }
+        if hide_mastered {
+            search_result.retain(|index| {
+                let recipe = &game_data().recipes()[*index];
+                !self.recipe_book.is_mastered(recipe.job_id, recipe.item_id)
+            });
+        }
 
         ui.ctx().data_mut(|data| {
             data.insert_persisted(Id::new("RECIPE_SEARCH_TEXT"), search_text);
@@ -83,7 +128,8 @@ impl<'a> RecipeSelect<'a> {
         // manually calculate the width of the last col to avoid janky behavior when resizing tables
         // this is a workaround until this bug is fixed in egui_extras
         let spacing = 2.0 * ui.spacing().item_spacing.x;
-        let item_name_width = (ui.available_width() - 42.0 - 28.0 - spacing).max(0.0);
+        let item_name_width =
+            (ui.available_width() - 42.0 - 28.0 - 20.0 - 12.0 - 2.0 * spacing).max(0.0);
 
         let table = egui_extras::TableBuilder::new(ui)
             .id_salt("RECIPE_SELECT_TABLE")
@@ -91,39 +137,97 @@ impl<'a> RecipeSelect<'a> {
             .striped(true)
             .column(Column::exact(42.0))
             .column(Column::exact(28.0))
+            .column(Column::exact(20.0))
+            .column(Column::exact(12.0))
             .column(Column::exact(item_name_width))
             .min_scrolled_height(table_height)
             .max_scroll_height(table_height);
         table.body(|body| {
             body.rows(line_height, search_result.len(), |mut row| {
-                let recipe = raphael_data::RECIPES[search_result[row.index()]];
+                let recipe = game_data().recipes()[search_result[row.index()]];
                 row.col(|ui| {
                     if ui.button("Select").clicked() {
                         self.crafter_config.selected_job = recipe.job_id;
                         *self.recipe_config = RecipeConfiguration {
                             recipe,
                             quality_source: QualitySource::HqMaterialList([0; 6]),
-                        }
+                        };
+                        selected = true;
                     }
                 });
                 row.col(|ui| {
                     ui.label(get_job_name(recipe.job_id, self.locale));
                 });
+                row.col(|ui| {
+                    let mastered = self.recipe_book.is_mastered(recipe.job_id, recipe.item_id);
+                    let (icon, hover_text) = match mastered {
+                        true => ("★", "Mastered, click to unmark"),
+                        false => ("☆", "Mark as mastered"),
+                    };
+                    if ui
+                        .add(egui::Button::new(icon).frame(false))
+                        .on_hover_text(hover_text)
+                        .clicked()
+                    {
+                        self.recipe_book
+                            .set_mastered(recipe.job_id, recipe.item_id, !mastered);
+                    }
+                });
+                row.col(|ui| {
+                    let settings = get_game_settings(
+                        recipe,
+                        self.crafter_config.job_stats(recipe.job_id),
+                        self.selected_food,
+                        self.selected_potion,
+                        false,
+                    );
+                    let difficulty = ui
+                        .ctx()
+                        .memory_mut(|mem| mem.caches.cache::<DifficultyCache>().get(&settings));
+                    ui.label(egui::RichText::new("●").color(difficulty.color()))
+                        .on_hover_text(difficulty.description());
+                });
                 row.col(|ui| {
                     ui.add(ItemNameLabel::new(recipe.item_id, false, self.locale));
                 });
             });
         });
+
+        let job_id = self.crafter_config.selected_job;
+        egui::CollapsingHeader::new(format!("{} completion", get_job_name(job_id, self.locale)))
+            .id_salt("RECIPE_COMPLETION")
+            .show(ui, |ui| {
+                egui::Grid::new("RECIPE_COMPLETION_GRID")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for progress in self.recipe_book.progress(job_id) {
+                            let fraction = match progress.total {
+                                0 => 0.0,
+                                total => progress.mastered as f32 / total as f32,
+                            };
+                            ui.label(progress.name);
+                            ui.add(
+                                egui::ProgressBar::new(fraction)
+                                    .desired_width(160.0)
+                                    .text(format!("{}/{}", progress.mastered, progress.total)),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+        selected
     }
 
     fn draw_custom_recipe_select(self, ui: &mut egui::Ui) {
-        let game_settings = get_game_settings(
+        let breakdown = get_game_settings_breakdown(
             self.recipe_config.recipe,
-            *self.crafter_config.active_stats(),
+            self.crafter_config.active_stats(),
             self.selected_food,
             self.selected_potion,
             false,
+            GameSettingsOverrides::default(),
         );
+        let game_settings = breakdown.settings;
 
         ui.horizontal_top(|ui| {
             ui.vertical(|ui| {
@@ -137,7 +241,7 @@ impl<'a> RecipeSelect<'a> {
                     ui.label("Recipe Level:");
                     ui.add(
                         egui::DragValue::new(&mut self.recipe_config.recipe.recipe_level)
-                            .range(1..=RLVLS.len() - 1),
+                            .range(1..=game_data().recipe_levels().len() - 1),
                     );
                 });
                 ui.horizontal(|ui| {
@@ -172,7 +276,8 @@ impl<'a> RecipeSelect<'a> {
             });
             ui.separator();
             ui.vertical(|ui| {
-                let mut rlvl = RLVLS[self.recipe_config.recipe.recipe_level as usize];
+                let mut rlvl =
+                    game_data().recipe_levels()[self.recipe_config.recipe.recipe_level as usize];
                 ui.horizontal(|ui| {
                     ui.label("Progress divider");
                     ui.add_enabled(false, egui::DragValue::new(&mut rlvl.progress_div));
@@ -191,20 +296,58 @@ impl<'a> RecipeSelect<'a> {
                 });
                 ui.horizontal(|ui| {
                     ui.label("Progress per 100% efficiency:");
-                    ui.label(egui::RichText::new(game_settings.base_progress.to_string()).strong());
+                    ui.label(egui::RichText::new(game_settings.base_progress.to_string()).strong())
+                        .on_hover_text(base_value_formula(
+                            &breakdown,
+                            "Craftsmanship",
+                            breakdown.effective_stats.craftsmanship,
+                            breakdown.recipe_level.progress_div,
+                            2,
+                            breakdown.recipe_level.progress_mod,
+                            breakdown.base_progress,
+                        ));
                 });
                 ui.horizontal(|ui| {
                     ui.label("Quality per 100% efficiency:");
-                    ui.label(egui::RichText::new(game_settings.base_quality.to_string()).strong());
+                    ui.label(egui::RichText::new(game_settings.base_quality.to_string()).strong())
+                        .on_hover_text(base_value_formula(
+                            &breakdown,
+                            "Control",
+                            breakdown.effective_stats.control,
+                            breakdown.recipe_level.quality_div,
+                            35,
+                            breakdown.recipe_level.quality_mod,
+                            breakdown.base_quality,
+                        ));
                 });
             });
         });
     }
 }
 
+/// Explains how the Progress or Quality per 100% efficiency is derived from the crafter stat.
+fn base_value_formula(
+    breakdown: &GameSettingsBreakdown,
+    stat_name: &str,
+    stat: u16,
+    divider: u16,
+    offset: u16,
+    modifier: u16,
+    value: f32,
+) -> String {
+    let formula = match breakdown.level_penalty {
+        true => format!("({stat_name} {stat} × 10 / {divider} + {offset}) × {modifier}%"),
+        false => format!("{stat_name} {stat} × 10 / {divider} + {offset}"),
+    };
+    format!(
+        "{formula} = {value:.2}\n\nStats include consumables. The recipe level modifier only applies if the crafter level isn't above the recipe level."
+    )
+}
+
 impl Widget for RecipeSelect<'_> {
     fn ui(self, ui: &mut egui::Ui) -> egui::Response {
-        ui.group(|ui| {
+        let mut selected = false;
+        let group = ui.group(|ui| {
             ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
             ui.vertical(|ui| {
                 let mut custom_recipe = false;
@@ -255,10 +398,15 @@ impl Widget for RecipeSelect<'_> {
                 if custom_recipe {
                     self.draw_custom_recipe_select(ui);
                 } else {
-                    self.draw_normal_recipe_select(ui);
+                    selected = self.draw_normal_recipe_select(ui);
                 }
             });
-        })
-        .response
+        });
+        let mut response = group.response;
+        // lets the app load the default rotation of the selected recipe
+        if selected {
+            response.mark_changed();
+        }
+        response
     }
 }
@@ -0,0 +1,228 @@
+use std::sync::mpsc::{self, Receiver};
+
+use raphael_sim::{Action, SimulationState};
+use raphael_solver::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+use crate::app::CraftParams;
+
+use super::util;
+
+struct ReportEntry {
+    actions: Vec<Action>,
+    /// Quality when all conditions are Normal.
+    quality: u16,
+    /// Quality that is reached no matter how unlucky the conditions are.
+    guaranteed_quality: u16,
+    steps: usize,
+    duration: u16,
+}
+
+impl ReportEntry {
+    fn new(params: &CraftParams, actions: Vec<Action>) -> Self {
+        let quality = |adversarial| {
+            let settings = params.solver_settings(params.crafter_stats, adversarial);
+            let (state, _) = SimulationState::from_macro_continue_on_error(&settings, &actions);
+            params.initial_quality.saturating_add(state.quality)
+        };
+        Self {
+            quality: quality(false),
+            guaranteed_quality: quality(true),
+            steps: actions.len(),
+            duration: actions
+                .iter()
+                .map(|action| u16::from(action.time_cost()))
+                .sum(),
+            actions,
+        }
+    }
+}
+
+type Report = [Result<ReportEntry, SolverException>; 2];
+
+struct ReportRun {
+    result: Receiver<Report>,
+    interrupt_signal: AtomicFlag,
+}
+
+/// Side-by-side comparison of the regular macro and the 100% reliable macro for the same Quality target.
+#[derive(Default)]
+pub struct ReliabilityReportData {
+    run: Option<ReportRun>,
+    report: Option<Report>,
+    target_quality: u16,
+}
+
+impl ReliabilityReportData {
+    pub fn start(&mut self, ctx: egui::Context, params: CraftParams) {
+        if let Some(run) = self.run.take() {
+            run.interrupt_signal.set();
+        }
+        self.report = None;
+        self.target_quality = params.target_quality();
+
+        let (tx, rx) = mpsc::channel();
+        let interrupt_signal = AtomicFlag::new();
+        self.run = Some(ReportRun {
+            result: rx,
+            interrupt_signal: interrupt_signal.clone(),
+        });
+
+        let solve = move |adversarial: bool| -> Result<ReportEntry, SolverException> {
+            let actions = MacroSolver::new(
+                SolverSettings {
+                    simulator_settings: params.solver_settings(params.crafter_stats, adversarial),
+                    backload_progress: params.solver_config.backload_progress,
+                    allow_unsound_branch_pruning: false,
+                    forbid_quality_during_muscle_memory: params
+                        .solver_config
+                        .forbid_quality_during_muscle_memory,
+                    first_quality_step: params.solver_config.first_quality_step,
+                },
+                Box::new(|_| {}),
+                Box::new(|_| {}),
+                interrupt_signal.clone(),
+            )
+            .solve()?;
+            Ok(ReportEntry::new(&params, actions))
+        };
+        std::thread::spawn(move || {
+            let thread_pool = match params.thread_pool_settings.build() {
+                Ok(thread_pool) => thread_pool,
+                Err(error) => {
+                    log::error!("Failed to build thread pool: {error}");
+                    return;
+                }
+            };
+            let (regular, reliable) =
+                thread_pool.install(|| rayon::join(|| solve(false), || solve(true)));
+            _ = tx.send([regular, reliable]);
+            ctx.request_repaint();
+        });
+    }
+
+    fn update(&mut self) {
+        if let Some(run) = self.run.as_ref() {
+            match run.result.try_recv() {
+                Ok(report) => {
+                    self.report = Some(report);
+                    self.run = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.run = None,
+                Err(mpsc::TryRecvError::Empty) => (),
+            }
+        }
+    }
+}
+
+pub struct ReliabilityReportWidget<'a> {
+    data: &'a mut ReliabilityReportData,
+    params: CraftParams,
+    actions: &'a mut Vec<Action>,
+}
+
+impl<'a> ReliabilityReportWidget<'a> {
+    pub fn new(
+        data: &'a mut ReliabilityReportData,
+        params: CraftParams,
+        actions: &'a mut Vec<Action>,
+    ) -> Self {
+        Self {
+            data,
+            params,
+            actions,
+        }
+    }
+
+    fn show_report(&mut self, ui: &mut egui::Ui) {
+        let Some(report) = self.data.report.as_ref() else {
+            ui.label("Generate a report to compare both macros");
+            return;
+        };
+        ui.label(format!("Target Quality: {}", self.data.target_quality));
+        egui::Grid::new("reliability_report")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("");
+                ui.label(egui::RichText::new("Regular").strong());
+                ui.label(egui::RichText::new("100% reliable").strong());
+                ui.end_row();
+
+                let row = |ui: &mut egui::Ui,
+                           label: &str,
+                           value: &dyn Fn(&ReportEntry) -> String| {
+                    ui.label(label);
+                    for entry in report {
+                        match entry {
+                            Ok(entry) => ui.label(value(entry)),
+                            Err(SolverException::NoSolution) => ui.label("No solution"),
+                            Err(SolverException::Interrupted) => ui.label("Cancelled"),
+                            Err(SolverException::MemoryLimitReached) => ui.label("Out of memory"),
+                            Err(SolverException::InternalError(_)) => ui.label("Error"),
+                        };
+                    }
+                    ui.end_row();
+                };
+                row(ui, "Quality", &|entry| entry.quality.to_string());
+                row(ui, "Guaranteed Quality", &|entry| {
+                    entry.guaranteed_quality.to_string()
+                });
+                row(ui, "Steps", &|entry| entry.steps.to_string());
+                row(ui, "Duration", &|entry| format!("{}s", entry.duration));
+
+                ui.label("");
+                for entry in report {
+                    match entry {
+                        Ok(entry) => {
+                            if ui.button("Load macro").clicked() {
+                                self.actions.clone_from(&entry.actions);
+                            }
+                        }
+                        Err(_) => {
+                            ui.label("");
+                        }
+                    }
+                }
+                ui.end_row();
+            });
+
+        if let [Ok(regular), Ok(reliable)] = report {
+            ui.label(format!(
+                "100% reliability costs {} additional steps and {}s of additional duration.",
+                reliable.steps as i64 - regular.steps as i64,
+                i32::from(reliable.duration) - i32::from(regular.duration),
+            ));
+        }
+        if let Some(Err(SolverException::InternalError(message))) =
+            report.iter().find(|entry| entry.is_err())
+        {
+            ui.label(message);
+        }
+    }
+}
+
+impl egui::Widget for ReliabilityReportWidget<'_> {
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        self.data.update();
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| match self.data.run.as_ref() {
+                Some(run) => {
+                    util::busy_indicator(ui);
+                    ui.label("Solving both macros ...");
+                    if ui.button("Cancel").clicked() {
+                        run.interrupt_signal.set();
+                    }
+                    ui.ctx()
+                        .request_repaint_after(util::background_repaint_interval(ui.ctx()));
+                }
+                None => {
+                    if ui.button("Generate report").clicked() {
+                        self.data.start(ui.ctx().clone(), self.params);
+                    }
+                }
+            });
+            ui.separator();
+            self.show_report(ui);
+        })
+        .response
+    }
+}
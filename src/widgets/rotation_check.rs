@@ -0,0 +1,123 @@
+use raphael_data::{Consumable, CrafterStats, Locale, Recipe, action_name};
+use raphael_sim::{Action, SimulationState};
+
+/// Checks whether the current rotation can be used at a different job level,
+/// e.g. on an alt with lower level crafters.
+pub struct RotationCheck<'a> {
+    job_level: &'a mut u8,
+    recipe: Recipe,
+    crafter_stats: CrafterStats,
+    food: Option<Consumable>,
+    potion: Option<Consumable>,
+    initial_quality: u16,
+    actions: &'a [Action],
+    locale: Locale,
+}
+
+impl<'a> RotationCheck<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        job_level: &'a mut u8,
+        recipe: Recipe,
+        crafter_stats: CrafterStats,
+        food: Option<Consumable>,
+        potion: Option<Consumable>,
+        initial_quality: u16,
+        actions: &'a [Action],
+        locale: Locale,
+    ) -> Self {
+        Self {
+            job_level,
+            recipe,
+            crafter_stats,
+            food,
+            potion,
+            initial_quality,
+            actions,
+            locale,
+        }
+    }
+}
+
+/// Describes why the rotation can't be used, or `None` if every action can be used in order.
+pub fn rotation_error_text(
+    recipe: Recipe,
+    crafter_stats: CrafterStats,
+    food: Option<Consumable>,
+    potion: Option<Consumable>,
+    actions: &[Action],
+    locale: Locale,
+) -> Option<String> {
+    let settings = raphael_data::get_game_settings(recipe, crafter_stats, food, potion, false);
+    let error = SimulationState::validate_macro(&settings, actions).err()?;
+    Some(format!(
+        "Step {} ({}): {}",
+        error.step + 1,
+        action_name(error.action, locale),
+        error.reason
+    ))
+}
+
+impl egui::Widget for RotationCheck<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Job level");
+                ui.add(egui::DragValue::new(self.job_level).range(1..=100));
+            });
+            ui.label(
+                "Uses the stats and consumables of the current crafter with the job level above.",
+            );
+            ui.separator();
+
+            if self.actions.is_empty() {
+                ui.label("No rotation to check");
+                return;
+            }
+            let crafter_stats = CrafterStats {
+                level: *self.job_level,
+                ..self.crafter_stats
+            };
+            if let Some(error) = rotation_error_text(
+                self.recipe,
+                crafter_stats,
+                self.food,
+                self.potion,
+                self.actions,
+                self.locale,
+            ) {
+                ui.label(
+                    egui::RichText::new(format!("⚠ {error}")).color(ui.visuals().warn_fg_color),
+                );
+                return;
+            }
+
+            let settings = raphael_data::get_game_settings(
+                self.recipe,
+                crafter_stats,
+                self.food,
+                self.potion,
+                false,
+            );
+            let state = SimulationState::from_macro(&settings, self.actions)
+                .expect("rotation was validated above");
+            if state.progress < settings.max_progress {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "⚠ All actions can be used, but the craft is not finished (Progress {}/{})",
+                        state.progress, settings.max_progress
+                    ))
+                    .color(ui.visuals().warn_fg_color),
+                );
+            } else {
+                ui.label(format!(
+                    "✔ The rotation works at level {} (Quality {}/{})",
+                    self.job_level,
+                    self.initial_quality.saturating_add(state.quality),
+                    settings.max_quality
+                ));
+            }
+        })
+        .response
+    }
+}
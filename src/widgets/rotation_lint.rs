@@ -0,0 +1,82 @@
+use raphael_data::{Locale, action_name};
+use raphael_sim::lint::{Linter, Severity};
+use raphael_sim::{Action, Settings};
+
+const DISABLED_RULES_ID: &str = "LINT_DISABLED_RULES";
+
+/// Lists the findings of the rotation linter, see [`raphael_sim::lint`].
+/// Rules can be disabled individually, which is persisted across sessions.
+pub struct RotationLint<'a> {
+    settings: &'a Settings,
+    actions: &'a [Action],
+    locale: Locale,
+}
+
+impl<'a> RotationLint<'a> {
+    pub fn new(settings: &'a Settings, actions: &'a [Action], locale: Locale) -> Self {
+        Self {
+            settings,
+            actions,
+            locale,
+        }
+    }
+}
+
+impl egui::Widget for RotationLint<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let id = egui::Id::new(DISABLED_RULES_ID);
+        let mut disabled_rules: Vec<String> = ui
+            .ctx()
+            .data_mut(|data| data.get_persisted(id).unwrap_or_default());
+
+        let mut linter = Linter::default();
+        let rules: Vec<&'static str> = linter.rules().map(|(rule, _)| rule).collect();
+        for rule in &disabled_rules {
+            linter.disable(rule);
+        }
+        let findings = linter.lint(self.settings, self.actions);
+
+        let response = ui
+            .vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Rotation lint").strong());
+                    ui.menu_button("Rules", |ui| {
+                        for rule in rules {
+                            let mut enabled = !disabled_rules.iter().any(|name| name == rule);
+                            if ui.checkbox(&mut enabled, rule).changed() {
+                                match enabled {
+                                    true => disabled_rules.retain(|name| name != rule),
+                                    false => disabled_rules.push(rule.to_string()),
+                                }
+                            }
+                        }
+                    });
+                });
+                if findings.is_empty() {
+                    ui.label("No problems found");
+                }
+                for finding in findings {
+                    let (icon, color) = match finding.severity {
+                        Severity::Info => ("ℹ", ui.visuals().text_color()),
+                        Severity::Warning => ("⚠", ui.visuals().warn_fg_color),
+                        Severity::Error => ("❌", ui.visuals().error_fg_color),
+                    };
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{icon} Step {} ({}): {}",
+                            finding.step + 1,
+                            action_name(finding.action, self.locale),
+                            finding.message
+                        ))
+                        .color(color),
+                    )
+                    .on_hover_text(finding.rule);
+                }
+            })
+            .response;
+
+        ui.ctx()
+            .data_mut(|data| data.insert_persisted(id, disabled_rules));
+        response
+    }
+}
@@ -3,7 +3,9 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use raphael_data::{Consumable, CrafterStats, Locale, Recipe};
+use raphael_data::{
+    Consumable, CrafterStats, Locale, Recipe, game_data, get_game_settings, hq_percentage,
+};
 use raphael_sim::*;
 use serde::{Deserialize, Serialize};
 
@@ -28,9 +30,18 @@ pub struct Rotation {
     pub potion: Option<(u32, bool)>,
     pub crafter_stats: CrafterStats,
     pub job_id: u8,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub initial_quality: u16,
+    /// Quality target including the initial Quality. `None` for rotations saved before the
+    /// target was recorded.
+    #[serde(default)]
+    pub target_quality: Option<u16>,
 }
 
 impl Rotation {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: impl Into<String>,
         actions: Vec<Action>,
@@ -39,9 +50,10 @@ impl Rotation {
         potion: Option<Consumable>,
         crafter_config: &CrafterConfig,
         solver_config: &SolverConfig,
+        initial_quality: u16,
     ) -> Self {
         let solver_params = format!(
-            "Raphael v{}{}{}{}",
+            "Raphael v{}{}{}{}{}{}",
             env!("CARGO_PKG_VERSION"),
             match solver_config.backload_progress {
                 true => " +backload",
@@ -54,6 +66,14 @@ impl Rotation {
             match solver_config.minimize_steps {
                 true => " +min_step",
                 false => "",
+            },
+            match solver_config.minimize_cp {
+                true => " +min_cp",
+                false => "",
+            },
+            match solver_config.expected_quality {
+                true => " +expected",
+                false => "",
             }
         );
         Self {
@@ -64,8 +84,89 @@ impl Rotation {
             item: recipe.item_id,
             food: food.map(|consumable| (consumable.item_id, consumable.hq)),
             potion: potion.map(|consumable| (consumable.item_id, consumable.hq)),
-            crafter_stats: *crafter_config.active_stats(),
+            crafter_stats: crafter_config.active_stats(),
             job_id: crafter_config.selected_job,
+            notes: String::new(),
+            initial_quality,
+            target_quality: Some(solver_config.quality_target.get_target(recipe.quality)),
+        }
+    }
+
+    /// Quality reached by the rotation and the max Quality of the recipe, assuming Normal
+    /// conditions. `None` for collectables, which aren't judged by their HQ percentage, and
+    /// for rotations that don't finish the craft.
+    pub fn final_quality(&self) -> Option<(u16, u16)> {
+        if game_data()
+            .item(self.item)
+            .is_some_and(|item| item.always_collectable)
+        {
+            return None;
+        }
+        let recipe = game_data()
+            .recipes()
+            .iter()
+            .find(|recipe| recipe.item_id == self.item && recipe.job_id == self.job_id)?;
+        let find_consumable = |consumable: Option<(u32, bool)>, consumables: &[Consumable]| {
+            consumable.and_then(|(item_id, hq)| {
+                consumables
+                    .iter()
+                    .find(|consumable| consumable.item_id == item_id && consumable.hq == hq)
+                    .copied()
+            })
+        };
+        let settings = get_game_settings(
+            *recipe,
+            self.crafter_stats,
+            find_consumable(self.food, game_data().meals()),
+            find_consumable(self.potion, game_data().potions()),
+            false,
+        );
+        let state = SimulationState::from_macro(&settings, &self.actions).ok()?;
+        match state.progress >= settings.max_progress {
+            true => Some((
+                self.initial_quality.saturating_add(state.quality),
+                settings.max_quality,
+            )),
+            false => None,
+        }
+    }
+}
+
+/// Identifies the recipe and crafter setup that a rotation was solved for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecipeFingerprint {
+    pub item: u32,
+    pub job_id: u8,
+    pub crafter_stats: CrafterStats,
+    pub food: Option<(u32, bool)>,
+    pub potion: Option<(u32, bool)>,
+}
+
+impl RecipeFingerprint {
+    pub fn new(
+        recipe: &Recipe,
+        food: Option<Consumable>,
+        potion: Option<Consumable>,
+        crafter_config: &CrafterConfig,
+    ) -> Self {
+        Self {
+            item: recipe.item_id,
+            job_id: crafter_config.selected_job,
+            crafter_stats: crafter_config.active_stats(),
+            food: food.map(|consumable| (consumable.item_id, consumable.hq)),
+            potion: potion.map(|consumable| (consumable.item_id, consumable.hq)),
+        }
+    }
+}
+
+impl Rotation {
+    pub fn fingerprint(&self) -> RecipeFingerprint {
+        RecipeFingerprint {
+            item: self.item,
+            job_id: self.job_id,
+            crafter_stats: self.crafter_stats,
+            food: self.food,
+            potion: self.potion,
         }
     }
 }
@@ -82,14 +183,30 @@ impl Clone for Rotation {
             potion: self.potion,
             crafter_stats: self.crafter_stats,
             job_id: self.job_id,
+            notes: self.notes.clone(),
+            initial_quality: self.initial_quality,
+            target_quality: self.target_quality,
         }
     }
 }
 
+/// A saved rotation whose actions are similar to a solve result, see
+/// [`SavedRotationsData::similar_rotations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimilarRotation {
+    pub name: String,
+    /// Number of actions that have to be inserted, removed or replaced to turn one rotation
+    /// into the other.
+    pub distance: usize,
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SavedRotationsData {
     pinned: Vec<Rotation>,
     solve_history: VecDeque<Rotation>,
+    /// Pinned rotations that are loaded automatically when selecting a recipe, identified by `unique_id`.
+    #[serde(default)]
+    recipe_defaults: Vec<(RecipeFingerprint, u64)>,
 }
 
 impl SavedRotationsData {
@@ -101,14 +218,120 @@ impl SavedRotationsData {
         }
         self.solve_history.push_front(rotation);
     }
+
+    pub fn pinned(&self) -> &[Rotation] {
+        &self.pinned
+    }
+
+    /// Returns the pinned rotation that was set as default for the given recipe and crafter setup.
+    pub fn recipe_default(&self, fingerprint: &RecipeFingerprint) -> Option<&Rotation> {
+        let (_, unique_id) = self
+            .recipe_defaults
+            .iter()
+            .find(|(key, _)| key == fingerprint)?;
+        self.pinned
+            .iter()
+            .find(|rotation| rotation.unique_id == *unique_id)
+    }
+
+    /// Pins the rotation as the default of its recipe.
+    #[cfg(test)]
+    pub fn pin_recipe_default(&mut self, rotation: Rotation) {
+        Self::set_recipe_default(&mut self.recipe_defaults, &rotation);
+        self.pinned.push(rotation);
+    }
+
+    fn is_recipe_default(
+        recipe_defaults: &[(RecipeFingerprint, u64)],
+        rotation: &Rotation,
+    ) -> bool {
+        recipe_defaults
+            .iter()
+            .any(|(_, unique_id)| *unique_id == rotation.unique_id)
+    }
+
+    fn set_recipe_default(
+        recipe_defaults: &mut Vec<(RecipeFingerprint, u64)>,
+        rotation: &Rotation,
+    ) {
+        let fingerprint = rotation.fingerprint();
+        recipe_defaults.retain(|(key, _)| *key != fingerprint);
+        recipe_defaults.push((fingerprint, rotation.unique_id));
+    }
+
+    /// Pinned rotations of the given job whose actions are at most `max_distance` edits away
+    /// from `actions`, most similar first.
+    pub fn similar_rotations(
+        &self,
+        actions: &[Action],
+        job_id: u8,
+        max_distance: usize,
+    ) -> Vec<SimilarRotation> {
+        let mut similar_rotations: Vec<SimilarRotation> = self
+            .pinned
+            .iter()
+            .filter(|rotation| rotation.job_id == job_id)
+            .filter_map(|rotation| {
+                let distance = edit_distance(&rotation.actions, actions);
+                (distance <= max_distance).then(|| SimilarRotation {
+                    name: rotation.name.clone(),
+                    distance,
+                })
+            })
+            .collect();
+        similar_rotations.sort_by_key(|rotation| rotation.distance);
+        similar_rotations
+    }
+
+    /// Adds all rotations from `other` that are not already present.
+    #[cfg(target_arch = "wasm32")]
+    pub fn merge(&mut self, other: Self) {
+        let is_new = |rotation: &Rotation, pinned: &[Rotation], history: &VecDeque<Rotation>| {
+            pinned
+                .iter()
+                .chain(history)
+                .all(|existing| existing.unique_id != rotation.unique_id)
+        };
+        for rotation in other.pinned {
+            if is_new(&rotation, &self.pinned, &self.solve_history) {
+                self.pinned.push(rotation);
+            }
+        }
+        for rotation in other.solve_history {
+            if is_new(&rotation, &self.pinned, &self.solve_history)
+                && self.solve_history.len() < Self::MAX_HISTORY_SIZE
+            {
+                self.solve_history.push_back(rotation);
+            }
+        }
+    }
+}
+
+/// Levenshtein distance between two rotations.
+fn edit_distance(lhs: &[Action], rhs: &[Action]) -> usize {
+    let mut previous_row: Vec<usize> = (0..=rhs.len()).collect();
+    let mut current_row = vec![0; rhs.len() + 1];
+    for (i, lhs_action) in lhs.iter().enumerate() {
+        current_row[0] = i + 1;
+        for (j, rhs_action) in rhs.iter().enumerate() {
+            let replace_cost = usize::from(lhs_action != rhs_action);
+            current_row[j + 1] = (previous_row[j] + replace_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+    previous_row[rhs.len()]
 }
 
 struct RotationWidget<'a> {
     locale: Locale,
     pinned: &'a mut bool,
     deleted: &'a mut bool,
-    rotation: &'a Rotation,
+    recipe_default: &'a mut bool,
+    rotation: &'a mut Rotation,
     actions: &'a mut Vec<Action>,
+    notes: &'a mut String,
 }
 
 impl<'a> RotationWidget<'a> {
@@ -116,15 +339,19 @@ impl<'a> RotationWidget<'a> {
         locale: Locale,
         pinned: &'a mut bool,
         deleted: &'a mut bool,
-        rotation: &'a Rotation,
+        recipe_default: &'a mut bool,
+        rotation: &'a mut Rotation,
         actions: &'a mut Vec<Action>,
+        notes: &'a mut String,
     ) -> Self {
         Self {
             locale,
             pinned,
             deleted,
+            recipe_default,
             rotation,
             actions,
+            notes,
         }
     }
 
@@ -135,7 +362,20 @@ impl<'a> RotationWidget<'a> {
     fn show_rotation_title(&mut self, ui: &mut egui::Ui, collapsed: &mut bool) {
         ui.horizontal(|ui| {
             util::collapse_temporary(ui, self.id_salt("collapsed").into(), collapsed);
-            ui.label(egui::RichText::new(&self.rotation.name).strong());
+            let renaming_id = egui::Id::new(self.id_salt("renaming"));
+            let renaming = ui.data(|data| data.get_temp(renaming_id).unwrap_or(false));
+            if renaming {
+                let response = ui
+                    .add(egui::TextEdit::singleline(&mut self.rotation.name).desired_width(160.0));
+                if response.lost_focus() || ui.button("✔").clicked() {
+                    ui.data_mut(|data| data.insert_temp(renaming_id, false));
+                }
+            } else {
+                ui.label(egui::RichText::new(&self.rotation.name).strong());
+                if ui.small_button("✏").on_hover_text("Rename").clicked() {
+                    ui.data_mut(|data| data.insert_temp(renaming_id, true));
+                }
+            }
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.add(egui::Button::new("🗑")).clicked() {
                     *self.deleted = true;
@@ -148,8 +388,18 @@ impl<'a> RotationWidget<'a> {
                     *self.pinned = true;
                 }
                 ui.add_space(-3.0);
+                if ui
+                    .add_enabled(!*self.recipe_default, egui::Button::new("⭐"))
+                    .on_hover_text("Load this macro automatically when selecting this recipe with the same stats and consumables")
+                    .on_disabled_hover_text("Default macro for this recipe")
+                    .clicked()
+                {
+                    *self.recipe_default = true;
+                }
+                ui.add_space(-3.0);
                 if ui.button("Load").clicked() {
                     self.actions.clone_from(&self.rotation.actions);
+                    self.notes.clone_from(&self.rotation.notes);
                 }
                 let duration = self
                     .rotation
@@ -162,6 +412,17 @@ impl<'a> RotationWidget<'a> {
                     self.rotation.actions.len(),
                     duration
                 ));
+                if let Some((quality, max_quality)) = self.rotation.final_quality() {
+                    let hq = hq_percentage(quality, max_quality).unwrap_or(0);
+                    let response = ui.label(egui::RichText::new(format!("{hq}% HQ")).strong());
+                    if let Some(target_quality) = self.rotation.target_quality {
+                        response.on_hover_text(util::quality_delta_text(
+                            quality,
+                            target_quality,
+                            max_quality,
+                        ));
+                    }
+                }
             });
         });
     }
@@ -186,7 +447,7 @@ impl<'a> RotationWidget<'a> {
         }
     }
 
-    fn show_rotation_info(&self, ui: &mut egui::Ui) {
+    fn show_rotation_info(&mut self, ui: &mut egui::Ui) {
         let stats_string = format!(
             "{} CMS, {} Control, {} CP",
             self.rotation.crafter_stats.craftsmanship,
@@ -208,6 +469,16 @@ impl<'a> RotationWidget<'a> {
         self.show_info_row(ui, "Food", self.get_consumable_name(self.rotation.food));
         self.show_info_row(ui, "Potion", self.get_consumable_name(self.rotation.potion));
         self.show_info_row(ui, "Solver", &self.rotation.solver);
+        ui.horizontal(|ui| {
+            let used_width = ui.label("Notes").rect.width();
+            ui.add_space(96.0 - used_width);
+            ui.add(
+                egui::TextEdit::multiline(&mut self.rotation.notes)
+                    .hint_text("Gear set, intended content, ...")
+                    .desired_rows(2)
+                    .desired_width(f32::INFINITY),
+            );
+        });
     }
 
     fn show_rotation_actions(&self, ui: &mut egui::Ui) {
@@ -250,6 +521,7 @@ pub struct SavedRotationsWidget<'a> {
     locale: Locale,
     rotations: &'a mut SavedRotationsData,
     actions: &'a mut Vec<Action>,
+    notes: &'a mut String,
 }
 
 impl<'a> SavedRotationsWidget<'a> {
@@ -257,11 +529,13 @@ impl<'a> SavedRotationsWidget<'a> {
         locale: Locale,
         rotations: &'a mut SavedRotationsData,
         actions: &'a mut Vec<Action>,
+        notes: &'a mut String,
     ) -> Self {
         Self {
             locale,
             rotations,
             actions,
+            notes,
         }
     }
 }
@@ -276,15 +550,27 @@ impl egui::Widget for SavedRotationsWidget<'_> {
                     if self.rotations.pinned.is_empty() {
                         ui.label("No saved macros");
                     }
-                    self.rotations.pinned.retain(|rotation| {
+                    let recipe_defaults = &mut self.rotations.recipe_defaults;
+                    self.rotations.pinned.retain_mut(|rotation| {
                         let mut deleted = false;
+                        let mut recipe_default =
+                            SavedRotationsData::is_recipe_default(recipe_defaults, rotation);
                         ui.add(RotationWidget::new(
                             self.locale,
                             &mut true,
                             &mut deleted,
+                            &mut recipe_default,
                             rotation,
                             self.actions,
+                            self.notes,
                         ));
+                        if recipe_default {
+                            SavedRotationsData::set_recipe_default(recipe_defaults, rotation);
+                        }
+                        if deleted {
+                            recipe_defaults
+                                .retain(|(_, unique_id)| *unique_id != rotation.unique_id);
+                        }
                         !deleted
                     });
                 });
@@ -304,20 +590,31 @@ impl egui::Widget for SavedRotationsWidget<'_> {
                     if self.rotations.solve_history.is_empty() {
                         ui.label("No solve history");
                     }
-                    self.rotations.solve_history.retain(|rotation| {
+                    self.rotations.solve_history.retain_mut(|rotation| {
                         let mut pinned = false;
                         let mut deleted = false;
+                        let mut recipe_default = false;
                         ui.add(RotationWidget::new(
                             self.locale,
                             &mut pinned,
                             &mut deleted,
+                            &mut recipe_default,
                             rotation,
                             self.actions,
+                            self.notes,
                         ));
-                        if pinned {
-                            self.rotations.pinned.push(rotation.clone());
+                        if pinned || recipe_default {
+                            // only pinned rotations can be recipe defaults
+                            let pinned_rotation = rotation.clone();
+                            if recipe_default {
+                                SavedRotationsData::set_recipe_default(
+                                    &mut self.rotations.recipe_defaults,
+                                    &pinned_rotation,
+                                );
+                            }
+                            self.rotations.pinned.push(pinned_rotation);
                         }
-                        !pinned && !deleted
+                        !pinned && !recipe_default && !deleted
                     });
                 });
             });
@@ -1,31 +1,165 @@
-use raphael_data::{Item, Locale};
-use raphael_sim::{Action, Settings, SimulationState};
+use egui::util::cache::{ComputerMut, FrameCache};
+use raphael_data::{CollectableRewards, Locale, action_name, get_collectable_rewards};
+use raphael_sim::{Action, ActionEffect, Condition, Effect, Effects, Settings, SimulationState};
+use raphael_solver::{FinishSuggestion, SolverSettings, best_byregot_placement, cheapest_finish};
 
 use crate::{
     app::SolverConfig,
     config::{CrafterConfig, QualityTarget},
 };
 
-use super::{HelpText, util};
+use super::{HelpText, ProcCheatSheet, RotationLint, util};
+
+/// Prefix of the rotation that is selected by clicking an action in the simulator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StepSelection {
+    /// Index of the last action of the prefix. `None` shows the state after the whole rotation.
+    pub step: Option<usize>,
+    /// Set when the rotation should be re-solved, keeping the selected prefix.
+    pub resolve_requested: bool,
+    /// Set when the rotation should be repaired after the parameters changed, see
+    /// [`raphael_solver::repairable_prefix`].
+    pub repair_requested: bool,
+    /// Number of actions at the start of the rotation that were kept by the last re-solve,
+    /// i.e. that are marked as already executed.
+    pub executed: usize,
+}
+
+#[derive(Default)]
+struct FinishFinder {}
+
+impl ComputerMut<(Settings, SimulationState), Option<FinishSuggestion>> for FinishFinder {
+    fn compute(
+        &mut self,
+        (settings, state): (Settings, SimulationState),
+    ) -> Option<FinishSuggestion> {
+        let solver_settings = SolverSettings {
+            simulator_settings: settings,
+            backload_progress: false,
+            allow_unsound_branch_pruning: false,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
+        };
+        cheapest_finish(solver_settings, state)
+    }
+}
+
+type FinishCache = FrameCache<Option<FinishSuggestion>, FinishFinder>;
+
+/// Actions of the palette of the rotation editor, grouped by what they are mostly used for.
+const ACTION_PALETTE: [(&str, &[Action]); 4] = [
+    (
+        "Progress",
+        &[
+            Action::BasicSynthesis,
+            Action::CarefulSynthesis,
+            Action::Groundwork,
+            Action::PrudentSynthesis,
+            Action::IntensiveSynthesis,
+            Action::MuscleMemory,
+            Action::RapidSynthesis,
+            Action::DelicateSynthesis,
+        ],
+    ),
+    (
+        "Quality",
+        &[
+            Action::BasicTouch,
+            Action::StandardTouch,
+            Action::AdvancedTouch,
+            Action::PrudentTouch,
+            Action::PreparatoryTouch,
+            Action::PreciseTouch,
+            Action::RefinedTouch,
+            Action::TrainedFinesse,
+            Action::Reflect,
+            Action::TrainedEye,
+            Action::HastyTouch,
+            Action::DaringTouch,
+            Action::ByregotsBlessing,
+        ],
+    ),
+    (
+        "Buffs",
+        &[
+            Action::Veneration,
+            Action::Innovation,
+            Action::GreatStrides,
+            Action::QuickInnovation,
+            Action::WasteNot,
+            Action::WasteNot2,
+        ],
+    ),
+    (
+        "Other",
+        &[
+            Action::MasterMend,
+            Action::Manipulation,
+            Action::ImmaculateMend,
+            Action::TrainedPerfection,
+            Action::Observe,
+            Action::TricksOfTheTrade,
+            Action::HeartAndSoul,
+        ],
+    ),
+];
+
+/// State of the rotation editor, kept in the temporary egui memory.
+#[derive(Debug, Clone, Copy, Default)]
+struct EditorState {
+    enabled: bool,
+    /// Index at which actions from the palette are inserted. `None` appends them.
+    insert_at: Option<usize>,
+}
+
+impl EditorState {
+    fn id() -> egui::Id {
+        egui::Id::new("SIMULATOR_EDITOR_STATE")
+    }
+
+    fn load(ctx: &egui::Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    fn store(self, ctx: &egui::Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+}
+
+/// Change of the rotation requested from the context menu of an action or by dragging it.
+#[derive(Debug, Clone, Copy)]
+enum ActionEdit {
+    Delete(usize),
+    /// Moves the action at index `from` so that it ends up at index `to`.
+    Move {
+        from: usize,
+        to: usize,
+    },
+    InsertAt(usize),
+}
 
 pub struct Simulator<'a> {
     settings: &'a Settings,
     initial_quality: u16,
     solver_config: SolverConfig,
     crafter_config: &'a CrafterConfig,
-    actions: &'a [Action],
-    item: &'a Item,
+    actions: &'a mut Vec<Action>,
+    selection: &'a mut StepSelection,
+    item_id: u32,
     locale: Locale,
+    editor: EditorState,
 }
 
 impl<'a> Simulator<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         settings: &'a Settings,
         initial_quality: u16,
         solver_config: SolverConfig,
         crafter_config: &'a CrafterConfig,
-        actions: &'a [Action],
-        item: &'a Item,
+        actions: &'a mut Vec<Action>,
+        selection: &'a mut StepSelection,
+        item_id: u32,
         locale: Locale,
     ) -> Self {
         Self {
@@ -34,13 +168,38 @@ impl<'a> Simulator<'a> {
             solver_config,
             crafter_config,
             actions,
-            item,
+            selection,
+            item_id,
             locale,
+            editor: EditorState::default(),
         }
     }
 }
 
 impl Simulator<'_> {
+    fn initial_state(&self) -> SimulationState {
+        self.solver_config
+            .initial_effects
+            .initial_state(self.settings)
+    }
+
+    /// Like [`SimulationState::from_macro_continue_on_error`], but starts from the state with
+    /// the initial effects of the solver config.
+    fn simulate(&self, actions: &[Action]) -> (SimulationState, Vec<Result<(), &'static str>>) {
+        let mut state = self.initial_state();
+        let mut errors = Vec::new();
+        for action in actions {
+            match state.use_action(*action, Condition::Normal, self.settings) {
+                Ok(new_state) => {
+                    errors.push(Ok(()));
+                    state = new_state;
+                }
+                Err(error) => errors.push(Err(error)),
+            }
+        }
+        (state, errors)
+    }
+
     fn config_changed(&self, ctx: &egui::Context) -> bool {
         ctx.data(|data| {
             match data.get_temp::<(Settings, u16, SolverConfig)>(egui::Id::new("LAST_SOLVE_PARAMS"))
@@ -55,15 +214,49 @@ impl Simulator<'_> {
         })
     }
 
-    fn draw_simulation(&self, ui: &mut egui::Ui, state: &SimulationState) {
+    fn draw_simulation(&mut self, ui: &mut egui::Ui, state: &SimulationState) {
         ui.group(|ui| {
             ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
             ui.vertical(|ui| {
                 ui.horizontal(|ui| {
                     ui.label(egui::RichText::new("Simulation").strong());
+                    if self.selection.executed != 0 {
+                        ui.label(format!("{} steps executed", self.selection.executed))
+                            .on_hover_text(
+                                "These actions were kept when the rest of the rotation was re-solved",
+                            );
+                    }
+                    if let Some(step) = self.selection.step {
+                        ui.label(format!("after step {}/{}", step + 1, self.actions.len()));
+                        if ui.button("Show all").clicked() {
+                            self.selection.step = None;
+                        }
+                        if !state.is_final(self.settings)
+                            && ui
+                                .button("Re-solve from here")
+                                .on_hover_text(
+                                    "Keep the actions up to this step and solve for the rest",
+                                )
+                                .clicked()
+                        {
+                            self.selection.resolve_requested = true;
+                        }
+                    }
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.toggle_value(&mut self.editor.enabled, "✏ Edit")
+                            .on_hover_text("Edit the rotation by hand");
+                        let config_changed =
+                            !self.actions.is_empty() && self.config_changed(ui.ctx());
+                        if config_changed
+                            && ui
+                                .small_button("Repair")
+                                .on_hover_text("Keep as many actions from the start of the rotation as still work with the current parameters and solve for the rest")
+                                .clicked()
+                        {
+                            self.selection.repair_requested = true;
+                        }
                         ui.add_visible(
-                            !self.actions.is_empty() && self.config_changed(ui.ctx()),
+                            config_changed,
                             egui::Label::new(
                                 egui::RichText::new(
                                     "⚠ Some parameters have changed since last solve.",
@@ -94,15 +287,10 @@ impl Simulator<'_> {
                     ui.allocate_ui_with_layout(text_size, text_layout, |ui| {
                         ui.label("Progress");
                     });
-                    ui.add(
-                        egui::ProgressBar::new(
-                            state.progress as f32 / self.settings.max_progress as f32,
-                        )
-                        .text(progress_bar_text(
-                            state.progress,
-                            self.settings.max_progress,
-                        ))
-                        .corner_radius(0),
+                    stat_bar(
+                        ui,
+                        state.progress as f32 / self.settings.max_progress as f32,
+                        progress_bar_text(state.progress, self.settings.max_progress),
                     );
                 });
 
@@ -111,10 +299,10 @@ impl Simulator<'_> {
                         ui.label("Quality");
                     });
                     let quality = self.initial_quality + state.quality;
-                    ui.add(
-                        egui::ProgressBar::new(quality as f32 / self.settings.max_quality as f32)
-                            .text(progress_bar_text(quality, self.settings.max_quality))
-                            .corner_radius(0),
+                    stat_bar(
+                        ui,
+                        quality as f32 / self.settings.max_quality as f32,
+                        progress_bar_text(quality, self.settings.max_quality),
                     );
                 });
 
@@ -122,15 +310,10 @@ impl Simulator<'_> {
                     ui.allocate_ui_with_layout(text_size, text_layout, |ui| {
                         ui.label("Durability");
                     });
-                    ui.add(
-                        egui::ProgressBar::new(
-                            state.durability as f32 / self.settings.max_durability as f32,
-                        )
-                        .text(progress_bar_text(
-                            state.durability,
-                            self.settings.max_durability,
-                        ))
-                        .corner_radius(0),
+                    stat_bar(
+                        ui,
+                        state.durability as f32 / self.settings.max_durability as f32,
+                        progress_bar_text(state.durability, self.settings.max_durability),
                     );
                 });
 
@@ -138,13 +321,19 @@ impl Simulator<'_> {
                     ui.allocate_ui_with_layout(text_size, text_layout, |ui| {
                         ui.label("CP");
                     });
-                    ui.add(
-                        egui::ProgressBar::new(state.cp as f32 / self.settings.max_cp as f32)
-                            .text(progress_bar_text(state.cp, self.settings.max_cp))
-                            .corner_radius(0),
+                    stat_bar(
+                        ui,
+                        state.cp as f32 / self.settings.max_cp as f32,
+                        progress_bar_text(state.cp, self.settings.max_cp),
                     );
                 });
 
+                self.draw_effects(ui, state.effects);
+
+                if !state.is_final(self.settings) {
+                    self.draw_cheapest_finish(ui, state);
+                }
+
                 ui.horizontal(|ui| {
                     ui.with_layout(text_layout, |ui| {
                         ui.set_height(ui.style().spacing.interact_size.y);
@@ -156,7 +345,20 @@ impl Simulator<'_> {
                             // do nothing
                         } else if state.progress < self.settings.max_progress {
                             ui.label("Synthesis failed");
-                        } else if self.item.always_collectable {
+                        } else if let Some(rewards) = get_collectable_rewards(self.item_id) {
+                            let quality = self.initial_quality + state.quality;
+                            let text = rewards.reward(quality).map_or_else(
+                                || "Collectability too low".to_string(),
+                                |(tier, reward)| {
+                                    format!(
+                                        "Tier {} collectable: {} scrips, {}% EXP",
+                                        tier, reward.scrips, reward.exp_ratio
+                                    )
+                                },
+                            );
+                            ui.label(text)
+                                .on_hover_text(collectable_rewards_text(rewards, quality));
+                        } else if is_always_collectable(self.item_id) {
                             let (t1, t2, t3) = (
                                 QualityTarget::CollectableT1.get_target(self.settings.max_quality),
                                 QualityTarget::CollectableT2.get_target(self.settings.max_quality),
@@ -170,12 +372,23 @@ impl Simulator<'_> {
                             };
                             ui.label(format!("Tier {} collectable", tier));
                         } else {
-                            let hq = raphael_data::hq_percentage(
-                                self.initial_quality + state.quality,
-                                self.settings.max_quality,
-                            )
-                            .unwrap_or(0);
-                            ui.label(format!("{}% HQ", hq));
+                            let quality = self.initial_quality + state.quality;
+                            let hq =
+                                raphael_data::hq_percentage(quality, self.settings.max_quality)
+                                    .unwrap_or(0);
+                            let target_quality = self
+                                .solver_config
+                                .quality_target
+                                .get_target(self.settings.max_quality);
+                            ui.label(egui::RichText::new(format!("{}% HQ", hq)).strong());
+                            ui.label(
+                                egui::RichText::new(util::quality_delta_text(
+                                    quality,
+                                    target_quality,
+                                    self.settings.max_quality,
+                                ))
+                                .weak(),
+                            );
                         }
                     });
                 });
@@ -183,25 +396,203 @@ impl Simulator<'_> {
         });
     }
 
-    fn draw_actions(&self, ui: &mut egui::Ui, errors: &[Result<(), &str>]) {
+    /// Shows the active effects with their icon and the number of steps they still last.
+    fn draw_effects(&self, ui: &mut egui::Ui, effects: Effects) {
+        if effects.active().next().is_none() {
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(3.0, 3.0);
+            for (effect, value) in effects.active() {
+                let response = match effect_icon(effect) {
+                    Some(action) => {
+                        let response = ui.add(
+                            util::get_action_icon(action, self.crafter_config.selected_job)
+                                .fit_to_exact_size(egui::Vec2::new(20.0, 20.0))
+                                .corner_radius(3.0),
+                        );
+                        match value {
+                            0 => response,
+                            _ => {
+                                response | ui.label(egui::RichText::new(value.to_string()).small())
+                            }
+                        }
+                    }
+                    None => ui.label(egui::RichText::new(format!("IQ {value}")).small().strong()),
+                };
+                response.on_hover_text(effect_text(effect, value));
+                ui.add_space(5.0);
+            }
+        });
+    }
+
+    /// Shows the cheapest way to max out Progress from `state`, so that the CP that can still be
+    /// spent on Quality is known while editing the rotation by hand.
+    fn draw_cheapest_finish(&self, ui: &mut egui::Ui, state: &SimulationState) {
+        let suggestion = ui.ctx().memory_mut(|mem| {
+            mem.caches
+                .cache::<FinishCache>()
+                .get((*self.settings, *state))
+        });
+        let text = match suggestion {
+            Some(suggestion) => format!(
+                "Cheapest finish: {} ({} CP, {} CP left for Quality)",
+                finish_actions_text(&suggestion.actions, self.locale),
+                suggestion.cp_cost,
+                state.cp - suggestion.cp_cost
+            ),
+            None => "Progress can't be maxed out anymore".to_owned(),
+        };
+        ui.label(egui::RichText::new(text).small());
+    }
+
+    /// Shows where to use Byregot's Blessing by hand when the rotation was solved without it,
+    /// see [`SolverConfig::exclude_byregot`].
+    fn draw_byregot_placement(&self, ui: &mut egui::Ui) {
+        if !self.solver_config.exclude_byregot
+            || self.actions.is_empty()
+            || self.actions.contains(&Action::ByregotsBlessing)
+        {
+            return;
+        }
+        let placement = best_byregot_placement(self.settings, self.initial_state(), self.actions);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.horizontal(|ui| {
+                ui.add(
+                    util::get_action_icon(Action::ByregotsBlessing, self.crafter_config.selected_job)
+                        .fit_to_exact_size(egui::Vec2::new(20.0, 20.0))
+                        .corner_radius(3.0),
+                );
+                match placement {
+                    Some(placement) => {
+                        ui.label(format!(
+                            "Use Byregot's Blessing before step {} ({}) for {} Quality",
+                            placement.index + 1,
+                            action_name(self.actions[placement.index], self.locale),
+                            self.initial_quality + placement.quality,
+                        ));
+                        ui.add(HelpText::new("The rotation was solved without Byregot's Blessing, and its CP and durability were kept unspent. Using it at this step gives the most Quality in Normal condition. Use it earlier if the condition turns Good or Excellent while Inner Quiet is high."));
+                    }
+                    None => {
+                        ui.label(
+                            egui::RichText::new(
+                                "⚠ No step leaves enough CP and durability for Byregot's Blessing",
+                            )
+                            .color(ui.visuals().warn_fg_color),
+                        );
+                    }
+                }
+            });
+        });
+    }
+
+    fn draw_lint(&self, ui: &mut egui::Ui) {
+        if self.actions.is_empty() {
+            return;
+        }
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.add(RotationLint::new(self.settings, self.actions, self.locale));
+        });
+    }
+
+    fn draw_cheat_sheet(&self, ui: &mut egui::Ui) {
+        if self.actions.is_empty() {
+            return;
+        }
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.add(ProcCheatSheet::new(
+                self.settings,
+                self.initial_state(),
+                self.initial_quality,
+                self.actions,
+                self.crafter_config.selected_job,
+                self.locale,
+            ));
+        });
+    }
+
+    fn draw_actions(&mut self, ui: &mut egui::Ui, errors: &[Result<(), &str>]) {
+        let mut edit = None;
         ui.group(|ui| {
             ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
             egui::ScrollArea::horizontal().show(ui, |ui| {
                 ui.set_height(30.0);
                 ui.set_width(ui.available_width());
                 ui.horizontal(|ui| {
-                    for (action, error) in self.actions.iter().zip(errors.iter()) {
+                    let mut state = self.initial_state();
+                    for (step, (action, error)) in
+                        self.actions.iter().zip(errors.iter()).enumerate()
+                    {
+                        if self.editor.enabled && self.editor.insert_at == Some(step) {
+                            insertion_marker(ui);
+                        }
+                        let effect = state.action_effect(*action, Condition::Normal, self.settings);
+                        if let Ok(new_state) =
+                            state.use_action(*action, Condition::Normal, self.settings)
+                        {
+                            state = new_state;
+                        }
                         let image =
                             util::get_action_icon(*action, self.crafter_config.selected_job)
                                 .fit_to_exact_size(egui::Vec2::new(30.0, 30.0))
                                 .corner_radius(4.0)
                                 .tint(match error {
+                                    Ok(_)
+                                        if self
+                                            .selection
+                                            .step
+                                            .is_some_and(|selected| step > selected) =>
+                                    {
+                                        egui::Color32::GRAY
+                                    }
                                     Ok(_) => egui::Color32::WHITE,
                                     Err(_) => egui::Color32::DARK_GRAY,
-                                });
-                        let response = ui
-                            .add(image)
-                            .on_hover_text(raphael_data::action_name(*action, self.locale));
+                                })
+                                .sense(egui::Sense::click());
+                        let response = match self.editor.enabled {
+                            true => {
+                                let (response, drop) = draggable_action(ui, step, image);
+                                if drop.is_some() {
+                                    edit = drop;
+                                }
+                                response
+                            }
+                            false => ui.add(image),
+                        };
+                        let response = response.on_hover_ui(|ui| {
+                            action_tooltip(ui, *action, &effect, *error, self.locale);
+                            if error.is_ok() {
+                                effects_tooltip(ui, state.effects);
+                            }
+                        });
+                        if response.clicked() {
+                            self.selection.step = match self.selection.step {
+                                Some(selected) if selected == step => None,
+                                _ => Some(step),
+                            };
+                        }
+                        if self.editor.enabled {
+                            response.context_menu(|ui| {
+                                edit = action_context_menu(ui, step, self.actions.len());
+                                if edit.is_some() {
+                                    ui.close();
+                                }
+                            });
+                        }
+                        if self.selection.step == Some(step) {
+                            ui.painter().rect_stroke(
+                                response.rect.expand(1.0),
+                                4.0,
+                                ui.visuals().selection.stroke,
+                                egui::StrokeKind::Outside,
+                            );
+                        }
                         if error.is_err() {
                             egui::Image::new(egui::include_image!(
                                 "../../assets/action-icons/disabled.webp"
@@ -209,23 +600,368 @@ impl Simulator<'_> {
                             .tint(egui::Color32::GRAY)
                             .paint_at(ui, response.rect);
                         }
+                        if step + 1 == self.selection.executed && step + 1 != self.actions.len() {
+                            // the re-solved part of the rotation starts here
+                            ui.separator();
+                        }
+                    }
+                    if self.editor.enabled && self.editor.insert_at.is_none() {
+                        insertion_marker(ui);
                     }
                 });
             });
         });
+        if let Some(edit) = edit {
+            self.apply_edit(edit);
+        }
+    }
+
+    /// Applies a change made with the context menu of an action or by dragging it. Actions that were marked as
+    /// executed stay executed only up to the first changed step.
+    fn apply_edit(&mut self, edit: ActionEdit) {
+        let changed_step = match edit {
+            ActionEdit::Delete(step) => {
+                self.actions.remove(step);
+                self.editor.insert_at = self
+                    .editor
+                    .insert_at
+                    .map(|index| if index > step { index - 1 } else { index });
+                step
+            }
+            ActionEdit::Move { from, to } => {
+                let action = self.actions.remove(from);
+                self.actions.insert(to, action);
+                std::cmp::min(from, to)
+            }
+            ActionEdit::InsertAt(step) => {
+                self.editor.insert_at = match step < self.actions.len() {
+                    true => Some(step),
+                    false => None,
+                };
+                return;
+            }
+        };
+        self.selection.executed = std::cmp::min(self.selection.executed, changed_step);
+    }
+
+    /// Inserts `action` at the insertion point of the editor.
+    fn insert_action(&mut self, action: Action) {
+        match self.editor.insert_at {
+            Some(index) => {
+                self.actions.insert(index, action);
+                self.editor.insert_at = Some(index + 1);
+                self.selection.executed = std::cmp::min(self.selection.executed, index);
+            }
+            None => self.actions.push(action),
+        }
+    }
+
+    /// Palette of the actions that are available at the current job level, grouped by category.
+    /// Clicking an action inserts it into the rotation.
+    fn draw_palette(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 3.0);
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Actions").strong());
+                match self.editor.insert_at {
+                    Some(index) => {
+                        ui.label(format!("Inserting before step {}", index + 1));
+                        if ui.small_button("Append instead").clicked() {
+                            self.editor.insert_at = None;
+                        }
+                    }
+                    None => {
+                        ui.label("Appending to the rotation");
+                    }
+                }
+                ui.add(HelpText::new(
+                    "Click an action to add it to the rotation. Drag the actions of the rotation to reorder them, or right-click them to delete them or to insert actions next to them.",
+                ));
+            });
+            ui.separator();
+            egui::Grid::new("action_palette").show(ui, |ui| {
+                for (category, actions) in ACTION_PALETTE {
+                    ui.label(category);
+                    ui.horizontal_wrapped(|ui| {
+                        ui.style_mut().spacing.item_spacing = egui::vec2(3.0, 3.0);
+                        let available_actions = actions.iter().copied().filter(|action| {
+                            self.settings.job_level >= action.level_requirement()
+                                && self.settings.allowed_actions.has(*action)
+                        });
+                        for action in available_actions {
+                            let image =
+                                util::get_action_icon(action, self.crafter_config.selected_job)
+                                    .fit_to_exact_size(egui::Vec2::new(24.0, 24.0))
+                                    .corner_radius(3.0)
+                                    .sense(egui::Sense::click());
+                            let response = ui.add(image).on_hover_ui(|ui| {
+                                ui.set_max_width(300.0);
+                                ui.label(
+                                    egui::RichText::new(action_name(action, self.locale)).strong(),
+                                );
+                                ui.label(raphael_data::action_description(action, self.locale));
+                            });
+                            if response.clicked() {
+                                self.insert_action(action);
+                            }
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        });
     }
 }
 
 impl egui::Widget for Simulator<'_> {
-    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
-        let (state, errors) =
-            SimulationState::from_macro_continue_on_error(self.settings, self.actions);
-        ui.vertical(|ui| {
-            self.draw_simulation(ui, &state);
-            self.draw_actions(ui, &errors);
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        if self
+            .selection
+            .step
+            .is_some_and(|step| step >= self.actions.len())
+        {
+            self.selection.step = None;
+        }
+        self.selection.executed = std::cmp::min(self.selection.executed, self.actions.len());
+        let (mut state, errors) = self.simulate(self.actions);
+        if let Some(step) = self.selection.step {
+            (state, _) = self.simulate(&self.actions[..=step]);
+        }
+        self.editor = EditorState::load(ui.ctx());
+        if self
+            .editor
+            .insert_at
+            .is_some_and(|index| index >= self.actions.len())
+        {
+            self.editor.insert_at = None;
+        }
+        let response = ui
+            .vertical(|ui| {
+                self.draw_simulation(ui, &state);
+                self.draw_actions(ui, &errors);
+                self.draw_byregot_placement(ui);
+                if self.editor.enabled {
+                    self.draw_palette(ui);
+                }
+                self.draw_lint(ui);
+                self.draw_cheat_sheet(ui);
+            })
+            .response;
+        self.editor.store(ui.ctx());
+        response
+    }
+}
+
+/// Marks where actions from the palette are inserted.
+fn insertion_marker(ui: &mut egui::Ui) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(2.0, 30.0), egui::Sense::hover());
+    ui.painter()
+        .rect_filled(rect, 1.0, ui.visuals().selection.stroke.color);
+}
+
+/// Adds the icon of the action at `step` so that it can be dragged onto the other actions of the
+/// rotation. Returns the response of the icon and the move if another action was dropped onto it.
+fn draggable_action(
+    ui: &mut egui::Ui,
+    step: usize,
+    image: egui::Image,
+) -> (egui::Response, Option<ActionEdit>) {
+    let drag = ui.dnd_drag_source(egui::Id::new(("simulator_action", step)), step, |ui| {
+        ui.add(image)
+    });
+    let rect = drag.response.rect;
+    // dropping onto the left half of an action moves the dragged action before it
+    let drop_index = |pointer: egui::Pos2| match pointer.x < rect.center().x {
+        true => step,
+        false => step + 1,
+    };
+    let target = |from: usize, pointer: egui::Pos2| {
+        let index = drop_index(pointer);
+        let to = if index > from { index - 1 } else { index };
+        (to != from).then_some(ActionEdit::Move { from, to })
+    };
+    let pointer = ui.ctx().pointer_interact_pos();
+    match (drag.response.dnd_hover_payload::<usize>(), pointer) {
+        (Some(from), Some(pointer)) if target(*from, pointer).is_some() => {
+            let x = match drop_index(pointer) == step {
+                true => rect.left() - 4.0,
+                false => rect.right() + 4.0,
+            };
+            ui.painter().vline(
+                x,
+                rect.y_range(),
+                egui::Stroke::new(2.0, ui.visuals().selection.stroke.color),
+            );
+        }
+        _ => (),
+    }
+    let edit = match (drag.response.dnd_release_payload::<usize>(), pointer) {
+        (Some(from), Some(pointer)) => target(*from, pointer),
+        _ => None,
+    };
+    (drag.inner, edit)
+}
+
+fn action_context_menu(ui: &mut egui::Ui, step: usize, len: usize) -> Option<ActionEdit> {
+    let mut edit = None;
+    if ui.button("Insert before").clicked() {
+        edit = Some(ActionEdit::InsertAt(step));
+    }
+    if ui.button("Insert after").clicked() {
+        edit = Some(ActionEdit::InsertAt(step + 1));
+    }
+    ui.separator();
+    if ui
+        .add_enabled(step != 0, egui::Button::new("Move left"))
+        .clicked()
+    {
+        edit = Some(ActionEdit::Move {
+            from: step,
+            to: step - 1,
+        });
+    }
+    if ui
+        .add_enabled(step + 1 != len, egui::Button::new("Move right"))
+        .clicked()
+    {
+        edit = Some(ActionEdit::Move {
+            from: step,
+            to: step + 1,
+        });
+    }
+    ui.separator();
+    if ui.button("Delete").clicked() {
+        edit = Some(ActionEdit::Delete(step));
+    }
+    edit
+}
+
+/// Joins the actions with " + ", merging consecutive uses of the same action, e.g.
+/// "Veneration + Groundwork ×2".
+fn finish_actions_text(actions: &[Action], locale: Locale) -> String {
+    actions
+        .chunk_by(|lhs, rhs| lhs == rhs)
+        .map(|chunk| match chunk.len() {
+            1 => action_name(chunk[0], locale).to_string(),
+            count => format!("{} ×{}", action_name(chunk[0], locale), count),
         })
-        .response
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Shows the action's description together with the numbers it has in the state it was used in.
+fn action_tooltip(
+    ui: &mut egui::Ui,
+    action: Action,
+    effect: &ActionEffect,
+    error: Result<(), &str>,
+    locale: Locale,
+) {
+    ui.set_max_width(300.0);
+    ui.label(egui::RichText::new(raphael_data::action_name(action, locale)).strong());
+    ui.label(raphael_data::action_description(action, locale));
+    let efficiency_text = |efficiency: u16, base_efficiency: u16| {
+        if efficiency == base_efficiency {
+            format!("{efficiency}% efficiency")
+        } else {
+            format!("{base_efficiency}% → {efficiency}% efficiency")
+        }
+    };
+    if effect.progress_efficiency != 0 || effect.base_progress_efficiency != 0 {
+        ui.label(format!(
+            "Progress: +{} ({})",
+            effect.progress_increase,
+            efficiency_text(effect.progress_efficiency, effect.base_progress_efficiency)
+        ));
+    }
+    if effect.quality_efficiency != 0 || effect.base_quality_efficiency != 0 {
+        ui.label(format!(
+            "Quality: +{} ({})",
+            effect.quality_increase,
+            efficiency_text(effect.quality_efficiency, effect.base_quality_efficiency)
+        ));
     }
+    if effect.durability_cost != 0 {
+        ui.label(format!("Durability: -{}", effect.durability_cost));
+    }
+    if effect.cp_cost != 0 {
+        ui.label(format!("CP: -{}", effect.cp_cost));
+    }
+    if let Err(error) = error {
+        ui.label(egui::RichText::new(format!("⚠ {error}")).color(ui.visuals().warn_fg_color));
+    }
+}
+
+/// Lists the effects that are active after an action.
+fn effects_tooltip(ui: &mut egui::Ui, effects: Effects) {
+    let effects = effects
+        .active()
+        .map(|(effect, value)| effect_text(effect, value))
+        .collect::<Vec<_>>();
+    let text = match effects.is_empty() {
+        true => "No active effects".to_owned(),
+        false => effects.join("\n"),
+    };
+    ui.separator();
+    ui.label(egui::RichText::new("Effects after this step").strong());
+    ui.label(text);
+}
+
+/// Action whose icon is shown for the effect. Inner Quiet has no such action.
+fn effect_icon(effect: Effect) -> Option<Action> {
+    match effect {
+        Effect::InnerQuiet => None,
+        Effect::WasteNot => Some(Action::WasteNot),
+        Effect::Innovation => Some(Action::Innovation),
+        Effect::Veneration => Some(Action::Veneration),
+        Effect::GreatStrides => Some(Action::GreatStrides),
+        Effect::MuscleMemory => Some(Action::MuscleMemory),
+        Effect::Manipulation => Some(Action::Manipulation),
+        Effect::TrainedPerfection => Some(Action::TrainedPerfection),
+        Effect::HeartAndSoul => Some(Action::HeartAndSoul),
+    }
+}
+
+fn effect_text(effect: Effect, value: u8) -> String {
+    let name = match effect {
+        Effect::InnerQuiet => "Inner Quiet",
+        Effect::WasteNot => "Waste Not",
+        Effect::Innovation => "Innovation",
+        Effect::Veneration => "Veneration",
+        Effect::GreatStrides => "Great Strides",
+        Effect::MuscleMemory => "Muscle Memory",
+        Effect::Manipulation => "Manipulation",
+        Effect::TrainedPerfection => "Trained Perfection",
+        Effect::HeartAndSoul => "Heart and Soul",
+    };
+    match (effect, value) {
+        (Effect::InnerQuiet, stacks) => format!("{name}: {stacks} stacks"),
+        (_, 0) => format!("{name}: until used"),
+        (_, 1) => format!("{name}: 1 step left"),
+        (_, steps) => format!("{name}: {steps} steps left"),
+    }
+}
+
+fn is_always_collectable(item_id: u32) -> bool {
+    raphael_data::game_data()
+        .item(item_id)
+        .is_some_and(|item| item.always_collectable)
+}
+
+/// Collectability and rewards of each tier.
+fn collectable_rewards_text(rewards: &CollectableRewards, quality: u16) -> String {
+    let mut lines = vec![format!("Collectability: {}", quality / 10)];
+    lines.extend(rewards.tiers.iter().enumerate().map(|(index, tier)| {
+        format!(
+            "Tier {} (≥ {}): {} scrips, {}% EXP",
+            index + 1,
+            tier.min_collectability,
+            tier.scrips,
+            tier.exp_ratio
+        )
+    }));
+    lines.join("\n")
 }
 
 fn text_width(ui: &mut egui::Ui, text: impl Into<String>) -> f32 {
@@ -239,6 +975,15 @@ fn text_width(ui: &mut egui::Ui, text: impl Into<String>) -> f32 {
     })
 }
 
+/// Progress bar for one of the simulation values. Only the text is shown in low-spec mode.
+fn stat_bar(ui: &mut egui::Ui, fraction: f32, text: String) {
+    if util::low_spec_mode(ui.ctx()) {
+        ui.monospace(text);
+    } else {
+        ui.add(egui::ProgressBar::new(fraction).text(text).corner_radius(0));
+    }
+}
+
 fn progress_bar_text<T: Copy + std::cmp::Ord + std::ops::Sub<Output = T> + std::fmt::Display>(
     value: T,
     maximum: T,
@@ -0,0 +1,296 @@
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use raphael_sim::{Action, SimulationState};
+use raphael_solver::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+use crate::app::CraftParams;
+use crate::previous_solver::{self, PreviousSolver};
+
+use super::util;
+
+struct ComparisonEntry {
+    actions: Vec<Action>,
+    quality: u16,
+    steps: usize,
+    duration: u16,
+    solve_time: Duration,
+}
+
+impl ComparisonEntry {
+    fn new(
+        params: &CraftParams,
+        settings: &SolverSettings,
+        actions: Vec<Action>,
+        solve_time: Duration,
+    ) -> Result<Self, String> {
+        if actions.is_empty() {
+            return Err("No solution".to_string());
+        }
+        let state = SimulationState::from_macro(&settings.simulator_settings, &actions)
+            .map_err(|error| format!("Invalid macro: {error}"))?;
+        Ok(Self {
+            quality: params.initial_quality.saturating_add(state.quality),
+            steps: actions.len(),
+            duration: actions
+                .iter()
+                .map(|action| u16::from(action.time_cost()))
+                .sum(),
+            solve_time,
+            actions,
+        })
+    }
+}
+
+/// Results of the current and the previous solver, in that order.
+type Comparison = [Result<ComparisonEntry, String>; 2];
+
+struct ComparisonRun {
+    result: Receiver<Comparison>,
+    interrupt_signal: AtomicFlag,
+}
+
+/// Runs the current solver and the solver of a previous release on the same input, to check
+/// that solver changes don't make the macros worse.
+#[derive(Default)]
+pub struct SolverComparisonData {
+    /// Path to the `raphael_bindings` dynamic library of the previous release.
+    pub library_path: String,
+    run: Option<ComparisonRun>,
+    comparison: Option<Comparison>,
+    load_error: Option<String>,
+}
+
+impl SolverComparisonData {
+    pub fn new(library_path: String) -> Self {
+        Self {
+            library_path,
+            ..Self::default()
+        }
+    }
+
+    fn start(&mut self, ctx: egui::Context, params: CraftParams) {
+        self.comparison = None;
+        self.load_error = None;
+        let previous_solver = match PreviousSolver::load(Path::new(&self.library_path)) {
+            Ok(previous_solver) => previous_solver,
+            Err(error) => {
+                self.load_error = Some(error);
+                return;
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let interrupt_signal = AtomicFlag::new();
+        self.run = Some(ComparisonRun {
+            result: rx,
+            interrupt_signal: interrupt_signal.clone(),
+        });
+
+        let settings = params
+            .solver_config
+            .solve_request(
+                params.recipe,
+                params.crafter_stats,
+                params.food,
+                params.potion,
+                params.initial_quality,
+            )
+            .solver_settings();
+        std::thread::spawn(move || {
+            let thread_pool = match params.thread_pool_settings.build() {
+                Ok(thread_pool) => thread_pool,
+                Err(error) => {
+                    log::error!("Failed to build thread pool: {error}");
+                    return;
+                }
+            };
+            // Both solvers run one after the other so that their solve times are comparable.
+            let start_time = Instant::now();
+            let current = thread_pool
+                .install(|| {
+                    MacroSolver::new(
+                        settings,
+                        Box::new(|_| {}),
+                        Box::new(|_| {}),
+                        interrupt_signal.clone(),
+                    )
+                    .solve()
+                })
+                .map_err(|error| match error {
+                    SolverException::NoSolution => "No solution".to_string(),
+                    SolverException::Interrupted => "Cancelled".to_string(),
+                    SolverException::MemoryLimitReached => "Out of memory".to_string(),
+                    SolverException::InternalError(message) => message,
+                })
+                .and_then(|actions| {
+                    ComparisonEntry::new(&params, &settings, actions, start_time.elapsed())
+                });
+            let previous = match interrupt_signal.is_set() {
+                true => Err("Cancelled".to_string()),
+                false => {
+                    let start_time = Instant::now();
+                    previous_solver.solve(&settings).and_then(|actions| {
+                        ComparisonEntry::new(&params, &settings, actions, start_time.elapsed())
+                    })
+                }
+            };
+            _ = tx.send([current, previous]);
+            ctx.request_repaint();
+        });
+    }
+
+    fn cancel(&self) {
+        if let Some(run) = self.run.as_ref() {
+            run.interrupt_signal.set();
+            previous_solver::interrupt();
+        }
+    }
+
+    fn update(&mut self) {
+        if let Some(run) = self.run.as_ref() {
+            match run.result.try_recv() {
+                Ok(comparison) => {
+                    self.comparison = Some(comparison);
+                    self.run = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.run = None,
+                Err(mpsc::TryRecvError::Empty) => (),
+            }
+        }
+    }
+}
+
+pub struct SolverComparisonWidget<'a> {
+    data: &'a mut SolverComparisonData,
+    params: CraftParams,
+    actions: &'a mut Vec<Action>,
+}
+
+impl<'a> SolverComparisonWidget<'a> {
+    pub fn new(
+        data: &'a mut SolverComparisonData,
+        params: CraftParams,
+        actions: &'a mut Vec<Action>,
+    ) -> Self {
+        Self {
+            data,
+            params,
+            actions,
+        }
+    }
+
+    fn show_comparison(&mut self, ui: &mut egui::Ui) {
+        if let Some(error) = self.data.load_error.as_ref() {
+            ui.label(format!("Unable to load the previous solver: {error}"));
+            return;
+        }
+        let Some(comparison) = self.data.comparison.as_ref() else {
+            ui.label(
+                "Select the raphael_bindings library of a previous release to compare both solvers",
+            );
+            return;
+        };
+        egui::Grid::new("solver_comparison")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("");
+                ui.label(egui::RichText::new("Current").strong());
+                ui.label(egui::RichText::new("Previous").strong());
+                ui.end_row();
+
+                let row =
+                    |ui: &mut egui::Ui, label: &str, value: &dyn Fn(&ComparisonEntry) -> String| {
+                        ui.label(label);
+                        for entry in comparison {
+                            match entry {
+                                Ok(entry) => ui.label(value(entry)),
+                                Err(error) => ui.label(error),
+                            };
+                        }
+                        ui.end_row();
+                    };
+                row(ui, "Quality", &|entry| entry.quality.to_string());
+                row(ui, "Steps", &|entry| entry.steps.to_string());
+                row(ui, "Duration", &|entry| format!("{}s", entry.duration));
+                row(ui, "Solve time", &|entry| {
+                    format!("{:.2}s", entry.solve_time.as_secs_f32())
+                });
+
+                ui.label("");
+                for entry in comparison {
+                    match entry {
+                        Ok(entry) => {
+                            if ui.button("Load macro").clicked() {
+                                self.actions.clone_from(&entry.actions);
+                            }
+                        }
+                        Err(_) => {
+                            ui.label("");
+                        }
+                    }
+                }
+                ui.end_row();
+            });
+
+        if let [Ok(current), Ok(previous)] = comparison {
+            let verdict = (current.quality, previous.steps, previous.duration).cmp(&(
+                previous.quality,
+                current.steps,
+                current.duration,
+            ));
+            let text = match verdict {
+                std::cmp::Ordering::Greater => "The current solver found a better macro.",
+                std::cmp::Ordering::Equal => "Both solvers found equally good macros.",
+                std::cmp::Ordering::Less => "Regression: the previous solver found a better macro.",
+            };
+            ui.label(text);
+        }
+    }
+}
+
+impl egui::Widget for SolverComparisonWidget<'_> {
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        self.data.update();
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Previous solver:");
+                ui.add_enabled(
+                    self.data.run.is_none(),
+                    egui::TextEdit::singleline(&mut self.data.library_path)
+                        .hint_text("raphael_bindings library"),
+                );
+                let browse = ui
+                    .add_enabled(self.data.run.is_none(), egui::Button::new("📂"))
+                    .clicked();
+                if let Some(path) = browse.then(|| rfd::FileDialog::new().pick_file()).flatten() {
+                    self.data.library_path = path.display().to_string();
+                }
+            });
+            ui.horizontal(|ui| match self.data.run.as_ref() {
+                Some(_) => {
+                    util::busy_indicator(ui);
+                    ui.label("Solving with both solvers ...");
+                    if ui.button("Cancel").clicked() {
+                        self.data.cancel();
+                    }
+                    ui.ctx()
+                        .request_repaint_after(util::background_repaint_interval(ui.ctx()));
+                }
+                None => {
+                    let enabled = !self.data.library_path.is_empty();
+                    if ui
+                        .add_enabled(enabled, egui::Button::new("Compare solvers"))
+                        .clicked()
+                    {
+                        self.data.start(ui.ctx().clone(), self.params);
+                    }
+                }
+            });
+            ui.separator();
+            self.show_comparison(ui);
+        })
+        .response
+    }
+}
@@ -0,0 +1,368 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+
+use raphael_data::CrafterStats;
+use raphael_solver::{AtomicFlag, CraftBounds, SolverSettings, craft_bounds};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::app::CraftParams;
+
+use super::{HelpText, util};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SweepMetric {
+    Quality,
+    Steps,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatSweepConfig {
+    pub craftsmanship_range: (u16, u16),
+    pub control_range: (u16, u16),
+    /// Number of cells along each axis of the grid.
+    pub resolution: usize,
+    pub metric: SweepMetric,
+}
+
+impl Default for StatSweepConfig {
+    fn default() -> Self {
+        Self {
+            craftsmanship_range: (4500, 5500),
+            control_range: (4500, 5500),
+            resolution: 8,
+            metric: SweepMetric::Quality,
+        }
+    }
+}
+
+struct SweepResult {
+    craftsmanship: Vec<u16>,
+    control: Vec<u16>,
+    /// Bounds for each cell, one row per control value. `None` if Progress can't be maxed out.
+    cells: Vec<Option<CraftBounds>>,
+    initial_quality: u16,
+    target_quality: u16,
+}
+
+struct SweepRun {
+    result: Receiver<SweepResult>,
+    completed_cells: Arc<AtomicUsize>,
+    total_cells: usize,
+    interrupt_signal: AtomicFlag,
+}
+
+#[derive(Default)]
+pub struct StatSweepData {
+    pub config: StatSweepConfig,
+    run: Option<SweepRun>,
+    result: Option<SweepResult>,
+}
+
+impl StatSweepData {
+    pub fn new(config: StatSweepConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    fn start(&mut self, ctx: egui::Context, params: CraftParams) {
+        let craftsmanship = sweep_values(self.config.craftsmanship_range, self.config.resolution);
+        let control = sweep_values(self.config.control_range, self.config.resolution);
+        let grid: Vec<(u16, u16)> = control
+            .iter()
+            .flat_map(|&control| {
+                craftsmanship
+                    .iter()
+                    .map(move |&craftsmanship| (craftsmanship, control))
+            })
+            .collect();
+
+        let (tx, rx) = mpsc::channel();
+        let completed_cells = Arc::new(AtomicUsize::new(0));
+        let interrupt_signal = AtomicFlag::new();
+        self.run = Some(SweepRun {
+            result: rx,
+            completed_cells: completed_cells.clone(),
+            total_cells: grid.len(),
+            interrupt_signal: interrupt_signal.clone(),
+        });
+
+        let target_quality = params.target_quality();
+        let evaluate_cell = move |&(craftsmanship, control): &(u16, u16)| {
+            let crafter_stats = CrafterStats {
+                craftsmanship,
+                control,
+                ..params.crafter_stats
+            };
+            let bounds = craft_bounds(
+                SolverSettings {
+                    simulator_settings: params
+                        .solver_settings(crafter_stats, params.solver_config.adversarial),
+                    backload_progress: params.solver_config.backload_progress,
+                    allow_unsound_branch_pruning: false,
+                    forbid_quality_during_muscle_memory: false,
+                    first_quality_step: 0,
+                },
+                interrupt_signal.clone(),
+            )
+            .ok();
+            completed_cells.fetch_add(1, Ordering::Relaxed);
+            bounds
+        };
+
+        std::thread::spawn(move || {
+            let thread_pool = match params.thread_pool_settings.build() {
+                Ok(thread_pool) => thread_pool,
+                Err(error) => {
+                    log::error!("Failed to build thread pool: {error}");
+                    return;
+                }
+            };
+            let cells: Vec<Option<CraftBounds>> =
+                thread_pool.install(|| grid.par_iter().map(evaluate_cell).collect());
+            _ = tx.send(SweepResult {
+                craftsmanship,
+                control,
+                cells,
+                initial_quality: params.initial_quality,
+                target_quality,
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn update(&mut self) {
+        if let Some(run) = self.run.as_ref() {
+            match run.result.try_recv() {
+                Ok(result) => {
+                    if !run.interrupt_signal.is_set() {
+                        self.result = Some(result);
+                    }
+                    self.run = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.run = None,
+                Err(mpsc::TryRecvError::Empty) => (),
+            }
+        }
+    }
+}
+
+fn sweep_values((min, max): (u16, u16), resolution: usize) -> Vec<u16> {
+    let (min, max) = (min.min(max), min.max(max));
+    let resolution = resolution.max(2);
+    (0..resolution)
+        .map(|index| {
+            let offset = usize::from(max - min) * index / (resolution - 1);
+            min + offset as u16
+        })
+        .collect()
+}
+
+/// Maps `t` in `[0, 1]` from red over yellow to green.
+fn heat_color(t: f32) -> egui::Color32 {
+    let t = t.clamp(0.0, 1.0);
+    let red = egui::Color32::from_rgb(200, 60, 50);
+    let yellow = egui::Color32::from_rgb(220, 190, 60);
+    let green = egui::Color32::from_rgb(60, 170, 80);
+    if t < 0.5 {
+        red.lerp_to_gamma(yellow, t * 2.0)
+    } else {
+        yellow.lerp_to_gamma(green, t * 2.0 - 1.0)
+    }
+}
+
+pub struct StatSweepWidget<'a> {
+    data: &'a mut StatSweepData,
+    params: CraftParams,
+}
+
+impl<'a> StatSweepWidget<'a> {
+    pub fn new(data: &'a mut StatSweepData, params: CraftParams) -> Self {
+        Self { data, params }
+    }
+
+    fn show_config(&mut self, ui: &mut egui::Ui) {
+        let config = &mut self.data.config;
+        egui::Grid::new("stat_sweep_config").show(ui, |ui| {
+            ui.label("Craftsmanship");
+            ui.add(egui::DragValue::new(&mut config.craftsmanship_range.0).range(0..=9000));
+            ui.label("to");
+            ui.add(egui::DragValue::new(&mut config.craftsmanship_range.1).range(0..=9000));
+            ui.end_row();
+            ui.label("Control");
+            ui.add(egui::DragValue::new(&mut config.control_range.0).range(0..=9000));
+            ui.label("to");
+            ui.add(egui::DragValue::new(&mut config.control_range.1).range(0..=9000));
+            ui.end_row();
+            ui.label("Grid size");
+            ui.add(egui::DragValue::new(&mut config.resolution).range(2..=24));
+            ui.end_row();
+        });
+        ui.horizontal(|ui| {
+            ui.label("Show");
+            ui.selectable_value(&mut config.metric, SweepMetric::Quality, "Quality");
+            ui.selectable_value(&mut config.metric, SweepMetric::Steps, "Steps");
+            ui.add(HelpText::new(
+                "Quality: upper bound on the achievable Quality.\nSteps: lower bound on the number of steps needed to reach the target Quality.\n\nBounds are computed without solving, so the actual rotation may be slightly worse.",
+            ));
+        });
+    }
+
+    fn show_controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| match self.data.run.as_ref() {
+            Some(run) => {
+                util::busy_indicator(ui);
+                ui.label(format!(
+                    "{} / {} evaluated",
+                    run.completed_cells.load(Ordering::Relaxed),
+                    run.total_cells
+                ));
+                if ui.button("Cancel").clicked() {
+                    run.interrupt_signal.set();
+                }
+                ui.ctx()
+                    .request_repaint_after(util::background_repaint_interval(ui.ctx()));
+            }
+            None => {
+                if ui.button("Run sweep").clicked() {
+                    self.data.start(ui.ctx().clone(), self.params);
+                }
+                ui.label(format!("CP fixed at {}", self.params.crafter_stats.cp));
+            }
+        });
+    }
+
+    fn show_heatmap(&self, ui: &mut egui::Ui, result: &SweepResult) {
+        let metric = self.data.config.metric;
+        let value = |bounds: &CraftBounds| -> Option<f32> {
+            match metric {
+                SweepMetric::Quality => Some(
+                    f32::from(
+                        result
+                            .initial_quality
+                            .saturating_add(bounds.quality_upper_bound),
+                    ) / f32::from(result.target_quality.max(1)),
+                ),
+                SweepMetric::Steps => bounds.steps_lower_bound.map(f32::from),
+            }
+        };
+        let (min_steps, max_steps) = result
+            .cells
+            .iter()
+            .flatten()
+            .filter_map(|bounds| bounds.steps_lower_bound)
+            .fold((u8::MAX, u8::MIN), |(min, max), steps| {
+                (min.min(steps), max.max(steps))
+            });
+        let normalize = |value: f32| match metric {
+            SweepMetric::Quality => value,
+            SweepMetric::Steps if max_steps > min_steps => {
+                // fewer steps is better
+                1.0 - (value - f32::from(min_steps)) / f32::from(max_steps - min_steps)
+            }
+            SweepMetric::Steps => 1.0,
+        };
+
+        let columns = result.craftsmanship.len();
+        let rows = result.control.len();
+        let label_width = 40.0;
+        let width = ui.available_width() - label_width;
+        let cell_size = egui::vec2(width / columns as f32, 24.0);
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(width + label_width, cell_size.y * (rows + 1) as f32),
+            egui::Sense::hover(),
+        );
+        let origin = response.rect.min + egui::vec2(label_width, 0.0);
+        let text_color = ui.visuals().text_color();
+        let font = egui::FontId::monospace(10.0);
+
+        let mut hovered = None;
+        for (row, control) in result.control.iter().enumerate() {
+            // highest control at the top
+            let y = (rows - 1 - row) as f32 * cell_size.y;
+            painter.text(
+                origin + egui::vec2(-4.0, y + cell_size.y / 2.0),
+                egui::Align2::RIGHT_CENTER,
+                control.to_string(),
+                font.clone(),
+                text_color,
+            );
+            for (column, craftsmanship) in result.craftsmanship.iter().enumerate() {
+                let rect = egui::Rect::from_min_size(
+                    origin + egui::vec2(column as f32 * cell_size.x, y),
+                    cell_size,
+                )
+                .shrink(1.0);
+                let cell = result.cells[row * columns + column];
+                let color = cell.as_ref().and_then(value).map_or_else(
+                    || ui.visuals().faint_bg_color,
+                    |value| heat_color(normalize(value)),
+                );
+                painter.rect_filled(rect, 2.0, color);
+                if response
+                    .hover_pos()
+                    .is_some_and(|position| rect.contains(position))
+                {
+                    hovered = Some((*craftsmanship, *control, cell));
+                }
+            }
+        }
+        for (column, craftsmanship) in result.craftsmanship.iter().enumerate() {
+            painter.text(
+                origin
+                    + egui::vec2(
+                        (column as f32 + 0.5) * cell_size.x,
+                        rows as f32 * cell_size.y + 4.0,
+                    ),
+                egui::Align2::CENTER_TOP,
+                craftsmanship.to_string(),
+                font.clone(),
+                text_color,
+            );
+        }
+
+        if let Some((craftsmanship, control, cell)) = hovered {
+            response.on_hover_ui_at_pointer(|ui| {
+                ui.label(format!("Craftsmanship {craftsmanship}, Control {control}"));
+                match cell {
+                    Some(bounds) => {
+                        ui.label(format!(
+                            "Quality: ≤ {}",
+                            result
+                                .initial_quality
+                                .saturating_add(bounds.quality_upper_bound)
+                        ));
+                        match bounds.steps_lower_bound {
+                            Some(steps) => ui.label(format!("Steps to reach target: ≥ {steps}")),
+                            None => ui.label("Target Quality not reachable"),
+                        };
+                    }
+                    None => {
+                        ui.label("Unable to finish the craft");
+                    }
+                }
+            });
+        }
+    }
+}
+
+impl egui::Widget for StatSweepWidget<'_> {
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        self.data.update();
+        ui.vertical(|ui| {
+            self.show_config(ui);
+            ui.separator();
+            self.show_controls(ui);
+            if let Some(result) = self.data.result.as_ref() {
+                ui.separator();
+                self.show_heatmap(ui, result);
+                ui.label("Horizontal: Craftsmanship / Vertical: Control");
+            }
+        })
+        .response
+    }
+}
@@ -1,8 +1,11 @@
 use egui::Widget;
-use raphael_data::{Locale, action_name, get_job_name};
+use raphael_data::{CrafterStats, Locale, action_name, get_job_name};
 use raphael_sim::Action;
 
 use crate::config::CrafterConfig;
+use crate::gearset_import::parse_character;
+
+use super::util;
 
 pub struct StatsEdit<'a> {
     locale: Locale,
@@ -60,18 +63,17 @@ impl Widget for StatsEdit<'_> {
             }
 
             ui.separator().rect.width();
+            let error_id = egui::Id::new("config_paste_error");
             ui.horizontal(|ui| {
                 let copy_id = egui::Id::new("config_copy");
                 let button_enabled = ui.ctx().animate_bool_with_time(copy_id, false, 0.25) == 0.0;
                 let button_response =
                     ui.add_enabled(button_enabled, egui::Button::new("🗐 Copy crafter config"));
                 if button_response.clicked() {
-                    ui.ctx()
-                        .copy_text(ron::to_string(self.crafter_config).unwrap());
+                    util::copy_to_clipboard(ui.ctx(), self.crafter_config.export());
                     ui.ctx().animate_bool_with_time(copy_id, true, 0.0);
                 }
 
-                let selected_job = self.crafter_config.selected_job;
                 let paste_id = egui::Id::new("config_paste");
                 let input_enabled = ui.ctx().animate_bool_with_time(paste_id, false, 0.25) == 0.0;
                 let input_string = &mut String::new();
@@ -81,14 +83,182 @@ impl Widget for StatsEdit<'_> {
                         .hint_text("📋 Paste config here to load"),
                 );
                 if input_response.changed() {
-                    if let Ok(crafter_config) = ron::from_str(input_string) {
-                        *self.crafter_config = crafter_config;
-                        self.crafter_config.selected_job = selected_job;
-                        ui.ctx().animate_bool_with_time(paste_id, true, 0.0);
+                    match self.crafter_config.import(input_string) {
+                        Ok(()) => {
+                            ui.ctx().data_mut(|data| data.remove::<String>(error_id));
+                            ui.ctx().animate_bool_with_time(paste_id, true, 0.0);
+                        }
+                        Err(error) => ui.ctx().data_mut(|data| data.insert_temp(error_id, error)),
                     }
                 }
             });
+            ui.horizontal(|ui| {
+                character_import_ui(ui, self.crafter_config, error_id);
+            });
+            csv_import_ui(ui, self.crafter_config, self.locale);
+            if let Some(error) = ui.ctx().data(|data| data.get_temp::<String>(error_id)) {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+                    if ui.small_button("Dismiss").clicked() {
+                        ui.ctx().data_mut(|data| data.remove::<String>(error_id));
+                    }
+                });
+            }
         })
         .response
     }
 }
+
+/// Loads the stats of a character from its Lodestone profile via XIVAPI. The native app fetches
+/// the profile itself, on the web the response has to be opened in the browser and pasted.
+fn character_import_ui(ui: &mut egui::Ui, crafter_config: &mut CrafterConfig, error_id: egui::Id) {
+    let lodestone_id_id = egui::Id::new("lodestone_id");
+    let result_id = egui::Id::new("lodestone_import_result");
+    let imported_id = egui::Id::new("lodestone_imported");
+
+    let mut lodestone_id = ui
+        .ctx()
+        .data(|data| data.get_temp::<String>(lodestone_id_id))
+        .unwrap_or_default();
+    ui.label("Lodestone ID");
+    if ui
+        .add(egui::TextEdit::singleline(&mut lodestone_id).desired_width(90.0))
+        .changed()
+    {
+        ui.ctx()
+            .data_mut(|data| data.insert_temp(lodestone_id_id, lodestone_id.clone()));
+    }
+    let parsed_id = lodestone_id.trim().parse::<u64>().ok();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let clicked = ui
+            .add_enabled(parsed_id.is_some(), egui::Button::new("Import stats"))
+            .on_hover_text("Uses the gearset the character has equipped on the Lodestone")
+            .clicked();
+        if let Some(lodestone_id) = parsed_id.filter(|_| clicked) {
+            crate::gearset_import::fetch_character(ui.ctx().clone(), lodestone_id, result_id);
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        if let Some(lodestone_id) = parsed_id {
+            ui.hyperlink_to(
+                "Open profile",
+                crate::gearset_import::character_url(lodestone_id),
+            );
+        }
+        let input_string = &mut String::new();
+        if ui
+            .add(
+                egui::TextEdit::singleline(input_string)
+                    .hint_text("📋 Paste the opened profile here"),
+            )
+            .changed()
+        {
+            ui.ctx().data_mut(|data| {
+                data.insert_temp(result_id, Ok::<_, String>(input_string.clone()))
+            });
+        }
+    }
+
+    let result = ui.ctx().data_mut(|data| {
+        let result = data.get_temp::<Result<String, String>>(result_id);
+        data.remove::<Result<String, String>>(result_id);
+        result
+    });
+    if let Some(result) = result {
+        match result.and_then(|json| parse_character(&json)) {
+            Ok(character) => {
+                crafter_config.import_character(&character);
+                ui.ctx().data_mut(|data| {
+                    data.remove::<String>(error_id);
+                    data.insert_temp(imported_id, character.name);
+                });
+            }
+            Err(error) => ui.ctx().data_mut(|data| {
+                data.remove::<String>(imported_id);
+                data.insert_temp(error_id, format!("Lodestone import failed: {error}"));
+            }),
+        }
+    }
+    if let Some(name) = ui.ctx().data(|data| data.get_temp::<String>(imported_id)) {
+        ui.label(format!("Imported stats of {name}"));
+    }
+}
+
+/// Loads the stats of several jobs from pasted CSV lines, see [`CrafterConfig::parse_csv`].
+/// The changed stats are shown before they are applied.
+fn csv_import_ui(ui: &mut egui::Ui, crafter_config: &mut CrafterConfig, locale: Locale) {
+    let text_id = egui::Id::new("csv_import_text");
+    let mut text = ui
+        .ctx()
+        .data(|data| data.get_temp::<String>(text_id))
+        .unwrap_or_default();
+    if ui
+        .add(
+            egui::TextEdit::multiline(&mut text)
+                .desired_rows(1)
+                .desired_width(f32::INFINITY)
+                .hint_text("📋 Paste CSV lines of job,craftsmanship,control,cp,level,specialist"),
+        )
+        .changed()
+    {
+        ui.ctx()
+            .data_mut(|data| data.insert_temp(text_id, text.clone()));
+    }
+    if text.trim().is_empty() {
+        return;
+    }
+    let crafter_stats = match crafter_config.parse_csv(&text) {
+        Ok(crafter_stats) => crafter_stats,
+        Err(error) => {
+            ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+            return;
+        }
+    };
+    let stats_text = |stats: &CrafterStats| {
+        let specialist = match stats.heart_and_soul {
+            true => " ★",
+            false => "",
+        };
+        format!(
+            "{}/{}/{} Lv. {}{specialist}",
+            stats.craftsmanship, stats.control, stats.cp, stats.level
+        )
+    };
+    let mut changed = false;
+    for (job_id, (old, new)) in crafter_config
+        .crafter_stats
+        .iter()
+        .zip(crafter_stats.iter())
+        .enumerate()
+    {
+        if old != new {
+            changed = true;
+            ui.label(format!(
+                "{}: {} ➡ {}",
+                get_job_name(job_id as u8, locale),
+                stats_text(old),
+                stats_text(new)
+            ))
+            .on_hover_text("Craftsmanship/Control/CP, ★ marks specialists");
+        }
+    }
+    ui.horizontal(|ui| {
+        match changed {
+            true => {
+                if ui.button("Apply").clicked() {
+                    crafter_config.crafter_stats = crafter_stats;
+                    ui.ctx().data_mut(|data| data.remove::<String>(text_id));
+                }
+            }
+            false => {
+                ui.label("No changes");
+            }
+        }
+        if ui.button("Cancel").clicked() {
+            ui.ctx().data_mut(|data| data.remove::<String>(text_id));
+        }
+    });
+}
@@ -0,0 +1,331 @@
+use std::sync::mpsc::{self, Receiver};
+
+use raphael_data::{Locale, action_name, condition_pool};
+use raphael_sim::{Action, Condition, Settings, SimulationState};
+use raphael_solver::{AtomicFlag, SolverException, StepAdvisor, StepRecommendation};
+
+use crate::app::CraftParams;
+
+use super::{HelpText, util};
+
+type Advice = Result<StepRecommendation, SolverException>;
+
+struct AdviceRun {
+    result: Receiver<(StepAdvisor, Advice)>,
+    interrupt_signal: AtomicFlag,
+}
+
+/// A craft that is executed in-game one step at a time, with the condition of each step
+/// entered by the user.
+pub struct StepAdvisorData {
+    /// Actions that were used in-game, with the condition they were used in.
+    steps: Vec<(Action, Condition)>,
+    condition: Condition,
+    /// Kept between steps, so that the rotation of the last recommendation can be reused.
+    advisor: Option<(StepAdvisor, AtomicFlag)>,
+    /// Settings and initial state of the craft. The craft restarts when they change.
+    craft: Option<(Settings, SimulationState)>,
+    run: Option<AdviceRun>,
+    advice: Option<Advice>,
+}
+
+impl Default for StepAdvisorData {
+    fn default() -> Self {
+        Self {
+            steps: Vec::new(),
+            condition: Condition::Normal,
+            advisor: None,
+            craft: None,
+            run: None,
+            advice: None,
+        }
+    }
+}
+
+impl StepAdvisorData {
+    fn restart(&mut self) {
+        if let Some(run) = self.run.take() {
+            run.interrupt_signal.set();
+        }
+        self.steps.clear();
+        self.condition = Condition::Normal;
+        self.advisor = None;
+        self.advice = None;
+    }
+
+    fn sync(&mut self, params: &CraftParams) {
+        let request = params.solve_request();
+        let settings = Settings {
+            // the conditions are entered by the user, see `StepAdvisor::new`
+            adversarial: false,
+            ..request.simulator_settings()
+        };
+        let craft = (settings, request.initial_state());
+        if self.craft != Some(craft) {
+            self.restart();
+            self.craft = Some(craft);
+        }
+    }
+
+    /// State of the craft after the steps that were used in-game.
+    fn state(&self) -> Option<SimulationState> {
+        let (settings, initial_state) = self.craft?;
+        let mut state = initial_state;
+        for (action, condition) in &self.steps {
+            state = state.use_action(*action, *condition, &settings).ok()?;
+        }
+        Some(state)
+    }
+
+    fn start(&mut self, ctx: egui::Context, params: CraftParams) {
+        let Some(state) = self.state() else {
+            return;
+        };
+        if let Some(run) = self.run.take() {
+            run.interrupt_signal.set();
+        }
+        self.advice = None;
+
+        let (mut advisor, interrupt_signal) = self.advisor.take().unwrap_or_else(|| {
+            let interrupt_signal = AtomicFlag::new();
+            let settings = params.solve_request().solver_settings();
+            (
+                StepAdvisor::new(settings, interrupt_signal.clone()),
+                interrupt_signal,
+            )
+        });
+        let (tx, rx) = mpsc::channel();
+        self.run = Some(AdviceRun {
+            result: rx,
+            interrupt_signal,
+        });
+        let condition = self.condition;
+        std::thread::spawn(move || {
+            let thread_pool = match params.thread_pool_settings.build() {
+                Ok(thread_pool) => thread_pool,
+                Err(error) => {
+                    log::error!("Failed to build thread pool: {error}");
+                    return;
+                }
+            };
+            let advice = thread_pool.install(|| advisor.recommend(state, condition));
+            _ = tx.send((advisor, advice));
+            ctx.request_repaint();
+        });
+    }
+
+    fn update(&mut self) {
+        if let Some(run) = self.run.as_ref() {
+            match run.result.try_recv() {
+                Ok((advisor, advice)) => {
+                    // the interrupt signal of an interrupted advisor stays set
+                    if !matches!(advice, Err(SolverException::Interrupted)) {
+                        self.advisor = Some((advisor, run.interrupt_signal.clone()));
+                    }
+                    self.advice = Some(advice);
+                    self.run = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.run = None,
+                Err(mpsc::TryRecvError::Empty) => (),
+            }
+        }
+    }
+}
+
+/// Recommends the next action of a craft after the user enters the condition of each step.
+pub struct StepAdvisorWidget<'a> {
+    data: &'a mut StepAdvisorData,
+    params: CraftParams,
+    locale: Locale,
+}
+
+impl<'a> StepAdvisorWidget<'a> {
+    pub fn new(data: &'a mut StepAdvisorData, params: CraftParams, locale: Locale) -> Self {
+        Self {
+            data,
+            params,
+            locale,
+        }
+    }
+
+    fn show_state(&self, ui: &mut egui::Ui, settings: &Settings, state: &SimulationState) {
+        egui::Grid::new("step_advisor_state").show(ui, |ui| {
+            ui.label("Step");
+            ui.label((self.data.steps.len() + 1).to_string());
+            ui.end_row();
+            ui.label("Progress");
+            ui.label(format!("{} / {}", state.progress, settings.max_progress));
+            ui.end_row();
+            ui.label("Quality");
+            ui.label(format!(
+                "{} / {}",
+                self.params.initial_quality + state.quality,
+                self.params.initial_quality + settings.max_quality
+            ));
+            ui.end_row();
+            ui.label("Durability");
+            ui.label(format!(
+                "{} / {}",
+                state.durability, settings.max_durability
+            ));
+            ui.end_row();
+            ui.label("CP");
+            ui.label(format!("{} / {}", state.cp, settings.max_cp));
+            ui.end_row();
+        });
+    }
+
+    fn show_condition_select(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Condition:");
+            for condition in condition_pool(self.params.recipe).conditions() {
+                if ui
+                    .selectable_value(
+                        &mut self.data.condition,
+                        condition,
+                        condition_name(condition),
+                    )
+                    .changed()
+                {
+                    self.data.advice = None;
+                }
+            }
+            ui.add(HelpText::new(
+                "The condition of the current step as shown in-game.",
+            ));
+        });
+    }
+
+    fn show_advice(&mut self, ui: &mut egui::Ui) {
+        let Some(advice) = self.data.advice.as_ref() else {
+            return;
+        };
+        let recommendation = match advice {
+            Ok(recommendation) => recommendation,
+            Err(SolverException::NoSolution) => {
+                ui.label("The craft can't be finished anymore.");
+                return;
+            }
+            Err(SolverException::Interrupted) => {
+                ui.label("Cancelled");
+                return;
+            }
+            Err(SolverException::MemoryLimitReached) => {
+                ui.label("Out of memory");
+                return;
+            }
+            Err(SolverException::InternalError(message)) => {
+                ui.label(message);
+                return;
+            }
+        };
+        let action = recommendation.action;
+        ui.horizontal(|ui| {
+            ui.add(
+                util::get_action_icon(action, self.params.recipe.job_id)
+                    .fit_to_exact_size(egui::Vec2::new(30.0, 30.0))
+                    .corner_radius(4.0),
+            );
+            ui.label(egui::RichText::new(action_name(action, self.locale)).strong());
+        });
+        ui.label(format!(
+            "Quality at the end of the craft: {}",
+            self.params.initial_quality + recommendation.quality
+        ));
+        if !recommendation.remaining_actions.is_empty() {
+            let remaining_actions: Vec<&str> = recommendation
+                .remaining_actions
+                .iter()
+                .map(|action| action_name(*action, self.locale))
+                .collect();
+            ui.label(format!(
+                "Then, if all conditions are Normal: {}",
+                remaining_actions.join(", ")
+            ));
+        }
+        if ui.button("Used it").clicked() {
+            self.data.steps.push((action, self.data.condition));
+            self.data.condition = Condition::Normal;
+            self.data.advice = None;
+            let is_finished = self
+                .data
+                .state()
+                .zip(self.data.craft)
+                .is_none_or(|(state, (settings, _))| state.is_final(&settings));
+            if !is_finished {
+                self.data.start(ui.ctx().clone(), self.params);
+            }
+        }
+    }
+}
+
+impl egui::Widget for StepAdvisorWidget<'_> {
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        self.data.update();
+        self.data.sync(&self.params);
+        ui.vertical(|ui| {
+            let (Some(state), Some((settings, _))) = (self.data.state(), self.data.craft) else {
+                return;
+            };
+            self.show_state(ui, &settings, &state);
+            ui.separator();
+            if state.is_final(&settings) {
+                match state.progress >= settings.max_progress {
+                    true => ui.label("The craft is finished."),
+                    false => ui.label("The craft failed."),
+                };
+            } else {
+                self.show_condition_select(ui);
+                ui.horizontal(|ui| match self.data.run.as_ref() {
+                    Some(run) => {
+                        util::busy_indicator(ui);
+                        ui.label("Solving ...");
+                        if ui.button("Cancel").clicked() {
+                            run.interrupt_signal.set();
+                        }
+                        ui.ctx()
+                            .request_repaint_after(util::background_repaint_interval(ui.ctx()));
+                    }
+                    None => {
+                        if ui.button("Recommend next action").clicked() {
+                            self.data.start(ui.ctx().clone(), self.params);
+                        }
+                    }
+                });
+                self.show_advice(ui);
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!self.data.steps.is_empty(), egui::Button::new("Undo step"))
+                    .clicked()
+                {
+                    if let Some(run) = self.data.run.take() {
+                        run.interrupt_signal.set();
+                    }
+                    self.data.steps.pop();
+                    self.data.advice = None;
+                }
+                if ui.button("Restart").clicked() {
+                    self.data.restart();
+                }
+            });
+        })
+        .response
+    }
+}
+
+fn condition_name(condition: Condition) -> &'static str {
+    match condition {
+        Condition::Normal => "Normal",
+        Condition::Good => "Good",
+        Condition::Excellent => "Excellent",
+        Condition::Poor => "Poor",
+        Condition::Centered => "Centered",
+        Condition::Sturdy => "Sturdy",
+        Condition::Pliant => "Pliant",
+        Condition::Malleable => "Malleable",
+        Condition::Primed => "Primed",
+        Condition::GoodOmen => "Good Omen",
+    }
+}
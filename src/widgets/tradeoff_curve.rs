@@ -0,0 +1,209 @@
+use std::sync::mpsc::{self, Receiver};
+
+use raphael_data::Locale;
+use raphael_sim::{Action, Settings, SimulationState};
+use raphael_solver::{AtomicFlag, SolverException, SolverSettings, quality_upper_bound_per_step};
+
+use super::{AnalysisPlugin, util};
+
+const MAX_STEPS: u8 = 60;
+
+struct TradeoffRun {
+    result: Receiver<Result<Vec<u16>, SolverException>>,
+    interrupt_signal: AtomicFlag,
+}
+
+/// Plots the best achievable Quality for each step count, so that the Quality gained by
+/// accepting a longer macro can be compared against the solved macro.
+#[derive(Default)]
+pub struct TradeoffCurvePlugin {
+    run: Option<TradeoffRun>,
+    /// Quality upper bound for each step count, starting at one step.
+    quality_upper_bounds: Vec<u16>,
+    max_quality: u16,
+    /// Step count and Quality of the solved macro.
+    solution: Option<(usize, u16)>,
+    error: Option<SolverException>,
+}
+
+impl TradeoffCurvePlugin {
+    fn update(&mut self) {
+        if let Some(run) = self.run.as_ref() {
+            match run.result.try_recv() {
+                Ok(Ok(quality_upper_bounds)) => {
+                    self.quality_upper_bounds = quality_upper_bounds;
+                    self.run = None;
+                }
+                Ok(Err(error)) => {
+                    self.error = Some(error);
+                    self.run = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.run = None,
+                Err(mpsc::TryRecvError::Empty) => (),
+            }
+        }
+    }
+
+    fn show_curve(&self, ui: &mut egui::Ui) {
+        let max_steps = self
+            .quality_upper_bounds
+            .len()
+            .max(self.solution.map_or(0, |(steps, _)| steps));
+        let max_quality = f32::from(self.max_quality.max(1));
+
+        let label_width = 48.0;
+        let label_height = 16.0;
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(ui.available_width(), 200.0),
+            egui::Sense::hover(),
+        );
+        let plot_rect = egui::Rect::from_min_max(
+            response.rect.min + egui::vec2(label_width, 0.0),
+            response.rect.max - egui::vec2(0.0, label_height),
+        );
+        let to_screen = |steps: usize, quality: u16| {
+            egui::pos2(
+                egui::lerp(plot_rect.x_range(), steps as f32 / max_steps as f32),
+                egui::lerp(plot_rect.y_range(), 1.0 - f32::from(quality) / max_quality),
+            )
+        };
+
+        let visuals = ui.visuals();
+        let text_color = visuals.text_color();
+        let font = egui::FontId::monospace(10.0);
+        painter.rect_stroke(
+            plot_rect,
+            0.0,
+            visuals.widgets.noninteractive.bg_stroke,
+            egui::StrokeKind::Inside,
+        );
+        painter.text(
+            plot_rect.left_top() - egui::vec2(4.0, 0.0),
+            egui::Align2::RIGHT_TOP,
+            self.max_quality.to_string(),
+            font.clone(),
+            text_color,
+        );
+        painter.text(
+            plot_rect.left_bottom() - egui::vec2(4.0, 0.0),
+            egui::Align2::RIGHT_BOTTOM,
+            "0",
+            font.clone(),
+            text_color,
+        );
+        painter.text(
+            plot_rect.right_bottom() + egui::vec2(0.0, 2.0),
+            egui::Align2::RIGHT_TOP,
+            format!("{max_steps} steps"),
+            font.clone(),
+            text_color,
+        );
+
+        // step function, since the Quality only changes when an additional step is allowed
+        let mut points = vec![to_screen(0, 0)];
+        for (index, quality) in self.quality_upper_bounds.iter().enumerate() {
+            points.push(to_screen(index, *quality));
+            points.push(to_screen(index + 1, *quality));
+        }
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, visuals.hyperlink_color),
+        ));
+        if let Some((steps, quality)) = self.solution {
+            painter.circle_filled(to_screen(steps, quality), 4.0, visuals.warn_fg_color);
+        }
+
+        if let Some(position) = response.hover_pos() {
+            let steps = ((position.x - plot_rect.left()) / plot_rect.width() * max_steps as f32)
+                .ceil()
+                .max(1.0) as usize;
+            if let Some(quality) = self.quality_upper_bounds.get(steps - 1) {
+                response.on_hover_ui_at_pointer(|ui| {
+                    ui.label(format!("{steps} steps: Quality ≤ {quality}"));
+                });
+            }
+        }
+    }
+}
+
+impl AnalysisPlugin for TradeoffCurvePlugin {
+    fn name(&self) -> &str {
+        "Quality vs steps"
+    }
+
+    fn on_solve(&mut self, settings: &Settings, actions: &[Action], state: &SimulationState) {
+        if let Some(run) = self.run.take() {
+            run.interrupt_signal.set();
+        }
+        self.quality_upper_bounds.clear();
+        self.max_quality = settings.max_quality;
+        self.solution = Some((actions.len(), state.quality));
+        self.error = None;
+
+        let (tx, rx) = mpsc::channel();
+        let interrupt_signal = AtomicFlag::new();
+        self.run = Some(TradeoffRun {
+            result: rx,
+            interrupt_signal: interrupt_signal.clone(),
+        });
+        let solver_settings = SolverSettings {
+            simulator_settings: *settings,
+            backload_progress: false,
+            allow_unsound_branch_pruning: false,
+            forbid_quality_during_muscle_memory: false,
+            first_quality_step: 0,
+        };
+        std::thread::spawn(move || {
+            _ = tx.send(quality_upper_bound_per_step(
+                solver_settings,
+                MAX_STEPS,
+                interrupt_signal,
+            ));
+        });
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, _locale: Locale) {
+        self.update();
+        if self.run.is_some() {
+            ui.horizontal(|ui| {
+                util::busy_indicator(ui);
+                ui.label("Computing ...");
+            });
+            ui.ctx()
+                .request_repaint_after(util::background_repaint_interval(ui.ctx()));
+            return;
+        }
+        if let Some(error) = self.error.as_ref() {
+            ui.label(format!("Unable to compute the curve: {error:?}"));
+            return;
+        }
+        if self.quality_upper_bounds.is_empty() {
+            ui.label("Solve a recipe to see the Quality for each step count");
+            return;
+        }
+        self.show_curve(ui);
+        ui.label("Upper bound on the Quality for each step count. The dot marks the solved macro.");
+        egui::Grid::new("tradeoff_curve")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Steps");
+                ui.label("Quality");
+                ui.end_row();
+                let first_step = self
+                    .quality_upper_bounds
+                    .iter()
+                    .position(|quality| *quality != 0)
+                    .unwrap_or_default();
+                for (index, quality) in self
+                    .quality_upper_bounds
+                    .iter()
+                    .enumerate()
+                    .skip(first_step)
+                {
+                    ui.label((index + 1).to_string());
+                    ui.label(format!("≤ {quality}"));
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -22,6 +22,124 @@ pub fn collapse_temporary(ui: &mut egui::Ui, id: egui::Id, collapsed: &mut bool)
     }
 }
 
+/// Whether repaints and animations should be kept to a minimum, e.g. when running the app
+/// alongside the game on weak hardware.
+pub fn low_spec_mode(ctx: &egui::Context) -> bool {
+    ctx.data_mut(|data| *data.get_persisted_mut_or(egui::Id::new("LOW_SPEC_MODE"), false))
+}
+
+pub fn set_low_spec_mode(ctx: &egui::Context, enabled: bool) {
+    ctx.data_mut(|data| data.insert_persisted(egui::Id::new("LOW_SPEC_MODE"), enabled));
+    ctx.style_mut(|style| {
+        style.animation_time = match enabled {
+            true => 0.0,
+            false => egui::Style::default().animation_time,
+        };
+    });
+}
+
+/// Difference between the reached Quality and the Quality target, for crafts that are judged
+/// by their HQ percentage.
+pub fn quality_delta_text(quality: u16, target_quality: u16, max_quality: u16) -> String {
+    let target_hq = raphael_data::hq_percentage(target_quality, max_quality).unwrap_or(0);
+    match quality >= target_quality {
+        true => format!(
+            "{target_hq}% HQ target reached (+{} Quality)",
+            quality - target_quality
+        ),
+        false => format!(
+            "{} Quality short of the {target_hq}% HQ target",
+            target_quality - quality
+        ),
+    }
+}
+
+/// Warns if the bonus of the selected consumable is capped for all of its stats and a cheaper
+/// consumable of `consumables` gives the same bonus, with a button to switch to it.
+pub fn capped_consumable_warning(
+    ui: &mut egui::Ui,
+    selected_consumable: &mut Option<raphael_data::Consumable>,
+    consumables: &[raphael_data::Consumable],
+    crafter_stats: raphael_data::CrafterStats,
+    locale: raphael_data::Locale,
+) {
+    let Some(consumable) = *selected_consumable else {
+        return;
+    };
+    let (craftsmanship, control, cp) = (
+        crafter_stats.craftsmanship,
+        crafter_stats.control,
+        crafter_stats.cp,
+    );
+    if !consumable.is_capped(craftsmanship, control, cp) {
+        return;
+    }
+    let Some(alternative) = consumable.cheapest_equivalent(consumables, craftsmanship, control, cp)
+    else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new("⚠ Bonus is capped, same bonus from")
+                .color(ui.visuals().warn_fg_color),
+        );
+        ui.add(super::ItemNameLabel::new(
+            alternative.item_id,
+            alternative.hq,
+            locale,
+        ));
+        if ui.small_button("Use").clicked() {
+            *selected_consumable = Some(alternative);
+        }
+    });
+}
+
+/// Spinner that is replaced by a static label in low-spec mode, as the spinner animation
+/// repaints the UI on every frame.
+pub fn busy_indicator(ui: &mut egui::Ui) {
+    if low_spec_mode(ui.ctx()) {
+        ui.label("⏳");
+    } else {
+        ui.spinner();
+    }
+}
+
+/// How often to repaint while waiting for work that runs in the background.
+pub fn background_repaint_interval(ctx: &egui::Context) -> std::time::Duration {
+    match low_spec_mode(ctx) {
+        true => std::time::Duration::from_secs(1),
+        false => std::time::Duration::from_millis(100),
+    }
+}
+
+/// Copies `text` to the system clipboard.
+///
+/// In the browser, the asynchronous Clipboard API is used directly since it keeps working in
+/// cases where egui's clipboard integration doesn't, e.g. when the copy isn't triggered by a
+/// key press. Falls back to egui's clipboard if the Clipboard API is unavailable or fails,
+/// e.g. on pages that aren't served over HTTPS. Native builds use egui's clipboard, which
+/// supports both X11 and Wayland.
+pub fn copy_to_clipboard(ctx: &egui::Context, text: String) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let clipboard = web_sys::window()
+            .map(|window| window.navigator().clipboard())
+            .filter(|clipboard| !clipboard.is_undefined());
+        if let Some(clipboard) = clipboard {
+            let promise = clipboard.write_text(&text);
+            let ctx = ctx.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(error) = wasm_bindgen_futures::JsFuture::from(promise).await {
+                    log::warn!("Clipboard API failed: {error:?}");
+                    ctx.copy_text(text);
+                }
+            });
+            return;
+        }
+    }
+    ctx.copy_text(text);
+}
+
 #[cfg(target_arch = "wasm32")]
 pub fn get_action_icon(action: Action, job_id: u8) -> egui::Image<'static> {
     let image_path = format!(
@@ -87,5 +205,8 @@ pub fn get_action_icon(action: Action, job_id: u8) -> egui::Image<'static> {
         Action::QuickInnovation => action_icon!("Quick Innovation", job_id),
         Action::ImmaculateMend => action_icon!("Immaculate Mend", job_id),
         Action::TrainedPerfection => action_icon!("Trained Perfection", job_id),
+        Action::RapidSynthesis => action_icon!("Rapid Synthesis", job_id),
+        Action::HastyTouch => action_icon!("Hasty Touch", job_id),
+        Action::DaringTouch => action_icon!("Daring Touch", job_id),
     })
 }
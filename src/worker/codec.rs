@@ -0,0 +1,37 @@
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use web_sys::wasm_bindgen::JsValue;
+
+/// Compact binary encoding for messages between the app and the web worker.
+///
+/// The default options of bincode use fixed-size integers, so every `usize` and every enum tag
+/// takes up 8 and 4 bytes respectively. Varint encoding shrinks progress events to a few bytes
+/// and action lists to a single byte per action.
+///
+/// Encoded messages own their buffer, which is transferred to the receiver instead of copied,
+/// see [`CompactCodec::transfer_list`].
+pub struct CompactCodec;
+
+fn options() -> impl Options {
+    bincode::DefaultOptions::new().with_varint_encoding()
+}
+
+impl CompactCodec {
+    pub fn encode<I: Serialize>(input: I) -> js_sys::Uint8Array {
+        let bytes = options()
+            .serialize(&input)
+            .expect("can't serialize a worker message");
+        js_sys::Uint8Array::from(bytes.as_slice())
+    }
+
+    /// Transfer list for `postMessage`, which moves the buffer of an encoded message to the
+    /// receiver. The message can't be used anymore after it was posted.
+    pub fn transfer_list(message: &js_sys::Uint8Array) -> js_sys::Array {
+        js_sys::Array::of1(&message.buffer())
+    }
+
+    pub fn decode<O: for<'de> Deserialize<'de>>(input: JsValue) -> Result<O, bincode::Error> {
+        let bytes = js_sys::Uint8Array::from(input).to_vec();
+        options().deserialize(&bytes)
+    }
+}
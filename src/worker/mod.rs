@@ -1,6 +1,14 @@
-use crate::app::{SolverEvent, SolverInput};
-use raphael_sim::Action;
-use raphael_solver::{AtomicFlag, SolverException, test_utils};
+use crate::app::{SolverConfig, SolverEvent, SolverInput};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::solve_progress::{self, ProgressHeader};
+use raphael_sim::{Action, Condition, Settings};
+use raphael_solver::{AtomicFlag, SolverCheckpoint, SolverException, test_utils};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
 use std::sync::{LazyLock, mpsc::Sender};
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -12,14 +20,19 @@ use crate::worker::native::NativeBridge;
 #[cfg(not(target_arch = "wasm32"))]
 pub type BridgeType = NativeBridge;
 
+#[cfg(target_arch = "wasm32")]
+pub mod codec;
 #[cfg(target_arch = "wasm32")]
 pub mod web;
 #[cfg(target_arch = "wasm32")]
 use crate::worker::web as worker;
 #[cfg(target_arch = "wasm32")]
-use gloo_worker::WorkerBridge;
-#[cfg(target_arch = "wasm32")]
-pub type BridgeType = WorkerBridge<Worker>;
+pub type BridgeType = web::WebBridge;
+
+pub mod remote;
+
+#[cfg(test)]
+mod tests;
 
 type Input = SolverInput;
 type Output = SolverEvent;
@@ -30,6 +43,103 @@ pub struct Worker {
 }
 
 static INTERRUPT_SIGNAL: LazyLock<AtomicFlag> = LazyLock::new(AtomicFlag::new);
+/// Stops the running solve like [`INTERRUPT_SIGNAL`], but keeps the progress of the search.
+static CHECKPOINT_SIGNAL: LazyLock<AtomicFlag> = LazyLock::new(AtomicFlag::new);
+/// Progress file and `.raphael` file of the app for the solve stopped by [`CHECKPOINT_SIGNAL`].
+#[cfg(not(target_arch = "wasm32"))]
+static SAVE_PROGRESS: Mutex<Option<(PathBuf, String)>> = Mutex::new(None);
+
+/// Writes the progress of the solve to the file requested by `SolverInput::SaveProgress`.
+#[cfg(not(target_arch = "wasm32"))]
+fn save_progress(
+    settings: Settings,
+    config: SolverConfig,
+    prefix: &[Action],
+    checkpoint: &SolverCheckpoint,
+) -> SolverEvent {
+    let Some((path, rotation)) = SAVE_PROGRESS.lock().unwrap().take() else {
+        return SolverEvent::Error(SolverException::Interrupted);
+    };
+    let header = ProgressHeader {
+        version: ProgressHeader::VERSION,
+        rotation,
+        settings,
+        config,
+        prefix: prefix.to_vec(),
+    };
+    match solve_progress::write_progress_file(&path, &header, checkpoint) {
+        Ok(()) => SolverEvent::ProgressSaved(path),
+        Err(error) => SolverEvent::Error(SolverException::InternalError(error)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WorkerConfig {
+    /// Upper limit on the number of `SolverEvent::Progress` events sent to the app per second.
+    pub max_progress_events_per_second: u32,
+    /// Limit on the estimated memory usage of the solver, in bytes.
+    pub memory_limit: Option<usize>,
+}
+
+/// 32-bit web assembly can address at most 4GB. The solver's estimate doesn't include the
+/// allocator overhead, so the limit leaves some headroom to fail cleanly before running out.
+#[cfg(target_arch = "wasm32")]
+const DEFAULT_MEMORY_LIMIT: Option<usize> = Some(3 << 30);
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_MEMORY_LIMIT: Option<usize> = None;
+
+/// Memory that a solve can use, in bytes, or `None` if it is unknown.
+pub fn available_memory() -> Option<usize> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let line = meminfo
+            .lines()
+            .find(|line| line.starts_with("MemAvailable:"))?;
+        let kilobytes: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kilobytes * 1024)
+    }
+    #[cfg(not(target_os = "linux"))]
+    DEFAULT_MEMORY_LIMIT
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            max_progress_events_per_second: 10,
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+        }
+    }
+}
+
+/// Drops progress events that arrive faster than the configured rate.
+/// Progress values are cumulative, so dropping intermediate events loses no information.
+struct ProgressThrottle {
+    min_interval: web_time::Duration,
+    last_sent: Cell<Option<web_time::Instant>>,
+}
+
+impl ProgressThrottle {
+    fn new(max_events_per_second: u32) -> Self {
+        Self {
+            min_interval: web_time::Duration::from_secs(1) / max_events_per_second.max(1),
+            last_sent: Cell::new(None),
+        }
+    }
+
+    fn should_send(&self, progress: usize) -> bool {
+        let now = web_time::Instant::now();
+        let should_send = progress == 0 // progress resets are always sent
+            || self
+                .last_sent
+                .get()
+                .is_none_or(|last_sent| now.duration_since(last_sent) >= self.min_interval);
+        if should_send {
+            self.last_sent.set(Some(now));
+        }
+        should_send
+    }
+}
 
 impl Worker {
     #[allow(unused)]
@@ -40,82 +150,240 @@ impl Worker {
         input: Option<Input>,
     ) {
         let input = if cfg!(not(target_arch = "wasm32")) {
-            self.input.unwrap()
+            self.input.clone().unwrap()
         } else {
             input.unwrap()
         };
 
         match input {
-            SolverInput::Start(settings, config) => {
-                INTERRUPT_SIGNAL.clear();
-
-                let tx = self.tx.clone();
-                let solution_callback = move |actions: &[Action]| {
-                    self.send_event(
-                        tx.clone(),
+            SolverInput::Start(settings, config, worker_config, prefix) => {
+                self.solve(scope, id, settings, config, worker_config, prefix, None);
+            }
+            SolverInput::Cancel => {
+                INTERRUPT_SIGNAL.set();
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            SolverInput::SaveProgress(path, rotation) => {
+                *SAVE_PROGRESS.lock().unwrap() = Some((path, rotation));
+                CHECKPOINT_SIGNAL.set();
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            SolverInput::Resume(path, worker_config) => {
+                match solve_progress::read_progress_file(&path) {
+                    Ok((header, checkpoint)) => self.solve(
                         scope,
                         id,
-                        SolverEvent::IntermediateSolution(actions.to_vec()),
-                    );
-                };
-
-                let tx = self.tx.clone();
-                let progress_callback = move |progress: usize| {
-                    self.send_event(tx.clone(), scope, id, SolverEvent::Progress(progress));
-                };
-
-                let mut result = if config.minimize_steps {
-                    Err(SolverException::NoSolution) // skip unsound solver
-                } else {
-                    raphael_solver::MacroSolver::new(
-                        raphael_solver::SolverSettings {
-                            simulator_settings: settings,
-                            backload_progress: true,
-                            allow_unsound_branch_pruning: true,
-                        },
-                        Box::new(solution_callback.clone()),
-                        Box::new(progress_callback.clone()),
-                        INTERRUPT_SIGNAL.clone(),
-                    )
-                    .solve()
-                };
-
-                let need_resolve = match &result {
-                    Ok(actions) => {
-                        test_utils::get_quality(&settings, actions) < settings.max_quality
+                        header.settings,
+                        header.config,
+                        worker_config,
+                        header.prefix,
+                        Some(checkpoint),
+                    ),
+                    Err(error) => {
+                        let error = SolverException::InternalError(error);
+                        self.send_event(self.tx.clone(), scope, id, SolverEvent::Error(error));
                     }
-                    Err(SolverException::Interrupted) => false,
-                    Err(SolverException::NoSolution) => true,
-                    Err(SolverException::InternalError(_)) => false,
-                };
-
-                if need_resolve {
-                    progress_callback(0); // reset solver progress
-                    result = raphael_solver::MacroSolver::new(
-                        raphael_solver::SolverSettings {
-                            simulator_settings: settings,
-                            backload_progress: config.backload_progress,
-                            allow_unsound_branch_pruning: false,
-                        },
-                        Box::new(solution_callback),
-                        Box::new(progress_callback),
-                        INTERRUPT_SIGNAL.clone(),
-                    )
-                    .solve();
                 }
+            }
+        }
+    }
+
+    /// Solves for the actions after `prefix`, continuing the search of `checkpoint` if any.
+    #[allow(clippy::too_many_arguments)]
+    fn solve(
+        &self,
+        scope: Option<&worker::Scope>,
+        id: Option<worker::Id>,
+        settings: Settings,
+        config: SolverConfig,
+        worker_config: WorkerConfig,
+        prefix: Vec<Action>,
+        mut checkpoint: Option<SolverCheckpoint>,
+    ) {
+        INTERRUPT_SIGNAL.clear();
+        CHECKPOINT_SIGNAL.clear();
+        #[cfg(not(target_arch = "wasm32"))]
+        SAVE_PROGRESS.lock().unwrap().take();
+
+        let initial_state = prefix.iter().try_fold(
+            config.initial_effects.initial_state(&settings),
+            |state, action| state.use_action(*action, Condition::Normal, &settings),
+        );
+        let initial_state = match initial_state {
+            Ok(state) => state,
+            Err(error) => {
+                let error = SolverException::InternalError(error.to_string());
+                self.send_event(self.tx.clone(), scope, id, SolverEvent::Error(error));
+                return;
+            }
+        };
+        // the solver only returns the actions after the prefix
+        let prefix = prefix.as_slice();
+        let with_prefix = move |actions: &[Action]| [prefix, actions].concat();
+
+        let tx = self.tx.clone();
+        let solution_callback = move |actions: &[Action]| {
+            self.send_event(
+                tx.clone(),
+                scope,
+                id,
+                SolverEvent::IntermediateSolution(with_prefix(actions)),
+            );
+        };
+
+        let tx = self.tx.clone();
+        let throttle = &ProgressThrottle::new(worker_config.max_progress_events_per_second);
+        let progress_callback = move |progress: usize| {
+            if throttle.should_send(progress) {
+                self.send_event(tx.clone(), scope, id, SolverEvent::Progress(progress));
+            }
+        };
+
+        if config.expected_quality {
+            let result = raphael_solver::ExpectedQualitySolver::new(
+                raphael_solver::SolverSettings {
+                    simulator_settings: settings,
+                    backload_progress: config.backload_progress,
+                    allow_unsound_branch_pruning: false,
+                    forbid_quality_during_muscle_memory: config.forbid_quality_during_muscle_memory,
+                    first_quality_step: config.first_quality_step,
+                },
+                INTERRUPT_SIGNAL.clone(),
+            )
+            .beam_width(config.effort.beam_width())
+            .solve_from(initial_state)
+            .map(|actions| with_prefix(&actions));
+            let event = match result {
+                Ok(actions) => SolverEvent::FinalSolution(actions),
+                Err(error) => SolverEvent::Error(error),
+            };
+            self.send_event(self.tx.clone(), scope, id, event);
+            return;
+        }
 
-                let tx = self.tx.clone();
-                match result {
-                    Ok(actions) => {
-                        self.send_event(tx.clone(), scope, id, SolverEvent::FinalSolution(actions));
+        if config.pareto_front {
+            let mut solver = raphael_solver::MacroSolver::new(
+                raphael_solver::SolverSettings {
+                    simulator_settings: settings,
+                    backload_progress: config.backload_progress,
+                    allow_unsound_branch_pruning: false,
+                    forbid_quality_during_muscle_memory: config.forbid_quality_during_muscle_memory,
+                    first_quality_step: config.first_quality_step,
+                },
+                Box::new(solution_callback),
+                Box::new(progress_callback),
+                INTERRUPT_SIGNAL.clone(),
+            )
+            .memory_limit(worker_config.memory_limit)
+            .human_friendly_bias(config.human_friendly_bias);
+            let result = solver.solve_pareto_front_from(initial_state);
+            self.send_event(
+                self.tx.clone(),
+                scope,
+                id,
+                SolverEvent::Stats(*solver.stats()),
+            );
+            let event = match result {
+                Ok(mut pareto_front) => {
+                    for solution in &mut pareto_front {
+                        solution.actions = with_prefix(&solution.actions);
                     }
-                    Err(error) => self.send_event(tx.clone(), scope, id, SolverEvent::Error(error)),
+                    SolverEvent::ParetoFront(pareto_front)
                 }
+                Err(error) => SolverEvent::Error(error),
+            };
+            self.send_event(self.tx.clone(), scope, id, event);
+            return;
+        }
+
+        // a checkpoint of the sound solver means that the unsound solver already ran
+        let unsound_checkpoint =
+            checkpoint.take_if(|checkpoint| checkpoint.settings().allow_unsound_branch_pruning);
+        let mut saved_checkpoint = None;
+        // the stats of the last search, which found the final macro
+        let mut stats = None;
+        let mut result = if config.minimize_steps
+            || config.minimize_cp
+            || !config.effort.unsound_search()
+            || checkpoint.is_some()
+        {
+            Err(SolverException::NoSolution) // skip unsound solver
+        } else {
+            let mut solver = raphael_solver::MacroSolver::new(
+                raphael_solver::SolverSettings {
+                    simulator_settings: settings,
+                    backload_progress: true,
+                    allow_unsound_branch_pruning: true,
+                    forbid_quality_during_muscle_memory: config.forbid_quality_during_muscle_memory,
+                    first_quality_step: config.first_quality_step,
+                },
+                Box::new(solution_callback.clone()),
+                Box::new(progress_callback.clone()),
+                INTERRUPT_SIGNAL.clone(),
+            )
+            .memory_limit(worker_config.memory_limit)
+            .minimize_cp(config.minimize_cp)
+            .human_friendly_bias(config.human_friendly_bias)
+            .checkpoint_signal(CHECKPOINT_SIGNAL.clone())
+            .resume(unsound_checkpoint);
+            let result = solver
+                .solve_from(initial_state)
+                .map(|actions| with_prefix(&actions));
+            saved_checkpoint = solver.take_checkpoint();
+            stats = Some(*solver.stats());
+            result
+        };
+
+        let need_resolve = match &result {
+            Ok(actions) => {
+                config.effort.sound_search()
+                    && test_utils::get_quality(&settings, actions) < settings.max_quality
             }
-            SolverInput::Cancel => {
-                INTERRUPT_SIGNAL.set();
-            }
+            Err(SolverException::Interrupted) => false,
+            Err(SolverException::NoSolution) => true,
+            Err(SolverException::MemoryLimitReached) => false,
+            Err(SolverException::InternalError(_)) => false,
+        };
+
+        if need_resolve {
+            progress_callback(0); // reset solver progress
+            let mut solver = raphael_solver::MacroSolver::new(
+                raphael_solver::SolverSettings {
+                    simulator_settings: settings,
+                    backload_progress: config.backload_progress,
+                    allow_unsound_branch_pruning: false,
+                    forbid_quality_during_muscle_memory: config.forbid_quality_during_muscle_memory,
+                    first_quality_step: config.first_quality_step,
+                },
+                Box::new(solution_callback),
+                Box::new(progress_callback),
+                INTERRUPT_SIGNAL.clone(),
+            )
+            .memory_limit(worker_config.memory_limit)
+            .minimize_cp(config.minimize_cp)
+            .human_friendly_bias(config.human_friendly_bias)
+            .checkpoint_signal(CHECKPOINT_SIGNAL.clone())
+            .resume(checkpoint);
+            result = solver
+                .solve_from(initial_state)
+                .map(|actions| with_prefix(&actions));
+            saved_checkpoint = solver.take_checkpoint();
+            stats = Some(*solver.stats());
         }
+
+        if let Some(stats) = stats {
+            self.send_event(self.tx.clone(), scope, id, SolverEvent::Stats(stats));
+        }
+
+        let event = match (result, saved_checkpoint) {
+            (Ok(actions), _) => SolverEvent::FinalSolution(actions),
+            #[cfg(not(target_arch = "wasm32"))]
+            (Err(SolverException::Interrupted), Some(checkpoint)) => {
+                save_progress(settings, config, prefix, &checkpoint)
+            }
+            (Err(error), _) => SolverEvent::Error(error),
+        };
+        self.send_event(self.tx.clone(), scope, id, event);
     }
 
     // Adapter to unify both implementations
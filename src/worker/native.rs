@@ -1,6 +1,8 @@
 use crate::Worker;
+use crate::app::SolverInput;
 use crate::worker::Input;
 use crate::worker::Output;
+use raphael_solver::ThreadPoolSettings;
 use std::sync::mpsc::{self, Receiver, Sender};
 
 pub(crate) type Scope = DummyScope;
@@ -14,18 +16,32 @@ impl DummyScope {
 pub struct NativeBridge {
     pub(crate) tx: Sender<Output>,
     pub(crate) rx: Receiver<Output>,
+    pub thread_pool_settings: ThreadPoolSettings,
 }
 
 impl NativeBridge {
     pub fn new() -> Self {
         let (tx, rx) = mpsc::channel::<Output>();
-        Self { tx, rx }
+        Self {
+            tx,
+            rx,
+            thread_pool_settings: ThreadPoolSettings::default(),
+        }
     }
 
     pub fn send(&mut self, input: Input) {
+        let thread_pool = match input {
+            SolverInput::Start(..) | SolverInput::Resume(..) => self
+                .thread_pool_settings
+                .build()
+                .inspect_err(|error| log::error!("Failed to build thread pool: {error}"))
+                .ok(),
+            SolverInput::Cancel | SolverInput::SaveProgress(..) => None,
+        };
         let worker = Worker::new(input, self.tx.clone());
-        std::thread::spawn(move || {
-            worker.solver_callback(None, None, None);
+        std::thread::spawn(move || match thread_pool {
+            Some(thread_pool) => thread_pool.install(|| worker.solver_callback(None, None, None)),
+            None => worker.solver_callback(None, None, None),
         });
     }
 }
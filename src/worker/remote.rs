@@ -0,0 +1,75 @@
+//! Client for solving on a remote raphael-server, e.g. to offload adversarial solves from a
+//! laptop or the web app to a desktop.
+//!
+//! The protocol mirrors the local worker: the client sends each [`SolverInput`] as a JSON text
+//! message and the server streams back the [`SolverEvent`]s of the solve as JSON text messages.
+//! The connection is closed after the final event of the solve.
+
+use ewebsock::{WsEvent, WsMessage, WsReceiver, WsSender};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{SolverEvent, SolverInput};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteSolveConfig {
+    pub enabled: bool,
+    /// WebSocket URL of the server, e.g. `ws://192.168.0.10:8642/solve`.
+    pub url: String,
+}
+
+/// Connection to a remote server for a single solve.
+pub struct RemoteBridge {
+    sender: WsSender,
+    receiver: WsReceiver,
+    /// Messages sent before the connection was opened, sent once it is.
+    pending_messages: Vec<String>,
+    opened: bool,
+}
+
+impl RemoteBridge {
+    pub fn connect(ctx: egui::Context, url: &str) -> Result<Self, String> {
+        let (sender, receiver) =
+            ewebsock::connect_with_wakeup(url, ewebsock::Options::default(), move || {
+                ctx.request_repaint();
+            })?;
+        Ok(Self {
+            sender,
+            receiver,
+            pending_messages: Vec::new(),
+            opened: false,
+        })
+    }
+
+    pub fn send(&mut self, input: &SolverInput) {
+        let message = serde_json::to_string(input).expect("SolverInput is always serializable");
+        match self.opened {
+            true => self.sender.send(WsMessage::Text(message)),
+            false => self.pending_messages.push(message),
+        }
+    }
+
+    /// Next event of the solve, if any arrived. Errors mean that the connection is lost.
+    pub fn try_recv(&mut self) -> Option<Result<SolverEvent, String>> {
+        while let Some(event) = self.receiver.try_recv() {
+            match event {
+                WsEvent::Opened => {
+                    self.opened = true;
+                    for message in self.pending_messages.drain(..) {
+                        self.sender.send(WsMessage::Text(message));
+                    }
+                }
+                WsEvent::Message(WsMessage::Text(message)) => {
+                    return Some(
+                        serde_json::from_str(&message)
+                            .map_err(|error| format!("Invalid message from server: {error}")),
+                    );
+                }
+                WsEvent::Message(_) => {}
+                WsEvent::Error(error) => return Some(Err(error)),
+                WsEvent::Closed => return Some(Err("Connection closed by server".to_string())),
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,315 @@
+//! Round trips through the bridge between the app and the solver worker.
+//!
+//! On native, the tests drive a `NativeBridge` the same way the app does. On web, the worker
+//! runs from the `webworker` binary that Trunk builds, which isn't available to
+//! `wasm-bindgen-test`, so the tests cover the encoding of the messages that cross the bridge.
+
+use raphael_sim::{Action, ActionMask, RulesOverride, Settings, SuccessModel};
+use raphael_solver::SolverException;
+
+use super::WorkerConfig;
+use crate::app::{SolverConfig, SolverEvent, SolverInput};
+
+const SIMPLE_SETTINGS: Settings = Settings {
+    max_cp: 80,
+    max_durability: 60,
+    max_progress: 1920,
+    max_quality: 1000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    success_model: SuccessModel::AssumeFail,
+    rules_override: RulesOverride::NONE,
+};
+
+fn start(settings: Settings, prefix: Vec<Action>) -> SolverInput {
+    SolverInput::Start(
+        settings,
+        SolverConfig::default(),
+        WorkerConfig::default(),
+        prefix,
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::sync::{Mutex, PoisonError};
+    use std::time::Duration;
+
+    use raphael_solver::test_utils::get_quality;
+
+    use super::*;
+    use crate::app::SolverEffort;
+    use crate::worker::native::NativeBridge;
+
+    /// All bridges share the interrupt signal of the worker, so a cancelled solve would also
+    /// interrupt the solves of tests that run in parallel.
+    static BRIDGE_LOCK: Mutex<()> = Mutex::new(());
+
+    const TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// Takes several seconds to solve.
+    const SLOW_SETTINGS: Settings = Settings {
+        max_cp: 680,
+        max_durability: 70,
+        max_progress: 5060,
+        max_quality: 12628,
+        base_progress: 229,
+        base_quality: 224,
+        ..SIMPLE_SETTINGS
+    };
+
+    /// Waits until the solve started, since the solve clears the interrupt signal when it starts.
+    fn wait_until_started(bridge: &NativeBridge) {
+        let first_event = bridge
+            .rx
+            .recv_timeout(TIMEOUT)
+            .expect("worker didn't start");
+        assert!(!matches!(
+            first_event,
+            SolverEvent::FinalSolution(_) | SolverEvent::Error(_)
+        ));
+    }
+
+    /// Receives events until the worker reports the final solution or an error.
+    fn receive_until_done(bridge: &NativeBridge) -> (Vec<SolverEvent>, SolverEvent) {
+        let mut events = Vec::new();
+        loop {
+            match bridge
+                .rx
+                .recv_timeout(TIMEOUT)
+                .expect("worker didn't finish")
+            {
+                event @ (SolverEvent::FinalSolution(_)
+                | SolverEvent::ParetoFront(_)
+                | SolverEvent::ProgressSaved(_)
+                | SolverEvent::Error(_)) => {
+                    return (events, event);
+                }
+                event => events.push(event),
+            }
+        }
+    }
+
+    #[test]
+    fn final_solution() {
+        let _lock = BRIDGE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut bridge = NativeBridge::new();
+        bridge.send(start(SIMPLE_SETTINGS, Vec::new()));
+        let (events, last_event) = receive_until_done(&bridge);
+        let SolverEvent::FinalSolution(actions) = last_event else {
+            panic!("expected a solution, got {last_event:?}");
+        };
+        assert!(!actions.is_empty());
+        assert_eq!(get_quality(&SIMPLE_SETTINGS, &actions), 0);
+        // the stats of the search are sent right before the solution
+        let Some((SolverEvent::Stats(stats), events)) = events.split_last() else {
+            panic!("expected the stats before the solution, got {events:?}");
+        };
+        assert!(stats.threads >= 1);
+        assert!(events.iter().all(|event| matches!(
+            event,
+            SolverEvent::Progress(_) | SolverEvent::IntermediateSolution(_)
+        )));
+    }
+
+    #[test]
+    fn solution_includes_prefix() {
+        let _lock = BRIDGE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut bridge = NativeBridge::new();
+        let prefix = vec![Action::MuscleMemory, Action::Veneration];
+        bridge.send(start(SIMPLE_SETTINGS, prefix.clone()));
+        let (events, last_event) = receive_until_done(&bridge);
+        let SolverEvent::FinalSolution(actions) = last_event else {
+            panic!("expected a solution, got {last_event:?}");
+        };
+        assert!(actions.starts_with(&prefix));
+        for event in events {
+            if let SolverEvent::IntermediateSolution(actions) = event {
+                assert!(actions.starts_with(&prefix));
+            }
+        }
+    }
+
+    #[test]
+    fn pareto_front_includes_prefix() {
+        let _lock = BRIDGE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut bridge = NativeBridge::new();
+        let prefix = vec![Action::MuscleMemory];
+        bridge.send(SolverInput::Start(
+            SIMPLE_SETTINGS,
+            SolverConfig {
+                pareto_front: true,
+                ..SolverConfig::default()
+            },
+            WorkerConfig::default(),
+            prefix.clone(),
+        ));
+        let (_, last_event) = receive_until_done(&bridge);
+        let SolverEvent::ParetoFront(pareto_front) = last_event else {
+            panic!("expected a pareto front, got {last_event:?}");
+        };
+        assert!(!pareto_front.is_empty());
+        for solution in pareto_front {
+            assert!(solution.actions.starts_with(&prefix));
+        }
+    }
+
+    #[test]
+    fn solver_efforts() {
+        let _lock = BRIDGE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        for effort in SolverEffort::ALL {
+            let mut bridge = NativeBridge::new();
+            bridge.send(SolverInput::Start(
+                SIMPLE_SETTINGS,
+                SolverConfig {
+                    effort,
+                    ..SolverConfig::default()
+                },
+                WorkerConfig::default(),
+                Vec::new(),
+            ));
+            let (_, last_event) = receive_until_done(&bridge);
+            let SolverEvent::FinalSolution(actions) = last_event else {
+                panic!("expected a solution with {effort:?} effort, got {last_event:?}");
+            };
+            assert_eq!(get_quality(&SIMPLE_SETTINGS, &actions), 0);
+        }
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        let _lock = BRIDGE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut bridge = NativeBridge::new();
+        // Manipulation costs more CP than available
+        bridge.send(start(SIMPLE_SETTINGS, vec![Action::Manipulation]));
+        let (events, last_event) = receive_until_done(&bridge);
+        assert!(events.is_empty());
+        assert!(matches!(
+            last_event,
+            SolverEvent::Error(SolverException::InternalError(_))
+        ));
+    }
+
+    #[test]
+    fn no_solution() {
+        let _lock = BRIDGE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut bridge = NativeBridge::new();
+        let settings = Settings {
+            max_cp: 100,
+            max_progress: 4000,
+            ..SIMPLE_SETTINGS
+        };
+        bridge.send(start(settings, Vec::new()));
+        let (_, last_event) = receive_until_done(&bridge);
+        assert!(matches!(
+            last_event,
+            SolverEvent::Error(SolverException::NoSolution)
+        ));
+    }
+
+    #[test]
+    fn cancel() {
+        let _lock = BRIDGE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut bridge = NativeBridge::new();
+        bridge.send(start(SLOW_SETTINGS, Vec::new()));
+        wait_until_started(&bridge);
+        bridge.send(SolverInput::Cancel);
+        let (_, last_event) = receive_until_done(&bridge);
+        assert!(matches!(
+            last_event,
+            SolverEvent::Error(SolverException::Interrupted)
+        ));
+
+        // the bridge can be reused after a cancelled solve
+        bridge.send(start(SIMPLE_SETTINGS, Vec::new()));
+        let (_, last_event) = receive_until_done(&bridge);
+        assert!(matches!(last_event, SolverEvent::FinalSolution(_)));
+    }
+
+    #[test]
+    fn save_and_resume_progress() {
+        let _lock = BRIDGE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut bridge = NativeBridge::new();
+        let prefix = vec![Action::MuscleMemory];
+        bridge.send(start(SLOW_SETTINGS, prefix.clone()));
+        wait_until_started(&bridge);
+        let path = std::env::temp_dir().join(format!(
+            "raphael-worker-test-{}.raphael-progress",
+            std::process::id()
+        ));
+        bridge.send(SolverInput::SaveProgress(path.clone(), String::new()));
+        let (_, last_event) = receive_until_done(&bridge);
+        let SolverEvent::ProgressSaved(saved_path) = last_event else {
+            panic!("expected the progress to be saved, got {last_event:?}");
+        };
+        assert_eq!(saved_path, path);
+
+        bridge.send(SolverInput::Resume(path.clone(), WorkerConfig::default()));
+        let (_, last_event) = receive_until_done(&bridge);
+        std::fs::remove_file(&path).unwrap();
+        let SolverEvent::FinalSolution(actions) = last_event else {
+            panic!("expected a solution, got {last_event:?}");
+        };
+        assert!(actions.starts_with(&prefix));
+        assert!(get_quality(&SLOW_SETTINGS, &actions) > 0);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use raphael_solver::{ParetoSolution, SolverStats};
+    use serde::{Serialize, de::DeserializeOwned};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::worker::codec::CompactCodec;
+
+    /// Messages don't implement `PartialEq`, so the round trip is checked on the debug output.
+    fn assert_round_trip<T: Serialize + DeserializeOwned + std::fmt::Debug>(message: T) {
+        let decoded: T = CompactCodec::decode(CompactCodec::encode(&message).into()).unwrap();
+        assert_eq!(format!("{decoded:?}"), format!("{message:?}"));
+    }
+
+    #[wasm_bindgen_test]
+    fn solver_input() {
+        assert_round_trip(start(SIMPLE_SETTINGS, Vec::new()));
+        assert_round_trip(start(
+            SIMPLE_SETTINGS,
+            vec![Action::MuscleMemory, Action::Manipulation],
+        ));
+        assert_round_trip(SolverInput::Cancel);
+    }
+
+    #[wasm_bindgen_test]
+    fn solver_event() {
+        let actions = vec![Action::BasicSynthesis, Action::CarefulSynthesis];
+        assert_round_trip(SolverEvent::Progress(0));
+        assert_round_trip(SolverEvent::Progress(usize::MAX));
+        assert_round_trip(SolverEvent::IntermediateSolution(actions.clone()));
+        assert_round_trip(SolverEvent::FinalSolution(actions.clone()));
+        assert_round_trip(SolverEvent::ParetoFront(vec![ParetoSolution {
+            actions,
+            quality: 1000,
+        }]));
+        assert_round_trip(SolverEvent::Stats(SolverStats::default()));
+        assert_round_trip(SolverEvent::Error(SolverException::NoSolution));
+        assert_round_trip(SolverEvent::Error(SolverException::Interrupted));
+        assert_round_trip(SolverEvent::WorkerCrashed);
+        assert_round_trip(SolverEvent::Error(SolverException::InternalError(
+            "Insufficient CP".to_string(),
+        )));
+    }
+
+    #[wasm_bindgen_test]
+    fn truncated_message() {
+        let message = CompactCodec::encode(start(SIMPLE_SETTINGS, Vec::new()));
+        let truncated = message.subarray(0, message.length() / 2);
+        assert!(CompactCodec::decode::<SolverInput>(truncated.into()).is_err());
+    }
+}
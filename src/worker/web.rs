@@ -1,32 +1,206 @@
+//! The solver runs in a dedicated web worker, spawned from the `webworker` binary that Trunk
+//! builds. A busy worker can't receive messages, so a solve is cancelled by terminating the
+//! worker and spawning a new one. A worker that crashed, e.g. because it ran out of memory, is
+//! replaced the same way before the next solve.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use raphael_solver::SolverException;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+use web_sys::{DedicatedWorkerGlobalScope, MessageEvent};
+
 use crate::Worker;
+use crate::app::{SolverEvent, SolverInput};
 use crate::worker::Input;
 use crate::worker::Output;
+use crate::worker::codec::CompactCodec;
 
-type Message = u64;
-pub(crate) type Scope = gloo_worker::WorkerScope<Worker>;
-pub(crate) type Id = gloo_worker::HandlerId;
+pub(crate) type Id = ();
 
-impl gloo_worker::Worker for Worker {
-    type Message = Message;
-    type Input = Input;
-    type Output = Output;
+/// Global scope of the worker, through which events are sent to the app.
+pub struct Scope(DedicatedWorkerGlobalScope);
 
-    fn create(_scope: &gloo_worker::WorkerScope<Self>) -> Self {
-        std::panic::set_hook(Box::new(console_error_panic_hook::hook));
-        Self {
+impl Scope {
+    pub fn respond(&self, _id: Id, event: Output) {
+        let message = CompactCodec::encode(event);
+        if let Err(error) = self
+            .0
+            .post_message_with_transfer(&message, &CompactCodec::transfer_list(&message))
+        {
+            log::error!("Failed to send a solver event: {error:?}");
+        }
+    }
+}
+
+/// Entry point of the web worker. The worker sends `null` to the app once it can receive
+/// inputs, since messages sent while the wasm module is still loading are lost.
+pub fn register() {
+    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+    let global: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+    let scope = Scope(global.clone());
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+        let input: Input = match CompactCodec::decode(event.data()) {
+            Ok(input) => input,
+            Err(error) => {
+                log::error!("Failed to decode a solver input: {error}");
+                return;
+            }
+        };
+        let worker = Worker {
             input: None,
             tx: None,
+        };
+        worker.solver_callback(Some(&scope), Some(()), Some(input));
+    });
+    global.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    // the handler lives as long as the worker
+    on_message.forget();
+    global
+        .post_message(&JsValue::NULL)
+        .expect("can't send a message to the app");
+}
+
+#[derive(Default)]
+struct ConnectionState {
+    /// Whether the worker finished loading and receives inputs.
+    loaded: bool,
+    /// Inputs sent before the worker finished loading.
+    pending_inputs: Vec<js_sys::Uint8Array>,
+    /// Whether the worker is busy with a solve.
+    solving: bool,
+    /// Whether the worker threw an error, after which it doesn't respond anymore.
+    crashed: bool,
+}
+
+/// A spawned worker, which is terminated when dropped.
+struct Connection {
+    worker: web_sys::Worker,
+    state: Rc<RefCell<ConnectionState>>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Connection {
+    fn spawn(url: &str, on_event: Rc<dyn Fn(Output)>) -> Self {
+        let worker = web_sys::Worker::new(url).expect("can't spawn the solver worker");
+        let state = Rc::new(RefCell::new(ConnectionState::default()));
+        let on_message = {
+            let worker = worker.clone();
+            let state = state.clone();
+            let on_event = on_event.clone();
+            Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                let data = event.data();
+                if data.is_null() {
+                    let mut state = state.borrow_mut();
+                    state.loaded = true;
+                    for input in state.pending_inputs.drain(..) {
+                        post_input(&worker, &input);
+                    }
+                    return;
+                }
+                let event: Output = match CompactCodec::decode(data) {
+                    Ok(event) => event,
+                    Err(error) => {
+                        log::error!("Failed to decode a solver event: {error}");
+                        return;
+                    }
+                };
+                if event.is_final() {
+                    state.borrow_mut().solving = false;
+                }
+                on_event(event);
+            })
+        };
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        let on_error = {
+            let state = state.clone();
+            Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+                log::error!("Solver worker crashed: {:?}", event.type_());
+                let solving = {
+                    let mut state = state.borrow_mut();
+                    state.crashed = true;
+                    std::mem::take(&mut state.solving)
+                };
+                if solving {
+                    on_event(SolverEvent::WorkerCrashed);
+                }
+            })
+        };
+        worker.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        Self {
+            worker,
+            state,
+            _on_message: on_message,
+            _on_error: on_error,
+        }
+    }
+
+    fn send(&self, input: &Input) {
+        let message = CompactCodec::encode(input);
+        let mut state = self.state.borrow_mut();
+        state.solving = true;
+        match state.loaded {
+            true => post_input(&self.worker, &message),
+            false => state.pending_inputs.push(message),
         }
     }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.worker.set_onmessage(None);
+        self.worker.set_onerror(None);
+        self.worker.terminate();
+    }
+}
 
-    fn update(&mut self, _scope: &gloo_worker::WorkerScope<Self>, _msg: Self::Message) {}
+fn post_input(worker: &web_sys::Worker, message: &js_sys::Uint8Array) {
+    if let Err(error) =
+        worker.post_message_with_transfer(message, &CompactCodec::transfer_list(message))
+    {
+        log::error!("Failed to send a solver input: {error:?}");
+    }
+}
+
+/// Connection of the app to the solver worker.
+pub struct WebBridge {
+    url: String,
+    on_event: Rc<dyn Fn(Output)>,
+    connection: Connection,
+}
 
-    fn received(
-        &mut self,
-        scope: &gloo_worker::WorkerScope<Self>,
-        msg: Self::Input,
-        id: gloo_worker::HandlerId,
-    ) {
-        self.solver_callback(Some(scope), Some(id), Some(msg));
+impl WebBridge {
+    /// Spawns the worker from the script at `url`. `on_event` is called with the events of the
+    /// solves.
+    pub fn new(url: &str, on_event: impl Fn(Output) + 'static) -> Self {
+        let on_event: Rc<dyn Fn(Output)> = Rc::new(on_event);
+        Self {
+            url: url.to_owned(),
+            connection: Connection::spawn(url, on_event.clone()),
+            on_event,
+        }
+    }
+
+    /// Replaces the worker with a new one, e.g. after it stopped responding. Events of the
+    /// solve of the old worker are dropped.
+    pub fn restart(&mut self) {
+        self.connection = Connection::spawn(&self.url, self.on_event.clone());
+    }
+
+    pub fn send(&mut self, input: Input) {
+        if self.connection.state.borrow().crashed {
+            self.restart();
+        }
+        match input {
+            SolverInput::Cancel => {
+                if self.connection.state.borrow().solving {
+                    self.restart();
+                    (self.on_event)(SolverEvent::Error(SolverException::Interrupted));
+                }
+            }
+            input => self.connection.send(&input),
+        }
     }
 }